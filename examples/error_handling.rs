@@ -17,8 +17,9 @@ fn main() -> Result<(), Error> {
     Ok(response) => {
       println!("Status: {}", response.status_code);
     },
-    Err(Error::HttpStatus(code)) => {
-      println!("✓ Caught HTTP status error: {}\n", code);
+    Err(Error::HttpStatus { code, response }) => {
+      println!("✓ Caught HTTP status error: {}", code);
+      println!("Error body: {} bytes\n", response.body.len());
     },
     Err(e) => {
       println!("Other error: {:?}\n", e);
@@ -75,7 +76,7 @@ fn main() -> Result<(), Error> {
     Ok(response) => {
       println!("Success! Status: {}", response.status_code);
     },
-    Err(Error::HttpStatus(code)) => {
+    Err(Error::HttpStatus { code, .. }) => {
       println!("✓ HTTP error: {}", code);
     },
     Err(Error::Dns(e)) => {
@@ -1,29 +1,41 @@
+use alloc::borrow::Cow;
 use alloc::string::String;
 use alloc::vec::Vec;
 
 /// HTTP request or response body
+///
+/// Holds either owned bytes or a borrowed `'static` slice, so fixed payloads
+/// can be sent without copying them first.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Body {
-  data: Vec<u8>,
+  data: Cow<'static, [u8]>,
 }
 
 impl Body {
   /// Create an empty body
   #[must_use]
   pub const fn empty() -> Self {
-    Self { data: Vec::new() }
+    Self { data: Cow::Borrowed(&[]) }
   }
 
   /// Create a body from bytes
   #[must_use]
   pub const fn from_bytes(data: Vec<u8>) -> Self {
-    Self { data }
+    Self { data: Cow::Owned(data) }
+  }
+
+  /// Create a body borrowing static bytes without copying them
+  #[must_use]
+  pub const fn from_static(data: &'static [u8]) -> Self {
+    Self {
+      data: Cow::Borrowed(data),
+    }
   }
 
   /// Create a body from a string
   #[must_use]
   pub const fn from_string(s: String) -> Self {
-    Self { data: s.into_bytes() }
+    Self::from_bytes(s.into_bytes())
   }
 
   /// Get the body as a byte slice
@@ -35,19 +47,30 @@ impl Body {
   /// Get the body length
   #[must_use]
   pub const fn len(&self) -> usize {
-    self.data.len()
+    match &self.data {
+      Cow::Borrowed(bytes) => bytes.len(),
+      Cow::Owned(bytes) => bytes.len(),
+    }
   }
 
   /// Check if the body is empty
   #[must_use]
   pub const fn is_empty(&self) -> bool {
-    self.data.is_empty()
+    self.len() == 0
+  }
+
+  /// Check if the body borrows its bytes rather than owning them
+  #[must_use]
+  pub const fn is_borrowed(&self) -> bool {
+    matches!(self.data, Cow::Borrowed(_))
   }
 
   /// Convert the body into bytes
+  ///
+  /// Borrowed bodies are copied into a new vector.
   #[must_use]
   pub fn into_bytes(self) -> Vec<u8> {
-    self.data
+    self.data.into_owned()
   }
 
   /// Try to convert the body to a UTF-8 string
@@ -55,7 +78,7 @@ impl Body {
   /// # Errors
   /// Returns an error if the body contains invalid UTF-8
   pub fn to_string(&self) -> Result<String, alloc::string::FromUtf8Error> {
-    String::from_utf8(self.data.clone())
+    String::from_utf8(self.data.to_vec())
   }
 
   /// Convert the body into a UTF-8 string
@@ -63,13 +86,15 @@ impl Body {
   /// # Errors
   /// Returns an error if the body contains invalid UTF-8
   pub fn into_string(self) -> Result<String, alloc::string::FromUtf8Error> {
-    String::from_utf8(self.data)
+    String::from_utf8(self.data.into_owned())
   }
 
   /// Get a mutable reference to the internal bytes
+  ///
+  /// Borrowed bodies are copied into an owned buffer first.
   #[must_use]
-  pub const fn as_bytes_mut(&mut self) -> &mut Vec<u8> {
-    &mut self.data
+  pub fn as_bytes_mut(&mut self) -> &mut Vec<u8> {
+    self.data.to_mut()
   }
 }
 
@@ -85,6 +110,12 @@ impl From<String> for Body {
   }
 }
 
+impl From<&'static [u8]> for Body {
+  fn from(data: &'static [u8]) -> Self {
+    Self::from_static(data)
+  }
+}
+
 impl From<&str> for Body {
   fn from(s: &str) -> Self {
     Self::from_string(String::from(s))
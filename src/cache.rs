@@ -0,0 +1,454 @@
+extern crate alloc;
+use crate::body::Body;
+use crate::headers::{HeaderName, Headers};
+use crate::method::Method;
+use crate::parser::Response;
+use crate::parser::cache_control::CacheControl;
+use crate::util::parse_http_date;
+use alloc::string::String;
+use alloc::vec::Vec;
+use spin::Mutex;
+
+/// Response stored in an `HttpCache` together with its freshness deadline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+  /// The stored response, including its body
+  pub response: Response,
+  /// Time (milliseconds since the Unix epoch) until which the response
+  /// may be served without contacting the origin
+  pub fresh_until: u64,
+  /// Request header fields named by the response's `Vary`, with the
+  /// values the original request sent (None if it did not send one)
+  pub vary: Vec<(String, Option<String>)>,
+}
+
+impl CachedResponse {
+  /// Check whether the entry can be served without revalidation
+  #[must_use]
+  pub const fn is_fresh(
+    &self,
+    now_millis: u64,
+  ) -> bool {
+    now_millis < self.fresh_until
+  }
+
+  /// Check whether a request selects this entry (RFC 9111 Section 4.1)
+  ///
+  /// Every header field named by `Vary` must have the same value as in
+  /// the request the entry was stored for.
+  #[must_use]
+  pub fn matches(
+    &self,
+    request: &Headers,
+  ) -> bool {
+    self
+      .vary
+      .iter()
+      .all(|(name, value)| request_value(request, name).as_deref() == value.as_deref())
+  }
+
+  /// `ETag` validator of the stored response
+  #[must_use]
+  pub fn etag(&self) -> Option<&str> {
+    self.response.get_header(HeaderName::ETAG)
+  }
+
+  /// `Last-Modified` validator of the stored response
+  #[must_use]
+  pub fn last_modified(&self) -> Option<&str> {
+    self.response.get_header(HeaderName::LAST_MODIFIED)
+  }
+}
+
+/// Storage backend for cached responses
+///
+/// Entries are keyed by the URL the response was received from. The client
+/// decides what to store and when an entry is fresh; implementations only
+/// need to keep entries and may drop them at any time.
+pub trait HttpCache: Send + Sync {
+  /// Look up the entry stored for a URL
+  fn get(
+    &self,
+    key: &str,
+  ) -> Option<CachedResponse>;
+
+  /// Store or replace the entry for a URL
+  fn put(
+    &self,
+    key: &str,
+    entry: CachedResponse,
+  );
+
+  /// Drop the entry for a URL, if any
+  fn remove(
+    &self,
+    key: &str,
+  );
+}
+
+/// In-memory least-recently-used cache
+///
+/// Holds at most `capacity` entries; storing a new entry beyond that evicts
+/// the entry that was used longest ago.
+#[derive(Debug)]
+pub struct MemoryCache {
+  entries: Mutex<Vec<(String, CachedResponse)>>,
+  capacity: usize,
+}
+
+impl MemoryCache {
+  /// Create an empty cache holding up to `capacity` responses
+  #[must_use]
+  pub const fn new(capacity: usize) -> Self {
+    Self {
+      entries: Mutex::new(Vec::new()),
+      capacity,
+    }
+  }
+
+  /// Number of stored responses
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.entries.lock().len()
+  }
+
+  /// Check whether the cache holds no responses
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.entries.lock().is_empty()
+  }
+}
+
+impl HttpCache for MemoryCache {
+  fn get(
+    &self,
+    key: &str,
+  ) -> Option<CachedResponse> {
+    let mut entries = self.entries.lock();
+    let index = entries.iter().position(|(k, _)| k == key)?;
+    // Most recently used entries live at the back
+    let entry = entries.remove(index);
+    let cached = entry.1.clone();
+    entries.push(entry);
+    Some(cached)
+  }
+
+  fn put(
+    &self,
+    key: &str,
+    entry: CachedResponse,
+  ) {
+    if self.capacity == 0 {
+      return;
+    }
+    let mut entries = self.entries.lock();
+    entries.retain(|(k, _)| k != key);
+    while entries.len() >= self.capacity {
+      entries.remove(0);
+    }
+    entries.push((String::from(key), entry));
+  }
+
+  fn remove(
+    &self,
+    key: &str,
+  ) {
+    self.entries.lock().retain(|(k, _)| k != key);
+  }
+}
+
+/// Combined value of a request header field, as compared for `Vary`
+fn request_value(
+  request: &Headers,
+  name: &str,
+) -> Option<String> {
+  let values = request.get_all(name);
+  if values.is_empty() {
+    return None;
+  }
+  Some(values.join(", "))
+}
+
+/// Freshness lifetime in milliseconds (RFC 9111 Section 4.2.1)
+fn freshness_lifetime(
+  headers: &Headers,
+  directives: &CacheControl,
+  now_millis: u64,
+) -> u64 {
+  if directives.no_cache {
+    return 0;
+  }
+  if let Some(max_age) = directives.max_age {
+    return max_age.saturating_mul(1000);
+  }
+  let Some(expires) = headers.get(HeaderName::EXPIRES) else {
+    return 0;
+  };
+  // An invalid Expires value means "already expired"
+  let Some(expires_secs) = parse_http_date(expires) else {
+    return 0;
+  };
+  let date_millis = headers
+    .get(HeaderName::DATE)
+    .and_then(parse_http_date)
+    .map_or(now_millis, |secs| secs.saturating_mul(1000));
+  expires_secs
+    .saturating_mul(1000)
+    .saturating_sub(date_millis)
+}
+
+/// Build the cache entry for a fresh response, or None if it must not be stored
+///
+/// `request` holds the headers the response was requested with; the ones
+/// named by `Vary` are kept to select the entry for later requests.
+pub(crate) fn entry_for(
+  response: &Response,
+  request: &Headers,
+  now_millis: u64,
+) -> Option<CachedResponse> {
+  if response.status_code != 200 {
+    return None;
+  }
+  let directives = CacheControl::from_headers(&response.headers).unwrap_or_default();
+  if directives.no_store {
+    return None;
+  }
+  let lifetime = freshness_lifetime(&response.headers, &directives, now_millis);
+  let has_validators =
+    response.headers.contains(HeaderName::ETAG) || response.headers.contains(HeaderName::LAST_MODIFIED);
+  if lifetime == 0 && !has_validators {
+    return None;
+  }
+  let varied = response.headers.get_list(HeaderName::VARY);
+  // `Vary: *` means no later request can be known to match
+  if varied.contains(&"*") {
+    return None;
+  }
+  let vary = varied
+    .into_iter()
+    .map(|name| (name.to_ascii_lowercase(), request_value(request, name)))
+    .collect();
+  Some(CachedResponse {
+    response: response.clone(),
+    fresh_until: now_millis.saturating_add(lifetime),
+    vary,
+  })
+}
+
+/// Update a stored entry with the header fields of a 304 response
+///
+/// RFC 9111 Section 4.3.4: fields in the 304 replace the stored ones,
+/// and freshness is recomputed from the updated fields.
+pub(crate) fn refresh(
+  mut entry: CachedResponse,
+  not_modified: &Response,
+  now_millis: u64,
+) -> CachedResponse {
+  entry.response.update_from_not_modified(not_modified);
+  let directives = CacheControl::from_headers(&entry.response.headers).unwrap_or_default();
+  let lifetime = freshness_lifetime(&entry.response.headers, &directives, now_millis);
+  entry.fresh_until = now_millis.saturating_add(lifetime);
+  entry
+}
+
+/// Add `If-None-Match` / `If-Modified-Since` for revalidating an entry
+///
+/// Returns false if the entry has no validators or the request already
+/// carries its own conditional headers.
+pub(crate) fn add_validators(
+  entry: &CachedResponse,
+  headers: &mut Headers,
+) -> bool {
+  if headers.contains(HeaderName::IF_NONE_MATCH) || headers.contains(HeaderName::IF_MODIFIED_SINCE) {
+    return false;
+  }
+  if let Some(etag) = entry.etag() {
+    headers.insert(HeaderName::IF_NONE_MATCH, etag);
+  }
+  if let Some(last_modified) = entry.last_modified() {
+    headers.insert(HeaderName::IF_MODIFIED_SINCE, last_modified);
+  }
+  entry.etag().is_some() || entry.last_modified().is_some()
+}
+
+/// Turn a stored entry into the response for a request
+pub(crate) fn serve(
+  entry: CachedResponse,
+  method: Method,
+) -> Response {
+  let mut response = entry.response;
+  if method == Method::Head {
+    response.body = Body::empty();
+  }
+  response
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+  use super::*;
+
+  fn response(headers: &[(&str, &str)]) -> Response {
+    let mut map = Headers::new();
+    for (name, value) in headers {
+      map.insert(*name, *value);
+    }
+    Response {
+      status_code: 200,
+      reason: String::from("OK"),
+      headers: map,
+      body: Body::from_bytes(b"cached".to_vec()),
+      trailers: Vec::new(),
+      remote_addr: None,
+      truncated: false,
+      url: None,
+    }
+  }
+
+  #[test]
+  fn max_age_sets_freshness() {
+    let entry = entry_for(
+      &response(&[("Cache-Control", "public, max-age=60")]),
+      &Headers::new(),
+      1_000,
+    )
+    .unwrap();
+    assert_eq!(entry.fresh_until, 61_000);
+    assert!(entry.is_fresh(60_999));
+    assert!(!entry.is_fresh(61_000));
+  }
+
+  #[test]
+  fn no_store_is_not_cached() {
+    assert!(
+      entry_for(
+        &response(&[("Cache-Control", "no-store, max-age=60")]),
+        &Headers::new(),
+        0
+      )
+      .is_none()
+    );
+  }
+
+  #[test]
+  fn no_cache_with_validator_is_stored_stale() {
+    let entry = entry_for(
+      &response(&[("Cache-Control", "no-cache"), ("ETag", "\"v1\"")]),
+      &Headers::new(),
+      5_000,
+    )
+    .unwrap();
+    assert!(!entry.is_fresh(5_000));
+  }
+
+  #[test]
+  fn no_freshness_and_no_validators_is_not_cached() {
+    assert!(entry_for(&response(&[]), &Headers::new(), 0).is_none());
+  }
+
+  #[test]
+  fn expires_is_relative_to_date() {
+    let entry = entry_for(
+      &response(&[
+        ("Date", "Sun, 06 Nov 1994 08:49:37 GMT"),
+        ("Expires", "Sun, 06 Nov 1994 08:50:37 GMT"),
+      ]),
+      &Headers::new(),
+      100_000,
+    )
+    .unwrap();
+    assert_eq!(entry.fresh_until, 160_000);
+  }
+
+  #[test]
+  fn refresh_merges_headers_and_recomputes_freshness() {
+    let entry = entry_for(
+      &response(&[("Cache-Control", "no-cache"), ("ETag", "\"v1\"")]),
+      &Headers::new(),
+      0,
+    )
+    .unwrap();
+    let mut not_modified = response(&[("Cache-Control", "max-age=30"), ("ETag", "\"v1\"")]);
+    not_modified.status_code = 304;
+
+    let refreshed = refresh(entry, &not_modified, 10_000);
+    assert_eq!(refreshed.fresh_until, 40_000);
+    assert_eq!(refreshed.response.body.as_bytes(), b"cached");
+    assert_eq!(refreshed.response.headers.get_all("cache-control"), ["max-age=30"]);
+  }
+
+  #[test]
+  fn validators_respect_user_conditionals() {
+    let entry = entry_for(
+      &response(&[
+        ("ETag", "\"v1\""),
+        ("Last-Modified", "Sun, 06 Nov 1994 08:49:37 GMT"),
+      ]),
+      &Headers::new(),
+      0,
+    )
+    .unwrap();
+
+    let mut headers = Headers::new();
+    assert!(add_validators(&entry, &mut headers));
+    assert_eq!(headers.get(HeaderName::IF_NONE_MATCH), Some("\"v1\""));
+    assert_eq!(
+      headers.get(HeaderName::IF_MODIFIED_SINCE),
+      Some("Sun, 06 Nov 1994 08:49:37 GMT")
+    );
+
+    let mut user = Headers::new();
+    user.insert("If-None-Match", "\"other\"");
+    assert!(!add_validators(&entry, &mut user));
+    assert_eq!(user.get_all(HeaderName::IF_NONE_MATCH), ["\"other\""]);
+  }
+
+  #[test]
+  fn memory_cache_evicts_least_recently_used() {
+    let cache = MemoryCache::new(2);
+    let entry = entry_for(&response(&[("Cache-Control", "max-age=60")]), &Headers::new(), 0).unwrap();
+
+    cache.put("a", entry.clone());
+    cache.put("b", entry.clone());
+    assert!(cache.get("a").is_some());
+    cache.put("c", entry);
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("a").is_some());
+    assert!(cache.get("b").is_none());
+    assert!(cache.get("c").is_some());
+
+    cache.remove("a");
+    assert!(cache.get("a").is_none());
+  }
+
+  #[test]
+  fn vary_selects_entry_by_request_headers() {
+    let mut request = Headers::new();
+    request.insert("Accept-Language", "en");
+    let varied = response(&[
+      ("Cache-Control", "max-age=60"),
+      ("Vary", "Accept-Language, Accept"),
+    ]);
+    let entry = entry_for(&varied, &request, 0).unwrap();
+    assert!(entry.matches(&request));
+
+    let mut other = Headers::new();
+    other.insert("accept-language", "de");
+    assert!(!entry.matches(&other));
+    request.insert("Accept", "text/html");
+    assert!(!entry.matches(&request));
+  }
+
+  #[test]
+  fn vary_star_is_not_cached() {
+    let varied = response(&[("Cache-Control", "max-age=60"), ("Vary", "*")]);
+    assert!(entry_for(&varied, &Headers::new(), 0).is_none());
+  }
+
+  #[test]
+  fn head_is_served_without_body() {
+    let entry = entry_for(&response(&[("Cache-Control", "max-age=60")]), &Headers::new(), 0).unwrap();
+    assert!(serve(entry, Method::Head).body.is_empty());
+  }
+}
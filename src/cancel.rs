@@ -0,0 +1,53 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Shared flag for aborting an in-flight request
+///
+/// Clones share the same flag, so a token handed to a request can be
+/// cancelled from another thread. The client checks the token between
+/// socket reads and fails the request with `Error::Cancelled` once set.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+  cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+  /// Create a token that is not cancelled
+  #[must_use]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Request cancellation of every request holding this token
+  pub fn cancel(&self) {
+    self.cancelled.store(true, Ordering::Release);
+  }
+
+  /// Check whether cancellation has been requested
+  #[must_use]
+  pub fn is_cancelled(&self) -> bool {
+    self.cancelled.load(Ordering::Acquire)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn new_token_is_not_cancelled() {
+    let token = CancelToken::new();
+    assert!(!token.is_cancelled());
+  }
+
+  #[test]
+  fn cancel_is_visible_through_clones() {
+    let token = CancelToken::new();
+    let shared = token.clone();
+
+    shared.cancel();
+
+    assert!(token.is_cancelled());
+    assert!(shared.is_cancelled());
+  }
+}
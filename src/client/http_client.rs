@@ -1,18 +1,26 @@
 use crate::body::Body;
+use crate::cancel::CancelToken;
 use crate::client::policy::{PolicyDecision, RequestPolicy};
-use crate::client::request_executor::RequestExecutor;
+use crate::client::request_executor::{RequestExecutor, TargetForm};
 use crate::config::Config;
 use crate::dns::DnsResolver;
 use crate::error::Error;
 use crate::parser::Response;
-use crate::parser::uri::Uri;
+use crate::parser::uri::{Uri, encode_unsafe_chars};
+use crate::request::SignableRequest;
 use crate::request_builder::ClientRequestBuilder;
+use crate::sink::ByteSink;
 use crate::socket::BlockingSocket;
-use crate::transport::ConnectionPool;
+use crate::transport::{BodySink, ConnectionPool, PoolStats, RawResponse, TransferProgress};
+use alloc::borrow::Cow;
 use alloc::string::String;
 use alloc::sync::Arc;
+#[cfg(feature = "cookie-jar")]
 use alloc::vec::Vec;
 
+#[cfg(feature = "cache")]
+use crate::cache::{self, HttpCache};
+use crate::clock::{self, Clock};
 #[cfg(feature = "cookie-jar")]
 use crate::cookie_jar::CookieStore;
 
@@ -45,6 +53,9 @@ pub struct HttpClient<S, D> {
   config: Arc<Config>,
   #[cfg(feature = "cookie-jar")]
   cookie_store: Arc<CookieStore>,
+  #[cfg(feature = "cache")]
+  cache: Option<Arc<dyn HttpCache>>,
+  clock: Option<Arc<dyn Clock>>,
 }
 
 impl<S, D> Clone for HttpClient<S, D> {
@@ -55,6 +66,9 @@ impl<S, D> Clone for HttpClient<S, D> {
       config: Arc::clone(&self.config),
       #[cfg(feature = "cookie-jar")]
       cookie_store: Arc::clone(&self.cookie_store),
+      #[cfg(feature = "cache")]
+      cache: self.cache.clone(),
+      clock: self.clock.clone(),
     }
   }
 }
@@ -73,7 +87,10 @@ impl HttpClient<crate::socket::blocking::OsBlockingSocket, crate::dns::resolver:
       dns: Arc::new(crate::dns::resolver::OsDnsResolver::new()),
       config: Arc::new(config),
       #[cfg(feature = "cookie-jar")]
-      cookie_store: Arc::new(CookieStore::new()),
+      cookie_store: default_cookie_store(),
+      #[cfg(feature = "cache")]
+      cache: None,
+      clock: clock::os_clock(),
     })
   }
 
@@ -89,7 +106,10 @@ impl HttpClient<crate::socket::blocking::OsBlockingSocket, crate::dns::resolver:
       dns: Arc::new(crate::dns::resolver::OsDnsResolver::new()),
       config: Arc::new(config),
       #[cfg(feature = "cookie-jar")]
-      cookie_store: Arc::new(CookieStore::new()),
+      cookie_store: default_cookie_store(),
+      #[cfg(feature = "cache")]
+      cache: None,
+      clock: clock::os_clock(),
     })
   }
 }
@@ -113,7 +133,10 @@ where
       dns: Arc::new(dns),
       config: Arc::new(config),
       #[cfg(feature = "cookie-jar")]
-      cookie_store: Arc::new(CookieStore::new()),
+      cookie_store: default_cookie_store(),
+      #[cfg(feature = "cache")]
+      cache: None,
+      clock: clock::os_clock(),
     }
   }
 
@@ -132,7 +155,10 @@ where
       dns: Arc::new(dns),
       config: Arc::new(config),
       #[cfg(feature = "cookie-jar")]
-      cookie_store: Arc::new(CookieStore::new()),
+      cookie_store: default_cookie_store(),
+      #[cfg(feature = "cache")]
+      cache: None,
+      clock: clock::os_clock(),
     }
   }
 
@@ -172,7 +198,7 @@ where
 
   /// Start building a DELETE request
   ///
-  /// Returns a request builder with no body by default (use `force_send_body()` if needed).
+  /// Returns a request builder with no body by default (use `force_send_body` if needed).
   pub fn delete(
     &self,
     url: impl Into<String>,
@@ -212,6 +238,22 @@ where
     )
   }
 
+  /// Start building a server-wide `OPTIONS *` request
+  ///
+  /// The URL only supplies the scheme, host and port; its path is ignored and the
+  /// request line uses the asterisk-form target (RFC 9112 Section 3.2.4).
+  pub fn options_server(
+    &self,
+    url: impl Into<String>,
+  ) -> ClientRequestBuilder<S, D, crate::request_builder::WithoutBody> {
+    ClientRequestBuilder::<S, D, crate::request_builder::WithoutBody>::new(
+      self.clone(),
+      crate::method::Method::Options,
+      url,
+    )
+    .with_target_form(TargetForm::Asterisk)
+  }
+
   /// Start building a PATCH request
   ///
   /// Returns a request builder that requires a request body.
@@ -225,6 +267,10 @@ where
   /// Start building a TRACE request
   ///
   /// Returns a request builder that enforces no request body.
+  ///
+  /// The server echoes the request back in the response, so `Cookie`,
+  /// `Authorization` and `Proxy-Authorization` are not sent unless
+  /// `Config::trace_sends_credentials` is set.
   pub fn trace(
     &self,
     url: impl Into<String>,
@@ -238,6 +284,9 @@ where
 
   /// Start building a CONNECT request
   ///
+  /// The request line uses the authority-form target `host:port` taken from the URL
+  /// (RFC 9112 Section 3.2.3); any path in the URL is ignored.
+  ///
   /// Returns a request builder that enforces no request body.
   pub fn connect(
     &self,
@@ -250,6 +299,104 @@ where
     )
   }
 
+  /// Cache GET responses in `cache` and serve HEAD/GET requests from it
+  ///
+  /// Honors `Cache-Control: max-age`, `no-store` and `no-cache` plus `Expires`.
+  /// Stale entries with an `ETag` or `Last-Modified` are revalidated with a
+  /// conditional request, and a 304 reply is answered from the cached body.
+  /// Clones of this client share the cache.
+  #[cfg(feature = "cache")]
+  #[must_use]
+  pub fn with_cache(
+    mut self,
+    cache: Arc<dyn HttpCache>,
+  ) -> Self {
+    self.cache = Some(cache);
+    self
+  }
+
+  /// Use `clock` as the time source for cache freshness, request timing,
+  /// pooled connection expiry and cookie expiry
+  ///
+  /// Defaults to `OsClock` on Unix and Windows. Other targets have no clock
+  /// until one is set here. A cookie store held only by this client, such as
+  /// the default one before the client is cloned, switches to `clock` too.
+  /// A store shared with other clients cannot be switched and keeps its own
+  /// clock, so set that with `CookieStore::with_clock` before sharing it, and
+  /// call this before cloning the client.
+  #[must_use]
+  pub fn with_clock(
+    mut self,
+    clock: Arc<dyn Clock>,
+  ) -> Self {
+    #[cfg(feature = "cookie-jar")]
+    {
+      if let Some(store) = Arc::get_mut(&mut self.cookie_store) {
+        store.set_clock(Arc::clone(&clock));
+      }
+    }
+    self.clock = Some(clock);
+    self
+  }
+
+  /// Time source used by this client, if any
+  pub(crate) fn clock(&self) -> Option<Arc<dyn Clock>> {
+    self.clock.clone()
+  }
+
+  /// Number of idle connections currently kept for reuse
+  ///
+  /// Clones of this client share the pool.
+  #[must_use]
+  pub fn pool_len(&self) -> usize {
+    self.pool.len()
+  }
+
+  /// Shut down and drop every idle pooled connection
+  ///
+  /// Useful after a network change, when pooled connections are likely dead.
+  /// Connections in use by running requests are not affected.
+  pub fn clear_pool(&self) {
+    self.pool.clear();
+  }
+
+  /// Connection reuse counters since the pool was created
+  ///
+  /// Only requests made with `connection_pooling` enabled are counted.
+  #[must_use]
+  pub fn pool_stats(&self) -> PoolStats {
+    self.pool.stats()
+  }
+
+  /// Send hand-written request bytes to the host of `url` and read back the raw response
+  ///
+  /// The bytes are written exactly as given: no header is added and nothing is
+  /// validated, so this can reproduce requests the request builder refuses to
+  /// make. The connection goes straight to the host, bypassing any proxy, and
+  /// is closed afterwards. Redirects, status errors, cookies and the cache are
+  /// not applied, and the body keeps its transfer and content codings.
+  ///
+  /// # Errors
+  /// Returns an error if URL parsing, DNS resolution, socket connection, or reading the response fails.
+  pub fn send_raw(
+    &self,
+    url: &str,
+    request_bytes: &[u8],
+  ) -> Result<RawResponse, Error> {
+    let uri = Uri::parse(url).map_err(Error::Parse)?;
+    RequestPolicy::new(&self.config).validate_protocol(&uri)?;
+    RequestExecutor::new(&self.pool, self.dns.as_ref(), &self.config)
+      .with_clock(self.clock.as_deref())
+      .with_deadline(self.deadline(&self.config))
+      .execute_raw(&uri, request_bytes)
+  }
+
+  /// Get the configuration shared by requests from this client
+  #[must_use]
+  pub fn config(&self) -> &Config {
+    &self.config
+  }
+
   /// Get reference to the cookie store (requires cookie-jar feature)
   ///
   /// Returns a reference to the Arc-wrapped cookie store.
@@ -259,6 +406,19 @@ where
     &self.cookie_store
   }
 
+  /// Use `store` for cookies, e.g. one from `CookieStore::with_max_cookies`
+  ///
+  /// Clients given the same store share their cookies.
+  #[cfg(feature = "cookie-jar")]
+  #[must_use]
+  pub fn with_cookie_store(
+    mut self,
+    store: Arc<CookieStore>,
+  ) -> Self {
+    self.cookie_store = store;
+    self
+  }
+
   /// Execute a `Request` object
   ///
   /// # Errors
@@ -268,16 +428,88 @@ where
     request: crate::request::Request,
   ) -> Result<Response, Error> {
     let (method, url, headers, body) = request.into_parts();
-    self.request(method, &url, &headers, body.map(Body::into_bytes), None)
+    self.request(
+      method,
+      &url,
+      &headers,
+      body,
+      TargetForm::Origin,
+      None,
+      None,
+      None,
+      TransferProgress::default(),
+      None,
+    )
+  }
+
+  /// Resolve a request URL against `config.base_url`, if one is set, and
+  /// encode characters not allowed in it when `config.auto_encode_url` is on
+  fn resolve_url(
+    config: &Config,
+    url: &str,
+  ) -> Result<String, Error> {
+    let resolved = config.base_url.as_deref().map_or_else(
+      || Ok(String::from(url)),
+      |base| {
+        Uri::parse(base)
+          .and_then(|base_uri| base_uri.resolve_relative(url))
+          .map_err(Error::Parse)
+      },
+    )?;
+    Ok(if config.auto_encode_url {
+      encode_unsafe_chars(&resolved)
+    } else {
+      resolved
+    })
+  }
+
+  /// Host and port `uri` connects to, with the host lowercased
+  fn authority_of(
+    config: &Config,
+    uri: &Uri,
+  ) -> Option<(String, u16)> {
+    uri.authority().map(|authority| {
+      let host = match authority.host() {
+        crate::parser::uri::Host::RegName(name) => name.to_ascii_lowercase(),
+        crate::parser::uri::Host::IpAddr(addr) => addr.to_authority_string(None),
+      };
+      (
+        host,
+        authority
+          .port()
+          .unwrap_or_else(|| config.default_port(uri.scheme())),
+      )
+    })
+  }
+
+  /// Clock time at which a request started now runs out of `config.timeout`
+  fn deadline(
+    &self,
+    config: &Config,
+  ) -> Option<u64> {
+    config
+      .timeout
+      .zip(self.clock.as_ref())
+      .map(|(timeout, clock)| {
+        let timeout_ms = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+        clock.now_millis().saturating_add(timeout_ms)
+      })
   }
 
   /// Internal request execution with clean orchestration
   ///
   /// This method orchestrates the high-level request flow:
   /// - Redirect loop handling
+  /// - Cancellation checks between redirect hops
   /// - Policy validation and decisions
   /// - Delegation to `RequestExecutor` for actual HTTP execution
   ///
+  /// With `sink` set, the final response's body is streamed to it, with its
+  /// content codings decoded on the way, when `RequestPolicy::streams_body`
+  /// allows; otherwise it stays in the response.
+  /// `progress` callbacks see every hop, including redirects, and `signer`
+  /// signs each request that is sent.
+  ///
   /// # Errors
   /// Returns an error if URL parsing, DNS resolution, socket connection, or HTTP communication fails.
   pub(crate) fn request(
@@ -285,44 +517,108 @@ where
     method: crate::method::Method,
     url: &str,
     custom_headers: &crate::headers::Headers,
-    body: Option<Vec<u8>>,
+    body: Option<Body>,
+    target: TargetForm,
     request_config: Option<&Config>,
+    cancel: Option<&CancelToken>,
+    mut sink: Option<&mut dyn ByteSink>,
+    progress: TransferProgress<'_>,
+    signer: Option<&dyn Fn(&mut SignableRequest<'_>)>,
   ) -> Result<Response, Error> {
     let config = request_config.unwrap_or_else(|| self.config.as_ref());
-    let mut current_url = String::from(url);
+    let mut current_url = Self::resolve_url(config, url)?;
     let mut current_method = method;
     let mut current_body = body;
+    let mut current_target = target;
 
     let mut policy = RequestPolicy::new(config);
+    let mut original_authority = None;
+
+    // The total timeout covers every redirect hop, so its deadline is fixed up front
+    let deadline = self.deadline(config);
 
     loop {
+      if cancel.is_some_and(CancelToken::is_cancelled) {
+        return Err(Error::Cancelled);
+      }
+
       // Parse and validate URL
+      #[cfg(feature = "idna")]
+      {
+        current_url = crate::parser::uri::encode_idna_host(&current_url);
+      }
       let uri = Uri::parse(&current_url).map_err(Error::Parse)?;
       policy.validate_protocol(&uri)?;
 
+      // Headers are only copied when something needs to change them
+      let mut headers_to_use = Cow::Borrowed(custom_headers);
+
+      // A Host override names the original server, so it is dropped once a
+      // redirect leads to another authority
+      let hop_authority = Self::authority_of(config, &uri);
+      if *original_authority.get_or_insert_with(|| hop_authority.clone()) != hop_authority
+        && custom_headers.contains(crate::headers::HeaderName::HOST)
+      {
+        headers_to_use
+          .to_mut()
+          .remove(crate::headers::HeaderName::HOST);
+      }
+
       // Add cookies to request headers if cookie-jar feature is enabled
       #[cfg(feature = "cookie-jar")]
-      let mut headers_with_cookies = custom_headers.clone();
-      #[cfg(feature = "cookie-jar")]
       {
         let is_secure = current_url.starts_with("https://");
         let cookie_header = self
           .cookie_store
           .get_request_cookies(&current_url, is_secure);
         if !cookie_header.is_empty() {
-          headers_with_cookies.insert(crate::headers::HeaderName::COOKIE, &cookie_header);
+          headers_to_use
+            .to_mut()
+            .insert(crate::headers::HeaderName::COOKIE, &cookie_header);
         }
       }
 
-      #[cfg(feature = "cookie-jar")]
-      let headers_to_use = &headers_with_cookies;
-      #[cfg(not(feature = "cookie-jar"))]
-      let headers_to_use = custom_headers;
+      // Serve fresh cache hits directly; revalidate stale entries that have validators
+      #[cfg(feature = "cache")]
+      let mut revalidating = None;
+      #[cfg(feature = "cache")]
+      if let Some(entry) = self.cached_entry(current_method, &current_url, &headers_to_use) {
+        // Without a clock freshness is unknown, so every entry is revalidated
+        if self
+          .clock
+          .as_ref()
+          .is_some_and(|clock| entry.is_fresh(clock.now_millis()))
+        {
+          return Ok(cache::serve(entry, current_method));
+        }
+        if cache::add_validators(&entry, headers_to_use.to_mut()) {
+          revalidating = Some(entry);
+        }
+      }
 
       // Execute single HTTP request
-      let executor = RequestExecutor::new(&self.pool, self.dns.as_ref(), config);
-      let body_slice = current_body.as_deref();
-      let raw = executor.execute(&uri, current_method, headers_to_use, body_slice)?;
+      let executor = RequestExecutor::new(&self.pool, self.dns.as_ref(), config)
+        .with_clock(self.clock.as_deref())
+        .with_deadline(deadline);
+      let body_slice = current_body.as_ref().map(Body::as_bytes);
+      let accept = |status_code: u16, headers: &crate::headers::Headers| policy.streams_body(status_code, headers);
+      let body_sink = sink.as_deref_mut().map(|destination| BodySink {
+        sink: destination,
+        accept: &accept,
+      });
+      let raw = executor.execute(
+        &uri,
+        current_method,
+        &headers_to_use,
+        body_slice,
+        current_target,
+        cancel,
+        body_sink,
+        progress,
+        signer,
+      )?;
+      #[cfg(feature = "cache")]
+      let body_streamed = raw.body_streamed;
 
       // Store cookies from response if cookie-jar feature is enabled
       #[cfg(feature = "cookie-jar")]
@@ -343,7 +639,17 @@ where
 
       // Process response and make policy decision
       match policy.process_raw_response(raw, &uri, &current_url, current_method, current_body)? {
-        PolicyDecision::Return(response) => return Ok(response),
+        PolicyDecision::Return(response) => {
+          // A streamed body is not in memory, so there is nothing to store
+          #[cfg(feature = "cache")]
+          if body_streamed {
+            return Ok(response);
+          }
+          #[cfg(feature = "cache")]
+          return Ok(self.update_cache(&current_url, current_method, &headers_to_use, response, revalidating));
+          #[cfg(not(feature = "cache"))]
+          return Ok(response);
+        },
         PolicyDecision::Redirect {
           next_uri,
           next_method,
@@ -352,8 +658,83 @@ where
           current_url = next_uri;
           current_method = next_method;
           current_body = next_body;
+          // A redirect names a concrete resource, so later hops use origin-form
+          current_target = TargetForm::Origin;
         },
       }
     }
   }
+
+  /// Stored entry for a GET or HEAD request, if caching is enabled
+  ///
+  /// An entry stored for different values of its `Vary` headers is not used.
+  #[cfg(feature = "cache")]
+  fn cached_entry(
+    &self,
+    method: crate::method::Method,
+    url: &str,
+    headers: &crate::headers::Headers,
+  ) -> Option<cache::CachedResponse> {
+    if !matches!(method, crate::method::Method::Get | crate::method::Method::Head) {
+      return None;
+    }
+    self
+      .cache
+      .as_ref()?
+      .get(url)
+      .filter(|entry| entry.matches(headers))
+  }
+
+  /// Store, refresh or invalidate the cache entry for a completed request
+  ///
+  /// Returns the response to hand to the caller: the cached one when a
+  /// revalidation came back 304, otherwise `response` itself.
+  #[cfg(feature = "cache")]
+  fn update_cache(
+    &self,
+    url: &str,
+    method: crate::method::Method,
+    headers: &crate::headers::Headers,
+    response: Response,
+    revalidating: Option<cache::CachedResponse>,
+  ) -> Response {
+    use crate::method::Method;
+
+    let Some(cache) = self.cache.as_ref() else {
+      return response;
+    };
+    let now = self.clock.as_ref().map_or(0, |clock| clock.now_millis());
+
+    if let Some(entry) = revalidating
+      && response.status_code == 304
+    {
+      let refreshed = cache::refresh(entry, &response, now);
+      cache.put(url, refreshed.clone());
+      return cache::serve(refreshed, method);
+    }
+
+    match method {
+      // A 304 to the caller's own conditional request says nothing new about the entry
+      Method::Get if response.status_code == 304 => {},
+      Method::Get => match cache::entry_for(&response, headers, now) {
+        Some(entry) => cache.put(url, entry),
+        None => cache.remove(url),
+      },
+      Method::Head => {},
+      // RFC 9111 Section 4.4: a successful unsafe request invalidates the stored response
+      _ if (200..400).contains(&response.status_code) => cache.remove(url),
+      _ => {},
+    }
+    response
+  }
+}
+
+/// Cookie store for a new client, timed by the OS clock where there is one
+#[cfg(feature = "cookie-jar")]
+fn default_cookie_store() -> Arc<CookieStore> {
+  let store = CookieStore::new();
+  Arc::new(match clock::os_clock() {
+    Some(os_clock) => store.with_clock(os_clock),
+    None => store,
+  })
 }
@@ -3,6 +3,7 @@ mod policy;
 mod request_executor;
 
 pub use http_client::HttpClient;
+pub use request_executor::TargetForm;
 
 #[cfg(test)]
 pub mod tests;
@@ -1,10 +1,14 @@
 use crate::body::Body;
-use crate::config::{Config, HttpStatusHandling, ProtocolRestriction, RedirectPolicy};
+use crate::config::{Config, ProtocolRestriction, RedirectBodyPolicy, RedirectPolicy};
 use crate::error::Error;
+use crate::headers::{HeaderName, Headers};
 use crate::method::Method;
 use crate::parser::Response;
-use crate::parser::uri::Uri;
+use crate::parser::content_coding::ContentDecoder;
+use crate::parser::uri::{Host, Uri};
 use crate::transport::RawResponse;
+use crate::util::IpAddr;
+use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -15,14 +19,31 @@ pub enum PolicyDecision {
   Redirect {
     next_uri: String,
     next_method: Method,
-    next_body: Option<Vec<u8>>,
+    next_body: Option<Body>,
   },
 }
 
+/// Host visited by a redirect chain, compared case-insensitively for names
+#[derive(Debug, PartialEq, Eq)]
+enum VisitedHost {
+  Name(String),
+  Ip(IpAddr),
+}
+
+impl VisitedHost {
+  fn of(uri: &Uri) -> Option<Self> {
+    uri.authority().map(|authority| match authority.host() {
+      Host::RegName(name) => Self::Name(name.to_ascii_lowercase()),
+      Host::IpAddr(addr) => Self::Ip(*addr),
+    })
+  }
+}
+
 /// Request policy handler for status codes and redirects
 pub struct RequestPolicy {
   config: Config,
   visited_urls: Vec<String>,
+  visited_hosts: Vec<VisitedHost>,
   redirect_count: u32,
 }
 
@@ -31,10 +52,36 @@ impl RequestPolicy {
     Self {
       config: config.clone(),
       visited_urls: Vec::new(),
+      visited_hosts: Vec::new(),
       redirect_count: 0,
     }
   }
 
+  /// Remember the hosts of `current` and `next`, failing once more distinct
+  /// hosts than `max_redirect_hosts` have been seen
+  fn track_redirect_hosts(
+    &mut self,
+    current: &Uri,
+    next_url: &str,
+  ) -> Result<(), Error> {
+    let Some(max_hosts) = self.config.max_redirect_hosts else {
+      return Ok(());
+    };
+    let next = Uri::parse(next_url).map_err(Error::Parse)?;
+    for host in [VisitedHost::of(current), VisitedHost::of(&next)]
+      .into_iter()
+      .flatten()
+    {
+      if !self.visited_hosts.contains(&host) {
+        self.visited_hosts.push(host);
+      }
+    }
+    if self.visited_hosts.len() > max_hosts {
+      return Err(Error::TooManyRedirectHosts);
+    }
+    Ok(())
+  }
+
   /// Validate protocol restrictions (HTTPS-only enforcement)
   pub fn validate_protocol(
     &self,
@@ -46,6 +93,61 @@ impl RequestPolicy {
     Ok(())
   }
 
+  /// Check whether a response's body can go straight to a caller's `ByteSink`
+  ///
+  /// Only final responses are streamed: not error statuses, which keep their
+  /// body in `Error::HttpStatus`, and not redirects that will be followed.
+  /// Returns the decoder for the content codings to remove on the way, or
+  /// None when the body stays in memory. A coding that cannot be decoded
+  /// keeps the body in memory, so `process_raw_response` reports it.
+  pub fn streams_body(
+    &self,
+    status_code: u16,
+    headers: &Headers,
+  ) -> Option<ContentDecoder> {
+    let follows_redirect = self.config.redirect_policy != RedirectPolicy::NoFollow && is_followed_redirect(status_code);
+    if self.config.http_status_handling.is_error(status_code) || follows_redirect {
+      return None;
+    }
+    if !self.decodes_content(headers) {
+      return Some(ContentDecoder::default());
+    }
+    ContentDecoder::for_headers(headers, self.config.max_decompressed_size).ok()
+  }
+
+  /// Check whether a response's `Content-Encoding` should be decoded
+  ///
+  /// Requires `auto_decompress`, a decompression feature, and a `Content-Type`
+  /// listed in `decompress_content_types` when that is set.
+  fn decodes_content(
+    &self,
+    headers: &Headers,
+  ) -> bool {
+    if cfg!(not(any(feature = "gzip-decompression", feature = "zstd-decompression"))) {
+      return false;
+    }
+    let Some(types) = &self.config.decompress_content_types else {
+      return self.config.auto_decompress;
+    };
+    let media_type = headers
+      .get(HeaderName::CONTENT_TYPE)
+      .and_then(|value| value.split(';').next())
+      .unwrap_or_default()
+      .trim();
+    self.config.auto_decompress
+      && !media_type.is_empty()
+      && types.iter().any(|listed| {
+        listed.strip_suffix("/*").map_or_else(
+          || listed.eq_ignore_ascii_case(media_type),
+          |top_level| {
+            media_type
+              .split_once('/')
+              .is_some_and(|(kind, _)| kind.eq_ignore_ascii_case(top_level))
+          },
+        )
+      })
+  }
+
   /// Process raw response and decide what to do next
   ///
   /// This method encapsulates all policy decisions:
@@ -59,14 +161,21 @@ impl RequestPolicy {
     current_uri: &Uri,
     current_url: &str,
     current_method: Method,
-    current_body: Option<Vec<u8>>,
+    current_body: Option<Body>,
   ) -> Result<PolicyDecision, Error> {
     let is_head_request = current_method == Method::Head;
 
-    let response_body = if is_head_request {
+    let response_body = if is_head_request || raw.body_streamed {
       Body::from_bytes(Vec::new())
     } else {
-      Response::parse_body_from_bytes(&raw.body_bytes, &raw.headers, raw.status_code).map_err(Error::Parse)?
+      Response::parse_body_from_bytes(
+        &raw.body_bytes,
+        &raw.headers,
+        raw.status_code,
+        self.decodes_content(&raw.headers),
+        self.config.max_decompressed_size,
+      )
+      .map_err(Error::Parse)?
     };
 
     let response = Response {
@@ -74,20 +183,28 @@ impl RequestPolicy {
       reason: raw.reason,
       headers: raw.headers,
       body: response_body,
-      trailers: Vec::new(), // No trailers in two-phase reading
+      trailers: raw.trailers,
+      remote_addr: raw.remote_addr,
+      truncated: false,
+      url: Some(String::from(current_url)),
     };
 
-    if self.config.http_status_handling == HttpStatusHandling::AsError
-      && (response.status_code >= 400 && response.status_code < 600)
+    if self
+      .config
+      .http_status_handling
+      .is_error(response.status_code)
     {
-      return Err(Error::HttpStatus(response.status_code));
+      return Err(Error::HttpStatus {
+        code: response.status_code,
+        response: Box::new(response),
+      });
     }
 
     if self.config.redirect_policy == RedirectPolicy::NoFollow {
       return Ok(PolicyDecision::Return(response));
     }
 
-    if response.status_code >= 300 && response.status_code < 400 {
+    if is_followed_redirect(response.status_code) {
       if self.redirect_count >= self.config.max_redirects {
         if self.config.redirect_policy == RedirectPolicy::Follow {
           return Err(Error::TooManyRedirects);
@@ -111,13 +228,19 @@ impl RequestPolicy {
       {
         return Err(Error::RedirectLoop);
       }
+      self.track_redirect_hosts(current_uri, &next_url)?;
 
       self.visited_urls.push(String::from(current_url));
 
-      let (next_method, next_body) = if response.status_code == 303
-        || (response.status_code == 301 || response.status_code == 302) && current_method == Method::Post
-      {
+      let moved = matches!(response.status_code, 301 | 302);
+      let (next_method, next_body) = if response.status_code == 303 || moved && current_method == Method::Post {
         (Method::Get, None)
+      } else if moved && current_body.is_some() {
+        match self.config.redirect_body_policy {
+          RedirectBodyPolicy::Drop => (current_method, None),
+          RedirectBodyPolicy::Keep => (current_method, current_body),
+          RedirectBodyPolicy::Error => return Err(Error::RedirectBodyRefused),
+        }
       } else {
         (current_method, current_body)
       };
@@ -134,3 +257,12 @@ impl RequestPolicy {
     Ok(PolicyDecision::Return(response))
   }
 }
+
+/// Check whether a status code redirects to its `Location` and so must carry one
+///
+/// Other 3xx codes are final: RFC 9110 Section 15.4.1 leaves the choice of a
+/// 300 alternative to the user agent, and Section 15.4.5 makes 304 an answer
+/// to a conditional request. They are returned even without `Location`.
+const fn is_followed_redirect(status_code: u16) -> bool {
+  matches!(status_code, 301 | 302 | 303 | 307 | 308)
+}
@@ -6,24 +6,41 @@
 /// - Request serialization
 /// - Response reading
 /// - Connection reuse logic
+use crate::cancel::CancelToken;
+use crate::clock::Clock;
 use crate::config::Config;
 use crate::dns::DnsResolver;
-use crate::error::Error;
+use crate::error::{Error, ParseError};
 use crate::headers::{HeaderName, Headers};
 use crate::method::Method;
 use crate::parser::RequestBuilder as ParserRequestBuilder;
 use crate::parser::uri::Uri;
+use crate::request::SignableRequest;
 use crate::socket::BlockingSocket;
-use crate::transport::{ConnectionPool, Connector, PoolKey, RawResponse, ResponseBodyExpectation};
+use crate::transport::{
+  BodySink, ConnectionPool, Connector, PoolKey, RawResponse, ResponseBodyExpectation, TransferProgress,
+};
 use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 
+/// Form of the request-target written on the request line (RFC 9112 Section 3.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TargetForm {
+  /// Path and query of the URI (`GET /path?q HTTP/1.1`)
+  #[default]
+  Origin,
+  /// Server-wide `OPTIONS * HTTP/1.1`
+  Asterisk,
+}
+
 /// Executes a single HTTP request without redirect handling
 pub struct RequestExecutor<'a, S, D> {
   pool: &'a Arc<ConnectionPool<S>>,
   dns: &'a D,
   config: &'a Config,
+  clock: Option<&'a dyn Clock>,
+  deadline: Option<u64>,
 }
 
 impl<'a, S, D> RequestExecutor<'a, S, D>
@@ -36,32 +53,103 @@ where
     dns: &'a D,
     config: &'a Config,
   ) -> Self {
-    Self { pool, dns, config }
+    Self {
+      pool,
+      dns,
+      config,
+      clock: None,
+      deadline: None,
+    }
+  }
+
+  /// Time pooled connections with `clock` so idle ones expire
+  #[must_use]
+  pub const fn with_clock(
+    mut self,
+    clock: Option<&'a dyn Clock>,
+  ) -> Self {
+    self.clock = clock;
+    self
+  }
+
+  /// Fail response reads once the clock reaches `deadline_millis`
+  ///
+  /// Has no effect without a clock.
+  #[must_use]
+  pub const fn with_deadline(
+    mut self,
+    deadline_millis: Option<u64>,
+  ) -> Self {
+    self.deadline = deadline_millis;
+    self
   }
 
   /// Execute a single HTTP request and return raw response
+  ///
+  /// With `body_sink` set, a body it accepts is streamed to it instead of being buffered.
+  /// `progress` is told about request and response body bytes as they are transferred.
+  /// `signer` sees the finished header section just before it is serialized.
   pub fn execute(
     &self,
     uri: &Uri,
     method: Method,
     custom_headers: &Headers,
     body: Option<&[u8]>,
+    target: TargetForm,
+    cancel: Option<&CancelToken>,
+    body_sink: Option<BodySink<'_>>,
+    progress: TransferProgress<'_>,
+    signer: Option<&dyn Fn(&mut SignableRequest<'_>)>,
   ) -> Result<RawResponse, Error> {
+    // RFC 9110 Section 9.3.8: a client must not send content in a TRACE request
+    if method == Method::Trace && body.is_some_and(|content| !content.is_empty()) {
+      return Err(Error::Parse(ParseError::BodyNotAllowed));
+    }
+
     // Extract host information from URI (copy to avoid lifetime issues)
-    let host_str = Self::extract_host_from_uri(uri)?;
-    let port = Self::extract_port_from_uri(uri);
-    let pool_key = PoolKey::new(host_str.clone(), port);
+    let host_str = Self::extract_host_from_uri(uri);
+    let port = self.extract_port_from_uri(uri);
 
-    // Get or create socket
-    let mut socket = self.get_or_create_socket(&pool_key)?;
+    // With a forward proxy configured, the connection goes to the proxy instead
+    let proxy_uri = self
+      .config
+      .proxy
+      .as_deref()
+      .map(Uri::parse)
+      .transpose()
+      .map_err(Error::Parse)?;
+    // CONNECT tunnels are not supported, so an https request would reach the
+    // proxy as cleartext
+    if proxy_uri.is_some() && uri.scheme() == "https" {
+      return Err(Error::ProxyTunnelUnsupported);
+    }
+    let connect_uri = proxy_uri.as_ref().unwrap_or(uri);
+    let pool_key = PoolKey::from_uri(connect_uri, self.config)?;
 
-    // Establish connection
+    // A pooled socket is still connected (and past any TLS handshake), so it
+    // is only set up again; connecting it a second time would break it
+    let (mut socket, pooled) = self.get_or_create_socket(&pool_key)?;
     let connector = Connector::new(&mut socket, self.dns);
-    let mut conn = connector.connect(uri, self.config)?;
+    if proxy_uri.is_some() {
+      connector.check_proxied_target(uri, self.config)?;
+    }
+    let connected = if pooled {
+      connector.resume(self.config)?
+    } else {
+      connector.connect(connect_uri, self.config)?
+    };
+    let mut conn = connected
+      .with_cancel_token(cancel.cloned())
+      .with_progress(progress);
+    if let (Some(clock), Some(deadline_millis)) = (self.clock, self.deadline) {
+      conn = conn.with_deadline(clock, deadline_millis);
+    }
 
-    // Build and send request
-    let request_bytes = self.build_request(uri, method, &host_str, port, custom_headers, body)?;
-    conn.send_request(&request_bytes)?;
+    // Build and send the head; the body goes out separately, straight from the
+    // caller's buffer, so its progress can be reported
+    let head = self.build_request(uri, method, target, &host_str, port, custom_headers, body, signer)?;
+    conn.send_request(&head)?;
+    conn.send_body(body.unwrap_or_default())?;
 
     // Read response
     let expectation = if method == Method::Head {
@@ -69,84 +157,146 @@ where
     } else {
       ResponseBodyExpectation::Normal
     };
-    let raw = conn.read_raw_response(expectation)?;
+    let mut final_sink = body_sink;
+    let raw = loop {
+      let sink = final_sink.as_mut().map(|destination| BodySink {
+        sink: &mut *destination.sink,
+        accept: destination.accept,
+      });
+      let raw = conn.read_raw_response_into(expectation, sink)?;
+      // RFC 9110 Section 15.2: interim responses are dropped while waiting for
+      // the final one; after 101 the connection no longer speaks HTTP
+      if !matches!(raw.status_code, 100 | 102..=199) {
+        break raw;
+      }
+    };
 
-    // Handle connection pooling
-    self.handle_connection_reuse(conn.is_reusable(), pool_key, socket);
+    // After a 2xx answer to CONNECT the connection is a tunnel, not HTTP
+    let tunnel = method == Method::Connect && (200..300).contains(&raw.status_code);
+    self.handle_connection_reuse(conn.is_reusable() && !tunnel, pool_key, socket);
 
     Ok(raw)
   }
 
-  /// Extract hostname from URI
-  fn extract_host_from_uri(uri: &Uri) -> Result<String, Error> {
-    let authority = uri.authority();
-    authority.map_or_else(
-      || Ok(String::new()),
-      |auth| match auth.host() {
-        crate::parser::uri::Host::RegName(name) => Ok(String::from(*name)),
-        crate::parser::uri::Host::IpAddr(_) => Err(Error::IpAddressNotSupported),
-      },
-    )
+  /// Send `request_bytes` unchanged to the host of `uri` and read the response
+  ///
+  /// A new connection is always opened and never pooled, since nothing is known
+  /// about what the bytes asked for. The response is read as an answer to a
+  /// request that allows a body, unless the bytes start with `HEAD `.
+  pub fn execute_raw(
+    &self,
+    uri: &Uri,
+    request_bytes: &[u8],
+  ) -> Result<RawResponse, Error> {
+    let mut socket = S::new().map_err(Error::Socket)?;
+    let connector = Connector::new(&mut socket, self.dns);
+    let mut conn = connector.connect(uri, self.config)?;
+    if let (Some(clock), Some(deadline_millis)) = (self.clock, self.deadline) {
+      conn = conn.with_deadline(clock, deadline_millis);
+    }
+
+    conn.send_request(request_bytes)?;
+    let expectation = if request_bytes.starts_with(b"HEAD ") {
+      ResponseBodyExpectation::NoBody
+    } else {
+      ResponseBodyExpectation::Normal
+    };
+    conn.read_raw_response(expectation)
   }
 
-  /// Extract port from URI with defaults
-  fn extract_port_from_uri(uri: &Uri) -> u16 {
+  /// Extract hostname from URI, with IPv6 literals in brackets
+  fn extract_host_from_uri(uri: &Uri) -> String {
     uri
       .authority()
-      .and_then(super::super::parser::uri::Authority::port)
-      .unwrap_or_else(|| {
-        if uri.scheme() == "https" {
-          443
-        } else {
-          80
-        }
+      .map_or_else(String::new, |auth| match auth.host() {
+        crate::parser::uri::Host::RegName(name) => String::from(*name),
+        crate::parser::uri::Host::IpAddr(addr) => addr.to_authority_string(None),
       })
   }
 
-  /// Get socket from pool or create new one
+  /// Extract port from URI, falling back to the scheme's default port
+  fn extract_port_from_uri(
+    &self,
+    uri: &Uri,
+  ) -> u16 {
+    uri
+      .authority()
+      .and_then(super::super::parser::uri::Authority::port)
+      .unwrap_or_else(|| self.config.default_port(uri.scheme()))
+  }
+
+  /// Get socket from pool or create new one, and whether it came from the pool
   fn get_or_create_socket(
     &self,
     pool_key: &PoolKey,
-  ) -> Result<S, Error> {
-    if self.config.connection_pooling {
-      self
-        .pool
-        .get(pool_key)
-        .map_or_else(|| S::new().map_err(Error::Socket), |s| Ok(s))
+  ) -> Result<(S, bool), Error> {
+    let pooled = if self.config.connection_pooling {
+      self.pool.get(pool_key, self.clock)
     } else {
-      S::new().map_err(Error::Socket)
-    }
+      None
+    };
+    pooled.map_or_else(
+      || {
+        S::new()
+          .map(|socket| (socket, false))
+          .map_err(Error::Socket)
+      },
+      |socket| Ok((socket, true)),
+    )
   }
 
-  /// Build HTTP request bytes
-  fn build_request(
+  /// Build the request line and header section for `body`, which is sent separately
+  pub(crate) fn build_request(
     &self,
     uri: &Uri,
     method: Method,
+    target: TargetForm,
     host_str: &str,
     port: u16,
     custom_headers: &Headers,
     body: Option<&[u8]>,
+    signer: Option<&dyn Fn(&mut SignableRequest<'_>)>,
   ) -> Result<Vec<u8>, Error> {
     use alloc::format;
 
-    // Build Host header with port if non-default
-    let host_header = if (uri.scheme() == "http" && port == 80) || (uri.scheme() == "https" && port == 443) {
+    // URI authority with port if non-default
+    let authority = if port == self.config.default_port(uri.scheme()) {
       String::from(host_str)
     } else {
       format!("{host_str}:{port}")
     };
+    // A Host set on the request replaces the one derived from the URI
+    let host_header = custom_headers.get(HeaderName::HOST).unwrap_or(&authority);
+
+    // RFC 9112 Section 3.2.3: CONNECT names the tunnel endpoint in authority-form.
+    // RFC 9112 Section 3.2.2: a forward proxy needs the absolute-form of the URI.
+    // RFC 9112 Section 3.2.4: asterisk-form still needs the URI's host for the Host header.
+    let request_target = match (method, target) {
+      (Method::Connect, _) => format!("{host_str}:{port}"),
+      (_, TargetForm::Origin) if self.config.proxy.is_some() => {
+        let path_and_query = uri.path_and_query();
+        let separator = if path_and_query.starts_with('/') {
+          ""
+        } else {
+          "/"
+        };
+        format!("{}://{authority}{separator}{path_and_query}", uri.scheme())
+      },
+      (_, TargetForm::Origin) => uri.path_and_query(),
+      (_, TargetForm::Asterisk) => String::from("*"),
+    };
 
-    let mut builder =
-      ParserRequestBuilder::new(method.as_str(), &uri.path_and_query()).header(HeaderName::HOST, host_header.as_str());
+    let mut builder = ParserRequestBuilder::new(method.as_str(), &request_target).header(HeaderName::HOST, host_header);
 
     // RFC 9112 Section 9.3: Send Connection: close if pooling is disabled
     if !self.config.connection_pooling {
       builder = builder.header(HeaderName::CONNECTION, "close");
     }
 
-    // Add default headers from config
-    if let Some(ref user_agent) = self.config.user_agent {
+    // Add default headers from config; a User-Agent on the request wins
+    if let Some(ref user_agent) = self.config.user_agent
+      && !custom_headers.contains(HeaderName::USER_AGENT)
+    {
       builder = builder.header(HeaderName::USER_AGENT, user_agent.as_str());
     }
 
@@ -157,9 +307,19 @@ where
       builder = builder.header(HeaderName::ACCEPT, accept.as_str());
     }
 
-    // Add Accept-Encoding header based on enabled decompression features
+    if let Some(ref accept_language) = self.config.accept_language
+      && !custom_headers.contains(HeaderName::ACCEPT_LANGUAGE)
+    {
+      builder = builder.header(HeaderName::ACCEPT_LANGUAGE, accept_language.as_str());
+    }
+
+    // Add Accept-Encoding header from config, or based on enabled decompression features
     // Only add if user hasn't specified it in custom headers
-    if !custom_headers.contains(HeaderName::ACCEPT_ENCODING) {
+    if let Some(ref accept_encoding) = self.config.accept_encoding {
+      if !accept_encoding.is_empty() && !custom_headers.contains(HeaderName::ACCEPT_ENCODING) {
+        builder = builder.header(HeaderName::ACCEPT_ENCODING, accept_encoding.as_str());
+      }
+    } else if !custom_headers.contains(HeaderName::ACCEPT_ENCODING) {
       #[allow(unused_mut)]
       let mut encodings: Vec<&str> = Vec::new();
 
@@ -178,17 +338,56 @@ where
       }
     }
 
-    // Add custom headers
+    // Add configured default headers unless the request sets the same name
+    for (name, value) in &self.config.default_headers {
+      if !custom_headers.contains(name) {
+        builder = builder.header(name.as_str(), value.as_str());
+      }
+    }
+
+    // Add custom headers; Host was already written
     for (name, value) in custom_headers {
-      builder = builder.header(name.as_str(), value.as_str());
+      if !name.eq_ignore_ascii_case(HeaderName::HOST) {
+        builder = builder.header(name.as_str(), value.as_str());
+      }
     }
 
-    // Add body if present
+    // RFC 9110 Section 9.3.8: TRACE is reflected back to the client, so
+    // credentials are left out unless the configuration keeps them
+    if method == Method::Trace && !self.config.trace_sends_credentials {
+      let headers = builder.headers_mut();
+      headers.remove(HeaderName::COOKIE);
+      headers.remove(HeaderName::AUTHORIZATION);
+      headers.remove(HeaderName::PROXY_AUTHORIZATION);
+    }
+
+    if self.config.fold_duplicate_request_headers {
+      builder.headers_mut().fold_duplicates();
+    }
+    if self.config.strict_ascii_header_values {
+      builder = builder.strict_ascii_values();
+    }
+
+    // Add body if present; Content-Length is added here rather than by `build`
+    // so a signer can see it. A body framed by Transfer-Encoding gets none
+    // (RFC 9112 Section 6.2), and callers may frame it themselves
+    if self.config.omit_content_length {
+      builder = builder.without_content_length();
+    }
     if let Some(body_data) = body {
-      builder = builder.body(body_data.to_vec());
+      let headers = builder.headers_mut();
+      if !self.config.omit_content_length && !headers.contains(HeaderName::TRANSFER_ENCODING) {
+        headers.insert_if_absent("Content-Length", format!("{}", body_data.len()));
+      }
+    }
+
+    // Signing runs last so the signature covers every field that will be sent
+    if let Some(sign) = signer {
+      let mut request = SignableRequest::new(method, &request_target, builder.headers_mut(), body.unwrap_or_default());
+      sign(&mut request);
     }
 
-    builder.build().map_err(Error::Parse)
+    builder.build_head(body).map_err(Error::Parse)
   }
 
   /// Handle connection reuse based on pooling config
@@ -199,7 +398,7 @@ where
     socket: S,
   ) {
     if self.config.connection_pooling && is_reusable {
-      self.pool.return_connection(pool_key, socket);
+      self.pool.return_connection(pool_key, socket, self.clock);
     }
   }
 }
@@ -6,5 +6,12 @@
 #![allow(clippy::shadow_reuse)]
 #![allow(clippy::shadow_same)]
 
+mod scripted;
+#[cfg(feature = "cache")]
+mod test_cache;
 mod test_http_client;
 mod test_policy;
+#[cfg(feature = "test-util")]
+mod test_recording;
+mod test_request_executor;
+mod test_timing;
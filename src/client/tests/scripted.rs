@@ -0,0 +1,172 @@
+use crate::dns::DnsResolver;
+use crate::error::{DnsError, SocketError};
+use crate::socket::{BlockingSocket, SocketAddr, SocketFlags};
+use crate::util::IpAddr;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use spin::Mutex;
+
+// Sockets are created by the client, so scripts live in statics. Each test
+// uses its own slot to stay independent when tests run in parallel:
+// 0-2 and 40-41 test_cache, 3 test_timing, 4-29, 31-39 and 42-50 test_http_client, 30 test_recording.
+static REPLIES: [Mutex<Vec<&'static [u8]>>; 51] = [const { Mutex::new(Vec::new()) }; 51];
+static REQUESTS: [Mutex<Vec<Vec<u8>>>; 51] = [const { Mutex::new(Vec::new()) }; 51];
+static CONNECTS: [AtomicUsize; 51] = [const { AtomicUsize::new(0) }; 51];
+static TIMEOUTS: [Mutex<Vec<(&'static str, u32)>>; 51] = [const { Mutex::new(Vec::new()) }; 51];
+
+/// Socket answering each new connection with the next scripted reply
+///
+/// Like an OS socket, it can be bound and connected only once. A request
+/// written after the reply was read in full takes the next scripted reply,
+/// so a pooled socket answers each request it carries.
+pub struct ScriptedSocket<const SLOT: usize> {
+  reply: &'static [u8],
+  position: usize,
+  bound: bool,
+  connected: bool,
+}
+
+impl<const SLOT: usize> BlockingSocket for ScriptedSocket<SLOT> {
+  fn new() -> Result<Self, SocketError> {
+    let mut replies = REPLIES[SLOT].lock();
+    let reply = if replies.is_empty() {
+      &b""[..]
+    } else {
+      replies.remove(0)
+    };
+    REQUESTS[SLOT].lock().push(Vec::new());
+    Ok(Self {
+      reply,
+      position: 0,
+      bound: false,
+      connected: false,
+    })
+  }
+
+  fn bind(
+    &mut self,
+    _addr: &SocketAddr<'_>,
+  ) -> Result<(), SocketError> {
+    if self.bound || self.connected {
+      return Err(SocketError::InvalidAddress);
+    }
+    self.bound = true;
+    Ok(())
+  }
+
+  fn connect(
+    &mut self,
+    _addr: &SocketAddr<'_>,
+  ) -> Result<(), SocketError> {
+    if self.connected {
+      return Err(SocketError::InvalidAddress);
+    }
+    self.connected = true;
+    CONNECTS[SLOT].fetch_add(1, Ordering::AcqRel);
+    Ok(())
+  }
+
+  fn read(
+    &mut self,
+    buf: &mut [u8],
+  ) -> Result<usize, SocketError> {
+    let remaining = &self.reply[self.position..];
+    let count = remaining.len().min(buf.len());
+    buf[..count].copy_from_slice(&remaining[..count]);
+    self.position += count;
+    Ok(count)
+  }
+
+  fn write(
+    &mut self,
+    buf: &[u8],
+  ) -> Result<usize, SocketError> {
+    if !self.reply.is_empty() && self.position == self.reply.len() {
+      let mut replies = REPLIES[SLOT].lock();
+      if !replies.is_empty() {
+        self.reply = replies.remove(0);
+        self.position = 0;
+        REQUESTS[SLOT].lock().push(Vec::new());
+      }
+    }
+    if let Some(request) = REQUESTS[SLOT].lock().last_mut() {
+      request.extend_from_slice(buf);
+    }
+    Ok(buf.len())
+  }
+
+  fn shutdown(&mut self) -> Result<(), SocketError> {
+    Ok(())
+  }
+
+  fn set_flags(
+    &mut self,
+    _flags: SocketFlags,
+  ) -> Result<(), SocketError> {
+    Ok(())
+  }
+
+  fn set_read_timeout(
+    &mut self,
+    timeout_ms: u32,
+  ) -> Result<(), SocketError> {
+    TIMEOUTS[SLOT].lock().push(("read", timeout_ms));
+    Ok(())
+  }
+
+  fn set_write_timeout(
+    &mut self,
+    timeout_ms: u32,
+  ) -> Result<(), SocketError> {
+    TIMEOUTS[SLOT].lock().push(("write", timeout_ms));
+    Ok(())
+  }
+}
+
+pub struct LocalDns;
+
+impl DnsResolver for LocalDns {
+  fn resolve(
+    &self,
+    _hostname: &str,
+  ) -> Result<Vec<IpAddr>, DnsError> {
+    Ok(vec![IpAddr::V4([127, 0, 0, 1])])
+  }
+}
+
+/// Queue the replies for connections opened on `slot` and forget old requests
+pub fn script(
+  slot: usize,
+  replies: &[&'static [u8]],
+) {
+  *REPLIES[slot].lock() = replies.to_vec();
+  REQUESTS[slot].lock().clear();
+  CONNECTS[slot].store(0, Ordering::Release);
+  TIMEOUTS[slot].lock().clear();
+}
+
+/// Number of connections opened on `slot` since it was scripted
+pub fn connects(slot: usize) -> usize {
+  CONNECTS[slot].load(Ordering::Acquire)
+}
+
+/// Socket timeouts set on `slot` since it was scripted, in order
+pub fn timeouts(slot: usize) -> Vec<(&'static str, u32)> {
+  TIMEOUTS[slot].lock().clone()
+}
+
+/// Requests written on `slot`, one per request, lowercased
+pub fn requests(slot: usize) -> Vec<String> {
+  REQUESTS[slot]
+    .lock()
+    .iter()
+    .map(|request| String::from_utf8_lossy(request).to_ascii_lowercase())
+    .collect()
+}
+
+/// Requests written on `slot`, one per request, byte for byte
+pub fn raw_requests(slot: usize) -> Vec<Vec<u8>> {
+  REQUESTS[slot].lock().clone()
+}
@@ -0,0 +1,146 @@
+use crate::cache::MemoryCache;
+use crate::client::HttpClient;
+use crate::client::tests::scripted::{LocalDns, ScriptedSocket, requests, script};
+use crate::clock::ManualClock;
+use crate::config::{Config, ConfigBuilder};
+use alloc::sync::Arc;
+
+fn client<const SLOT: usize>(
+  replies: &[&'static [u8]],
+  clock: &Arc<ManualClock>,
+) -> HttpClient<ScriptedSocket<SLOT>, LocalDns> {
+  script(SLOT, replies);
+  let config: Config = ConfigBuilder::new().connection_pooling(false).build();
+  HttpClient::with_adapters_and_config(LocalDns, config)
+    .with_cache(Arc::new(MemoryCache::new(8)))
+    .with_clock(clock.clone())
+}
+
+#[test]
+fn fresh_response_is_served_without_contacting_origin() {
+  let clock = Arc::new(ManualClock::new(1_000));
+  let client = client::<0>(
+    &[b"HTTP/1.1 200 OK\r\nCache-Control: max-age=60\r\nContent-Length: 6\r\n\r\ncached"],
+    &clock,
+  );
+
+  let first = client.get("http://example.com/data").call().unwrap();
+  clock.advance(59_000);
+  let second = client.get("http://example.com/data").call().unwrap();
+  let head = client.head("http://example.com/data").call().unwrap();
+
+  assert_eq!(first.body.as_bytes(), b"cached");
+  assert_eq!(second.body.as_bytes(), b"cached");
+  assert!(head.body.is_empty());
+  assert_eq!(requests(0).len(), 1);
+}
+
+#[test]
+fn stale_entry_is_revalidated_and_304_serves_cached_body() {
+  let clock = Arc::new(ManualClock::new(1_000));
+  let client = client::<1>(
+    &[
+      b"HTTP/1.1 200 OK\r\nCache-Control: max-age=10\r\nETag: \"v1\"\r\nContent-Length: 6\r\n\r\ncached",
+      b"HTTP/1.1 304 Not Modified\r\nCache-Control: max-age=30\r\nETag: \"v1\"\r\n\r\n",
+    ],
+    &clock,
+  );
+
+  client.get("http://example.com/data").call().unwrap();
+  clock.advance(20_000);
+  let revalidated = client.get("http://example.com/data").call().unwrap();
+  // The 304 refreshed the entry for another 30 seconds
+  clock.advance(20_000);
+  let fresh = client.get("http://example.com/data").call().unwrap();
+
+  let sent = requests(1);
+  assert_eq!(sent.len(), 2);
+  assert!(!sent[0].contains("if-none-match"));
+  assert!(sent[1].contains("if-none-match: \"v1\"\r\n"));
+  assert_eq!(revalidated.status_code, 200);
+  assert_eq!(revalidated.body.as_bytes(), b"cached");
+  assert_eq!(fresh.body.as_bytes(), b"cached");
+}
+
+#[test]
+fn unsafe_request_invalidates_stored_response() {
+  let clock = Arc::new(ManualClock::new(1_000));
+  let client = client::<2>(
+    &[
+      b"HTTP/1.1 200 OK\r\nCache-Control: max-age=60\r\nContent-Length: 3\r\n\r\nold",
+      b"HTTP/1.1 204 No Content\r\n\r\n",
+      b"HTTP/1.1 200 OK\r\nCache-Control: max-age=60\r\nContent-Length: 3\r\n\r\nnew",
+    ],
+    &clock,
+  );
+
+  client.get("http://example.com/data").call().unwrap();
+  client.delete("http://example.com/data").call().unwrap();
+  let after = client.get("http://example.com/data").call().unwrap();
+
+  assert_eq!(after.body.as_bytes(), b"new");
+  assert_eq!(requests(2).len(), 3);
+}
+
+#[test]
+fn entry_is_only_served_to_requests_with_matching_vary_headers() {
+  let clock = Arc::new(ManualClock::new(1_000));
+  let client = client::<40>(
+    &[
+      b"HTTP/1.1 200 OK\r\nCache-Control: max-age=60\r\nVary: Accept-Language\r\nContent-Length: 2\r\n\r\nen",
+      b"HTTP/1.1 200 OK\r\nCache-Control: max-age=60\r\nVary: Accept-Language\r\nContent-Length: 2\r\n\r\nde",
+    ],
+    &clock,
+  );
+
+  let english = client
+    .get("http://example.com/page")
+    .header("Accept-Language", "en")
+    .call()
+    .unwrap();
+  let german = client
+    .get("http://example.com/page")
+    .header("Accept-Language", "de")
+    .call()
+    .unwrap();
+  let cached = client
+    .get("http://example.com/page")
+    .header("Accept-Language", "de")
+    .call()
+    .unwrap();
+
+  assert_eq!(english.body.as_bytes(), b"en");
+  assert_eq!(german.body.as_bytes(), b"de");
+  assert_eq!(cached.body.as_bytes(), b"de");
+  assert_eq!(requests(40).len(), 2);
+}
+
+#[test]
+fn user_conditional_request_leaves_entry_in_place() {
+  let clock = Arc::new(ManualClock::new(1_000));
+  let client = client::<41>(
+    &[
+      b"HTTP/1.1 200 OK\r\nCache-Control: max-age=10\r\nETag: \"v1\"\r\nContent-Length: 6\r\n\r\ncached",
+      b"HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\n\r\n",
+      b"HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\n\r\n",
+    ],
+    &clock,
+  );
+
+  client.get("http://example.com/data").call().unwrap();
+  clock.advance(20_000);
+  let conditional = client
+    .get("http://example.com/data")
+    .header("If-None-Match", "\"v1\"")
+    .call()
+    .unwrap();
+  // The stale entry is still there for the client to revalidate itself
+  let revalidated = client.get("http://example.com/data").call().unwrap();
+
+  let sent = requests(41);
+  assert_eq!(sent.len(), 3);
+  assert_eq!(conditional.status_code, 304);
+  assert!(sent[2].contains("if-none-match: \"v1\"\r\n"));
+  assert_eq!(revalidated.status_code, 200);
+  assert_eq!(revalidated.body.as_bytes(), b"cached");
+}
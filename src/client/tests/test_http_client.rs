@@ -1,7 +1,938 @@
-// HttpClient unit tests removed - behaviors are tested via:
-// - Policy module tests (src/client/tests/test_policy.rs)
-// - Transport module tests (src/transport/tests/)
-// - Integration tests (tests/config_test.rs, tests/httpbin_test.rs)
-//
-// Mock-based unit tests don't work with connection pooling since
-// sockets are created internally by the pool.
+// End-to-end HttpClient tests over scripted sockets. Pooling is disabled so
+// every request opens a new socket and consumes the next scripted reply.
+// Policy decisions are covered in detail by test_policy.rs.
+use crate::client::HttpClient;
+use crate::client::tests::scripted::{LocalDns, ScriptedSocket, connects, raw_requests, requests, script, timeouts};
+use crate::config::{ConfigBuilder, ProtocolRestriction};
+use crate::error::{Error, ParseError};
+use crate::method::Method;
+use crate::parser::event_stream::EventSink;
+use crate::request::Request;
+use crate::response::ResponseExt;
+use crate::sink::PeekSink;
+use crate::transport::PoolStats;
+use crate::util::IpAddr;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+use core::time::Duration;
+use spin::Mutex;
+
+fn client<const SLOT: usize>(builder: ConfigBuilder) -> HttpClient<ScriptedSocket<SLOT>, LocalDns> {
+  HttpClient::with_adapters_and_config(LocalDns, builder.connection_pooling(false).build())
+}
+
+#[test]
+fn head_response_does_not_wait_for_body() {
+  script(4, &[b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\n"]);
+  let client = client::<4>(ConfigBuilder::new());
+
+  let response = client.head("http://example.com/").call().unwrap();
+
+  assert_eq!(response.status_code, 200);
+  assert!(response.body.is_empty());
+  assert!(requests(4)[0].starts_with("head / http/1.1\r\n"));
+}
+
+#[test]
+fn head_response_reports_advertised_content_length() {
+  script(11, &[b"HTTP/1.1 200 OK\r\nContent-Length: 1048576\r\n\r\n"]);
+  let client = client::<11>(ConfigBuilder::new());
+
+  let response = client.head("http://example.com/big.iso").call().unwrap();
+
+  assert_eq!(response.content_length(), Some(1_048_576));
+  assert!(response.body.is_empty());
+}
+
+#[test]
+fn pool_can_be_inspected_and_cleared() {
+  script(12, &[b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok"]);
+  let client: HttpClient<ScriptedSocket<12>, LocalDns> =
+    HttpClient::with_adapters_and_config(LocalDns, ConfigBuilder::new().build());
+
+  client.get("http://example.com/").call().unwrap();
+
+  assert_eq!(client.pool_len(), 1);
+  assert_eq!(client.pool_stats(), PoolStats { hits: 0, misses: 1 });
+  let clone = client.clone();
+  clone.clear_pool();
+  assert_eq!(client.pool_len(), 0);
+}
+
+#[test]
+fn send_raw_writes_bytes_unchanged() {
+  script(
+    14,
+    &[b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n2\r\nok\r\n0\r\n\r\n"],
+  );
+  let client = client::<14>(ConfigBuilder::new());
+
+  let raw = client
+    .send_raw("http://example.com/", b"GET /odd path HTTP/1.0\r\nX-Bad : 1\r\n\r\n")
+    .unwrap();
+
+  assert_eq!(requests(14), ["get /odd path http/1.0\r\nx-bad : 1\r\n\r\n"]);
+  assert_eq!(raw.status_code, 200);
+  assert_eq!(raw.body_bytes, b"2\r\nok\r\n0\r\n\r\n");
+}
+
+#[test]
+fn relative_url_resolves_against_base_url() {
+  script(15, &[b"HTTP/1.1 204 No Content\r\n\r\n"]);
+  let client = client::<15>(ConfigBuilder::new().base_url("https://api.example.com:8443/v1/"));
+
+  let response = client.get("users/1?full=true").call().unwrap();
+
+  assert_eq!(
+    response.url.as_deref(),
+    Some("https://api.example.com:8443/v1/users/1?full=true")
+  );
+  let sent = &requests(15)[0];
+  assert!(sent.starts_with("get /v1/users/1?full=true http/1.1\r\n"));
+  assert!(sent.contains("host: api.example.com:8443\r\n"));
+}
+
+#[test]
+fn absolute_url_ignores_base_url() {
+  script(16, &[b"HTTP/1.1 204 No Content\r\n\r\n"]);
+  let client = client::<16>(ConfigBuilder::new().base_url("https://api.example.com"));
+
+  let response = client.get("http://other.example.com/ping").call().unwrap();
+
+  assert_eq!(response.url.as_deref(), Some("http://other.example.com/ping"));
+  assert!(requests(16)[0].contains("host: other.example.com\r\n"));
+}
+
+#[test]
+fn auto_encode_url_encodes_spaces_in_path_and_query() {
+  script(17, &[b"HTTP/1.1 204 No Content\r\n\r\n"]);
+  let client = client::<17>(ConfigBuilder::new().auto_encode_url(true));
+
+  let response = client.get("http://example.com/a b/c?q=x y").call().unwrap();
+
+  assert_eq!(response.url.as_deref(), Some("http://example.com/a%20b/c?q=x%20y"));
+  assert!(requests(17)[0].starts_with("get /a%20b/c?q=x%20y http/1.1\r\n"));
+}
+
+#[test]
+fn url_with_space_is_rejected_without_auto_encode() {
+  let client = client::<38>(ConfigBuilder::new());
+
+  let result = client.get("http://example.com/a b/c").call();
+
+  assert!(matches!(result, Err(Error::Parse(_))));
+}
+
+#[cfg(feature = "idna")]
+#[test]
+fn unicode_host_is_sent_as_punycode() {
+  script(18, &[b"HTTP/1.1 204 No Content\r\n\r\n"]);
+  let client = client::<18>(ConfigBuilder::new());
+
+  let response = client.get("http://bücher.de:8080/katalog").call().unwrap();
+
+  assert_eq!(response.url.as_deref(), Some("http://xn--bcher-kva.de:8080/katalog"));
+  assert!(requests(18)[0].contains("\r\nhost: xn--bcher-kva.de:8080\r\n"));
+}
+
+#[test]
+fn trace_omits_credentials_by_default() {
+  script(19, &[b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n"]);
+  let client = client::<19>(ConfigBuilder::new());
+
+  client
+    .trace("http://example.com/")
+    .header("Cookie", "session=secret")
+    .header("Authorization", "Bearer token")
+    .header("X-Trace-Id", "7")
+    .call()
+    .unwrap();
+
+  let sent = &requests(19)[0];
+  assert!(sent.starts_with("trace / http/1.1\r\n"));
+  assert!(sent.contains("x-trace-id: 7\r\n"));
+  assert!(!sent.contains("cookie"));
+  assert!(!sent.contains("authorization"));
+}
+
+#[test]
+fn trace_keeps_credentials_when_configured() {
+  script(20, &[b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n"]);
+  let client = client::<20>(ConfigBuilder::new().trace_sends_credentials(true));
+
+  client
+    .trace("http://example.com/")
+    .header("Cookie", "session=secret")
+    .call()
+    .unwrap();
+
+  assert!(requests(20)[0].contains("cookie: session=secret\r\n"));
+}
+
+#[test]
+fn trace_with_body_is_rejected() {
+  let client = client::<39>(ConfigBuilder::new());
+  let request = Request::new(Method::Trace, "http://example.com/").body("echo me");
+
+  let result = client.run(request);
+
+  assert!(matches!(result, Err(Error::Parse(ParseError::BodyNotAllowed))));
+}
+
+#[test]
+fn custom_headers_are_forwarded() {
+  script(5, &[b"HTTP/1.1 204 No Content\r\n\r\n"]);
+  let client = client::<5>(ConfigBuilder::new());
+
+  client
+    .get("http://example.com/")
+    .header("X-Trace", "abc")
+    .call()
+    .unwrap();
+
+  assert!(requests(5)[0].contains("\r\nx-trace: abc\r\n"));
+}
+
+#[test]
+fn redirect_is_followed_to_final_response() {
+  script(
+    6,
+    &[
+      b"HTTP/1.1 302 Found\r\nLocation: /next\r\nContent-Length: 0\r\n\r\n",
+      b"HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\ndone",
+    ],
+  );
+  let client = client::<6>(ConfigBuilder::new());
+
+  let response = client.get("http://example.com/start").call().unwrap();
+
+  assert_eq!(response.body.as_bytes(), b"done");
+  let sent = requests(6);
+  assert_eq!(sent.len(), 2);
+  assert!(sent[1].starts_with("get /next http/1.1\r\n"));
+}
+
+#[test]
+fn https_only_rejects_http_before_connecting() {
+  script(7, &[]);
+  let client = client::<7>(ConfigBuilder::new().protocol_restriction(ProtocolRestriction::HttpsOnly));
+
+  let result = client.get("http://example.com/").call();
+
+  assert!(matches!(result, Err(Error::HttpsRequired)));
+  assert!(requests(7).is_empty());
+}
+
+#[test]
+fn call_to_writer_streams_final_response_body() {
+  script(
+    8,
+    &[
+      b"HTTP/1.1 301 Moved\r\nLocation: /file\r\nContent-Length: 5\r\n\r\nmoved",
+      b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nfile\r\n5\r\n body\r\n0\r\n\r\n",
+    ],
+  );
+  let client = client::<8>(ConfigBuilder::new());
+  let mut written = Vec::new();
+
+  let response = client
+    .get("http://example.com/download")
+    .call_to_writer(&mut written)
+    .unwrap();
+
+  assert_eq!(response.status_code, 200);
+  assert!(response.body.is_empty());
+  assert_eq!(written, b"file body");
+}
+
+#[test]
+fn call_to_writer_returns_trailers_of_streamed_body() {
+  script(
+    13,
+    &[b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nfile\r\n0\r\nDigest: sha-256=abc\r\n\r\n"],
+  );
+  let client = client::<13>(ConfigBuilder::new());
+  let mut written = Vec::new();
+
+  let response = client
+    .get("http://example.com/download")
+    .call_to_writer(&mut written)
+    .unwrap();
+
+  assert_eq!(written, b"file");
+  assert_eq!(
+    response.trailers,
+    Vec::from([(String::from("Digest"), String::from("sha-256=abc"))])
+  );
+}
+
+#[test]
+fn peek_sink_shows_prefix_before_writing_whole_body() {
+  script(
+    21,
+    &[b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n2\r\n%P\r\n6\r\nDF-1.7\r\n0\r\n\r\n"],
+  );
+  let client = client::<21>(ConfigBuilder::new());
+  let mut peeked = Vec::new();
+  let mut sink = PeekSink::new(Vec::new(), 4, |prefix: &[u8]| {
+    peeked.extend_from_slice(prefix);
+    Ok(())
+  });
+
+  client
+    .get("http://example.com/doc")
+    .call_to_writer(&mut sink)
+    .unwrap();
+
+  assert_eq!(sink.into_inner(), b"%PDF-1.7");
+  assert_eq!(peeked, b"%PDF");
+}
+
+#[test]
+fn peek_sink_inspects_short_body_and_can_abort() {
+  script(
+    22,
+    &[
+      b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nhi",
+      b"HTTP/1.1 200 OK\r\nContent-Length: 8\r\n\r\nGIF89a..",
+    ],
+  );
+  let client = client::<22>(ConfigBuilder::new());
+
+  let mut short = PeekSink::new(Vec::new(), 4, |prefix: &[u8]| {
+    assert_eq!(prefix, b"hi");
+    Ok(())
+  });
+  client
+    .get("http://example.com/")
+    .call_to_writer(&mut short)
+    .unwrap();
+  assert_eq!(short.into_inner(), b"hi");
+
+  let mut rejecting = PeekSink::new(Vec::new(), 4, |prefix: &[u8]| {
+    if prefix == b"%PDF" {
+      Ok(())
+    } else {
+      Err(Error::SinkFailed)
+    }
+  });
+  let result = client
+    .get("http://example.com/")
+    .call_to_writer(&mut rejecting);
+  assert!(matches!(result, Err(Error::SinkFailed)));
+  assert!(rejecting.into_inner().is_empty());
+}
+
+#[test]
+fn call_to_writer_keeps_error_body_out_of_writer() {
+  script(9, &[b"HTTP/1.1 404 Not Found\r\nContent-Length: 4\r\n\r\nnope"]);
+  let client = client::<9>(ConfigBuilder::new());
+  let mut written = Vec::new();
+
+  let result = client
+    .get("http://example.com/")
+    .call_to_writer(&mut written);
+
+  let Err(Error::HttpStatus { code, response }) = result else {
+    panic!("expected HttpStatus error");
+  };
+  assert_eq!(code, 404);
+  assert_eq!(response.body.as_bytes(), b"nope");
+  assert!(written.is_empty());
+}
+
+#[test]
+fn progress_callbacks_see_request_and_response_bodies() {
+  script(10, &[b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok"]);
+  let client = client::<10>(ConfigBuilder::new());
+  let uploads = Arc::new(Mutex::new(Vec::new()));
+  let downloads = Arc::new(Mutex::new(Vec::new()));
+  let upload_log = Arc::clone(&uploads);
+  let download_log = Arc::clone(&downloads);
+
+  client
+    .post("http://example.com/upload")
+    .on_upload_progress(move |sent, total| upload_log.lock().push((sent, total)))
+    .on_download_progress(move |received, total| download_log.lock().push((received, total)))
+    .send("payload")
+    .unwrap();
+
+  assert_eq!(*uploads.lock(), [(7, Some(7))]);
+  assert_eq!(*downloads.lock(), [(2, Some(2))]);
+  assert!(requests(10)[0].ends_with("\r\n\r\npayload"));
+}
+
+#[test]
+fn proxied_ip_literal_host_is_bracketed() {
+  script(
+    23,
+    &[
+      b"HTTP/1.1 204 No Content\r\n\r\n",
+      b"HTTP/1.1 204 No Content\r\n\r\n",
+    ],
+  );
+  let client = client::<23>(ConfigBuilder::new().proxy("http://proxy.local:3128"));
+
+  client.get("http://[0:0::1]:8080/a").call().unwrap();
+  client.get("http://192.0.2.1/b").call().unwrap();
+
+  let sent = requests(23);
+  assert!(sent[0].starts_with("get http://[::1]:8080/a http/1.1\r\n"));
+  assert!(sent[0].contains("host: [::1]:8080\r\n"));
+  assert!(sent[1].starts_with("get http://192.0.2.1/b http/1.1\r\n"));
+  assert!(sent[1].contains("host: 192.0.2.1\r\n"));
+}
+
+#[test]
+fn set_header_replaces_earlier_value() {
+  script(24, &[b"HTTP/1.1 204 No Content\r\n\r\n"]);
+  let client = client::<24>(ConfigBuilder::new());
+
+  client
+    .get("http://example.com/")
+    .header("Accept", "text/plain")
+    .header("X-Trace", "1")
+    .header("accept", "text/html")
+    .set_header("ACCEPT", "application/json")
+    .call()
+    .unwrap();
+
+  let sent = &requests(24)[0];
+  assert_eq!(sent.matches("accept:").count(), 1);
+  assert!(sent.contains("accept: application/json\r\n"));
+  assert!(sent.contains("x-trace: 1\r\n"));
+}
+
+#[test]
+fn host_header_overrides_connected_host() {
+  script(25, &[b"HTTP/1.1 204 No Content\r\n\r\n"]);
+  let client = client::<25>(ConfigBuilder::new());
+
+  client
+    .get("http://example.com:8080/status")
+    .host_header("internal.test")
+    .call()
+    .unwrap();
+
+  let sent = &requests(25)[0];
+  assert!(sent.starts_with("get /status http/1.1\r\n"));
+  assert_eq!(sent.matches("host:").count(), 1);
+  assert!(sent.contains("host: internal.test\r\n"));
+}
+
+#[test]
+fn force_send_body_sends_get_body_with_content_length() {
+  script(26, &[b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}"]);
+  let client = client::<26>(ConfigBuilder::new());
+
+  client
+    .get("http://example.com/_search")
+    .header("Content-Type", "application/json")
+    .force_send_body(Vec::from(*br#"{"query":{}}"#))
+    .call()
+    .unwrap();
+
+  let sent = &requests(26)[0];
+  assert!(sent.starts_with("get /_search http/1.1\r\n"));
+  assert!(sent.contains("content-length: 12\r\n"));
+  assert!(sent.ends_with("\r\n\r\n{\"query\":{}}"));
+}
+
+#[test]
+fn send_empty_sends_zero_content_length() {
+  script(
+    27,
+    &[
+      b"HTTP/1.1 204 No Content\r\n\r\n",
+      b"HTTP/1.1 204 No Content\r\n\r\n",
+      b"HTTP/1.1 204 No Content\r\n\r\n",
+    ],
+  );
+  let client = client::<27>(ConfigBuilder::new());
+
+  client.post("http://example.com/a").send_empty().unwrap();
+  client.put("http://example.com/b").send_empty().unwrap();
+  client.patch("http://example.com/c").send_empty().unwrap();
+
+  let sent = requests(27);
+  assert!(sent[0].starts_with("post /a http/1.1\r\n"));
+  assert!(sent[1].starts_with("put /b http/1.1\r\n"));
+  assert!(sent[2].starts_with("patch /c http/1.1\r\n"));
+  for request in &sent {
+    assert!(request.ends_with("content-length: 0\r\n\r\n"), "{request}");
+  }
+}
+
+#[test]
+fn validate_with_returns_cached_body_on_not_modified() {
+  script(
+    28,
+    &[
+      b"HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nLast-Modified: Wed, 21 Oct 2015 07:28:00 GMT\r\nCache-Control: max-age=60\r\nContent-Length: 6\r\n\r\ncached",
+      b"HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nCache-Control: max-age=120\r\n\r\n",
+      b"HTTP/1.1 200 OK\r\nETag: \"v2\"\r\nContent-Length: 5\r\n\r\nfresh",
+    ],
+  );
+  let client = client::<28>(ConfigBuilder::new());
+  let cached = client.get("http://example.com/doc").call().unwrap();
+
+  let revalidated = client
+    .get("http://example.com/doc")
+    .validate_with(&cached)
+    .unwrap();
+  let replaced = client
+    .get("http://example.com/doc")
+    .validate_with(&cached)
+    .unwrap();
+
+  let sent = requests(28);
+  assert!(sent[1].contains("if-none-match: \"v1\"\r\n"));
+  assert!(sent[1].contains("if-modified-since: wed, 21 oct 2015 07:28:00 gmt\r\n"));
+  assert_eq!(revalidated.status_code, 200);
+  assert_eq!(revalidated.body.as_bytes(), b"cached");
+  assert_eq!(revalidated.get_header("Cache-Control"), Some("max-age=120"));
+  assert_eq!(replaced.body.as_bytes(), b"fresh");
+}
+
+#[test]
+fn call_prefix_truncates_large_body_and_drops_connection() {
+  script(
+    29,
+    &[
+      b"HTTP/1.1 200 OK\r\nContent-Length: 1000000\r\n\r\n0123456789abcdef",
+      b"HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\nfull",
+    ],
+  );
+  let client: HttpClient<ScriptedSocket<29>, LocalDns> =
+    HttpClient::with_adapters_and_config(LocalDns, ConfigBuilder::new().build());
+
+  let truncated = client
+    .get("http://example.com/huge")
+    .call_prefix(10)
+    .unwrap();
+  assert_eq!(truncated.body.as_bytes(), b"0123456789");
+  assert!(truncated.is_truncated());
+  assert_eq!(client.pool_len(), 0);
+
+  let whole = client
+    .get("http://example.com/small")
+    .call_prefix(10)
+    .unwrap();
+  assert_eq!(whole.body.as_bytes(), b"full");
+  assert!(!whole.is_truncated());
+  assert_eq!(client.pool_len(), 1);
+}
+
+#[cfg(feature = "cookie-jar")]
+#[test]
+fn client_clock_times_cookie_expiry() {
+  script(
+    31,
+    &[
+      b"HTTP/1.1 200 OK\r\nSet-Cookie: session=abc; Max-Age=60\r\nContent-Length: 0\r\n\r\n",
+      b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n",
+      b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n",
+    ],
+  );
+  let clock = Arc::new(crate::clock::ManualClock::new(1_000_000));
+  let client = client::<31>(ConfigBuilder::new()).with_clock(clock.clone());
+
+  client.get("http://example.com/login").call().unwrap();
+  client.get("http://example.com/").call().unwrap();
+  clock.advance(61_000);
+  client.get("http://example.com/").call().unwrap();
+
+  let sent = requests(31);
+  assert!(sent[1].contains("cookie: session=abc\r\n"));
+  assert!(!sent[2].contains("cookie:"));
+}
+
+#[test]
+fn pooled_https_connection_is_reused_without_reconnecting() {
+  script(
+    32,
+    &[
+      b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nfirst",
+      b"HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\nsecond",
+    ],
+  );
+  let client: HttpClient<ScriptedSocket<32>, LocalDns> =
+    HttpClient::with_adapters_and_config(LocalDns, ConfigBuilder::new().build());
+
+  let first = client.get("https://example.com/a").call().unwrap();
+  let second = client.get("https://example.com/b").call().unwrap();
+
+  assert_eq!(first.body.as_bytes(), b"first");
+  assert_eq!(second.body.as_bytes(), b"second");
+  assert_eq!(connects(32), 1);
+  assert_eq!(client.pool_stats(), PoolStats { hits: 1, misses: 1 });
+  let sent = requests(32);
+  assert!(sent[0].starts_with("get /a http/1.1\r\n"));
+  assert!(sent[1].starts_with("get /b http/1.1\r\n"));
+}
+
+#[test]
+fn local_bind_applies_only_to_new_pooled_connections() {
+  script(
+    33,
+    &[
+      b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok",
+      b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok",
+    ],
+  );
+  let config = ConfigBuilder::new()
+    .local_bind(IpAddr::V4([127, 0, 0, 1]))
+    .build();
+  let client: HttpClient<ScriptedSocket<33>, LocalDns> = HttpClient::with_adapters_and_config(LocalDns, config);
+
+  client.get("http://example.com/").call().unwrap();
+  client.get("http://example.com/").call().unwrap();
+
+  assert_eq!(connects(33), 1);
+  assert_eq!(requests(33).len(), 2);
+}
+
+#[test]
+fn https_request_through_proxy_is_refused() {
+  script(34, &[b"HTTP/1.1 204 No Content\r\n\r\n"]);
+  let client = client::<34>(ConfigBuilder::new().proxy("http://proxy.local:3128"));
+
+  let result = client.get("https://example.com/secret").call();
+
+  assert!(matches!(result, Err(Error::ProxyTunnelUnsupported)));
+  assert!(requests(34).is_empty());
+}
+
+#[test]
+fn caller_framed_bodies_get_no_content_length() {
+  script(
+    35,
+    &[
+      b"HTTP/1.1 204 No Content\r\n\r\n",
+      b"HTTP/1.1 204 No Content\r\n\r\n",
+    ],
+  );
+  let client = client::<35>(ConfigBuilder::new());
+
+  client
+    .post("http://example.com/upload")
+    .header("Transfer-Encoding", "chunked")
+    .send("4\r\nWiki\r\n0\r\n\r\n")
+    .unwrap();
+  client
+    .post("http://example.com/upload")
+    .without_content_length()
+    .header("Transfer-Encoding", "chunked")
+    .send("0\r\n\r\n")
+    .unwrap();
+  let unframed = client
+    .post("http://example.com/upload")
+    .without_content_length()
+    .send("data");
+
+  let sent = requests(35);
+  assert!(sent[0].contains("transfer-encoding: chunked\r\n"));
+  assert!(!sent[0].contains("content-length"));
+  assert!(sent[0].ends_with("\r\n\r\n4\r\nwiki\r\n0\r\n\r\n"));
+  assert!(!sent[1].contains("content-length"));
+  assert!(matches!(unframed, Err(Error::Parse(ParseError::MissingBodyFraming))));
+}
+
+#[test]
+fn put_redirected_by_302_resends_method_and_body() {
+  script(
+    36,
+    &[
+      b"HTTP/1.1 302 Found\r\nLocation: /next\r\nContent-Length: 0\r\n\r\n",
+      b"HTTP/1.1 204 No Content\r\n\r\n",
+    ],
+  );
+  let client = client::<36>(ConfigBuilder::new());
+
+  client
+    .put("http://example.com/start")
+    .send("payload")
+    .unwrap();
+
+  let sent = requests(36);
+  assert_eq!(sent.len(), 2);
+  assert!(sent[1].starts_with("put /next http/1.1\r\n"));
+  assert!(sent[1].contains("\r\ncontent-length: 7\r\n"));
+  assert!(sent[1].ends_with("\r\n\r\npayload"));
+}
+
+#[test]
+fn request_timeout_overrides_apply_without_touching_client_config() {
+  script(37, &[b"HTTP/1.1 204 No Content\r\n\r\n"]);
+  let client = client::<37>(ConfigBuilder::new().timeout(Duration::from_secs(1)));
+
+  client
+    .get("http://example.com/")
+    .timeout(Duration::from_secs(30))
+    .connect_timeout(Duration::from_secs(4))
+    .call()
+    .unwrap();
+
+  // Reads then wait only for what is left of the 30s deadline
+  assert_eq!(
+    timeouts(37)[..3],
+    [("write", 4_000), ("read", 30_000), ("write", 30_000)]
+  );
+  assert_eq!(client.config().timeout, Some(Duration::from_secs(1)));
+  assert!(client.config().timeout_connect.is_none());
+}
+
+#[cfg(feature = "gzip-decompression")]
+#[test]
+fn call_to_writer_decodes_gzip_body_as_it_arrives() {
+  struct Pieces(Vec<Vec<u8>>);
+
+  impl crate::sink::ByteSink for Pieces {
+    fn write_all(
+      &mut self,
+      bytes: &[u8],
+    ) -> Result<(), Error> {
+      self.0.push(bytes.to_vec());
+      Ok(())
+    }
+  }
+
+  // Barely compressible, so the coded body spans many socket reads
+  let mut state = 0x2545_f491_u32;
+  let original: Vec<u8> = (0..64 * 1024)
+    .map(|_| {
+      state ^= state << 13;
+      state ^= state >> 17;
+      state ^= state << 5;
+      state.to_le_bytes()[0]
+    })
+    .collect();
+  let mut encoded = alloc::vec![0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0xff];
+  encoded.extend_from_slice(&miniz_oxide::deflate::compress_to_vec(&original, 6));
+  encoded.extend_from_slice(&crate::parser::content_coding::crc32(0, &original).to_le_bytes());
+  encoded.extend_from_slice(&u32::try_from(original.len()).unwrap().to_le_bytes());
+  let mut reply = alloc::format!(
+    "HTTP/1.1 200 OK\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\n\r\n",
+    encoded.len()
+  )
+  .into_bytes();
+  reply.extend_from_slice(&encoded);
+  script(42, &[reply.leak()]);
+  let client = client::<42>(ConfigBuilder::new());
+  let mut sink = Pieces(Vec::new());
+
+  let response = client
+    .get("http://example.com/archive")
+    .call_to_writer(&mut sink)
+    .unwrap();
+
+  assert!(response.body.is_empty());
+  assert!(sink.0.len() > 1);
+  assert_eq!(sink.0.concat(), original);
+}
+
+#[test]
+fn event_sink_receives_events_before_stream_ends() {
+  // The stream never ends: the last chunk is missing
+  script(
+    43,
+    &[b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nTransfer-Encoding: chunked\r\n\r\n12\r\nid: 1\ndata: tick\n\n\r\n"],
+  );
+  let client = client::<43>(ConfigBuilder::new());
+  let mut events = Vec::new();
+  let mut sink = EventSink::new(|event| {
+    events.push(event.data);
+    ControlFlow::Break(())
+  });
+
+  let response = client
+    .get("http://example.com/events")
+    .call_to_writer(&mut sink)
+    .unwrap();
+
+  assert_eq!(sink.stream().last_event_id(), Some("1"));
+  assert_eq!(response.status_code, 200);
+  assert_eq!(events, ["tick"]);
+}
+
+#[test]
+fn connection_tunneled_by_connect_is_not_pooled() {
+  script(
+    44,
+    &[
+      b"HTTP/1.1 200 Connection Established\r\nContent-Length: 0\r\n\r\n",
+      b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok",
+    ],
+  );
+  let client: HttpClient<ScriptedSocket<44>, LocalDns> =
+    HttpClient::with_adapters_and_config(LocalDns, ConfigBuilder::new().build());
+
+  client.connect("http://example.com:80").call().unwrap();
+  client.get("http://example.com/").call().unwrap();
+
+  assert_eq!(connects(44), 2);
+  assert_eq!(client.pool_stats().hits, 0);
+  let sent = requests(44);
+  assert!(sent[0].starts_with("connect example.com:80 http/1.1\r\n"));
+  assert!(sent[1].starts_with("get / http/1.1\r\n"));
+}
+
+#[test]
+fn interim_response_is_skipped_for_the_final_one() {
+  script(
+    45,
+    &[
+      b"HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello",
+      b"HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\nnext",
+    ],
+  );
+  let client: HttpClient<ScriptedSocket<45>, LocalDns> =
+    HttpClient::with_adapters_and_config(LocalDns, ConfigBuilder::new().build());
+
+  let response = client
+    .post("http://example.com/upload")
+    .body(b"data".to_vec())
+    .call()
+    .unwrap();
+  let next = client.get("http://example.com/").call().unwrap();
+
+  assert_eq!(response.status_code, 200);
+  assert_eq!(response.body.as_bytes(), b"hello");
+  assert_eq!(next.body.as_bytes(), b"next");
+  assert_eq!(connects(45), 1);
+}
+
+#[cfg(feature = "cookie-jar")]
+#[test]
+fn with_clock_leaves_shared_cookie_store_on_its_own_clock() {
+  script(
+    46,
+    &[
+      b"HTTP/1.1 200 OK\r\nSet-Cookie: session=abc; Max-Age=60\r\nContent-Length: 0\r\n\r\n",
+      b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n",
+    ],
+  );
+  let store_clock = Arc::new(crate::clock::ManualClock::new(1_000_000));
+  let store = Arc::new(crate::cookie_jar::CookieStore::new().with_clock(store_clock));
+  let client_clock = Arc::new(crate::clock::ManualClock::new(1_000_000));
+  let client = client::<46>(ConfigBuilder::new())
+    .with_cookie_store(Arc::clone(&store))
+    .with_clock(client_clock.clone());
+
+  client.get("http://example.com/login").call().unwrap();
+  client_clock.advance(61_000);
+  client.get("http://example.com/").call().unwrap();
+
+  assert!(requests(46)[1].contains("cookie: session=abc\r\n"));
+}
+
+#[test]
+fn host_header_is_kept_on_same_authority_redirect() {
+  script(
+    47,
+    &[
+      b"HTTP/1.1 302 Found\r\nLocation: http://EXAMPLE.com:80/next\r\nContent-Length: 0\r\n\r\n",
+      b"HTTP/1.1 204 No Content\r\n\r\n",
+    ],
+  );
+  let client = client::<47>(ConfigBuilder::new());
+
+  client
+    .get("http://example.com/start")
+    .host_header("internal.test")
+    .call()
+    .unwrap();
+
+  let sent = requests(47);
+  assert_eq!(sent.len(), 2);
+  assert!(sent[1].starts_with("get /next http/1.1\r\nhost: internal.test\r\n"));
+  assert_eq!(sent[1].matches("host:").count(), 1);
+}
+
+#[test]
+fn host_header_is_dropped_on_cross_authority_redirect() {
+  script(
+    48,
+    &[
+      b"HTTP/1.1 302 Found\r\nLocation: http://other.test:8080/next\r\nContent-Length: 0\r\n\r\n",
+      b"HTTP/1.1 204 No Content\r\n\r\n",
+    ],
+  );
+  let client = client::<48>(ConfigBuilder::new());
+
+  client
+    .get("http://example.com/start")
+    .host_header("internal.test")
+    .call()
+    .unwrap();
+
+  let sent = requests(48);
+  assert_eq!(sent.len(), 2);
+  assert!(sent[0].contains("host: internal.test\r\n"));
+  assert!(sent[1].starts_with("get /next http/1.1\r\nhost: other.test:8080\r\n"));
+  assert_eq!(sent[1].matches("host:").count(), 1);
+}
+
+#[test]
+fn send_borrowed_writes_the_static_body() {
+  script(49, &[b"HTTP/1.1 201 Created\r\nContent-Length: 2\r\n\r\nok"]);
+  let client = client::<49>(
+    ConfigBuilder::new()
+      .user_agent("test")
+      .accept_encoding("identity"),
+  );
+
+  let response = client
+    .post("http://example.com/upload")
+    .send_borrowed(b"fixed payload")
+    .unwrap();
+
+  assert_eq!(response.status_code, 201);
+  assert_eq!(response.body.as_bytes(), b"ok");
+  assert_eq!(
+    raw_requests(49),
+    [&b"POST /upload HTTP/1.1\r\nhost: example.com\r\nconnection: close\r\nuser-agent: test\r\naccept: */*\r\naccept-encoding: identity\r\nContent-Length: 13\r\n\r\nfixed payload"[..]]
+  );
+}
+
+#[test]
+fn conditional_headers_are_sent_and_not_modified_is_reported() {
+  script(
+    50,
+    &[
+      b"HTTP/1.1 304 Not Modified\r\nETag: \"abc\"\r\n\r\n",
+      b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\n\r\nnew",
+    ],
+  );
+  let client = client::<50>(
+    ConfigBuilder::new()
+      .user_agent("test")
+      .accept_encoding("identity"),
+  );
+
+  let cached = client
+    .get("http://example.com/etag")
+    .if_none_match("\"abc\"")
+    .call()
+    .unwrap();
+  let changed = client
+    .get("http://example.com/dated")
+    .if_modified_since(crate::format_http_date(784_111_777))
+    .call()
+    .unwrap();
+
+  assert!(cached.is_not_modified());
+  assert!(cached.body.is_empty());
+  assert_eq!(cached.headers.get("etag"), Some("\"abc\""));
+  assert!(!changed.is_not_modified());
+  assert_eq!(changed.body.as_bytes(), b"new");
+  assert_eq!(
+    raw_requests(50),
+    [
+      &b"GET /etag HTTP/1.1\r\nhost: example.com\r\nconnection: close\r\nuser-agent: test\r\naccept: */*\r\naccept-encoding: identity\r\nif-none-match: \"abc\"\r\n\r\n"[..],
+      &b"GET /dated HTTP/1.1\r\nhost: example.com\r\nconnection: close\r\nuser-agent: test\r\naccept: */*\r\naccept-encoding: identity\r\nif-modified-since: Sun, 06 Nov 1994 08:49:37 GMT\r\n\r\n"[..],
+    ]
+  );
+}
@@ -1,5 +1,8 @@
+use crate::body::Body;
 use crate::client::policy::{PolicyDecision, RequestPolicy};
-use crate::config::{Config, HttpStatusHandling, ProtocolRestriction, RedirectPolicy};
+use crate::config::{
+  Config, ConfigBuilder, HttpStatusHandling, ProtocolRestriction, RedirectBodyPolicy, RedirectPolicy,
+};
 use crate::error::Error;
 use crate::headers::Headers;
 use crate::method::Method;
@@ -21,6 +24,9 @@ fn make_redirect_response(
     reason: String::from("Redirect"),
     headers,
     body_bytes: Vec::new(),
+    remote_addr: None,
+    body_streamed: false,
+    trailers: Vec::new(),
   }
 }
 
@@ -60,6 +66,9 @@ fn policy_drops_body_for_head_requests() {
     reason: String::from("OK"),
     headers,
     body_bytes: b"1234567890".to_vec(),
+    remote_addr: None,
+    body_streamed: false,
+    trailers: Vec::new(),
   };
 
   let decision = policy
@@ -93,7 +102,7 @@ fn post_302_redirect_becomes_get() {
       &Uri::parse("http://a.com").unwrap(),
       "http://a.com",
       Method::Post,
-      Some(vec![1, 2, 3]),
+      Some(Body::from_bytes(vec![1, 2, 3])),
     )
     .unwrap();
 
@@ -120,7 +129,7 @@ fn post_301_redirect_becomes_get() {
       &Uri::parse("http://a.com").unwrap(),
       "http://a.com",
       Method::Post,
-      Some(vec![1, 2, 3]),
+      Some(Body::from_bytes(vec![1, 2, 3])),
     )
     .unwrap();
 
@@ -147,7 +156,7 @@ fn post_303_redirect_becomes_get() {
       &Uri::parse("http://a.com").unwrap(),
       "http://a.com",
       Method::Post,
-      Some(vec![1, 2, 3]),
+      Some(Body::from_bytes(vec![1, 2, 3])),
     )
     .unwrap();
 
@@ -186,6 +195,53 @@ fn get_redirect_stays_get() {
   }
 }
 
+fn put_302_redirect(policy: RedirectBodyPolicy) -> Result<PolicyDecision, Error> {
+  let config = ConfigBuilder::new().redirect_body_policy(policy).build();
+  RequestPolicy::new(&config).process_raw_response(
+    make_redirect_response(302, "/next"),
+    &Uri::parse("http://a.com").unwrap(),
+    "http://a.com",
+    Method::Put,
+    Some(Body::from_bytes(vec![1, 2, 3])),
+  )
+}
+
+#[test]
+fn put_302_redirect_drops_body_when_configured() {
+  match put_302_redirect(RedirectBodyPolicy::Drop).unwrap() {
+    PolicyDecision::Redirect {
+      next_method, next_body, ..
+    } => {
+      assert_eq!(next_method, Method::Put);
+      assert!(next_body.is_none());
+    },
+    PolicyDecision::Return(_) => panic!("Expected PolicyDecision::Redirect"),
+  }
+}
+
+#[test]
+fn put_302_redirect_keeps_body_by_default() {
+  assert_eq!(Config::default().redirect_body_policy, RedirectBodyPolicy::Keep);
+
+  match put_302_redirect(RedirectBodyPolicy::Keep).unwrap() {
+    PolicyDecision::Redirect {
+      next_method, next_body, ..
+    } => {
+      assert_eq!(next_method, Method::Put);
+      assert_eq!(next_body.unwrap().as_bytes(), [1, 2, 3]);
+    },
+    PolicyDecision::Return(_) => panic!("Expected PolicyDecision::Redirect"),
+  }
+}
+
+#[test]
+fn put_302_redirect_fails_when_configured() {
+  assert!(matches!(
+    put_302_redirect(RedirectBodyPolicy::Error),
+    Err(Error::RedirectBodyRefused)
+  ));
+}
+
 #[test]
 fn redirect_loop_is_detected() {
   let mut policy = RequestPolicy::new(&Config::default());
@@ -211,11 +267,16 @@ fn status_4xx_is_error_when_configured() {
     ..Default::default()
   });
 
+  let mut headers = Headers::new();
+  headers.insert("Content-Type", "application/json");
   let raw = RawResponse {
     status_code: 404,
     reason: String::from("Not Found"),
-    headers: Headers::new(),
-    body_bytes: Vec::new(),
+    headers,
+    body_bytes: br#"{"error":"not found"}"#.to_vec(),
+    remote_addr: None,
+    body_streamed: false,
+    trailers: Vec::new(),
   };
 
   let err = policy
@@ -228,7 +289,13 @@ fn status_4xx_is_error_when_configured() {
     )
     .unwrap_err();
 
-  assert!(matches!(err, Error::HttpStatus(404)));
+  let Error::HttpStatus { code, response } = err else {
+    panic!("expected HttpStatus, got {err:?}");
+  };
+  assert_eq!(code, 404);
+  assert_eq!(response.get_header("Content-Type"), Some("application/json"));
+  assert_eq!(response.body.as_bytes(), br#"{"error":"not found"}"#);
+  assert_eq!(response.url.as_deref(), Some("http://example.com"));
 }
 
 #[test]
@@ -243,6 +310,9 @@ fn status_5xx_is_error_when_configured() {
     reason: String::from("Internal Server Error"),
     headers: Headers::new(),
     body_bytes: Vec::new(),
+    remote_addr: None,
+    body_streamed: false,
+    trailers: Vec::new(),
   };
 
   let err = policy
@@ -255,7 +325,7 @@ fn status_5xx_is_error_when_configured() {
     )
     .unwrap_err();
 
-  assert!(matches!(err, Error::HttpStatus(500)));
+  assert!(matches!(err, Error::HttpStatus { code: 500, .. }));
 }
 
 #[test]
@@ -270,6 +340,9 @@ fn status_4xx_is_ok_when_configured_as_response() {
     reason: String::from("Not Found"),
     headers: Headers::new(),
     body_bytes: Vec::new(),
+    remote_addr: None,
+    body_streamed: false,
+    trailers: Vec::new(),
   };
 
   let result = policy.process_raw_response(
@@ -287,6 +360,35 @@ fn status_4xx_is_ok_when_configured_as_response() {
   }
 }
 
+#[test]
+fn custom_status_handling_errors_only_on_listed_ranges() {
+  let config = Config {
+    http_status_handling: HttpStatusHandling::Custom(&[500..=599]),
+    ..Default::default()
+  };
+  let uri = Uri::parse("http://example.com").unwrap();
+  let raw = |status_code: u16| RawResponse {
+    status_code,
+    reason: String::new(),
+    headers: Headers::new(),
+    body_bytes: Vec::new(),
+    remote_addr: None,
+    body_streamed: false,
+    trailers: Vec::new(),
+  };
+
+  let not_found =
+    RequestPolicy::new(&config).process_raw_response(raw(404), &uri, "http://example.com", Method::Get, None);
+  assert!(matches!(
+    not_found,
+    Ok(PolicyDecision::Return(ref resp)) if resp.status_code == 404
+  ));
+
+  let server_error =
+    RequestPolicy::new(&config).process_raw_response(raw(500), &uri, "http://example.com", Method::Get, None);
+  assert!(matches!(server_error, Err(Error::HttpStatus { code: 500, .. })));
+}
+
 #[test]
 fn too_many_redirects_is_error() {
   let mut policy = RequestPolicy::new(&Config {
@@ -329,6 +431,32 @@ fn too_many_redirects_is_error() {
   assert!(matches!(err, Error::TooManyRedirects));
 }
 
+#[test]
+fn redirect_chain_across_too_many_hosts_is_error() {
+  let mut policy = RequestPolicy::new(&Config {
+    max_redirect_hosts: Some(3),
+    ..Default::default()
+  });
+  let mut follow = |from: &str, to: &str| {
+    policy.process_raw_response(
+      make_redirect_response(302, to),
+      &Uri::parse(from).unwrap(),
+      from,
+      Method::Get,
+      None,
+    )
+  };
+
+  // Staying on a host, or returning to one already seen, does not count again
+  follow("http://a.com/1", "/2").unwrap();
+  follow("http://a.com/2", "http://B.com/").unwrap();
+  follow("http://b.com/", "http://a.com/3").unwrap();
+  follow("http://a.com/3", "http://c.com/").unwrap();
+  let err = follow("http://c.com/", "http://d.com/").unwrap_err();
+
+  assert!(matches!(err, Error::TooManyRedirectHosts));
+}
+
 #[test]
 fn no_follow_policy_returns_redirect_response() {
   let mut policy = RequestPolicy::new(&Config {
@@ -353,3 +481,228 @@ fn no_follow_policy_returns_redirect_response() {
     },
   }
 }
+
+#[test]
+fn policy_carries_remote_addr_to_response() {
+  let mut policy = RequestPolicy::new(&Config::default());
+
+  let raw = RawResponse {
+    status_code: 200,
+    reason: String::from("OK"),
+    headers: Headers::new(),
+    body_bytes: Vec::new(),
+    remote_addr: Some(crate::util::IpAddr::V4([127, 0, 0, 1])),
+    body_streamed: false,
+    trailers: Vec::new(),
+  };
+
+  let decision = policy
+    .process_raw_response(
+      raw,
+      &Uri::parse("http://example.com").unwrap(),
+      "http://example.com",
+      Method::Get,
+      None,
+    )
+    .unwrap();
+
+  match decision {
+    PolicyDecision::Return(response) => {
+      assert_eq!(response.remote_addr(), Some(crate::util::IpAddr::V4([127, 0, 0, 1])));
+      assert_eq!(response.url.as_deref(), Some("http://example.com"));
+    },
+    PolicyDecision::Redirect { .. } => panic!("Expected PolicyDecision::Return"),
+  }
+}
+
+#[test]
+fn not_modified_is_returned_not_followed() {
+  let mut policy = RequestPolicy::new(&Config::default());
+
+  let raw = RawResponse {
+    status_code: 304,
+    reason: String::from("Not Modified"),
+    headers: Headers::new(),
+    body_bytes: Vec::new(),
+    remote_addr: None,
+    body_streamed: false,
+    trailers: Vec::new(),
+  };
+
+  let decision = policy
+    .process_raw_response(
+      raw,
+      &Uri::parse("http://example.com").unwrap(),
+      "http://example.com",
+      Method::Get,
+      None,
+    )
+    .unwrap();
+
+  match decision {
+    PolicyDecision::Return(response) => {
+      assert_eq!(response.status_code, 304);
+      assert!(response.body.is_empty());
+    },
+    PolicyDecision::Redirect { .. } => panic!("304 must not be treated as a redirect"),
+  }
+}
+
+#[test]
+fn multiple_choices_without_location_is_returned() {
+  let mut policy = RequestPolicy::new(&Config::default());
+
+  let raw = RawResponse {
+    status_code: 300,
+    reason: String::from("Multiple Choices"),
+    headers: Headers::new(),
+    body_bytes: Vec::new(),
+    remote_addr: None,
+    body_streamed: false,
+    trailers: Vec::new(),
+  };
+
+  let decision = policy
+    .process_raw_response(
+      raw,
+      &Uri::parse("http://example.com").unwrap(),
+      "http://example.com",
+      Method::Get,
+      None,
+    )
+    .unwrap();
+
+  match decision {
+    PolicyDecision::Return(response) => assert_eq!(response.status_code, 300),
+    PolicyDecision::Redirect { .. } => panic!("300 must not be treated as a redirect"),
+  }
+}
+
+#[test]
+fn found_without_location_is_an_error() {
+  let mut policy = RequestPolicy::new(&Config::default());
+
+  let raw = RawResponse {
+    status_code: 302,
+    reason: String::from("Found"),
+    headers: Headers::new(),
+    body_bytes: Vec::new(),
+    remote_addr: None,
+    body_streamed: false,
+    trailers: Vec::new(),
+  };
+
+  let result = policy.process_raw_response(
+    raw,
+    &Uri::parse("http://example.com").unwrap(),
+    "http://example.com",
+    Method::Get,
+    None,
+  );
+
+  assert!(matches!(result, Err(Error::MissingRedirectLocation)));
+}
+
+#[test]
+fn streams_body_only_for_final_responses() {
+  let policy = RequestPolicy::new(&Config::default());
+  let plain = Headers::new();
+  let mut gzip = Headers::new();
+  gzip.insert("Content-Encoding", "gzip");
+  let mut brotli = Headers::new();
+  brotli.insert("Content-Encoding", "br");
+
+  assert!(policy.streams_body(200, &plain).is_some());
+  assert!(policy.streams_body(304, &plain).is_some());
+  assert!(policy.streams_body(404, &plain).is_none());
+  assert!(policy.streams_body(302, &plain).is_none());
+  assert!(policy.streams_body(300, &plain).is_some());
+  // Decodable codings are removed while streaming, and without any decoder
+  // compiled in the body is passed through as received
+  let gzip_streamed = if cfg!(feature = "gzip-decompression") {
+    Some(false)
+  } else if cfg!(feature = "zstd-decompression") {
+    None
+  } else {
+    Some(true)
+  };
+  assert_eq!(
+    policy
+      .streams_body(200, &gzip)
+      .map(|decoder| decoder.is_identity()),
+    gzip_streamed
+  );
+  // Codings without a decoder stay in memory so the error can be reported
+  assert_eq!(
+    policy.streams_body(200, &brotli).is_none(),
+    cfg!(any(feature = "gzip-decompression", feature = "zstd-decompression"))
+  );
+
+  let no_follow = RequestPolicy::new(&Config {
+    redirect_policy: RedirectPolicy::NoFollow,
+    http_status_handling: HttpStatusHandling::AsResponse,
+    ..Default::default()
+  });
+  assert!(no_follow.streams_body(302, &plain).is_some());
+  assert!(no_follow.streams_body(404, &plain).is_some());
+}
+
+#[test]
+#[cfg(any(feature = "gzip-decompression", feature = "zstd-decompression"))]
+fn decompress_content_types_limits_decoding() {
+  let config = ConfigBuilder::new()
+    .decompress_content_types(["application/json", "text/*"])
+    .build();
+  let response = |content_type: &str| {
+    let mut headers = Headers::new();
+    headers.insert("Content-Type", content_type);
+    headers.insert("Content-Encoding", "br");
+    RawResponse {
+      status_code: 200,
+      reason: String::from("OK"),
+      headers,
+      body_bytes: b"\x1f\x8b compressed".to_vec(),
+      remote_addr: None,
+      body_streamed: false,
+      trailers: Vec::new(),
+    }
+  };
+  let uri = Uri::parse("http://example.com/file.gz").unwrap();
+
+  // Not listed: returned as received, so it can also be streamed
+  let mut policy = RequestPolicy::new(&config);
+  let kept = policy.process_raw_response(
+    response("application/gzip"),
+    &uri,
+    "http://example.com/file.gz",
+    Method::Get,
+    None,
+  );
+  match kept.unwrap() {
+    PolicyDecision::Return(resp) => assert_eq!(resp.body.as_bytes(), b"\x1f\x8b compressed"),
+    PolicyDecision::Redirect { .. } => panic!("Expected PolicyDecision::Return"),
+  }
+  assert!(
+    policy
+      .streams_body(200, &response("application/gzip").headers)
+      .is_some_and(|decoder| decoder.is_identity())
+  );
+
+  // Listed, exactly or by wildcard: decoding is attempted and `br` is unsupported
+  for listed in ["Application/JSON; charset=utf-8", "text/plain"] {
+    let mut decoding = RequestPolicy::new(&config);
+    let result = decoding.process_raw_response(response(listed), &uri, "http://example.com/file.gz", Method::Get, None);
+    assert!(
+      matches!(
+        result,
+        Err(Error::Parse(crate::error::ParseError::UnsupportedContentEncoding))
+      ),
+      "{listed}"
+    );
+    assert!(
+      decoding
+        .streams_body(200, &response(listed).headers)
+        .is_none()
+    );
+  }
+}
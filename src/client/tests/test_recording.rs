@@ -0,0 +1,33 @@
+// RecordingClient over a scripted socket: the recorded bytes are exactly
+// what the client wrote and read.
+use crate::client::tests::scripted::{LocalDns, ScriptedSocket, script};
+use crate::config::ConfigBuilder;
+use crate::test_util::RecordingClient;
+
+#[test]
+fn records_exact_request_and_response_bytes() {
+  let reply: &[u8] = b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok";
+  script(30, &[reply]);
+  let config = ConfigBuilder::new()
+    .connection_pooling(false)
+    .user_agent("recorder")
+    .accept_encoding("identity")
+    .build();
+  let recorder: RecordingClient<ScriptedSocket<30>, LocalDns> =
+    RecordingClient::with_adapters_and_config(LocalDns, config);
+
+  let response = recorder.record(|client| {
+    client
+      .get("http://example.com/path?q=1")
+      .header("X-Trace", "abc")
+      .call()
+  });
+
+  assert_eq!(response.unwrap().body.as_bytes(), b"ok");
+  assert_eq!(
+    recorder.last_request_bytes().unwrap(),
+    b"GET /path?q=1 HTTP/1.1\r\nhost: example.com\r\nconnection: close\r\nuser-agent: recorder\r\naccept: */*\r\naccept-encoding: identity\r\nX-Trace: abc\r\n\r\n"
+  );
+  assert_eq!(recorder.last_response_bytes().unwrap(), reply);
+  assert_eq!(recorder.exchanges().len(), 1);
+}
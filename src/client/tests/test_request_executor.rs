@@ -0,0 +1,394 @@
+use crate::client::request_executor::{RequestExecutor, TargetForm};
+use crate::config::{Config, ConfigBuilder, DEFAULT_USER_AGENT};
+use crate::dns::resolver::OsDnsResolver;
+use crate::error::{Error, ParseError};
+use crate::headers::Headers;
+use crate::method::Method;
+use crate::parser::uri::Uri;
+use crate::request::SignableRequest;
+use crate::socket::blocking::OsBlockingSocket;
+use crate::transport::ConnectionPool;
+use alloc::format;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+fn build(
+  config: &Config,
+  url: &str,
+  method: Method,
+  target: TargetForm,
+) -> Result<Vec<u8>, Error> {
+  build_with_headers(config, url, method, target, &Headers::new())
+}
+
+fn build_with_headers(
+  config: &Config,
+  url: &str,
+  method: Method,
+  target: TargetForm,
+  headers: &Headers,
+) -> Result<Vec<u8>, Error> {
+  let pool = Arc::new(ConnectionPool::<OsBlockingSocket>::new(0, None));
+  let dns = OsDnsResolver::new();
+  let executor = RequestExecutor::new(&pool, &dns, config);
+  let uri = Uri::parse(url).unwrap();
+  executor.build_request(&uri, method, target, "example.com", 80, headers, None, None)
+}
+
+fn request_line(bytes: &[u8]) -> &str {
+  let text = core::str::from_utf8(bytes).unwrap();
+  text.split("\r\n").next().unwrap()
+}
+
+#[test]
+fn origin_form_uses_path_and_query() {
+  let bytes = build(
+    &Config::default(),
+    "http://example.com/a?b=c",
+    Method::Get,
+    TargetForm::Origin,
+  )
+  .unwrap();
+  assert_eq!(request_line(&bytes), "GET /a?b=c HTTP/1.1");
+}
+
+#[test]
+fn asterisk_form_writes_star_target() {
+  let bytes = build(
+    &Config::default(),
+    "http://example.com/ignored",
+    Method::Options,
+    TargetForm::Asterisk,
+  )
+  .unwrap();
+  let text = core::str::from_utf8(&bytes).unwrap();
+
+  assert!(text.starts_with("OPTIONS * HTTP/1.1\r\n"));
+  assert!(text.contains("host: example.com\r\n"));
+}
+
+#[test]
+fn asterisk_form_rejected_for_other_methods() {
+  let result = build(
+    &Config::default(),
+    "http://example.com",
+    Method::Get,
+    TargetForm::Asterisk,
+  );
+  assert!(matches!(result, Err(Error::Parse(ParseError::InvalidRequestTarget))));
+}
+
+#[test]
+fn connect_uses_authority_form() {
+  let bytes = build(
+    &Config::default(),
+    "http://example.com/ignored?x=1",
+    Method::Connect,
+    TargetForm::Origin,
+  )
+  .unwrap();
+  let text = core::str::from_utf8(&bytes).unwrap();
+
+  assert!(text.starts_with("CONNECT example.com:80 HTTP/1.1\r\n"));
+  assert!(text.contains("host: example.com\r\n"));
+}
+
+#[test]
+fn proxy_mode_uses_absolute_form() {
+  let config = ConfigBuilder::new()
+    .proxy("http://proxy.local:3128")
+    .build();
+  let bytes = build(&config, "http://example.com/p", Method::Get, TargetForm::Origin).unwrap();
+  let text = core::str::from_utf8(&bytes).unwrap();
+
+  assert!(text.starts_with("GET http://example.com/p HTTP/1.1\r\n"));
+  assert!(text.contains("host: example.com\r\n"));
+}
+
+#[test]
+fn proxy_mode_keeps_authority_form_for_connect() {
+  let config = ConfigBuilder::new()
+    .proxy("http://proxy.local:3128")
+    .build();
+  let bytes = build(&config, "http://example.com", Method::Connect, TargetForm::Origin).unwrap();
+
+  assert_eq!(request_line(&bytes), "CONNECT example.com:80 HTTP/1.1");
+}
+
+#[test]
+fn default_user_agent_is_sent() {
+  let bytes = build(
+    &Config::default(),
+    "http://example.com/",
+    Method::Get,
+    TargetForm::Origin,
+  )
+  .unwrap();
+  let text = core::str::from_utf8(&bytes).unwrap();
+
+  assert!(text.contains(&alloc::format!("user-agent: {DEFAULT_USER_AGENT}\r\n")));
+}
+
+#[test]
+fn no_user_agent_suppresses_header() {
+  let config = ConfigBuilder::new().no_user_agent().build();
+  let bytes = build(&config, "http://example.com/", Method::Get, TargetForm::Origin).unwrap();
+  let text = core::str::from_utf8(&bytes).unwrap().to_ascii_lowercase();
+
+  assert!(!text.contains("user-agent"));
+}
+
+#[test]
+fn request_user_agent_overrides_config() {
+  let mut headers = Headers::new();
+  headers.insert("User-Agent", "custom/2.0");
+
+  for config in [
+    Config::default(),
+    ConfigBuilder::new().user_agent("configured/1.0").build(),
+    ConfigBuilder::new().no_user_agent().build(),
+  ] {
+    let bytes = build_with_headers(
+      &config,
+      "http://example.com/",
+      Method::Get,
+      TargetForm::Origin,
+      &headers,
+    )
+    .unwrap();
+    let text = core::str::from_utf8(&bytes).unwrap().to_ascii_lowercase();
+
+    assert_eq!(text.matches("user-agent:").count(), 1);
+    assert!(text.contains("user-agent: custom/2.0\r\n"));
+  }
+}
+
+#[test]
+fn configured_accept_encoding_is_sent() {
+  let config = ConfigBuilder::new().accept_encoding("gzip").build();
+  let bytes = build(&config, "http://example.com/", Method::Get, TargetForm::Origin).unwrap();
+  let text = core::str::from_utf8(&bytes).unwrap();
+
+  assert!(text.contains("accept-encoding: gzip\r\n"));
+}
+
+#[test]
+fn empty_accept_encoding_disables_header() {
+  let config = ConfigBuilder::new().accept_encoding("").build();
+  let bytes = build(&config, "http://example.com/", Method::Get, TargetForm::Origin).unwrap();
+  let text = core::str::from_utf8(&bytes).unwrap();
+
+  assert!(!text.contains("accept-encoding"));
+}
+
+#[test]
+fn request_accept_encoding_overrides_config() {
+  let mut headers = Headers::new();
+  headers.insert("Accept-Encoding", "identity");
+  let config = ConfigBuilder::new().accept_encoding("gzip").build();
+  let bytes = build_with_headers(
+    &config,
+    "http://example.com/",
+    Method::Get,
+    TargetForm::Origin,
+    &headers,
+  )
+  .unwrap();
+  let text = core::str::from_utf8(&bytes).unwrap().to_ascii_lowercase();
+
+  assert_eq!(text.matches("accept-encoding:").count(), 1);
+  assert!(text.contains("accept-encoding: identity\r\n"));
+}
+
+#[test]
+fn default_headers_are_sent_and_overridable() {
+  let config = ConfigBuilder::new()
+    .default_header("X-Api-Key", "secret")
+    .default_headers([("X-Trace", "on")])
+    .build();
+
+  let bytes = build(&config, "http://example.com/", Method::Get, TargetForm::Origin).unwrap();
+  let text = core::str::from_utf8(&bytes).unwrap();
+  assert!(text.contains("X-Api-Key: secret\r\n"));
+  assert!(text.contains("X-Trace: on\r\n"));
+
+  let mut headers = Headers::new();
+  headers.insert("x-api-key", "other");
+  let overridden = build_with_headers(
+    &config,
+    "http://example.com/",
+    Method::Get,
+    TargetForm::Origin,
+    &headers,
+  )
+  .unwrap();
+  let overridden_text = core::str::from_utf8(&overridden)
+    .unwrap()
+    .to_ascii_lowercase();
+  assert_eq!(overridden_text.matches("x-api-key:").count(), 1);
+  assert!(overridden_text.contains("x-api-key: other\r\n"));
+  assert!(overridden_text.contains("x-trace: on\r\n"));
+}
+
+#[test]
+fn accept_language_is_sent_once() {
+  let config = ConfigBuilder::new().accept_language("de").build();
+  let bytes = build(&config, "http://example.com/", Method::Get, TargetForm::Origin).unwrap();
+  let text = core::str::from_utf8(&bytes).unwrap();
+  assert!(text.contains("accept-language: de\r\n"));
+
+  let client = crate::HttpClient::new().unwrap();
+  let builder = client
+    .get("http://example.com/")
+    .header("Accept-Language", "fr")
+    .accept_language("en-US, en;q=0.8");
+  let requested = build_with_headers(
+    &config,
+    "http://example.com/",
+    Method::Get,
+    TargetForm::Origin,
+    builder.headers_ref(),
+  )
+  .unwrap();
+  let requested_text = core::str::from_utf8(&requested)
+    .unwrap()
+    .to_ascii_lowercase();
+  assert_eq!(requested_text.matches("accept-language:").count(), 1);
+  assert!(requested_text.contains("accept-language: en-us, en;q=0.8\r\n"));
+}
+
+#[test]
+fn header_values_with_line_breaks_are_rejected() {
+  let client = crate::HttpClient::new().unwrap();
+  let builder = client
+    .get("http://example.com/")
+    .accept_language("en\r\nX-Injected: 1");
+  let result = build_with_headers(
+    &Config::default(),
+    "http://example.com/",
+    Method::Get,
+    TargetForm::Origin,
+    builder.headers_ref(),
+  );
+  assert!(matches!(result, Err(Error::Parse(ParseError::InvalidHeaderValue))));
+}
+
+#[test]
+fn signer_sees_assembled_request_and_adds_fields_last() {
+  let pool = Arc::new(ConnectionPool::<OsBlockingSocket>::new(0, None));
+  let dns = OsDnsResolver::new();
+  let config = ConfigBuilder::new().user_agent("test").build();
+  let executor = RequestExecutor::new(&pool, &dns, &config);
+  let uri = Uri::parse("http://example.com/upload?part=1").unwrap();
+  let mut custom = Headers::new();
+  custom.insert("X-Trace", "abc");
+  let sign = |request: &mut SignableRequest<'_>| {
+    assert_eq!(request.method(), Method::Put);
+    assert_eq!(request.target(), "/upload?part=1");
+    assert_eq!(request.body(), b"data");
+    assert_eq!(request.headers().get("host"), Some("example.com"));
+    assert_eq!(request.headers().get("content-length"), Some("4"));
+    assert_eq!(request.headers().get("x-trace"), Some("abc"));
+    let signature = format!("len={}", request.body().len());
+    request.headers_mut().insert("Authorization", signature);
+  };
+
+  let bytes = executor
+    .build_request(
+      &uri,
+      Method::Put,
+      TargetForm::Origin,
+      "example.com",
+      80,
+      &custom,
+      Some(b"data"),
+      Some(&sign),
+    )
+    .unwrap();
+
+  let text = core::str::from_utf8(&bytes).unwrap();
+  assert!(text.ends_with("X-Trace: abc\r\nContent-Length: 4\r\nAuthorization: len=4\r\n\r\n"));
+}
+
+#[test]
+fn host_override_keeps_uri_authority_in_absolute_form() {
+  let config = ConfigBuilder::new()
+    .proxy("http://proxy.local:3128")
+    .build();
+  let mut headers = Headers::new();
+  headers.insert("Host", "internal.test");
+  let bytes = build_with_headers(
+    &config,
+    "http://example.com/p",
+    Method::Get,
+    TargetForm::Origin,
+    &headers,
+  )
+  .unwrap();
+  let text = core::str::from_utf8(&bytes).unwrap();
+
+  assert!(text.starts_with("GET http://example.com/p HTTP/1.1\r\n"));
+  assert_eq!(text.matches("host:").count(), 1);
+  assert!(text.contains("host: internal.test\r\n"));
+}
+
+#[test]
+fn duplicate_headers_fold_only_when_configured() {
+  let mut headers = Headers::new();
+  headers.insert("Accept", "a");
+  headers.insert("Accept", "b");
+
+  let config = ConfigBuilder::new()
+    .fold_duplicate_request_headers(true)
+    .build();
+  let folded = build_with_headers(
+    &config,
+    "http://example.com/",
+    Method::Get,
+    TargetForm::Origin,
+    &headers,
+  )
+  .unwrap();
+  let folded_text = core::str::from_utf8(&folded).unwrap();
+  assert!(folded_text.contains("Accept: a, b\r\n"));
+  assert_eq!(folded_text.matches("Accept:").count(), 1);
+
+  let unfolded = build_with_headers(
+    &Config::default(),
+    "http://example.com/",
+    Method::Get,
+    TargetForm::Origin,
+    &headers,
+  )
+  .unwrap();
+  let unfolded_text = core::str::from_utf8(&unfolded).unwrap();
+  assert!(unfolded_text.contains("Accept: a\r\nAccept: b\r\n"));
+}
+
+#[test]
+fn non_ascii_header_values_rejected_only_with_strict_config() {
+  let mut headers = Headers::new();
+  headers.insert("X-Name", "José");
+
+  let lenient = build_with_headers(
+    &Config::default(),
+    "http://example.com/",
+    Method::Get,
+    TargetForm::Origin,
+    &headers,
+  )
+  .unwrap();
+  assert!(lenient.ends_with("X-Name: José\r\n\r\n".as_bytes()));
+
+  let config = ConfigBuilder::new()
+    .strict_ascii_header_values(true)
+    .build();
+  let strict = build_with_headers(
+    &config,
+    "http://example.com/",
+    Method::Get,
+    TargetForm::Origin,
+    &headers,
+  );
+  assert!(matches!(strict, Err(Error::Parse(ParseError::InvalidHeaderValue))));
+}
@@ -0,0 +1,54 @@
+use crate::client::HttpClient;
+use crate::client::tests::scripted::{LocalDns, ScriptedSocket, requests, script};
+use crate::clock::Clock;
+use crate::config::{Config, ConfigBuilder};
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+
+/// Clock whose monotonic reading moves forward 25ms every time it is read
+///
+/// Wall-clock reads, such as the cookie store's, leave it where it is, so
+/// only the reads that bound a timed request move it.
+struct TickingClock {
+  millis: AtomicU64,
+}
+
+impl Clock for TickingClock {
+  fn now_millis(&self) -> u64 {
+    self.millis.load(Ordering::Acquire)
+  }
+
+  fn monotonic_millis(&self) -> u64 {
+    self.millis.fetch_add(25, Ordering::AcqRel)
+  }
+}
+
+#[test]
+fn call_timed_and_send_timed_measure_with_client_clock() {
+  script(
+    3,
+    &[
+      b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok",
+      b"HTTP/1.1 201 Created\r\nContent-Length: 0\r\n\r\n",
+    ],
+  );
+  let config: Config = ConfigBuilder::new().connection_pooling(false).build();
+  let client = HttpClient::<ScriptedSocket<3>, LocalDns>::with_adapters_and_config(LocalDns, config).with_clock(
+    Arc::new(TickingClock {
+      millis: AtomicU64::new(1_000),
+    }),
+  );
+
+  let (response, elapsed) = client.get("http://example.com/").call_timed().unwrap();
+  assert_eq!(response.body.as_bytes(), b"ok");
+  assert_eq!(elapsed, Duration::from_millis(25));
+
+  let (created, post_elapsed) = client
+    .post("http://example.com/")
+    .send_timed("data")
+    .unwrap();
+  assert_eq!(created.status_code, 201);
+  assert_eq!(post_elapsed, Duration::from_millis(25));
+  assert_eq!(requests(3).len(), 2);
+}
@@ -0,0 +1,169 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Wall-clock time source
+///
+/// Returns milliseconds since the Unix epoch. Time-dependent features such as
+/// cache freshness use this instead of reading the system clock directly, so
+/// `no_std` targets can supply their own source and tests can control time.
+/// `HttpClient::with_clock` sets the one clock a client's timeouts, pool,
+/// cache and cookie store all read.
+pub trait Clock: Send + Sync {
+  /// Current time in milliseconds since 1970-01-01 UTC
+  fn now_millis(&self) -> u64;
+
+  /// Milliseconds since an arbitrary fixed point, for measuring durations
+  ///
+  /// Unlike `now_millis` this must not jump when the wall clock is set, so
+  /// idle times measured with it are not stretched or cut short. Defaults to
+  /// `now_millis`, which suits clocks that are never set back.
+  fn monotonic_millis(&self) -> u64 {
+    self.now_millis()
+  }
+}
+
+/// Operating system clock
+///
+/// Reads `CLOCK_REALTIME` on Unix and the system time on Windows, with
+/// `CLOCK_MONOTONIC` and the tick count for durations. On other targets it
+/// always reports the epoch.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OsClock {
+  _marker: (),
+}
+
+impl OsClock {
+  /// Create a new OS clock
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { _marker: () }
+  }
+}
+
+/// Milliseconds reported by the Unix clock `clock_id`, or 0 if it cannot be read
+#[cfg(unix)]
+fn unix_clock_millis(clock_id: libc::clockid_t) -> u64 {
+  let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+  let rc = unsafe { libc::clock_gettime(clock_id, &raw mut ts) };
+  if rc != 0 {
+    return 0;
+  }
+  let secs = u64::try_from(ts.tv_sec).unwrap_or(0);
+  let millis = u64::try_from(ts.tv_nsec).unwrap_or(0).div_euclid(1_000_000);
+  secs.saturating_mul(1000).saturating_add(millis)
+}
+
+impl Clock for OsClock {
+  fn now_millis(&self) -> u64 {
+    #[cfg(unix)]
+    {
+      unix_clock_millis(libc::CLOCK_REALTIME)
+    }
+    #[cfg(windows)]
+    {
+      // FILETIME counts 100ns intervals since 1601-01-01
+      const EPOCH_OFFSET_100NS: u64 = 116_444_736_000_000_000;
+      let mut ft = windows_sys::Win32::Foundation::FILETIME {
+        dwLowDateTime: 0,
+        dwHighDateTime: 0,
+      };
+      unsafe {
+        windows_sys::Win32::System::SystemInformation::GetSystemTimeAsFileTime(&raw mut ft);
+      }
+      let ticks = (u64::from(ft.dwHighDateTime) << 32) | u64::from(ft.dwLowDateTime);
+      ticks.saturating_sub(EPOCH_OFFSET_100NS).div_euclid(10_000)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+      0
+    }
+  }
+
+  fn monotonic_millis(&self) -> u64 {
+    #[cfg(unix)]
+    {
+      unix_clock_millis(libc::CLOCK_MONOTONIC)
+    }
+    #[cfg(windows)]
+    {
+      unsafe { windows_sys::Win32::System::SystemInformation::GetTickCount64() }
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+      0
+    }
+  }
+}
+
+/// Default clock for the target, or None where the OS clock is unavailable
+pub fn os_clock() -> Option<Arc<dyn Clock>> {
+  cfg!(any(unix, windows)).then(|| Arc::new(OsClock::new()) as Arc<dyn Clock>)
+}
+
+/// Clock that only moves when told to
+///
+/// Useful for tests and for targets that track time externally.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+  millis: AtomicU64,
+}
+
+impl ManualClock {
+  /// Create a clock starting at the given time
+  #[must_use]
+  pub const fn new(start_millis: u64) -> Self {
+    Self {
+      millis: AtomicU64::new(start_millis),
+    }
+  }
+
+  /// Move the clock forward
+  pub fn advance(
+    &self,
+    millis: u64,
+  ) {
+    self.millis.fetch_add(millis, Ordering::AcqRel);
+  }
+
+  /// Set the clock to an absolute time
+  pub fn set(
+    &self,
+    millis: u64,
+  ) {
+    self.millis.store(millis, Ordering::Release);
+  }
+}
+
+impl Clock for ManualClock {
+  fn now_millis(&self) -> u64 {
+    self.millis.load(Ordering::Acquire)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn manual_clock_advances() {
+    let clock = ManualClock::new(1_000);
+    clock.advance(500);
+    assert_eq!(clock.now_millis(), 1_500);
+
+    clock.set(10);
+    assert_eq!(clock.now_millis(), 10);
+  }
+
+  #[test]
+  fn os_clock_monotonic_time_does_not_go_back() {
+    let clock = OsClock::new();
+    let earlier = clock.monotonic_millis();
+    assert!(clock.monotonic_millis() >= earlier);
+  }
+
+  #[test]
+  fn os_clock_is_after_2020() {
+    #[cfg(any(unix, windows))]
+    assert!(OsClock::new().now_millis() > 1_577_836_800_000);
+  }
+}
@@ -1,3 +1,5 @@
+use crate::headers::Headers;
+use crate::util::IpAddr;
 use core::time::Duration;
 
 /// Policy for forwarding authorization headers during redirects
@@ -20,6 +22,22 @@ pub enum RedirectPolicy {
   NoFollow,
 }
 
+/// What to do with a request body when a 301 or 302 redirect is followed
+///
+/// POST becomes a GET without body on 301 and 302, and every method becomes
+/// one on 303 (RFC 9110 Section 15.4). Other methods, such as PUT or DELETE,
+/// keep their method and, per RFC 9110, their content; this decides whether
+/// the body goes along to a target it was not written for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectBodyPolicy {
+  /// Follow with the same method and no body, sending e.g. an empty PUT
+  Drop,
+  /// Follow with the same method and body (default)
+  Keep,
+  /// Do not follow; fail with `Error::RedirectBodyRefused`
+  Error,
+}
+
 /// How to handle HTTP error status codes (4xx, 5xx)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HttpStatusHandling {
@@ -27,6 +45,24 @@ pub enum HttpStatusHandling {
   AsError,
   /// Treat all status codes as successful responses
   AsResponse,
+  /// Treat status codes in any of the given ranges as errors,
+  /// e.g. `Custom(&[500..=599])` for server errors only
+  Custom(&'static [core::ops::RangeInclusive<u16>]),
+}
+
+impl HttpStatusHandling {
+  /// Check whether a response with this status code is returned as `Error::HttpStatus`
+  #[must_use]
+  pub fn is_error(
+    self,
+    status_code: u16,
+  ) -> bool {
+    match self {
+      Self::AsError => (400..600).contains(&status_code),
+      Self::AsResponse => false,
+      Self::Custom(ranges) => ranges.iter().any(|range| range.contains(&status_code)),
+    }
+  }
 }
 
 /// Protocol restrictions for requests
@@ -38,67 +74,233 @@ pub enum ProtocolRestriction {
   Any,
 }
 
+/// User-Agent sent when the configuration does not override it
+pub const DEFAULT_USER_AGENT: &str = concat!("barehttp/", env!("CARGO_PKG_VERSION"));
+
 /// HTTP client configuration
 ///
 /// Controls behavior for timeouts, redirects, headers, and protocol restrictions.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
   /// General timeout for the entire request
+  ///
+  /// With a client clock, response reads share this budget across redirects:
+  /// each read waits only for the time left of it.
   pub timeout: Option<Duration>,
   /// User-Agent header value
+  /// Defaults to `DEFAULT_USER_AGENT`; None sends no User-Agent
   pub user_agent: Option<alloc::string::String>,
   /// How to handle HTTP redirects
   pub redirect_policy: RedirectPolicy,
+  /// What to do with the body of a non-POST request redirected by 301 or 302
+  pub redirect_body_policy: RedirectBodyPolicy,
   /// Maximum number of redirects to follow
   pub max_redirects: u32,
+  /// Maximum number of distinct hosts a redirect chain may visit, the first included
+  /// None sets no limit beyond `max_redirects`
+  pub max_redirect_hosts: Option<usize>,
   /// How to handle 4xx/5xx status codes
   pub http_status_handling: HttpStatusHandling,
   /// Policy for forwarding auth headers on redirects
   pub redirect_auth_headers: RedirectAuthHeaders,
   /// Maximum size for response headers in bytes
   pub max_response_header_size: usize,
+  /// Maximum number of response header fields
+  /// Caps many tiny fields that stay under the size limit. None means no limit
+  pub max_header_count: Option<usize>,
+  /// Maximum length of a chunk-size line, chunk extensions included
+  /// Longer lines fail with `ParseError::ChunkLineTooLong` instead of being
+  /// buffered without bound. None means no limit
+  pub max_chunk_line_length: Option<usize>,
+  /// Maximum size of a response body kept in memory, before content decoding
+  /// Larger bodies fail with `ParseError::BodyTooLarge`; for chunked bodies
+  /// each declared chunk size counts before its data is read. Bodies streamed
+  /// with `call_to_writer` are not limited. None means no limit
+  pub max_response_body_size: Option<usize>,
+  /// Maximum number of chunks in a chunked response body
+  /// Guards against endless streams of tiny chunks. None means no limit
+  pub max_chunk_count: Option<usize>,
   /// Timeout for establishing connection
   pub timeout_connect: Option<Duration>,
   /// Timeout for reading response
   pub timeout_read: Option<Duration>,
   /// Accept header value
   pub accept: Option<alloc::string::String>,
+  /// Accept-Language header value
+  /// None sends no Accept-Language. A per-request Accept-Language header always wins
+  pub accept_language: Option<alloc::string::String>,
   /// Protocol restrictions (HTTP/HTTPS)
   pub protocol_restriction: ProtocolRestriction,
   /// Enable connection pooling for persistent connections
   pub connection_pooling: bool,
   /// Maximum idle connections to keep per host
   pub max_idle_per_host: usize,
-  /// Timeout for idle connections in the pool (in seconds)
+  /// How long a connection may sit idle in the pool before it is dropped
+  /// Measured with the client's `Clock`; without one idle connections never expire
   pub idle_timeout: Option<Duration>,
   /// Maximum allowed URI length in bytes (RFC 9112 Section 3)
   /// Server should respond with 414 (URI Too Long) if exceeded
   /// None means no limit
   pub max_uri_length: Option<usize>,
+  /// Decode response bodies according to `Content-Encoding`
+  /// When disabled, bodies are returned exactly as received
+  pub auto_decompress: bool,
+  /// Disable Nagle's algorithm (`TCP_NODELAY`) on new connections
+  pub tcp_nodelay: bool,
+  /// Enable TCP keepalive on new connections with the given idle time
+  /// before the first probe; None leaves keepalive disabled
+  pub tcp_keepalive: Option<Duration>,
+  /// Local address to bind outgoing sockets to before connecting
+  /// None lets the OS choose the source address
+  pub local_bind: Option<IpAddr>,
+  /// Extra connect attempts after a refused or timed-out connection
+  pub connect_retries: u32,
+  /// Delay before the first connect retry, doubled after each further attempt
+  pub connect_backoff: Duration,
+  /// Refuse to connect to loopback, link-local, private (RFC 1918), unique
+  /// local and unspecified addresses, checked after DNS resolution of every hop,
+  /// redirects included; with a proxy, both the proxy's address and the
+  /// request host's addresses are checked
+  /// Guards services that fetch user-supplied URLs against SSRF
+  pub block_private_addresses: bool,
+  /// Accept slightly malformed status lines (lowercase `http/1.1`,
+  /// missing space before an empty reason phrase), folded header values
+  /// (obs-fold, RFC 9112 Section 5.2) and whitespace-preceded lines before the
+  /// first header field (skipped, RFC 9112 Section 2.2) from non-compliant servers
+  /// When disabled, responses with folded header values or such lines are rejected
+  pub lenient_parsing: bool,
+  /// Drop bytes sent after a response's framing ends, closing the connection,
+  /// instead of failing with `ParseError::ExtraDataAfterResponse`
+  pub discard_surplus_bytes: bool,
+  /// HTTP forward proxy URL (e.g. `http://proxy.local:3128`)
+  /// When set, every connection goes to the proxy and requests use
+  /// absolute-form targets (RFC 9112 Section 3.2.2)
+  /// Only `http` URLs can be proxied: `https` ones fail with
+  /// `Error::ProxyTunnelUnsupported`, since CONNECT tunnels are not supported
+  pub proxy: Option<alloc::string::String>,
+  /// URL that relative request URLs are resolved against (RFC 3986 Section 5.2)
+  /// With `https://api.example.com/v1/`, `users` becomes `/v1/users` while
+  /// `/users` replaces the base path; absolute request URLs ignore it
+  pub base_url: Option<alloc::string::String>,
+  /// Percent-encode characters not allowed in a URI, such as spaces, in the
+  /// path and query of request URLs instead of rejecting them
+  pub auto_encode_url: bool,
+  /// Keep `Cookie`, `Authorization` and `Proxy-Authorization` on TRACE requests
+  /// The server echoes a TRACE request back, so these are stripped by default
+  /// to keep credentials out of responses that intermediaries or scripts may see
+  pub trace_sends_credentials: bool,
+  /// Send repeated request header fields as one comma-separated line
+  /// (RFC 9110 Section 5.3), e.g. two `Accept` fields as `Accept: a, b`
+  /// `Cookie` and `Set-Cookie` are never combined
+  pub fold_duplicate_request_headers: bool,
+  /// Do not add `Content-Length` to requests with a body
+  /// The caller then frames the body with its own `Content-Length` or
+  /// `Transfer-Encoding` header; requests with `Transfer-Encoding` never get one
+  pub omit_content_length: bool,
+  /// Refuse request header values containing bytes above 0x7F (e.g. raw UTF-8)
+  /// When enabled, such requests fail with `ParseError::InvalidHeaderValue`;
+  /// control characters other than tab are rejected either way
+  pub strict_ascii_header_values: bool,
+  /// Accept-Encoding header value
+  /// None advertises the codings enabled by the decompression features,
+  /// an empty string sends no Accept-Encoding at all. A per-request
+  /// Accept-Encoding header always wins. Responses are still decoded
+  /// according to `auto_decompress` either way
+  pub accept_encoding: Option<alloc::string::String>,
+  /// Headers added to every request
+  /// A per-request header with the same name replaces the default
+  pub default_headers: Headers,
+  /// Maximum size of a response body after content decoding
+  /// Decoding aborts once the output grows past this limit, guarding against
+  /// compression bombs. None means no limit
+  pub max_decompressed_size: Option<usize>,
+  /// Media types whose `Content-Encoding` is decoded, e.g. `text/html` or `text/*`
+  /// `Content-Encoding` describes the representation (RFC 9110 Section 8.4), but
+  /// some servers label a stored `.gz` file with it; listing types here keeps
+  /// other bodies as received. Matched against `Content-Type` without parameters,
+  /// case-insensitively. None decodes every type, subject to `auto_decompress`
+  pub decompress_content_types: Option<alloc::vec::Vec<alloc::string::String>>,
+  /// Default ports for URL schemes, used when a URL has no explicit port
+  /// Schemes compare case-insensitively; schemes not listed fall back to
+  /// 443 for `https` and 80 for everything else
+  pub default_ports: alloc::vec::Vec<(alloc::string::String, u16)>,
 }
 
 impl Default for Config {
   fn default() -> Self {
     Self {
       timeout: None,
-      user_agent: Some(alloc::string::String::from("barehttp/1.0")),
+      user_agent: Some(alloc::string::String::from(DEFAULT_USER_AGENT)),
       redirect_policy: RedirectPolicy::Follow,
+      redirect_body_policy: RedirectBodyPolicy::Keep,
       max_redirects: 10,
+      max_redirect_hosts: None,
       http_status_handling: HttpStatusHandling::AsError,
       redirect_auth_headers: RedirectAuthHeaders::Never,
       max_response_header_size: 64 * 1024,
+      max_header_count: Some(100),
+      max_chunk_line_length: Some(16 * 1024),
+      max_response_body_size: None,
+      max_chunk_count: None,
       timeout_connect: None,
       timeout_read: None,
       accept: Some(alloc::string::String::from("*/*")),
+      accept_language: None,
       protocol_restriction: ProtocolRestriction::Any,
       connection_pooling: true,
       max_idle_per_host: 5,
       idle_timeout: Some(Duration::from_secs(90)),
       max_uri_length: Some(8192), // RFC 9112 Section 3: reasonable default
+      auto_decompress: true,
+      tcp_nodelay: true,
+      tcp_keepalive: None,
+      local_bind: None,
+      connect_retries: 0,
+      connect_backoff: Duration::from_millis(100),
+      block_private_addresses: false,
+      lenient_parsing: false,
+      discard_surplus_bytes: false,
+      proxy: None,
+      base_url: None,
+      auto_encode_url: false,
+      trace_sends_credentials: false,
+      fold_duplicate_request_headers: false,
+      omit_content_length: false,
+      strict_ascii_header_values: false,
+      accept_encoding: None,
+      default_headers: Headers::new(),
+      max_decompressed_size: None,
+      decompress_content_types: None,
+      default_ports: alloc::vec::Vec::new(),
     }
   }
 }
 
+impl Config {
+  /// Port to connect to for a URL of the given scheme without an explicit port
+  #[must_use]
+  pub fn default_port(
+    &self,
+    scheme: &str,
+  ) -> u16 {
+    self
+      .default_ports
+      .iter()
+      .find(|(name, _)| name.eq_ignore_ascii_case(scheme))
+      .map_or_else(
+        || {
+          if scheme.eq_ignore_ascii_case("https") {
+            443
+          } else {
+            80
+          }
+        },
+        |(_, port)| *port,
+      )
+  }
+}
+
 /// Builder for constructing HTTP client configuration
 ///
 /// Provides a fluent interface for setting configuration options.
@@ -135,6 +337,13 @@ impl ConfigBuilder {
     self
   }
 
+  /// Do not send a User-Agent header unless one is set on the request
+  #[must_use]
+  pub fn no_user_agent(mut self) -> Self {
+    self.config.user_agent = None;
+    self
+  }
+
   /// Set the redirect following policy
   #[must_use]
   pub const fn redirect_policy(
@@ -145,6 +354,16 @@ impl ConfigBuilder {
     self
   }
 
+  /// Set what to do with the body of a non-POST request redirected by 301 or 302
+  #[must_use]
+  pub const fn redirect_body_policy(
+    mut self,
+    policy: RedirectBodyPolicy,
+  ) -> Self {
+    self.config.redirect_body_policy = policy;
+    self
+  }
+
   /// Set the maximum number of redirects to follow
   #[must_use]
   pub const fn max_redirects(
@@ -155,6 +374,16 @@ impl ConfigBuilder {
     self
   }
 
+  /// Set the maximum number of distinct hosts a redirect chain may visit
+  #[must_use]
+  pub const fn max_redirect_hosts(
+    mut self,
+    max: Option<usize>,
+  ) -> Self {
+    self.config.max_redirect_hosts = max;
+    self
+  }
+
   /// Set how to handle HTTP error status codes
   #[must_use]
   pub const fn http_status_handling(
@@ -185,6 +414,46 @@ impl ConfigBuilder {
     self
   }
 
+  /// Set the maximum number of response header fields
+  #[must_use]
+  pub const fn max_header_count(
+    mut self,
+    count: usize,
+  ) -> Self {
+    self.config.max_header_count = Some(count);
+    self
+  }
+
+  /// Set the maximum length of a chunk-size line in chunked response bodies
+  #[must_use]
+  pub const fn max_chunk_line_length(
+    mut self,
+    length: usize,
+  ) -> Self {
+    self.config.max_chunk_line_length = Some(length);
+    self
+  }
+
+  /// Set the maximum size of a response body kept in memory
+  #[must_use]
+  pub const fn max_response_body_size(
+    mut self,
+    size: usize,
+  ) -> Self {
+    self.config.max_response_body_size = Some(size);
+    self
+  }
+
+  /// Set the maximum number of chunks in a chunked response body
+  #[must_use]
+  pub const fn max_chunk_count(
+    mut self,
+    count: usize,
+  ) -> Self {
+    self.config.max_chunk_count = Some(count);
+    self
+  }
+
   /// Set the connection timeout
   #[must_use]
   pub const fn timeout_connect(
@@ -215,6 +484,16 @@ impl ConfigBuilder {
     self
   }
 
+  #[must_use]
+  /// Set the Accept-Language header sent with every request
+  pub fn accept_language(
+    mut self,
+    value: impl Into<alloc::string::String>,
+  ) -> Self {
+    self.config.accept_language = Some(value.into());
+    self
+  }
+
   #[must_use]
   /// Set protocol restrictions (HTTP/HTTPS only)
   pub const fn protocol_restriction(
@@ -255,6 +534,250 @@ impl ConfigBuilder {
     self
   }
 
+  #[must_use]
+  /// Enable or disable automatic decoding of `Content-Encoding`
+  pub const fn auto_decompress(
+    mut self,
+    enabled: bool,
+  ) -> Self {
+    self.config.auto_decompress = enabled;
+    self
+  }
+
+  #[must_use]
+  /// Enable or disable `TCP_NODELAY` on new connections
+  pub const fn tcp_nodelay(
+    mut self,
+    enabled: bool,
+  ) -> Self {
+    self.config.tcp_nodelay = enabled;
+    self
+  }
+
+  #[must_use]
+  /// Enable TCP keepalive with the given idle time before the first probe
+  pub const fn tcp_keepalive(
+    mut self,
+    idle: Duration,
+  ) -> Self {
+    self.config.tcp_keepalive = Some(idle);
+    self
+  }
+
+  #[must_use]
+  /// Bind outgoing sockets to the given local address
+  pub const fn local_bind(
+    mut self,
+    addr: IpAddr,
+  ) -> Self {
+    self.config.local_bind = Some(addr);
+    self
+  }
+
+  #[must_use]
+  /// Set how many times a refused or timed-out connect is retried
+  pub const fn connect_retries(
+    mut self,
+    retries: u32,
+  ) -> Self {
+    self.config.connect_retries = retries;
+    self
+  }
+
+  #[must_use]
+  /// Set the delay before the first connect retry
+  pub const fn connect_backoff(
+    mut self,
+    duration: Duration,
+  ) -> Self {
+    self.config.connect_backoff = duration;
+    self
+  }
+
+  /// Refuse connections to loopback, link-local, private, unique local and unspecified addresses
+  #[must_use]
+  pub const fn block_private_addresses(
+    mut self,
+    enabled: bool,
+  ) -> Self {
+    self.config.block_private_addresses = enabled;
+    self
+  }
+
+  #[must_use]
+  /// Enable or disable lenient status-line, obs-fold and leading-whitespace-line parsing
+  pub const fn lenient_parsing(
+    mut self,
+    enabled: bool,
+  ) -> Self {
+    self.config.lenient_parsing = enabled;
+    self
+  }
+
+  #[must_use]
+  /// Drop bytes sent after a response's framing ends instead of failing
+  pub const fn discard_surplus_bytes(
+    mut self,
+    enabled: bool,
+  ) -> Self {
+    self.config.discard_surplus_bytes = enabled;
+    self
+  }
+
+  /// Send `http` requests through an HTTP forward proxy
+  #[must_use]
+  pub fn proxy(
+    mut self,
+    url: impl Into<alloc::string::String>,
+  ) -> Self {
+    self.config.proxy = Some(url.into());
+    self
+  }
+
+  /// Resolve relative request URLs against `url`
+  #[must_use]
+  pub fn base_url(
+    mut self,
+    url: impl Into<alloc::string::String>,
+  ) -> Self {
+    self.config.base_url = Some(url.into());
+    self
+  }
+
+  /// Percent-encode spaces and other characters not allowed in request URLs
+  #[must_use]
+  pub const fn auto_encode_url(
+    mut self,
+    enabled: bool,
+  ) -> Self {
+    self.config.auto_encode_url = enabled;
+    self
+  }
+
+  /// Keep credential headers on TRACE requests instead of stripping them
+  #[must_use]
+  pub const fn trace_sends_credentials(
+    mut self,
+    enabled: bool,
+  ) -> Self {
+    self.config.trace_sends_credentials = enabled;
+    self
+  }
+
+  /// Combine repeated request header fields into one comma-separated line
+  #[must_use]
+  pub const fn fold_duplicate_request_headers(
+    mut self,
+    enabled: bool,
+  ) -> Self {
+    self.config.fold_duplicate_request_headers = enabled;
+    self
+  }
+
+  /// Stop adding `Content-Length` to requests with a body
+  #[must_use]
+  pub const fn omit_content_length(
+    mut self,
+    enabled: bool,
+  ) -> Self {
+    self.config.omit_content_length = enabled;
+    self
+  }
+
+  /// Refuse bytes above 0x7F in request header values
+  #[must_use]
+  pub const fn strict_ascii_header_values(
+    mut self,
+    enabled: bool,
+  ) -> Self {
+    self.config.strict_ascii_header_values = enabled;
+    self
+  }
+
+  /// Decode `Content-Encoding` only for the given media types
+  ///
+  /// Entries are full types such as `application/json` or wildcards such as `text/*`.
+  #[must_use]
+  pub fn decompress_content_types<I, T>(
+    mut self,
+    types: I,
+  ) -> Self
+  where
+    I: IntoIterator<Item = T>,
+    T: Into<alloc::string::String>,
+  {
+    self.config.decompress_content_types = Some(types.into_iter().map(Into::into).collect());
+    self
+  }
+
+  /// Set the Accept-Encoding header sent with every request
+  ///
+  /// Pass an empty string to stop sending Accept-Encoding.
+  #[must_use]
+  pub fn accept_encoding(
+    mut self,
+    value: impl Into<alloc::string::String>,
+  ) -> Self {
+    self.config.accept_encoding = Some(value.into());
+    self
+  }
+
+  /// Add a header sent with every request
+  #[must_use]
+  pub fn default_header(
+    mut self,
+    name: impl Into<alloc::string::String>,
+    value: impl Into<alloc::string::String>,
+  ) -> Self {
+    self.config.default_headers.insert(name, value);
+    self
+  }
+
+  /// Add several headers sent with every request
+  #[must_use]
+  pub fn default_headers<I, K, V>(
+    mut self,
+    iter: I,
+  ) -> Self
+  where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<alloc::string::String>,
+    V: Into<alloc::string::String>,
+  {
+    for (name, value) in iter {
+      self.config.default_headers.insert(name, value);
+    }
+    self
+  }
+
+  #[must_use]
+  /// Set the maximum size of a decoded response body
+  pub const fn max_decompressed_size(
+    mut self,
+    size: usize,
+  ) -> Self {
+    self.config.max_decompressed_size = Some(size);
+    self
+  }
+
+  /// Register the default port for a URL scheme, e.g. `("wss", 443)`
+  ///
+  /// Replaces any port registered earlier for the same scheme.
+  #[must_use]
+  pub fn default_port(
+    mut self,
+    scheme: impl Into<alloc::string::String>,
+    port: u16,
+  ) -> Self {
+    let name = scheme.into();
+    self
+      .config
+      .default_ports
+      .retain(|(registered, _)| !registered.eq_ignore_ascii_case(&name));
+    self.config.default_ports.push((name, port));
+    self
+  }
+
   #[must_use]
   /// Build the final configuration
   pub fn build(self) -> Config {
@@ -277,16 +800,70 @@ mod tests {
     let config = Config::default();
 
     assert!(config.timeout.is_none());
-    assert_eq!(config.user_agent, Some(alloc::string::String::from("barehttp/1.0")));
+    assert_eq!(config.user_agent, Some(alloc::string::String::from(DEFAULT_USER_AGENT)));
     assert_eq!(config.redirect_policy, RedirectPolicy::Follow);
+    assert_eq!(config.redirect_body_policy, RedirectBodyPolicy::Keep);
     assert_eq!(config.max_redirects, 10);
+    assert!(config.max_redirect_hosts.is_none());
     assert_eq!(config.http_status_handling, HttpStatusHandling::AsError);
     assert_eq!(config.redirect_auth_headers, RedirectAuthHeaders::Never);
     assert_eq!(config.max_response_header_size, 64 * 1024);
+    assert_eq!(config.max_header_count, Some(100));
+    assert_eq!(config.max_chunk_line_length, Some(16 * 1024));
+    assert!(config.max_response_body_size.is_none());
+    assert!(config.max_chunk_count.is_none());
     assert!(config.timeout_connect.is_none());
     assert!(config.timeout_read.is_none());
     assert_eq!(config.accept, Some(alloc::string::String::from("*/*")));
+    assert!(config.accept_language.is_none());
     assert_eq!(config.protocol_restriction, ProtocolRestriction::Any);
+    assert!(config.tcp_nodelay);
+    assert!(config.tcp_keepalive.is_none());
+    assert!(config.local_bind.is_none());
+    assert_eq!(config.connect_retries, 0);
+    assert!(!config.block_private_addresses);
+    assert!(!config.lenient_parsing);
+    assert!(!config.discard_surplus_bytes);
+    assert!(config.proxy.is_none());
+    assert!(config.base_url.is_none());
+    assert!(!config.auto_encode_url);
+    assert!(!config.trace_sends_credentials);
+    assert!(!config.fold_duplicate_request_headers);
+    assert!(!config.omit_content_length);
+    assert!(!config.strict_ascii_header_values);
+    assert!(config.accept_encoding.is_none());
+    assert!(config.default_headers.is_empty());
+    assert!(config.max_decompressed_size.is_none());
+    assert!(config.decompress_content_types.is_none());
+    assert!(config.default_ports.is_empty());
+  }
+
+  #[test]
+  fn http_status_handling_is_error() {
+    assert!(HttpStatusHandling::AsError.is_error(404));
+    assert!(HttpStatusHandling::AsError.is_error(503));
+    assert!(!HttpStatusHandling::AsError.is_error(302));
+    assert!(!HttpStatusHandling::AsResponse.is_error(500));
+
+    let custom = HttpStatusHandling::Custom(&[429..=429, 500..=599]);
+    assert!(custom.is_error(429));
+    assert!(custom.is_error(502));
+    assert!(!custom.is_error(404));
+  }
+
+  #[test]
+  fn config_default_port_uses_registered_schemes() {
+    let config = ConfigBuilder::new()
+      .default_port("ws", 80)
+      .default_port("wss", 8443)
+      .default_port("WSS", 443)
+      .build();
+
+    assert_eq!(config.default_port("wss"), 443);
+    assert_eq!(config.default_port("ws"), 80);
+    assert_eq!(config.default_port("https"), 443);
+    assert_eq!(config.default_port("http"), 80);
+    assert_eq!(config.default_ports.len(), 2);
   }
 
   #[test]
@@ -305,6 +882,13 @@ mod tests {
     assert_eq!(config.user_agent, Some(alloc::string::String::from("MyClient/1.0")));
   }
 
+  #[test]
+  fn config_builder_no_user_agent() {
+    let config = ConfigBuilder::new().no_user_agent().build();
+
+    assert!(config.user_agent.is_none());
+  }
+
   #[test]
   fn config_builder_redirect_policy() {
     let config = ConfigBuilder::new()
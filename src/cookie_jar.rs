@@ -1,14 +1,21 @@
 extern crate alloc;
 use alloc::string::{String, ToString};
+#[cfg(feature = "cookie-jar")]
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::sync::atomic::{AtomicU64, Ordering};
 use spin::Mutex;
 
+#[cfg(feature = "cookie-jar")]
+use crate::clock::Clock;
+#[cfg(feature = "cookie-jar")]
+pub use crate::parser::cookie::SameSite;
 #[cfg(feature = "cookie-jar")]
 use crate::parser::cookie::SetCookie;
 
 #[cfg(feature = "cookie-jar")]
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 /// Represents a stored HTTP cookie with all RFC 6265 attributes
 ///
 /// This struct contains all information needed to store and match cookies
@@ -26,36 +33,110 @@ pub struct StoredCookie {
   pub secure: bool,
   /// `HttpOnly` flag - cookie not accessible via JavaScript
   pub http_only: bool,
+  /// `SameSite` attribute, None if absent or unrecognized
+  ///
+  /// Stored for callers that enforce it; the client itself does not
+  /// distinguish same-site from cross-site requests.
+  pub same_site: Option<SameSite>,
+  /// `Partitioned` flag (CHIPS) - cookie is keyed to the top-level site
+  pub partitioned: bool,
   /// Host-only flag - cookie only matches exact host
   pub host_only: bool,
-  /// Creation time (logical counter)
+  /// Creation time (milliseconds since the Unix epoch, or a logical
+  /// counter if the store has no clock)
   pub creation_time: u64,
-  /// Expiry time (logical counter), None means session cookie
+  /// Time the cookie was last sent or stored, in the same unit as `creation_time`
+  pub last_access_time: u64,
+  /// Expiry time in the same unit as `creation_time`, None means session cookie
   pub expiry_time: Option<u64>,
 }
 
 #[cfg(feature = "cookie-jar")]
-#[derive(Debug)]
 /// Thread-safe RFC 6265 compliant cookie storage
 ///
 /// Automatically handles cookie domain/path matching, expiration,
 /// and secure cookie restrictions.
+///
+/// Without a clock the store measures time with a logical counter that
+/// advances on every operation, so `Max-Age` only approximates a lifetime
+/// and `Expires` dates are treated as one year from storage. Use
+/// `with_clock` to compare against real time.
 pub struct CookieStore {
   cookies: Mutex<Vec<StoredCookie>>,
   counter: AtomicU64,
+  max_cookies: usize,
+  clock: Option<Arc<dyn Clock>>,
+}
+
+#[cfg(feature = "cookie-jar")]
+impl core::fmt::Debug for CookieStore {
+  fn fmt(
+    &self,
+    f: &mut core::fmt::Formatter<'_>,
+  ) -> core::fmt::Result {
+    f.debug_struct("CookieStore")
+      .field("cookies", &self.cookies)
+      .field("max_cookies", &self.max_cookies)
+      .field("has_clock", &self.clock.is_some())
+      .finish_non_exhaustive()
+  }
 }
 
+#[cfg(feature = "cookie-jar")]
+/// Cookie limit of `CookieStore::new` (RFC 6265 Section 6.1 minimum capacity)
+pub const DEFAULT_MAX_COOKIES: usize = 3000;
+
 #[cfg(feature = "cookie-jar")]
 impl CookieStore {
-  /// Creates a new empty cookie store
+  /// Creates a new empty cookie store holding up to `DEFAULT_MAX_COOKIES` cookies
   #[must_use]
   pub const fn new() -> Self {
+    Self::with_max_cookies(DEFAULT_MAX_COOKIES)
+  }
+
+  /// Creates a new empty cookie store holding up to `max_cookies` cookies
+  ///
+  /// When a new cookie would exceed the limit, expired cookies are purged
+  /// first and then the least recently used cookies are evicted.
+  #[must_use]
+  pub const fn with_max_cookies(max_cookies: usize) -> Self {
     Self {
       cookies: Mutex::new(Vec::new()),
       counter: AtomicU64::new(0),
+      max_cookies,
+      clock: None,
     }
   }
 
+  /// Use a wall clock for cookie timestamps and expiry
+  ///
+  /// `Max-Age` then counts real seconds and `Expires` dates are compared
+  /// against the clock's current time.
+  #[must_use]
+  pub fn with_clock(
+    mut self,
+    clock: Arc<dyn Clock>,
+  ) -> Self {
+    self.set_clock(clock);
+    self
+  }
+
+  /// Switch the store to `clock`, as `with_clock` does for a new store
+  pub(crate) fn set_clock(
+    &mut self,
+    clock: Arc<dyn Clock>,
+  ) {
+    self.clock = Some(clock);
+  }
+
+  /// Current time: clock milliseconds, or the next logical counter value
+  fn now(&self) -> u64 {
+    self.clock.as_ref().map_or_else(
+      || self.counter.fetch_add(1, Ordering::SeqCst),
+      |clock| clock.now_millis(),
+    )
+  }
+
   /// Stores cookies from Set-Cookie response headers
   ///
   /// Parses and stores cookies according to RFC 6265 rules, including
@@ -78,7 +159,16 @@ impl CookieStore {
 
     for header_value in set_cookie_headers {
       if let Some(parsed) = SetCookie::parse(header_value) {
-        Self::insert_cookie_locked(&mut cookies, parsed, request_host, &request_path, &self.counter);
+        let now = self.now();
+        Self::insert_cookie_locked(
+          &mut cookies,
+          parsed,
+          request_host,
+          &request_path,
+          now,
+          self.clock.is_some(),
+          self.max_cookies,
+        );
       }
     }
   }
@@ -88,9 +178,13 @@ impl CookieStore {
     cookie: SetCookie,
     request_host: &str,
     request_path: &str,
-    counter: &AtomicU64,
+    current: u64,
+    wall_clock: bool,
+    max_cookies: usize,
   ) {
-    let current = counter.fetch_add(1, Ordering::SeqCst);
+    if !satisfies_prefix_rules(&cookie) {
+      return;
+    }
 
     let host_only = cookie.domain.is_none();
 
@@ -105,19 +199,21 @@ impl CookieStore {
 
     let path = cookie.path.unwrap_or_else(|| default_path(request_path));
 
-    let expiry_time = if let Some(max_age) = cookie.max_age {
-      if max_age <= 0 {
-        Some(0)
-      } else {
-        Some(current.saturating_add(max_age.unsigned_abs()))
-      }
-    } else {
-      cookie.expires.map(|_| current.saturating_add(31_536_000))
+    // RFC 6265 Section 5.3 step 3: Max-Age takes precedence over Expires
+    let expiry_time = match (cookie.max_age, cookie.expires) {
+      (Some(max_age), _) if max_age <= 0 => Some(0),
+      (Some(max_age), _) if wall_clock => Some(current.saturating_add(max_age.unsigned_abs().saturating_mul(1000))),
+      (Some(max_age), _) => Some(current.saturating_add(max_age.unsigned_abs())),
+      // An Expires date outside the representable range has already passed
+      (None, Some(date)) if wall_clock => Some(date.to_unix().map_or(0, |secs| secs.saturating_mul(1000))),
+      (None, Some(_)) => Some(current.saturating_add(31_536_000)),
+      (None, None) => None,
     };
 
     cookies.retain(|c| !(c.name == cookie.name && c.domain == domain && c.path == path));
 
-    if expiry_time != Some(0) {
+    // A cookie that is already expired only deletes the one it replaces
+    if expiry_time.is_none_or(|expiry| expiry > current) {
       let stored = StoredCookie {
         name: cookie.name,
         value: cookie.value,
@@ -125,13 +221,18 @@ impl CookieStore {
         path,
         secure: cookie.secure,
         http_only: cookie.http_only,
+        same_site: cookie.same_site,
+        partitioned: cookie.partitioned,
         host_only,
         creation_time: current,
+        last_access_time: current,
         expiry_time,
       };
 
       cookies.push(stored);
     }
+
+    enforce_limit(cookies, current, max_cookies);
   }
 
   /// Gets cookies to send in Cookie request header
@@ -155,15 +256,13 @@ impl CookieStore {
     };
 
     let request_path = extract_path_from_uri(uri);
-    let current = self.counter.fetch_add(1, Ordering::SeqCst);
+    let current = self.now();
 
-    let cookies = self.cookies.lock();
+    let mut cookies = self.cookies.lock();
     let mut matching_cookies = Vec::new();
 
-    for cookie in cookies.iter() {
-      if let Some(expiry) = cookie.expiry_time
-        && expiry <= current
-      {
+    for cookie in cookies.iter_mut() {
+      if is_expired(cookie, current) {
         continue;
       }
 
@@ -185,6 +284,7 @@ impl CookieStore {
         continue;
       }
 
+      cookie.last_access_time = current;
       matching_cookies.push(cookie);
     }
 
@@ -213,18 +313,86 @@ impl CookieStore {
     self.cookies.lock().clear();
   }
 
+  /// Removes the cookie with the given name, domain and path
+  ///
+  /// The domain is compared case-insensitively. Returns true if a cookie was removed.
+  pub fn remove(
+    &self,
+    name: &str,
+    domain: &str,
+    path: &str,
+  ) -> bool {
+    let mut cookies = self.cookies.lock();
+    let before = cookies.len();
+    cookies.retain(|c| !(c.name == name && c.domain.eq_ignore_ascii_case(domain) && c.path == path));
+    cookies.len() != before
+  }
+
+  /// Drops expired cookies from storage
+  ///
+  /// Expired cookies are never sent, but stay in memory until purged or evicted.
+  pub fn purge_expired(&self) {
+    let current = self.now();
+    self.cookies.lock().retain(|c| !is_expired(c, current));
+  }
+
+  /// Returns the number of stored cookies, including expired ones not yet purged
+  pub fn len(&self) -> usize {
+    self.cookies.lock().len()
+  }
+
+  /// Returns true if no cookies are stored
+  pub fn is_empty(&self) -> bool {
+    self.cookies.lock().is_empty()
+  }
+
   /// Returns unexpired cookies as a Vec
   ///
   /// Filters out cookies that have passed their expiration time.
   pub fn get_unexpired(&self) -> Vec<StoredCookie> {
-    let current = self.counter.fetch_add(1, Ordering::SeqCst);
+    let current = self.now();
     let cookies = self.cookies.lock();
     cookies
       .iter()
-      .filter(|c| c.expiry_time.is_none_or(|expiry| expiry > current))
+      .filter(|c| !is_expired(c, current))
       .cloned()
       .collect()
   }
+
+  /// Snapshot of the store for persisting it, e.g. between runs of a CLI tool
+  ///
+  /// Contains every unexpired cookie, session cookies included. Timestamps
+  /// are only meaningful to a store using the same kind of time source, so
+  /// save and restore with stores that both have a clock.
+  pub fn export(&self) -> Vec<StoredCookie> {
+    self.get_unexpired()
+  }
+
+  /// Restore cookies from `export`
+  ///
+  /// Each cookie replaces a stored one with the same name, domain and path.
+  /// Expired cookies are skipped, and the cookie limit applies as usual.
+  pub fn import<I>(
+    &self,
+    cookies: I,
+  ) where
+    I: IntoIterator<Item = StoredCookie>,
+  {
+    let current = self.now();
+    let mut stored = self.cookies.lock();
+    for cookie in cookies {
+      if is_expired(&cookie, current) {
+        continue;
+      }
+      // Keep later logical timestamps ordered after the imported ones
+      self
+        .counter
+        .fetch_max(cookie.last_access_time.saturating_add(1), Ordering::SeqCst);
+      stored.retain(|c| !(c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path));
+      stored.push(cookie);
+    }
+    enforce_limit(&mut stored, current, self.max_cookies);
+  }
 }
 
 #[cfg(feature = "cookie-jar")]
@@ -234,6 +402,59 @@ impl Default for CookieStore {
   }
 }
 
+/// Evict cookies beyond `max_cookies`
+///
+/// RFC 6265 Section 5.3 step 12: expired cookies go first, then the least recently used.
+fn enforce_limit(
+  cookies: &mut Vec<StoredCookie>,
+  now: u64,
+  max_cookies: usize,
+) {
+  if cookies.len() > max_cookies {
+    cookies.retain(|c| !is_expired(c, now));
+  }
+  while cookies.len() > max_cookies {
+    let Some(oldest) = cookies
+      .iter()
+      .enumerate()
+      .min_by_key(|(_, c)| c.last_access_time)
+      .map(|(index, _)| index)
+    else {
+      break;
+    };
+    cookies.remove(oldest);
+  }
+}
+
+fn is_expired(
+  cookie: &StoredCookie,
+  now: u64,
+) -> bool {
+  cookie.expiry_time.is_some_and(|expiry| expiry <= now)
+}
+
+/// Check the `__Secure-` and `__Host-` name prefix requirements
+///
+/// draft-ietf-httpbis-rfc6265bis Section 4.1.3: `__Secure-` cookies must be
+/// Secure; `__Host-` cookies must also have no Domain and a Path of "/".
+/// Prefixes match case-insensitively.
+fn satisfies_prefix_rules(cookie: &SetCookie) -> bool {
+  let has_prefix = |prefix: &str| {
+    cookie
+      .name
+      .get(..prefix.len())
+      .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+  };
+
+  if has_prefix("__Host-") {
+    return cookie.secure && cookie.domain.is_none() && cookie.path.as_deref() == Some("/");
+  }
+  if has_prefix("__Secure-") {
+    return cookie.secure;
+  }
+  true
+}
+
 fn extract_host_from_uri(uri: &str) -> Option<&str> {
   let after_scheme = uri.find("://").map_or(uri, |pos| &uri[pos + 3..]);
 
@@ -456,6 +677,18 @@ mod tests {
     assert_eq!(cookies_second, "id=second");
   }
 
+  #[test]
+  fn test_duplicate_cookie_in_one_response_keeps_last() {
+    let store = CookieStore::new();
+
+    store.store_response_cookies(
+      "http://example.com/",
+      &alloc::vec!["id=first".to_string(), "id=second".to_string()],
+    );
+
+    assert_eq!(store.get_request_cookies("http://example.com/", false), "id=second");
+  }
+
   #[test]
   fn test_multiple_cookies() {
     let store = CookieStore::new();
@@ -469,4 +702,187 @@ mod tests {
     assert!(cookies.contains("session=abc"));
     assert!(cookies.contains("lang=en"));
   }
+
+  #[test]
+  fn test_same_site_and_partitioned_are_stored() {
+    let store = CookieStore::new();
+
+    store.store_response_cookies(
+      "https://example.com/",
+      &alloc::vec!["id=1; Secure; SameSite=None; Partitioned".to_string()],
+    );
+
+    let cookies = store.get_unexpired();
+    assert_eq!(cookies.len(), 1);
+    assert_eq!(cookies.first().map(|c| c.same_site), Some(Some(SameSite::None)));
+    assert_eq!(cookies.first().map(|c| c.partitioned), Some(true));
+  }
+
+  #[test]
+  fn test_host_prefix_requires_secure_root_path_without_domain() {
+    let store = CookieStore::new();
+    let uri = "https://example.com/app/";
+
+    store.store_response_cookies(
+      uri,
+      &alloc::vec![
+        "__Host-a=1".to_string(),
+        "__Host-b=1; Path=/".to_string(),
+        "__Host-c=1; Secure".to_string(),
+        "__Host-d=1; Secure; Path=/; Domain=example.com".to_string(),
+        "__host-e=1; Path=/".to_string(),
+        "__Host-id=1; Secure; Path=/".to_string(),
+      ],
+    );
+
+    assert_eq!(store.get_request_cookies(uri, true), "__Host-id=1");
+  }
+
+  #[test]
+  fn test_secure_prefix_requires_secure() {
+    let store = CookieStore::new();
+    let uri = "https://example.com/";
+
+    store.store_response_cookies(
+      uri,
+      &alloc::vec![
+        "__Secure-a=1".to_string(),
+        "__SECURE-b=1; Path=/".to_string(),
+        "__Secure-ok=1; Secure; Domain=example.com".to_string(),
+      ],
+    );
+
+    assert_eq!(store.get_request_cookies(uri, true), "__Secure-ok=1");
+  }
+
+  #[test]
+  fn test_eviction_drops_least_recently_used() {
+    let store = CookieStore::with_max_cookies(2);
+    let uri = "http://example.com/";
+
+    store.store_response_cookies(
+      uri,
+      &alloc::vec!["a=1; Path=/a".to_string(), "b=2; Path=/b".to_string()],
+    );
+    // "a" is older, but sending it makes "b" the least recently used
+    assert_eq!(store.get_request_cookies("http://example.com/a", false), "a=1");
+    store.store_response_cookies(uri, &alloc::vec!["c=3".to_string()]);
+
+    assert_eq!(store.len(), 2);
+    assert_eq!(store.get_request_cookies("http://example.com/b", false), "c=3");
+    assert_eq!(store.get_request_cookies("http://example.com/a", false), "a=1; c=3");
+  }
+
+  #[test]
+  fn test_eviction_prefers_expired_cookies() {
+    let store = CookieStore::with_max_cookies(2);
+    let uri = "http://example.com/";
+
+    store.store_response_cookies(uri, &alloc::vec!["old=1".to_string()]);
+    store.store_response_cookies(uri, &alloc::vec!["short=1; Max-Age=1".to_string()]);
+    store.store_response_cookies(uri, &alloc::vec!["new=1".to_string()]);
+
+    assert_eq!(store.get_request_cookies(uri, false), "old=1; new=1");
+  }
+
+  #[test]
+  fn test_purge_expired_removes_from_storage() {
+    let store = CookieStore::new();
+    let uri = "http://example.com/";
+
+    store.store_response_cookies(
+      uri,
+      &alloc::vec!["short=1; Max-Age=1".to_string(), "session=1".to_string()],
+    );
+    assert_eq!(store.len(), 2);
+    let _ = store.get_request_cookies(uri, false);
+
+    store.purge_expired();
+
+    assert_eq!(store.len(), 1);
+    assert_eq!(store.get_request_cookies(uri, false), "session=1");
+  }
+
+  #[test]
+  fn test_remove_matches_name_domain_and_path() {
+    let store = CookieStore::new();
+    store.store_response_cookies(
+      "http://example.com/app/page",
+      &alloc::vec!["id=1".to_string(), "id=2; Path=/".to_string()],
+    );
+
+    assert!(!store.remove("id", "example.com", "/other"));
+    assert!(store.remove("id", "EXAMPLE.com", "/app"));
+    assert!(!store.remove("id", "example.com", "/app"));
+    assert_eq!(store.get_request_cookies("http://example.com/app/page", false), "id=2");
+  }
+
+  #[test]
+  fn test_max_age_counts_clock_seconds() {
+    let clock = Arc::new(crate::clock::ManualClock::new(1_000_000));
+    let store = CookieStore::new().with_clock(clock.clone());
+    let uri = "http://example.com/";
+
+    store.store_response_cookies(uri, &alloc::vec!["short=1; Max-Age=1".to_string()]);
+    assert_eq!(store.get_request_cookies(uri, false), "short=1");
+    clock.advance(999);
+    assert_eq!(store.get_request_cookies(uri, false), "short=1");
+    clock.advance(1);
+    assert_eq!(store.get_request_cookies(uri, false), "");
+  }
+
+  #[test]
+  fn test_expires_compares_against_clock() {
+    // Sun, 06 Nov 1994 08:49:37 GMT
+    let expires_millis = 784_111_777_000;
+    let clock = Arc::new(crate::clock::ManualClock::new(expires_millis - 60_000));
+    let store = CookieStore::new().with_clock(clock.clone());
+    let uri = "http://example.com/";
+
+    store.store_response_cookies(
+      uri,
+      &alloc::vec!["id=1; Expires=Sun, 06 Nov 1994 08:49:37 GMT".to_string()],
+    );
+    assert_eq!(store.get_request_cookies(uri, false), "id=1");
+
+    // A date in the past deletes the stored cookie
+    store.store_response_cookies(
+      uri,
+      &alloc::vec!["id=2; Expires=Sun, 06 Nov 1994 08:00:00 GMT".to_string()],
+    );
+    assert!(store.is_empty());
+
+    store.store_response_cookies(
+      uri,
+      &alloc::vec!["id=3; Expires=Sun, 06 Nov 1994 08:49:37 GMT".to_string()],
+    );
+    clock.set(expires_millis);
+    assert_eq!(store.get_request_cookies(uri, false), "");
+  }
+
+  #[test]
+  fn test_export_import_round_trip() {
+    let clock = Arc::new(crate::clock::ManualClock::new(1_000_000));
+    let saved = CookieStore::new().with_clock(clock.clone());
+    let uri = "http://example.com/";
+    saved.store_response_cookies(
+      uri,
+      &alloc::vec![
+        "session=1".to_string(),
+        "short=1; Max-Age=1".to_string(),
+        "long=1; Max-Age=3600".to_string(),
+      ],
+    );
+    clock.advance(2_000);
+
+    let exported = saved.export();
+    assert_eq!(exported.len(), 2);
+
+    let restored = CookieStore::new().with_clock(clock);
+    restored.store_response_cookies(uri, &alloc::vec!["long=old".to_string(), "other=1".to_string()]);
+    restored.import(exported);
+
+    assert_eq!(restored.len(), 3);
+    assert_eq!(restored.get_request_cookies(uri, false), "session=1; long=1; other=1");
+  }
 }
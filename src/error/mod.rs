@@ -11,6 +11,9 @@ pub use dns::DnsError;
 pub use parse::ParseError;
 pub use socket::SocketError;
 
+use crate::parser::Response;
+use alloc::boxed::Box;
+
 /// Main error type for HTTP operations
 ///
 /// Encompasses all possible errors that can occur during HTTP requests,
@@ -31,20 +34,50 @@ pub enum Error {
   IpAddressNotSupported,
   /// Maximum redirect limit exceeded
   TooManyRedirects,
+  /// Redirect chain visited more distinct hosts than `max_redirect_hosts` allows
+  TooManyRedirectHosts,
   /// Redirect response missing Location header
   MissingRedirectLocation,
   /// Invalid or malformed redirect location
   InvalidRedirectLocation,
   /// Circular redirect detected
   RedirectLoop,
+  /// A 301 or 302 redirect would re-send the request body, refused by
+  /// `RedirectBodyPolicy::Error`
+  RedirectBodyRefused,
   /// HTTP error status code (4xx or 5xx)
-  HttpStatus(u16),
+  HttpStatus {
+    /// Status code of the response
+    code: u16,
+    /// The error response, including the headers and body the server sent
+    response: Box<Response>,
+  },
   /// HTTPS required but HTTP URL provided
   HttpsRequired,
   /// Response headers exceed maximum allowed size
-  ResponseHeaderTooLarge,
-  /// UTF-8 decoding error
+  ResponseHeaderTooLarge {
+    /// Bytes received before the limit was hit
+    bytes_read: usize,
+    /// Status code from the status line, if it was received and parseable
+    status_code: Option<u16>,
+  },
+  /// Body text is not valid UTF-8, or not valid in the UTF-16 encoding it declares
   Utf8Error,
+  /// Body is in a character encoding that cannot be decoded
+  UnsupportedCharset,
+  /// Request aborted through its `CancelToken`
+  Cancelled,
+  /// Operation needs a `Clock` but the client has none
+  ClockUnavailable,
+  /// Writing the response body to a `ByteSink` failed
+  SinkFailed,
+  /// Host resolved to an address refused by `block_private_addresses`
+  BlockedAddress,
+  /// An `https` URL was requested through a proxy, which needs a CONNECT
+  /// tunnel; tunnels are not supported, so the request is not sent
+  ProxyTunnelUnsupported,
+  /// Connection is closed or broken and cannot carry another request
+  ConnectionUnusable,
 }
 
 impl From<ParseError> for Error {
@@ -29,19 +29,30 @@ pub enum ParseError {
   LineTooLong,
   /// Invalid chunk size in chunked transfer encoding
   InvalidChunkSize,
+  /// Chunk-size line, including chunk extensions, exceeds the configured limit
+  ChunkLineTooLong,
+  /// Chunked body has more chunks than the configured limit
+  TooManyChunks,
+  /// Response body exceeds the configured maximum size
+  BodyTooLarge,
   /// Invalid Content-Length header value
   InvalidContentLength,
   /// Response header section exceeds size limit
   HeaderTooLarge,
+  /// Response header section has more fields than allowed
+  TooManyHeaders,
   /// Invalid state transition in response reader
   InvalidState,
   /// Both Transfer-Encoding and Content-Length present (RFC 9112 Section 6.3)
   ConflictingFraming,
+  /// Several Content-Length header fields with different values (RFC 9112 Section 6.3)
+  MultipleContentLength,
   /// Transfer-Encoding present but chunked is not the final encoding (RFC 9112 Section 6.3)
   ChunkedNotFinal,
   /// Whitespace found between start-line and first header field (RFC 9112 Section 2.2)
   WhitespaceBeforeHeaders,
-  /// Extra data found after complete response body (RFC 9112 Section 6.3)
+  /// Extra data found after complete response body, such as a second response
+  /// the client never asked for (RFC 9112 Section 6.3)
   ExtraDataAfterResponse,
   /// Host header is required in HTTP/1.1 requests (RFC 9112 Section 3.2)
   MissingHostHeader,
@@ -67,6 +78,14 @@ pub enum ParseError {
   ChunkedAppliedMultipleTimes,
   /// Failed to decompress response body (gzip/deflate)
   DecompressionFailed,
+  /// Content-Encoding lists a coding that cannot be decoded (RFC 9110 Section 8.4)
+  UnsupportedContentEncoding,
+  /// Decoded response body exceeds the configured maximum size
+  DecompressionTooLarge,
+  /// Request method must not carry a body (TRACE, RFC 9110 Section 9.3.8)
+  BodyNotAllowed,
+  /// Request body has neither `Content-Length` nor `Transfer-Encoding` to frame it
+  MissingBodyFraming,
 }
 
 impl ParseError {
@@ -80,6 +99,7 @@ impl ParseError {
     matches!(
       self,
       Self::ConflictingFraming
+        | Self::MultipleContentLength
         | Self::ChunkedNotFinal
         | Self::InvalidContentLength
         | Self::UnexpectedEndOfInput
@@ -110,12 +130,17 @@ impl core::fmt::Display for ParseError {
       Self::InvalidWhitespace => write!(f, "invalid whitespace"),
       Self::LineTooLong => write!(f, "line too long"),
       Self::InvalidChunkSize => write!(f, "invalid chunk size"),
+      Self::ChunkLineTooLong => write!(f, "chunk-size line too long"),
+      Self::TooManyChunks => write!(f, "too many chunks in chunked body"),
+      Self::BodyTooLarge => write!(f, "response body exceeds maximum size"),
       Self::InvalidContentLength => write!(f, "invalid Content-Length value"),
       Self::HeaderTooLarge => write!(f, "response header too large"),
+      Self::TooManyHeaders => write!(f, "too many response header fields"),
       Self::InvalidState => write!(f, "invalid parser state"),
       Self::ConflictingFraming => {
         write!(f, "both Transfer-Encoding and Content-Length present")
       },
+      Self::MultipleContentLength => write!(f, "Content-Length header fields disagree"),
       Self::ChunkedNotFinal => write!(f, "chunked must be the final Transfer-Encoding"),
       Self::WhitespaceBeforeHeaders => {
         write!(f, "whitespace found between start-line and first header")
@@ -147,6 +172,10 @@ impl core::fmt::Display for ParseError {
         write!(f, "chunked transfer coding applied multiple times")
       },
       Self::DecompressionFailed => write!(f, "failed to decompress response body"),
+      Self::UnsupportedContentEncoding => write!(f, "unsupported Content-Encoding"),
+      Self::DecompressionTooLarge => write!(f, "decompressed response body exceeds maximum size"),
+      Self::BodyNotAllowed => write!(f, "request method does not allow a message body"),
+      Self::MissingBodyFraming => write!(f, "request body has no Content-Length or Transfer-Encoding"),
     }
   }
 }
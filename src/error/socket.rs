@@ -5,6 +5,10 @@ pub enum SocketError {
   NotConnected,
   /// Connection refused by remote host
   ConnectionRefused,
+  /// Connection reset or aborted by the remote host
+  ConnectionReset,
+  /// Write to a connection the remote host has already closed
+  BrokenPipe,
   /// Operation timed out
   TimedOut,
   /// Operation would block (non-blocking mode)
@@ -19,6 +23,8 @@ pub enum SocketError {
   DnsResolutionFailed(i32),
   /// Operating system error with code
   OsError(i32),
+  /// TLS handshake failed or the peer sent invalid TLS data
+  TlsFailed,
 }
 
 impl core::fmt::Display for SocketError {
@@ -29,6 +35,8 @@ impl core::fmt::Display for SocketError {
     match self {
       Self::NotConnected => write!(f, "socket not connected"),
       Self::ConnectionRefused => write!(f, "connection refused"),
+      Self::ConnectionReset => write!(f, "connection reset"),
+      Self::BrokenPipe => write!(f, "broken pipe"),
       Self::TimedOut => write!(f, "operation timed out"),
       Self::WouldBlock => write!(f, "operation would block"),
       Self::Interrupted => write!(f, "operation interrupted"),
@@ -36,6 +44,7 @@ impl core::fmt::Display for SocketError {
       Self::Unsupported => write!(f, "operation not supported"),
       Self::DnsResolutionFailed(code) => write!(f, "DNS resolution failed: {code}"),
       Self::OsError(code) => write!(f, "OS error: {code}"),
+      Self::TlsFailed => write!(f, "TLS failure"),
     }
   }
 }
@@ -2,6 +2,11 @@ use alloc::string::String;
 use alloc::vec::Vec;
 
 /// HTTP headers collection
+///
+/// Fields are kept in insertion order, duplicates included, and iterate and
+/// serialize in that order. `set` keeps a replaced field in its place, while
+/// `remove` followed by `insert` moves it to the end. Use `sorted` for a
+/// canonical order independent of how the collection was built.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Headers {
   headers: Vec<(String, String)>,
@@ -29,6 +34,47 @@ impl Headers {
     self.headers.push((name.into(), value.into()));
   }
 
+  /// Add a header only if none with that name exists (case-insensitive)
+  ///
+  /// Returns true if the header was added.
+  pub fn insert_if_absent(
+    &mut self,
+    name: impl Into<String>,
+    value: impl Into<String>,
+  ) -> bool {
+    let name_str = name.into();
+    if self.contains(&name_str) {
+      return false;
+    }
+    self.headers.push((name_str, value.into()));
+    true
+  }
+
+  /// Replace all values for a header name with a single value (case-insensitive)
+  ///
+  /// The value takes the place of the first existing header with that name;
+  /// later duplicates are removed. Without an existing header this appends.
+  pub fn set(
+    &mut self,
+    name: impl Into<String>,
+    value: impl Into<String>,
+  ) {
+    let name_str = name.into();
+    let mut pending = Some(value.into());
+    self.headers.retain_mut(|(n, v)| {
+      if !n.eq_ignore_ascii_case(&name_str) {
+        return true;
+      }
+      pending.take().is_some_and(|new_value| {
+        *v = new_value;
+        true
+      })
+    });
+    if let Some(new_value) = pending {
+      self.headers.push((name_str, new_value));
+    }
+  }
+
   /// Get the first value for a header name (case-insensitive)
   #[must_use]
   pub fn get(
@@ -56,6 +102,23 @@ impl Headers {
       .collect()
   }
 
+  /// Get the comma-separated list elements of a header (case-insensitive)
+  ///
+  /// Collects every header line with that name and splits each value on
+  /// commas outside quoted strings, trimming whitespace and skipping empty
+  /// elements (RFC 9110 Section 5.6.1).
+  #[must_use]
+  pub fn get_list(
+    &self,
+    name: &str,
+  ) -> Vec<&str> {
+    self
+      .get_all(name)
+      .into_iter()
+      .flat_map(split_list)
+      .collect()
+  }
+
   /// Check if a header exists (case-insensitive)
   #[must_use]
   pub fn contains(
@@ -76,6 +139,45 @@ impl Headers {
     self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
   }
 
+  /// Combine repeated fields into one comma-separated line (RFC 9110 Section 5.3)
+  ///
+  /// Each name keeps the position and case of its first occurrence, with
+  /// later values appended in order. `Set-Cookie` and `Cookie` are left as
+  /// separate lines, since their values are not comma-separated lists.
+  pub fn fold_duplicates(&mut self) {
+    let mut folded: Vec<(String, String)> = Vec::with_capacity(self.headers.len());
+    for (name, value) in core::mem::take(&mut self.headers) {
+      let foldable =
+        !name.eq_ignore_ascii_case(HeaderName::SET_COOKIE) && !name.eq_ignore_ascii_case(HeaderName::COOKIE);
+      match folded
+        .iter_mut()
+        .find(|(existing, _)| foldable && existing.eq_ignore_ascii_case(&name))
+      {
+        Some((_, combined)) => {
+          combined.push_str(", ");
+          combined.push_str(&value);
+        },
+        None => folded.push((name, value)),
+      }
+    }
+    self.headers = folded;
+  }
+
+  /// Copy of the headers ordered by name, for canonical forms such as request signing
+  ///
+  /// Names compare case-insensitively and keep their original case. The sort
+  /// is stable, so values of the same name stay in insertion order.
+  #[must_use]
+  pub fn sorted(&self) -> Self {
+    let mut headers = self.headers.clone();
+    headers.sort_by(|(a, _), (b, _)| {
+      a.bytes()
+        .map(|byte| byte.to_ascii_lowercase())
+        .cmp(b.bytes().map(|byte| byte.to_ascii_lowercase()))
+    });
+    Self { headers }
+  }
+
   /// Get an iterator over all headers
   pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
     self.headers.iter().map(|(n, v)| (n.as_str(), v.as_str()))
@@ -112,6 +214,32 @@ impl Headers {
   }
 }
 
+/// Split a field value into its comma-separated list elements
+///
+/// Commas inside quoted strings do not split; elements are trimmed and
+/// empty ones are skipped (RFC 9110 Section 5.6.1).
+pub fn split_list(value: &str) -> Vec<&str> {
+  let mut elements = Vec::new();
+  let mut in_quotes = false;
+  let mut escaped = false;
+  let mut start = 0;
+  for (index, byte) in value.bytes().enumerate() {
+    if escaped {
+      escaped = false;
+    } else if in_quotes && byte == b'\\' {
+      escaped = true;
+    } else if byte == b'"' {
+      in_quotes = !in_quotes;
+    } else if byte == b',' && !in_quotes {
+      elements.extend(value.get(start..index).map(str::trim));
+      start = index + 1;
+    }
+  }
+  elements.extend(value.get(start..).map(str::trim));
+  elements.retain(|element| !element.is_empty());
+  elements
+}
+
 impl From<Vec<(String, String)>> for Headers {
   fn from(headers: Vec<(String, String)>) -> Self {
     Self::from_vec(headers)
@@ -397,6 +525,33 @@ mod tests {
     assert_eq!(headers.get("Accept"), Some("text/html"));
   }
 
+  #[test]
+  fn headers_fold_duplicates_keeps_cookies_separate() {
+    let mut headers = Headers::new();
+    headers.insert("Accept", "a");
+    headers.insert("Cookie", "x=1");
+    headers.insert("X-Id", "7");
+    headers.insert("accept", "b");
+    headers.insert("Cookie", "y=2");
+    headers.insert("Set-Cookie", "s=1");
+    headers.insert("set-cookie", "t=2");
+
+    headers.fold_duplicates();
+
+    let lines: Vec<(&str, &str)> = headers.iter().collect();
+    assert_eq!(
+      lines,
+      [
+        ("Accept", "a, b"),
+        ("Cookie", "x=1"),
+        ("X-Id", "7"),
+        ("Cookie", "y=2"),
+        ("Set-Cookie", "s=1"),
+        ("set-cookie", "t=2"),
+      ]
+    );
+  }
+
   #[test]
   fn headers_remove_is_case_insensitive() {
     let mut headers = Headers::new();
@@ -423,6 +578,114 @@ mod tests {
     assert!(!headers.contains("Cache-Control"));
   }
 
+  #[test]
+  fn headers_keep_insertion_order_across_set_and_remove() {
+    let mut headers = Headers::new();
+    headers.insert("X-B", "1");
+    headers.insert("X-A", "2");
+    headers.insert("x-b", "3");
+    headers.set("X-A", "4");
+    headers.insert("X-C", "5");
+    headers.remove("X-A");
+    headers.insert("X-A", "6");
+
+    let order: Vec<_> = headers.iter().collect();
+    assert_eq!(order, [("X-B", "1"), ("x-b", "3"), ("X-C", "5"), ("X-A", "6")]);
+  }
+
+  #[test]
+  fn headers_sorted_orders_names_case_insensitively_and_stably() {
+    let mut headers = Headers::new();
+    headers.insert("X-Amz-Date", "20240101T000000Z");
+    headers.insert("host", "example.com");
+    headers.insert("Accept", "b");
+    headers.insert("accept", "a");
+    headers.insert("Content-Type", "text/plain");
+
+    let sorted = headers.sorted();
+    let order: Vec<_> = sorted.iter().collect();
+    assert_eq!(
+      order,
+      [
+        ("Accept", "b"),
+        ("accept", "a"),
+        ("Content-Type", "text/plain"),
+        ("host", "example.com"),
+        ("X-Amz-Date", "20240101T000000Z"),
+      ]
+    );
+    assert_eq!(headers.iter().next(), Some(("X-Amz-Date", "20240101T000000Z")));
+  }
+
+  #[test]
+  fn headers_get_list_splits_comma_joined_values() {
+    let mut headers = Headers::new();
+    headers.insert("Cache-Control", "no-cache, no-store");
+
+    assert_eq!(headers.get_list("cache-control"), ["no-cache", "no-store"]);
+  }
+
+  #[test]
+  fn headers_get_list_combines_multiple_lines() {
+    let mut headers = Headers::new();
+    headers.insert("Vary", "Accept-Encoding");
+    headers.insert("Content-Type", "text/plain");
+    headers.insert("vary", " Accept ,, Origin ");
+
+    assert_eq!(headers.get_list("VARY"), ["Accept-Encoding", "Accept", "Origin"]);
+    assert!(headers.get_list("Accept").is_empty());
+  }
+
+  #[test]
+  fn headers_get_list_keeps_quoted_commas() {
+    let mut headers = Headers::new();
+    headers.insert("Cache-Control", "private=\"set-cookie, x-a\", max-age=5");
+    headers.insert("ETag", "\"a\\\",b\", W/\"c\"");
+
+    assert_eq!(
+      headers.get_list("cache-control"),
+      ["private=\"set-cookie, x-a\"", "max-age=5"]
+    );
+    assert_eq!(headers.get_list("etag"), ["\"a\\\",b\"", "W/\"c\""]);
+  }
+
+  #[test]
+  fn headers_insert_if_absent_keeps_existing_values() {
+    let mut headers = Headers::new();
+    headers.insert("Accept", "text/html");
+    headers.insert("accept", "application/json");
+
+    assert!(!headers.insert_if_absent("ACCEPT", "*/*"));
+    assert_eq!(headers.get_all("Accept"), ["text/html", "application/json"]);
+
+    assert!(headers.insert_if_absent("Content-Type", "text/plain"));
+    assert_eq!(headers.get("content-type"), Some("text/plain"));
+    assert_eq!(headers.len(), 3);
+  }
+
+  #[test]
+  fn headers_set_replaces_all_duplicates_in_place() {
+    let mut headers = Headers::new();
+    headers.insert("Cache-Control", "no-cache");
+    headers.insert("Content-Type", "text/plain");
+    headers.insert("cache-control", "no-store");
+
+    headers.set("CACHE-CONTROL", "max-age=60");
+
+    assert_eq!(headers.get_all("cache-control"), ["max-age=60"]);
+    assert_eq!(
+      headers.iter().collect::<Vec<_>>(),
+      vec![
+        ("Cache-Control", "max-age=60"),
+        ("Content-Type", "text/plain")
+      ]
+    );
+
+    headers.set("X-New", "1");
+    assert_eq!(headers.get("x-new"), Some("1"));
+    assert_eq!(headers.len(), 3);
+  }
+
   #[test]
   fn headers_iter_returns_all_headers() {
     let mut headers = Headers::new();
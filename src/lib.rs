@@ -129,7 +129,9 @@
 //!
 //! match client.get("http://httpbin.org/status/404").call() {
 //!     Ok(resp) => println!("Status: {}", resp.status_code),
-//!     Err(Error::HttpStatus(code)) => println!("HTTP error: {}", code),
+//!     Err(Error::HttpStatus { code, response }) => {
+//!         println!("HTTP error {}: {}", code, String::from_utf8_lossy(response.body.as_bytes()))
+//!     },
 //!     Err(e) => println!("Other error: {:?}", e),
 //! }
 //! # Ok::<(), barehttp::Error>(())
@@ -160,11 +162,14 @@
 //!
 //! Implement `BlockingSocket` and `DnsResolver` traits to provide:
 //!
-//! - TLS via external libraries
+//! - TLS via external libraries (the `tls` feature provides `RustlsSocket`, built on rustls)
 //! - Proxies or tunnels
 //! - Embedded or WASM networking
 //! - Test mocks
 //!
+//! With the `std` feature, `IoSocket` turns any `std::io` stream implementing `IoStream`
+//! (such as `TcpStream`) into a socket.
+//!
 //! ```no_run
 //! use barehttp::{HttpClient, OsBlockingSocket, OsDnsResolver};
 //!
@@ -174,6 +179,29 @@
 //! # Ok::<(), barehttp::Error>(())
 //! ```
 //!
+//! ## Testing
+//!
+//! With the `test-util` feature, `test_util::RecordingClient` wraps a client and
+//! records the exact request and response bytes of every exchange:
+//!
+//! ```no_run
+//! # #[cfg(feature = "test-util")]
+//! # fn main() -> Result<(), barehttp::Error> {
+//! use barehttp::OsDnsResolver;
+//! use barehttp::config::Config;
+//! use barehttp::test_util::RecordingClient;
+//!
+//! let recorder: RecordingClient = RecordingClient::with_adapters_and_config(OsDnsResolver::new(), Config::default());
+//! recorder.record(|client| client.get("http://example.com/").call())?;
+//!
+//! let request = recorder.last_request_bytes().unwrap_or_default();
+//! assert!(request.starts_with(b"GET / HTTP/1.1\r\n"));
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "test-util"))]
+//! # fn main() {}
+//! ```
+//!
 //! ## Design Notes
 //!
 //! - Blocking I/O keeps the API simple and dependency-free
@@ -226,22 +254,49 @@ extern crate alloc;
 /// in HTTP requests and responses, including domain/path matching and expiration.
 pub mod cookie_jar;
 
+#[cfg(feature = "cache")]
+/// HTTP response caching (RFC 9111)
+///
+/// This module provides the `HttpCache` storage trait and an in-memory LRU
+/// `MemoryCache` that `HttpClient::with_cache` uses to serve GET and HEAD requests.
+pub mod cache;
+
+#[cfg(feature = "test-util")]
+/// Helpers for testing code that uses the client
+///
+/// This module provides `RecordingClient`, which wraps a client and records
+/// the exact request and response bytes of every exchange.
+pub mod test_util;
+
 // Re-exports of core types
+pub use cancel::CancelToken;
 pub use client::HttpClient;
+pub use clock::{Clock, ManualClock, OsClock};
 pub use error::Error;
 pub use request_builder::IntoBody;
+pub use sink::{ByteSink, PeekSink};
+pub use transport::{PoolStats, RawResponse};
 
 // Re-exports of default OS adapters
 pub use dns::resolver::OsDnsResolver;
 pub use socket::blocking::OsBlockingSocket;
+#[cfg(feature = "std")]
+pub use socket::io::{IoSocket, IoStream};
+#[cfg(feature = "tls")]
+pub use socket::tls::RustlsSocket;
 
 // Re-exports of request/response types
 pub use body::Body;
 pub use headers::{HeaderName, Headers};
-pub use method::Method;
+pub use method::{InvalidMethod, Method};
+pub use parser::cache_control::CacheControl;
+pub use parser::cookie::{SameSite, SetCookie};
+pub use parser::event_stream::{EventSink, EventStream, ServerSentEvent};
+pub use parser::link::LinkRef;
 pub use parser::status::{StatusClass, StatusCode};
 pub use parser::version::Version;
-pub use request::Request;
+pub use request::{Request, SignableRequest};
+pub use util::{HttpDate, IpAddr, format_http_date, parse_http_date};
 
 // Convenience functions for quick HTTP requests
 
@@ -353,6 +408,21 @@ pub fn connect(url: &str) -> Result<parser::Response, crate::error::Error> {
   client.connect(url).call()
 }
 
+/// Parse a complete HTTP/1.1 response from raw bytes
+///
+/// Runs the same RFC 9112 parser the client uses, for responses obtained
+/// through another transport (a file, a custom framing layer). `bytes` must
+/// hold the whole message: status line, header section and body. Without
+/// `Content-Length` or chunked encoding, the rest of `bytes` is the body, as
+/// if the connection had closed after it (RFC 9112 Section 6.3).
+/// Content codings are decoded when the matching feature is enabled.
+///
+/// # Errors
+/// Returns `Error::Parse` if the bytes are not a valid or complete response.
+pub fn parse_response(bytes: &[u8]) -> Result<parser::Response, crate::error::Error> {
+  Ok(parser::Response::parse(bytes)?)
+}
+
 // Public modules
 
 /// Configuration for HTTP client behavior
@@ -361,15 +431,20 @@ pub mod config;
 pub mod request_builder;
 /// Response extensions and helpers
 pub mod response;
+pub mod wire;
 
 mod body;
+mod cancel;
 mod client;
+mod clock;
 mod dns;
 mod error;
 mod headers;
 mod method;
 pub(crate) mod parser;
 mod request;
+mod sink;
+mod sleep;
 pub(crate) mod socket;
 mod transport;
 pub(crate) mod util;
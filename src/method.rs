@@ -59,6 +59,24 @@ impl Method {
     matches!(self, Self::Post | Self::Put | Self::Patch)
   }
 
+  /// Returns true if the method is safe: read-only by definition (RFC 9110 Section 9.2.1)
+  ///
+  /// GET, HEAD, OPTIONS and TRACE are safe.
+  #[must_use]
+  pub const fn is_safe(self) -> bool {
+    matches!(self, Self::Get | Self::Head | Self::Options | Self::Trace)
+  }
+
+  /// Returns true if repeating the request has the same effect as sending it once
+  /// (RFC 9110 Section 9.2.2)
+  ///
+  /// The safe methods plus PUT and DELETE are idempotent, so they can be
+  /// retried automatically after a connection failure.
+  #[must_use]
+  pub const fn is_idempotent(self) -> bool {
+    self.is_safe() || matches!(self, Self::Put | Self::Delete)
+  }
+
   /// Returns true if this method should never have a request body
   #[must_use]
   pub const fn without_body(self) -> bool {
@@ -87,3 +105,71 @@ impl FromStr for Method {
     }
   }
 }
+
+impl TryFrom<&str> for Method {
+  type Error = InvalidMethod;
+
+  /// Parse a method name; names are case-sensitive, so `"get"` is rejected
+  fn try_from(s: &str) -> Result<Self, Self::Error> {
+    s.parse()
+  }
+}
+
+impl core::fmt::Display for Method {
+  fn fmt(
+    &self,
+    f: &mut core::fmt::Formatter<'_>,
+  ) -> core::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use alloc::format;
+
+  const ALL: [Method; 9] = [
+    Method::Get,
+    Method::Post,
+    Method::Put,
+    Method::Delete,
+    Method::Head,
+    Method::Options,
+    Method::Patch,
+    Method::Trace,
+    Method::Connect,
+  ];
+
+  #[test]
+  fn round_trips_through_str_and_display() {
+    for method in ALL {
+      assert_eq!(Method::try_from(method.as_str()), Ok(method));
+      assert_eq!(format!("{method}"), method.as_str());
+    }
+  }
+
+  #[test]
+  fn rejects_unknown_and_non_token_names() {
+    for name in ["get", "", "G ET", "GET\r\n", "PROPFIND", "GET/"] {
+      assert_eq!(Method::try_from(name), Err(InvalidMethod), "{name:?}");
+    }
+  }
+
+  #[test]
+  fn classifies_safe_and_idempotent_methods() {
+    let safe: [Method; 4] = [Method::Get, Method::Head, Method::Options, Method::Trace];
+    let idempotent: [Method; 6] = [
+      Method::Get,
+      Method::Head,
+      Method::Options,
+      Method::Trace,
+      Method::Put,
+      Method::Delete,
+    ];
+    for method in ALL {
+      assert_eq!(method.is_safe(), safe.contains(&method), "{method}");
+      assert_eq!(method.is_idempotent(), idempotent.contains(&method), "{method}");
+    }
+  }
+}
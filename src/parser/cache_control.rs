@@ -0,0 +1,150 @@
+extern crate alloc;
+use crate::headers::{HeaderName, Headers, split_list};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Largest delta-seconds value a cache must handle (RFC 9111 Section 1.2.2)
+const DELTA_SECONDS_MAX: u64 = 2_147_483_648;
+
+/// Parsed `Cache-Control` directives (RFC 9111 Section 5.2)
+///
+/// Directive names are matched case-insensitively and arguments may use
+/// either the token or the quoted-string form. When a directive appears
+/// more than once the first occurrence wins. Directives this type does not
+/// know are kept in `extensions`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct CacheControl {
+  /// `max-age`: seconds the response stays fresh; an invalid value parses as 0
+  pub max_age: Option<u64>,
+  /// `s-maxage`: `max-age` for shared caches; an invalid value parses as 0
+  pub s_maxage: Option<u64>,
+  /// `max-stale` (request): accept stale responses, up to the given seconds if present
+  pub max_stale: Option<Option<u64>>,
+  /// `min-fresh` (request): require the response to stay fresh this many more seconds
+  pub min_fresh: Option<u64>,
+  /// `stale-while-revalidate` (RFC 5861)
+  pub stale_while_revalidate: Option<u64>,
+  /// `stale-if-error` (RFC 5861)
+  pub stale_if_error: Option<u64>,
+  /// `no-store`: must not be stored by any cache
+  pub no_store: bool,
+  /// `no-cache`: must be revalidated before every use
+  pub no_cache: bool,
+  /// `no-transform`: intermediaries must not transform the content
+  pub no_transform: bool,
+  /// `only-if-cached` (request): only answer from a cache
+  pub only_if_cached: bool,
+  /// `must-revalidate`: must not be served stale without revalidation
+  pub must_revalidate: bool,
+  /// `proxy-revalidate`: `must-revalidate` for shared caches
+  pub proxy_revalidate: bool,
+  /// `must-understand`: only cache if the status code is understood
+  pub must_understand: bool,
+  /// `private`: must not be stored by shared caches
+  pub private: bool,
+  /// `public`: may be stored by any cache
+  pub public: bool,
+  /// `immutable` (RFC 8246): will not change while fresh
+  pub immutable: bool,
+  /// Unrecognized directives as lowercase name and unquoted argument
+  pub extensions: Vec<(String, Option<String>)>,
+}
+
+impl CacheControl {
+  /// Parse a single `Cache-Control` field value
+  ///
+  /// Never fails: malformed elements are skipped or recorded as extensions.
+  #[must_use]
+  pub fn parse(value: &str) -> Self {
+    let mut cache_control = Self::default();
+    for directive in split_list(value) {
+      cache_control.apply(directive);
+    }
+    cache_control
+  }
+
+  /// Parse every `Cache-Control` line in `headers`, or None if there is none
+  #[must_use]
+  pub fn from_headers(headers: &Headers) -> Option<Self> {
+    if !headers.contains(HeaderName::CACHE_CONTROL) {
+      return None;
+    }
+    let mut cache_control = Self::default();
+    for directive in headers.get_list(HeaderName::CACHE_CONTROL) {
+      cache_control.apply(directive);
+    }
+    Some(cache_control)
+  }
+
+  fn apply(
+    &mut self,
+    directive: &str,
+  ) {
+    let (raw_name, argument) = match directive.split_once('=') {
+      Some((n, a)) => (n.trim(), Some(unquote(a.trim()))),
+      None => (directive, None),
+    };
+    let name = raw_name.to_ascii_lowercase();
+    let seconds = argument.as_deref().and_then(parse_delta_seconds);
+
+    match name.as_str() {
+      // RFC 9111 Section 4.2.1: an invalid freshness value means "stale"
+      "max-age" => {
+        self.max_age.get_or_insert_with(|| seconds.unwrap_or(0));
+      },
+      "s-maxage" => {
+        self.s_maxage.get_or_insert_with(|| seconds.unwrap_or(0));
+      },
+      "max-stale" => {
+        self.max_stale.get_or_insert(seconds);
+      },
+      "min-fresh" => self.min_fresh = self.min_fresh.or(seconds),
+      "stale-while-revalidate" => self.stale_while_revalidate = self.stale_while_revalidate.or(seconds),
+      "stale-if-error" => self.stale_if_error = self.stale_if_error.or(seconds),
+      "no-store" => self.no_store = true,
+      "no-cache" => self.no_cache = true,
+      "no-transform" => self.no_transform = true,
+      "only-if-cached" => self.only_if_cached = true,
+      "must-revalidate" => self.must_revalidate = true,
+      "proxy-revalidate" => self.proxy_revalidate = true,
+      "must-understand" => self.must_understand = true,
+      "private" => self.private = true,
+      "public" => self.public = true,
+      "immutable" => self.immutable = true,
+      _ => self.extensions.push((name, argument)),
+    }
+  }
+}
+
+/// Remove quoted-string quoting, resolving backslash escapes
+fn unquote(argument: &str) -> String {
+  let Some(inner) = argument
+    .strip_prefix('"')
+    .and_then(|rest| rest.strip_suffix('"'))
+  else {
+    return String::from(argument);
+  };
+  let mut unquoted = String::with_capacity(inner.len());
+  let mut chars = inner.chars();
+  while let Some(c) = chars.next() {
+    if c == '\\' {
+      unquoted.extend(chars.next());
+    } else {
+      unquoted.push(c);
+    }
+  }
+  unquoted
+}
+
+/// Parse delta-seconds, capping overly large values (RFC 9111 Section 1.2.2)
+fn parse_delta_seconds(argument: &str) -> Option<u64> {
+  if argument.is_empty() || !argument.bytes().all(|b| b.is_ascii_digit()) {
+    return None;
+  }
+  Some(
+    argument
+      .parse::<u64>()
+      .map_or(DELTA_SECONDS_MAX, |seconds| seconds.min(DELTA_SECONDS_MAX)),
+  )
+}
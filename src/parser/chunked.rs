@@ -1,9 +1,17 @@
 use crate::error::ParseError;
 
+/// Default limit on a chunk-size line, chunk extensions included
+pub const DEFAULT_MAX_CHUNK_LINE_LENGTH: usize = 16 * 1024;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ChunkedDecoder {
   state: DecodeState,
   trailers: alloc::vec::Vec<(alloc::vec::Vec<u8>, alloc::vec::Vec<u8>)>,
+  max_line_length: Option<usize>,
+  max_body_size: Option<usize>,
+  max_chunk_count: Option<usize>,
+  body_size: usize,
+  chunk_count: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,9 +28,53 @@ impl ChunkedDecoder {
     Self {
       state: DecodeState::ChunkSize,
       trailers: alloc::vec::Vec::new(),
+      max_line_length: Some(DEFAULT_MAX_CHUNK_LINE_LENGTH),
+      max_body_size: None,
+      max_chunk_count: None,
+      body_size: 0,
+      chunk_count: 0,
     }
   }
 
+  /// Reject bodies whose chunk sizes add up to more than `max` bytes
+  ///
+  /// Checked against each declared chunk size before its data is read, so an
+  /// oversized chunk fails without being buffered. None means no limit.
+  #[must_use]
+  pub const fn with_max_body_size(
+    mut self,
+    max: Option<usize>,
+  ) -> Self {
+    self.max_body_size = max;
+    self
+  }
+
+  /// Reject bodies with more than `max` chunks, not counting the last (zero-size) one
+  ///
+  /// Guards against endless streams of tiny chunks. None means no limit.
+  #[must_use]
+  pub const fn with_max_chunk_count(
+    mut self,
+    max: Option<usize>,
+  ) -> Self {
+    self.max_chunk_count = max;
+    self
+  }
+
+  /// Reject chunk-size lines longer than `max` bytes, not counting the line ending
+  ///
+  /// Chunk extensions are ignored but still have to be buffered until the
+  /// line ends, so without a limit a peer could grow one line without bound.
+  /// None means no limit.
+  #[must_use]
+  pub const fn with_max_line_length(
+    mut self,
+    max: Option<usize>,
+  ) -> Self {
+    self.max_line_length = max;
+    self
+  }
+
   /// Get the parsed trailer fields from the chunked response.
   /// Per RFC 9112 Section 7.1.2: Trailers are optional fields that appear after the last chunk.
   #[must_use]
@@ -40,12 +92,14 @@ impl ChunkedDecoder {
     loop {
       match self.state {
         DecodeState::ChunkSize => {
+          self.check_line_length(remaining)?;
           let (size, rest) = Self::parse_chunk_size(remaining)?;
           remaining = rest;
 
           if size == 0 {
             self.state = DecodeState::TrailerSection;
           } else {
+            self.count_chunk(size)?;
             self.state = DecodeState::ChunkData(size);
           }
         },
@@ -84,6 +138,125 @@ impl ChunkedDecoder {
     }
   }
 
+  /// Check whether the last chunk and the trailer section have been decoded
+  #[must_use]
+  pub const fn is_complete(&self) -> bool {
+    matches!(self.state, DecodeState::Complete)
+  }
+
+  /// Decode as much of `input` as is available, for bodies that arrive in pieces
+  ///
+  /// Unlike `decode_chunk`, running out of input is not an error: chunk data is
+  /// passed to `output` as soon as it arrives, and a size line, CRLF or trailer
+  /// line that is still incomplete is left unconsumed. Returns the number of
+  /// bytes consumed; call again with the unconsumed rest plus newly read bytes
+  /// until `is_complete` returns true.
+  pub fn decode_available(
+    &mut self,
+    input: &[u8],
+    output: &mut alloc::vec::Vec<u8>,
+  ) -> Result<usize, ParseError> {
+    let mut remaining = input;
+
+    loop {
+      match self.state {
+        DecodeState::ChunkSize => {
+          // An unfinished line that is already too long is rejected without waiting for more
+          self.check_line_length(remaining)?;
+          if !remaining.contains(&b'\n') {
+            break;
+          }
+          let (size, rest) = Self::parse_chunk_size(remaining)?;
+          remaining = rest;
+          self.state = if size == 0 {
+            DecodeState::TrailerSection
+          } else {
+            self.count_chunk(size)?;
+            DecodeState::ChunkData(size)
+          };
+        },
+        DecodeState::ChunkData(size) => {
+          if remaining.is_empty() {
+            break;
+          }
+          let (data, rest) = remaining.split_at(size.min(remaining.len()));
+          output.extend_from_slice(data);
+          remaining = rest;
+          let left = size - data.len();
+          self.state = if left == 0 {
+            DecodeState::ChunkDataCrlf
+          } else {
+            DecodeState::ChunkData(left)
+          };
+        },
+        DecodeState::ChunkDataCrlf => {
+          if remaining.is_empty() || remaining == b"\r" {
+            break;
+          }
+          remaining = Self::expect_crlf(remaining)?;
+          self.state = DecodeState::ChunkSize;
+        },
+        DecodeState::TrailerSection => {
+          if !remaining.contains(&b'\n') {
+            break;
+          }
+          let (found_end, rest) = self.parse_trailer_section(remaining)?;
+          remaining = rest;
+          if found_end {
+            self.state = DecodeState::Complete;
+          }
+        },
+        DecodeState::Complete => break,
+      }
+    }
+
+    Ok(input.len() - remaining.len())
+  }
+
+  /// Account for a new chunk of `size` bytes, failing once a limit is exceeded
+  fn count_chunk(
+    &mut self,
+    size: usize,
+  ) -> Result<(), ParseError> {
+    self.chunk_count = self.chunk_count.saturating_add(1);
+    if self
+      .max_chunk_count
+      .is_some_and(|max| self.chunk_count > max)
+    {
+      return Err(ParseError::TooManyChunks);
+    }
+    self.body_size = self.body_size.saturating_add(size);
+    if self.max_body_size.is_some_and(|max| self.body_size > max) {
+      return Err(ParseError::BodyTooLarge);
+    }
+    Ok(())
+  }
+
+  /// Fail if the chunk-size line at the start of `input` is over the limit
+  fn check_line_length(
+    &self,
+    input: &[u8],
+  ) -> Result<(), ParseError> {
+    let Some(max) = self.max_line_length else {
+      return Ok(());
+    };
+    let line = input
+      .iter()
+      .position(|&b| b == b'\n')
+      .and_then(|end| input.get(..end))
+      .unwrap_or(input);
+    let length = line.strip_suffix(b"\r").unwrap_or(line).len();
+    if length > max {
+      return Err(ParseError::ChunkLineTooLong);
+    }
+    Ok(())
+  }
+
+  /// Parse a chunk-size line
+  ///
+  /// Whitespace anywhere next to the size is rejected, including the `BWS`
+  /// RFC 9112 Section 7.1.1 allows before `;`: proxies disagree on lines
+  /// like `5 \r\n`, which makes them a request smuggling vector.
   fn parse_chunk_size(input: &[u8]) -> Result<(usize, &[u8]), ParseError> {
     let mut i = 0;
     let mut size = 0usize;
@@ -0,0 +1,568 @@
+extern crate alloc;
+use crate::error::{Error, ParseError};
+use crate::headers::{HeaderName, Headers};
+use crate::sink::ByteSink;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "gzip-decompression")]
+use miniz_oxide::inflate::stream::{InflateState, inflate};
+#[cfg(feature = "gzip-decompression")]
+use miniz_oxide::{DataFormat, MZError, MZFlush, MZStatus};
+
+#[cfg(feature = "zstd-decompression")]
+use ruzstd::decoding::FrameDecoder;
+
+/// Content codings listed in `Content-Encoding`, lowercased, in the order applied
+///
+/// Multiple `Content-Encoding` field lines are combined in order.
+pub fn content_codings(headers: &Headers) -> Vec<String> {
+  headers
+    .get_all(HeaderName::CONTENT_ENCODING)
+    .into_iter()
+    .flat_map(|value| value.split(','))
+    .map(|coding| coding.trim().to_ascii_lowercase())
+    .filter(|coding| !coding.is_empty())
+    .collect()
+}
+
+/// Incremental decoder for one content coding
+trait Decode {
+  /// Decode as much of `input` as possible, appending at most `limit` bytes to `out`
+  fn decode(
+    &mut self,
+    input: &[u8],
+    out: &mut Vec<u8>,
+    limit: usize,
+  ) -> Result<(), ParseError>;
+
+  /// Decode what is left once all input has been passed to `decode`, and
+  /// check that the coded data ended where its format says it does
+  fn finish(
+    &mut self,
+    out: &mut Vec<u8>,
+    limit: usize,
+  ) -> Result<(), ParseError>;
+}
+
+/// One coding of a body being decoded, with the bytes it has produced so far
+struct Layer {
+  decoder: Box<dyn Decode>,
+  produced: usize,
+}
+
+/// Removes the content codings of a body that arrives piece by piece
+///
+/// `Response::parse_body_from_bytes` decodes whole bodies with it too.
+/// Codings are undone in reverse order, and with `max_size` set, decoding
+/// stops with `ParseError::DecompressionTooLarge` as soon as any coding
+/// produces more than `max_size` bytes. A decoder with no codings passes
+/// bytes through unchanged.
+#[derive(Default)]
+pub struct ContentDecoder {
+  layers: Vec<Layer>,
+  max_size: Option<usize>,
+  received: bool,
+}
+
+impl ContentDecoder {
+  /// Decoder for the codings listed in `headers`
+  ///
+  /// # Errors
+  /// Returns `ParseError::UnsupportedContentEncoding` if a coding has no decoder
+  pub fn for_headers(
+    headers: &Headers,
+    max_size: Option<usize>,
+  ) -> Result<Self, ParseError> {
+    let layers = content_codings(headers)
+      .iter()
+      .rev()
+      .filter(|coding| *coding != "identity")
+      .map(|coding| {
+        coding_decoder(coding)
+          .map(|decoder| Layer { decoder, produced: 0 })
+          .ok_or(ParseError::UnsupportedContentEncoding)
+      })
+      .collect::<Result<_, _>>()?;
+    Ok(Self {
+      layers,
+      max_size,
+      received: false,
+    })
+  }
+
+  /// Check whether bytes pass through unchanged
+  pub const fn is_identity(&self) -> bool {
+    self.layers.is_empty()
+  }
+
+  /// Decode the next piece of the body, returning the bytes it completes
+  ///
+  /// # Errors
+  /// Returns an error if the data is corrupt or decodes to more than `max_size`
+  pub fn decode(
+    &mut self,
+    input: &[u8],
+  ) -> Result<Vec<u8>, ParseError> {
+    self.received |= !input.is_empty();
+    self.run(input, false)
+  }
+
+  /// Flush the decoders once the whole body has been passed to `decode`
+  ///
+  /// An empty body is accepted as is, like the buffered path does.
+  ///
+  /// # Errors
+  /// Returns `ParseError::DecompressionFailed` if the coded data was cut short
+  pub fn finish(&mut self) -> Result<Vec<u8>, ParseError> {
+    if !self.received {
+      return Ok(Vec::new());
+    }
+    self.run(&[], true)
+  }
+
+  fn run(
+    &mut self,
+    input: &[u8],
+    finishing: bool,
+  ) -> Result<Vec<u8>, ParseError> {
+    let max_size = self.max_size.unwrap_or(usize::MAX);
+    let mut data = Vec::from(input);
+    for layer in &mut self.layers {
+      let mut decoded = Vec::new();
+      let limit = max_size.saturating_sub(layer.produced);
+      layer.decoder.decode(&data, &mut decoded, limit)?;
+      if finishing {
+        layer.decoder.finish(&mut decoded, limit)?;
+      }
+      layer.produced = layer.produced.saturating_add(decoded.len());
+      data = decoded;
+    }
+    Ok(data)
+  }
+}
+
+/// Decoder for a single coding name, if this build supports it
+#[cfg_attr(
+  not(any(feature = "gzip-decompression", feature = "zstd-decompression")),
+  allow(clippy::missing_const_for_fn, unused_variables)
+)]
+fn coding_decoder(coding: &str) -> Option<Box<dyn Decode>> {
+  match coding {
+    #[cfg(feature = "gzip-decompression")]
+    "gzip" | "x-gzip" => Some(Box::new(Gzip::new())),
+    #[cfg(feature = "gzip-decompression")]
+    "deflate" => Some(Box::new(Inflate::new(DataFormat::Zlib))),
+    #[cfg(feature = "zstd-decompression")]
+    "zstd" => Some(Box::new(Zstd {
+      decoder: FrameDecoder::new(),
+      pending: Vec::new(),
+      started: false,
+    })),
+    _ => None,
+  }
+}
+
+/// `deflate` decoding, also used for the data in each gzip member
+#[cfg(feature = "gzip-decompression")]
+struct Inflate {
+  state: Box<InflateState>,
+  done: bool,
+}
+
+#[cfg(feature = "gzip-decompression")]
+impl Inflate {
+  fn new(format: DataFormat) -> Self {
+    Self {
+      state: InflateState::new_boxed(format),
+      done: false,
+    }
+  }
+
+  /// Inflate `input` until it runs out or the stream ends, returning how
+  /// many bytes of it were consumed
+  fn inflate_into(
+    &mut self,
+    input: &[u8],
+    out: &mut Vec<u8>,
+    limit: usize,
+  ) -> Result<usize, ParseError> {
+    let mut rest = input;
+    let mut chunk = [0u8; 8192];
+    while !self.done {
+      let result = inflate(&mut self.state, rest, &mut chunk, MZFlush::None);
+      rest = rest.get(result.bytes_consumed..).unwrap_or_default();
+      out.extend_from_slice(chunk.get(..result.bytes_written).unwrap_or_default());
+      if out.len() > limit {
+        return Err(ParseError::DecompressionTooLarge);
+      }
+      match result.status {
+        Ok(MZStatus::StreamEnd) => self.done = true,
+        Ok(_) if result.bytes_consumed > 0 || result.bytes_written > 0 => {},
+        Ok(_) | Err(MZError::Buf) => break,
+        Err(_) => return Err(ParseError::DecompressionFailed),
+      }
+    }
+    Ok(input.len().saturating_sub(rest.len()))
+  }
+}
+
+#[cfg(feature = "gzip-decompression")]
+impl Decode for Inflate {
+  fn decode(
+    &mut self,
+    input: &[u8],
+    out: &mut Vec<u8>,
+    limit: usize,
+  ) -> Result<(), ParseError> {
+    self.inflate_into(input, out, limit).map(|_| ())
+  }
+
+  fn finish(
+    &mut self,
+    _out: &mut Vec<u8>,
+    _limit: usize,
+  ) -> Result<(), ParseError> {
+    if self.done {
+      Ok(())
+    } else {
+      Err(ParseError::DecompressionFailed)
+    }
+  }
+}
+
+/// Header flag: a CRC-16 of the header follows it (RFC 1952 Section 2.3.1)
+#[cfg(feature = "gzip-decompression")]
+const FHCRC: u8 = 2;
+/// Header flag: a length-prefixed extra field follows the fixed header
+#[cfg(feature = "gzip-decompression")]
+const FEXTRA: u8 = 4;
+/// Header flag: a NUL-terminated file name follows
+#[cfg(feature = "gzip-decompression")]
+const FNAME: u8 = 8;
+/// Header flag: a NUL-terminated comment follows
+#[cfg(feature = "gzip-decompression")]
+const FCOMMENT: u8 = 16;
+/// Header flags RFC 1952 reserves, which must be zero; `FTEXT` is only a hint
+#[cfg(feature = "gzip-decompression")]
+const RESERVED_FLAGS: u8 = 0xe0;
+
+/// Length of the gzip member header at the start of `bytes`, or `None` if
+/// it continues past them (RFC 1952 Section 2.3)
+#[cfg(feature = "gzip-decompression")]
+fn gzip_header_len(bytes: &[u8]) -> Result<Option<usize>, ParseError> {
+  // ID1, ID2 and CM, which is always deflate
+  const MAGIC: [u8; 3] = [0x1f, 0x8b, 8];
+  if !MAGIC
+    .iter()
+    .zip(bytes)
+    .all(|(expected, byte)| expected == byte)
+  {
+    return Err(ParseError::DecompressionFailed);
+  }
+  let Some(&flags) = bytes.get(3) else {
+    return Ok(None);
+  };
+  if flags & RESERVED_FLAGS != 0 {
+    return Err(ParseError::DecompressionFailed);
+  }
+  let mut len = 10;
+  if flags & FEXTRA != 0 {
+    let Some(xlen) = bytes
+      .get(len..len + 2)
+      .and_then(|xlen| <[u8; 2]>::try_from(xlen).ok())
+    else {
+      return Ok(None);
+    };
+    len += 2 + usize::from(u16::from_le_bytes(xlen));
+  }
+  for flag in [FNAME, FCOMMENT] {
+    if flags & flag != 0 {
+      let Some(field_len) = bytes
+        .get(len..)
+        .and_then(|field| field.iter().position(|&byte| byte == 0))
+      else {
+        return Ok(None);
+      };
+      len += field_len + 1;
+    }
+  }
+  if flags & FHCRC != 0 {
+    let Some(stored) = bytes.get(len..len + 2) else {
+      return Ok(None);
+    };
+    let header_crc = crc32(0, bytes.get(..len).unwrap_or_default()).to_le_bytes();
+    if header_crc.get(..2) != Some(stored) {
+      return Err(ParseError::DecompressionFailed);
+    }
+    len += 2;
+  }
+  Ok((bytes.len() >= len).then_some(len))
+}
+
+/// CRC-32 of `bytes` continuing from `crc`, as gzip uses it (RFC 1952 Section 8)
+///
+/// Start from 0 for a new checksum.
+#[cfg(feature = "gzip-decompression")]
+pub fn crc32(
+  crc: u32,
+  bytes: &[u8],
+) -> u32 {
+  !bytes.iter().fold(!crc, |state, &byte| {
+    (0..8).fold(state ^ u32::from(byte), |value, _| {
+      (value >> 1) ^ (0xedb8_8320 & (value & 1).wrapping_neg())
+    })
+  })
+}
+
+/// Where a gzip decoder is within the current member
+#[cfg(feature = "gzip-decompression")]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum GzipPart {
+  Header,
+  Data,
+  Trailer,
+}
+
+/// `gzip` decoding of one or more members (RFC 1952 Section 2.2)
+///
+/// The optional header fields are skipped, and each member's CRC-32 and
+/// length trailer is checked against the data it decoded to.
+#[cfg(feature = "gzip-decompression")]
+struct Gzip {
+  inflate: Inflate,
+  part: GzipPart,
+  /// Header or trailer bytes held until the rest of them arrives
+  pending: Vec<u8>,
+  crc: u32,
+  size: u64,
+  /// Whether at least one whole member has been decoded
+  complete: bool,
+}
+
+#[cfg(feature = "gzip-decompression")]
+impl Gzip {
+  fn new() -> Self {
+    Self {
+      inflate: Inflate::new(DataFormat::Raw),
+      part: GzipPart::Header,
+      pending: Vec::new(),
+      crc: 0,
+      size: 0,
+      complete: false,
+    }
+  }
+
+  /// Check the trailer that ends a member and get ready for the next one
+  fn end_member(
+    &mut self,
+    trailer: &[u8],
+  ) -> Result<(), ParseError> {
+    let (crc, size) = trailer
+      .split_at_checked(4)
+      .and_then(|(crc, size)| Some((<[u8; 4]>::try_from(crc).ok()?, <[u8; 4]>::try_from(size).ok()?)))
+      .ok_or(ParseError::DecompressionFailed)?;
+    // ISIZE is the decoded length modulo 2^32
+    if u32::from_le_bytes(crc) != self.crc || u64::from(u32::from_le_bytes(size)) != self.size & 0xffff_ffff {
+      return Err(ParseError::DecompressionFailed);
+    }
+    self.inflate = Inflate::new(DataFormat::Raw);
+    self.part = GzipPart::Header;
+    self.crc = 0;
+    self.size = 0;
+    self.complete = true;
+    Ok(())
+  }
+}
+
+#[cfg(feature = "gzip-decompression")]
+impl Decode for Gzip {
+  fn decode(
+    &mut self,
+    input: &[u8],
+    out: &mut Vec<u8>,
+    limit: usize,
+  ) -> Result<(), ParseError> {
+    let mut data = core::mem::take(&mut self.pending);
+    data.extend_from_slice(input);
+    let mut rest = data.as_slice();
+    while !rest.is_empty() {
+      match self.part {
+        GzipPart::Header => {
+          let Some(header_len) = gzip_header_len(rest)? else {
+            break;
+          };
+          rest = rest.get(header_len..).unwrap_or_default();
+          self.part = GzipPart::Data;
+        },
+        GzipPart::Data => {
+          let start = out.len();
+          let consumed = self.inflate.inflate_into(rest, out, limit)?;
+          let decoded = out.get(start..).unwrap_or_default();
+          self.crc = crc32(self.crc, decoded);
+          self.size = self
+            .size
+            .wrapping_add(u64::try_from(decoded.len()).unwrap_or(u64::MAX));
+          rest = rest.get(consumed..).unwrap_or_default();
+          if !self.inflate.done {
+            break;
+          }
+          self.part = GzipPart::Trailer;
+        },
+        GzipPart::Trailer => {
+          let Some(trailer) = rest.get(..8) else {
+            break;
+          };
+          self.end_member(trailer)?;
+          rest = rest.get(8..).unwrap_or_default();
+        },
+      }
+    }
+    self.pending = rest.to_vec();
+    Ok(())
+  }
+
+  fn finish(
+    &mut self,
+    _out: &mut Vec<u8>,
+    _limit: usize,
+  ) -> Result<(), ParseError> {
+    if self.complete && self.part == GzipPart::Header && self.pending.is_empty() {
+      Ok(())
+    } else {
+      Err(ParseError::DecompressionFailed)
+    }
+  }
+}
+
+/// `zstd` decoding of a single frame
+///
+/// Input is held until it covers the frame header and then each whole block,
+/// as the frame decoder only decodes complete blocks.
+#[cfg(feature = "zstd-decompression")]
+struct Zstd {
+  decoder: FrameDecoder,
+  pending: Vec<u8>,
+  started: bool,
+}
+
+#[cfg(feature = "zstd-decompression")]
+impl Zstd {
+  /// Longest possible frame header
+  const MAX_HEADER_LEN: usize = 18;
+
+  fn drain(
+    &mut self,
+    out: &mut Vec<u8>,
+    limit: usize,
+    at_end: bool,
+  ) -> Result<(), ParseError> {
+    if !self.started {
+      if self.pending.len() < Self::MAX_HEADER_LEN && !at_end {
+        return Ok(());
+      }
+      let mut source = self.pending.as_slice();
+      self
+        .decoder
+        .init(&mut source)
+        .map_err(|_| ParseError::DecompressionFailed)?;
+      let header_len = self.pending.len() - source.len();
+      self.pending.drain(..header_len);
+      self.started = true;
+    }
+    let mut chunk = [0u8; 8192];
+    loop {
+      // The returned read count is not reliable while waiting for the checksum
+      let before = self.decoder.bytes_read_from_source();
+      let (_, written) = self
+        .decoder
+        .decode_from_to(&self.pending, &mut chunk)
+        .map_err(|_| ParseError::DecompressionFailed)?;
+      let read = usize::try_from(self.decoder.bytes_read_from_source().saturating_sub(before))
+        .map_or(self.pending.len(), |read| read.min(self.pending.len()));
+      self.pending.drain(..read);
+      out.extend_from_slice(chunk.get(..written).unwrap_or_default());
+      if out.len() > limit {
+        return Err(ParseError::DecompressionTooLarge);
+      }
+      if read == 0 && written == 0 {
+        return Ok(());
+      }
+    }
+  }
+}
+
+#[cfg(feature = "zstd-decompression")]
+impl Decode for Zstd {
+  fn decode(
+    &mut self,
+    input: &[u8],
+    out: &mut Vec<u8>,
+    limit: usize,
+  ) -> Result<(), ParseError> {
+    self.pending.extend_from_slice(input);
+    self.drain(out, limit, false)
+  }
+
+  fn finish(
+    &mut self,
+    out: &mut Vec<u8>,
+    limit: usize,
+  ) -> Result<(), ParseError> {
+    self.drain(out, limit, true)?;
+    if self.decoder.is_finished() {
+      Ok(())
+    } else {
+      Err(ParseError::DecompressionFailed)
+    }
+  }
+}
+
+/// Sink that decodes a body's content codings before passing it on
+pub struct DecodingSink<'s> {
+  sink: &'s mut dyn ByteSink,
+  decoder: ContentDecoder,
+}
+
+impl<'s> DecodingSink<'s> {
+  /// Decode what is written with `decoder` and write the result to `sink`
+  pub fn new(
+    sink: &'s mut dyn ByteSink,
+    decoder: ContentDecoder,
+  ) -> Self {
+    Self { sink, decoder }
+  }
+
+  /// Flush the decoder after the last write
+  ///
+  /// Nothing is checked when `sink` filled up, since the body was then
+  /// deliberately left unread.
+  ///
+  /// # Errors
+  /// Returns an error if the coded body was incomplete or `sink` fails
+  pub fn end(mut self) -> Result<(), Error> {
+    if self.sink.is_full() {
+      return Ok(());
+    }
+    let rest = self.decoder.finish().map_err(Error::Parse)?;
+    self.sink.write_all(&rest)
+  }
+}
+
+impl ByteSink for DecodingSink<'_> {
+  fn write_all(
+    &mut self,
+    bytes: &[u8],
+  ) -> Result<(), Error> {
+    if self.decoder.is_identity() {
+      return self.sink.write_all(bytes);
+    }
+    let decoded = self.decoder.decode(bytes).map_err(Error::Parse)?;
+    self.sink.write_all(&decoded)
+  }
+
+  fn is_full(&self) -> bool {
+    self.sink.is_full()
+  }
+}
@@ -1,30 +1,69 @@
 extern crate alloc;
+use crate::util::HttpDate;
 use alloc::string::String;
 use alloc::vec::Vec;
 
+/// A cookie from a `Set-Cookie` response header (RFC 6265 Section 5.2)
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SetCookie {
+  /// Cookie name
   pub name: String,
+  /// Cookie value, with surrounding whitespace removed
   pub value: String,
+  /// `Expires` attribute
   pub expires: Option<CookieDate>,
+  /// `Max-Age` attribute in seconds; takes precedence over `expires`
   pub max_age: Option<i64>,
+  /// `Domain` attribute, lowercased and without a leading dot
   pub domain: Option<String>,
+  /// `Path` attribute, if it starts with `/`
   pub path: Option<String>,
+  /// `Secure` attribute
   pub secure: bool,
+  /// `HttpOnly` attribute
   pub http_only: bool,
+  /// `SameSite` attribute
+  pub same_site: Option<SameSite>,
+  /// `Partitioned` attribute (CHIPS)
+  pub partitioned: bool,
 }
 
+/// Value of the `SameSite` cookie attribute
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct CookieDate {
-  pub year: u16,
-  pub month: u8,
-  pub day: u8,
-  pub hour: u8,
-  pub minute: u8,
-  pub second: u8,
+pub enum SameSite {
+  /// Only sent with same-site requests
+  Strict,
+  /// Also sent with top-level cross-site navigations
+  Lax,
+  /// Sent with all requests; browsers require `Secure` alongside it
+  None,
 }
 
+impl SameSite {
+  /// Parse an attribute value case-insensitively; unknown values give None
+  #[must_use]
+  pub const fn parse(value: &str) -> Option<Self> {
+    if value.eq_ignore_ascii_case("strict") {
+      Some(Self::Strict)
+    } else if value.eq_ignore_ascii_case("lax") {
+      Some(Self::Lax)
+    } else if value.eq_ignore_ascii_case("none") {
+      Some(Self::None)
+    } else {
+      None
+    }
+  }
+}
+
+/// Cookie expiry dates share the general HTTP-date representation
+pub type CookieDate = HttpDate;
+
 impl SetCookie {
+  /// Parse a `Set-Cookie` header value
+  ///
+  /// Returns None if there is no `=` or the name is empty; unknown or
+  /// invalid attributes are ignored.
+  #[must_use]
   pub fn parse(input: &str) -> Option<Self> {
     let input_bytes = input.as_bytes();
 
@@ -60,6 +99,8 @@ impl SetCookie {
       path: attributes.path,
       secure: attributes.secure,
       http_only: attributes.http_only,
+      same_site: attributes.same_site,
+      partitioned: attributes.partitioned,
     })
   }
 }
@@ -72,6 +113,8 @@ struct CookieAttributes {
   path: Option<String>,
   secure: bool,
   http_only: bool,
+  same_site: Option<SameSite>,
+  partitioned: bool,
 }
 
 struct AttrIter<'a> {
@@ -153,6 +196,13 @@ fn parse_cookie_attributes(input: &[u8]) -> CookieAttributes {
     match name_trimmed {
       _ if eq_ignore_ascii(name_trimmed, b"secure") => attrs.secure = true,
       _ if eq_ignore_ascii(name_trimmed, b"httponly") => attrs.http_only = true,
+      _ if eq_ignore_ascii(name_trimmed, b"partitioned") => attrs.partitioned = true,
+      // draft-ietf-httpbis-rfc6265bis Section 5.6.7: an unrecognized value is ignored
+      _ if eq_ignore_ascii(name_trimmed, b"samesite") => {
+        attrs.same_site = core::str::from_utf8(value_trimmed)
+          .ok()
+          .and_then(SameSite::parse);
+      },
       _ if eq_ignore_ascii(name_trimmed, b"expires") => {
         if let Ok(s) = core::str::from_utf8(value_trimmed) {
           attrs.expires = parse_cookie_date(s);
@@ -0,0 +1,221 @@
+extern crate alloc;
+use crate::error::Error;
+use crate::sink::ByteSink;
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
+/// One event from a `text/event-stream` body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerSentEvent {
+  /// Event type from the `event` field, `"message"` if the event had none
+  pub event: String,
+  /// Lines of every `data` field, joined with `\n`
+  pub data: String,
+  /// Last event ID seen so far in the stream, if any
+  pub id: Option<String>,
+}
+
+/// Incremental parser for the `text/event-stream` format
+///
+/// Follows the HTML Living Standard "Server-sent events" parsing rules:
+/// lines end with CRLF, LF or CR, fields are `name: value`, lines starting
+/// with `:` are comments, and a blank line dispatches the pending event.
+/// An event still pending when the input ends is discarded.
+///
+/// Feed bytes as they arrive with `feed`; completed events are yielded by
+/// the `Iterator` implementation. `EventSink` does this for a response body
+/// streamed with `call_to_writer`. To resume after a disconnect, send
+/// `last_event_id` in a `Last-Event-ID` request header and wait `retry`
+/// milliseconds, if the server set it, before reconnecting.
+#[derive(Debug, Clone, Default)]
+pub struct EventStream {
+  line: Vec<u8>,
+  after_cr: bool,
+  seen_first_line: bool,
+  event_type: String,
+  data: String,
+  last_event_id: Option<String>,
+  retry: Option<u64>,
+  ready: VecDeque<ServerSentEvent>,
+}
+
+impl EventStream {
+  /// Create a parser that has not seen any input
+  #[must_use]
+  pub const fn new() -> Self {
+    Self {
+      line: Vec::new(),
+      after_cr: false,
+      seen_first_line: false,
+      event_type: String::new(),
+      data: String::new(),
+      last_event_id: None,
+      retry: None,
+      ready: VecDeque::new(),
+    }
+  }
+
+  /// Parse a complete body
+  #[must_use]
+  pub fn from_bytes(body: &[u8]) -> Self {
+    let mut stream = Self::new();
+    stream.feed(body);
+    stream
+  }
+
+  /// Parse the next chunk of the stream
+  ///
+  /// Chunks may split lines, and even a CRLF pair, anywhere.
+  pub fn feed(
+    &mut self,
+    bytes: &[u8],
+  ) {
+    for &byte in bytes {
+      if core::mem::take(&mut self.after_cr) && byte == b'\n' {
+        continue;
+      }
+      if byte == b'\r' || byte == b'\n' {
+        self.after_cr = byte == b'\r';
+        let line = core::mem::take(&mut self.line);
+        self.process_line(&line);
+      } else {
+        self.line.push(byte);
+      }
+    }
+  }
+
+  /// ID to send in `Last-Event-ID` when reconnecting
+  #[must_use]
+  pub fn last_event_id(&self) -> Option<&str> {
+    self.last_event_id.as_deref()
+  }
+
+  /// Reconnection delay in milliseconds requested by the server's `retry` field
+  #[must_use]
+  pub const fn retry(&self) -> Option<u64> {
+    self.retry
+  }
+
+  fn process_line(
+    &mut self,
+    raw: &[u8],
+  ) {
+    let decoded = String::from_utf8_lossy(raw);
+    // A leading byte order mark is skipped once, at the start of the stream
+    let line = if self.seen_first_line {
+      &*decoded
+    } else {
+      self.seen_first_line = true;
+      decoded.strip_prefix('\u{FEFF}').unwrap_or(&decoded)
+    };
+
+    if line.is_empty() {
+      self.dispatch();
+      return;
+    }
+    if line.starts_with(':') {
+      return;
+    }
+    let (field, value) = match line.split_once(':') {
+      Some((name, rest)) => (name, rest.strip_prefix(' ').unwrap_or(rest)),
+      None => (line, ""),
+    };
+    match field {
+      "event" => self.event_type = String::from(value),
+      "data" => {
+        self.data.push_str(value);
+        self.data.push('\n');
+      },
+      // An ID containing NULL is ignored; an empty ID clears the last event ID
+      "id" if !value.contains('\0') => {
+        self.last_event_id = (!value.is_empty()).then(|| String::from(value));
+      },
+      "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+        self.retry = value.parse().ok();
+      },
+      _ => {},
+    }
+  }
+
+  fn dispatch(&mut self) {
+    let event_type = core::mem::take(&mut self.event_type);
+    let mut data = core::mem::take(&mut self.data);
+    if data.is_empty() {
+      return;
+    }
+    data.pop();
+    self.ready.push_back(ServerSentEvent {
+      event: if event_type.is_empty() {
+        String::from("message")
+      } else {
+        event_type
+      },
+      data,
+      id: self.last_event_id.clone(),
+    });
+  }
+}
+
+impl Iterator for EventStream {
+  type Item = ServerSentEvent;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.ready.pop_front()
+  }
+}
+
+/// Sink that parses a `text/event-stream` body and hands each event to a callback
+///
+/// Pass it to `call_to_writer` to receive events while the response is still
+/// arriving, instead of after the whole body has been read. Returning
+/// `ControlFlow::Break` from `on_event` stops reading: the rest of the stream
+/// is left unread and the request returns normally.
+pub struct EventSink<F> {
+  stream: EventStream,
+  on_event: F,
+  stopped: bool,
+}
+
+impl<F> EventSink<F>
+where
+  F: FnMut(ServerSentEvent) -> ControlFlow<()>,
+{
+  /// Call `on_event` with every event of the body, in order
+  pub const fn new(on_event: F) -> Self {
+    Self {
+      stream: EventStream::new(),
+      on_event,
+      stopped: false,
+    }
+  }
+
+  /// Parser state, for `last_event_id` and `retry` when reconnecting
+  #[must_use]
+  pub const fn stream(&self) -> &EventStream {
+    &self.stream
+  }
+}
+
+impl<F> ByteSink for EventSink<F>
+where
+  F: FnMut(ServerSentEvent) -> ControlFlow<()>,
+{
+  fn write_all(
+    &mut self,
+    bytes: &[u8],
+  ) -> Result<(), Error> {
+    self.stream.feed(bytes);
+    while !self.stopped
+      && let Some(event) = self.stream.next()
+    {
+      self.stopped = (self.on_event)(event).is_break();
+    }
+    Ok(())
+  }
+
+  fn is_full(&self) -> bool {
+    self.stopped
+  }
+}
@@ -1,5 +1,71 @@
 use crate::error::ParseError;
 
+/// Incremental search for the end of a response header section
+///
+/// Feed it the growing receive buffer after every read; each byte is examined
+/// only once, so headers arriving in many small reads cost linear time.
+/// Empty lines before the status line are skipped like `header_end_offset` does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeaderScanner {
+  scanned: usize,
+  matched: u8,
+  started: bool,
+  end: Option<usize>,
+}
+
+impl HeaderScanner {
+  /// Create a scanner that has not seen any bytes
+  #[must_use]
+  pub const fn new() -> Self {
+    Self {
+      scanned: 0,
+      matched: 0,
+      started: false,
+      end: None,
+    }
+  }
+
+  /// Examine the bytes appended to `buffer` since the last call
+  ///
+  /// `buffer` must be the same buffer as before with new bytes appended.
+  /// Returns the offset just past the CRLF CRLF once it has been found.
+  pub fn scan(
+    &mut self,
+    buffer: &[u8],
+  ) -> Option<usize> {
+    if self.end.is_some() {
+      return self.end;
+    }
+    for &byte in buffer.get(self.scanned..).unwrap_or_default() {
+      self.scanned += 1;
+      if !self.started {
+        // RFC 9112 Section 2.2: ignore empty lines before the status line
+        self.started = !matches!(byte, b'\r' | b'\n');
+        continue;
+      }
+      // Length of the "\r\n\r\n" prefix matched by the bytes seen so far
+      self.matched = match (self.matched, byte) {
+        (2, b'\r') => 3,
+        (1, b'\n') => 2,
+        (3, b'\n') => {
+          self.end = Some(self.scanned);
+          return self.end;
+        },
+        (_, b'\r') => 1,
+        _ => 0,
+      };
+    }
+    None
+  }
+
+  /// Number of bytes examined so far
+  #[cfg(test)]
+  #[must_use]
+  pub(crate) const fn scanned(&self) -> usize {
+    self.scanned
+  }
+}
+
 /// Detects HTTP framing boundaries in byte streams
 ///
 /// RFC 9112 Section 2: Message Format
@@ -13,7 +79,6 @@ impl FramingDetector {
   ///
   /// RFC 9112 Section 2.1: Message Format
   /// Returns the position after the CRLF CRLF if found, None otherwise
-  #[allow(dead_code)]
   pub fn find_header_end(data: &[u8]) -> Option<usize> {
     // RFC 9112 Section 2: header section ends with CRLF CRLF
     data
@@ -22,13 +87,22 @@ impl FramingDetector {
       .map(|pos| pos + 4)
   }
 
-  /// Check if buffer contains complete HTTP headers
+  /// Offset just past the CRLF CRLF that ends the header section of a response
   ///
-  /// This is more efficient than `find_header_end` when you only need
-  /// to know if headers are complete, not where they end.
+  /// Empty lines before the status line are skipped first (RFC 9112 Section 2.2),
+  /// so they are not mistaken for the end of the headers. The bytes from the
+  /// returned offset on are the start of the body.
+  pub fn header_end_offset(data: &[u8]) -> Option<usize> {
+    let start = data
+      .iter()
+      .position(|byte| !matches!(byte, b'\r' | b'\n'))?;
+    Self::find_header_end(data.get(start..)?).map(|end| end + start)
+  }
+
+  /// Check if buffer contains complete HTTP headers
   #[inline]
   pub fn has_complete_headers(data: &[u8]) -> bool {
-    data.windows(4).any(|w| w == b"\r\n\r\n")
+    Self::header_end_offset(data).is_some()
   }
 
   /// Search for the terminating chunk in chunked transfer encoding
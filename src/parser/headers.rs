@@ -14,6 +14,18 @@ impl<'a> HeaderField<'a> {
   /// with a single space character.
   pub fn parse(
     input: &'a [u8]
+  ) -> Result<(alloc::vec::Vec<(alloc::vec::Vec<u8>, alloc::vec::Vec<u8>)>, &'a [u8]), ParseError> {
+    Self::parse_with(input, true)
+  }
+
+  /// Parse header fields, rejecting obs-fold with `ParseError::ObsoleteFoldInHeader`
+  /// unless `allow_obs_fold` is true.
+  ///
+  /// Each accepted obs-fold, together with the whitespace around it, becomes exactly
+  /// one SP, so `"first \r\n  second"` reads as `"first second"`.
+  pub fn parse_with(
+    input: &'a [u8],
+    allow_obs_fold: bool,
   ) -> Result<(alloc::vec::Vec<(alloc::vec::Vec<u8>, alloc::vec::Vec<u8>)>, &'a [u8]), ParseError> {
     use alloc::vec::Vec;
 
@@ -117,8 +129,11 @@ impl<'a> HeaderField<'a> {
           && next_byte1 == Some(b'\n')
           && (next_byte2 == Some(b' ') || next_byte2 == Some(b'\t'))
         {
+          if !allow_obs_fold {
+            return Err(ParseError::ObsoleteFoldInHeader);
+          }
           // RFC 9112 Section 5.2: Replace obs-fold with SP
-          value_bytes.push(b' ');
+          push_fold_space(&mut value_bytes);
           remaining = remaining.get(2..).ok_or(ParseError::MissingCrlf)?;
           // Skip the whitespace character(s)
           while !remaining.is_empty() {
@@ -135,8 +150,11 @@ impl<'a> HeaderField<'a> {
         // Check for obs-fold: LF followed by SP or HTAB
         if remaining.len() >= 2 && next_byte0 == Some(b'\n') && (next_byte1 == Some(b' ') || next_byte1 == Some(b'\t'))
         {
+          if !allow_obs_fold {
+            return Err(ParseError::ObsoleteFoldInHeader);
+          }
           // RFC 9112 Section 5.2: Replace obs-fold with SP
-          value_bytes.push(b' ');
+          push_fold_space(&mut value_bytes);
           remaining = remaining.get(1..).ok_or(ParseError::MissingCrlf)?;
           // Skip the whitespace character(s)
           while !remaining.is_empty() {
@@ -182,6 +200,19 @@ impl<'a> HeaderField<'a> {
   }
 }
 
+/// Replace the whitespace ending `value` with the single SP standing for an obs-fold
+///
+/// Nothing is added while the value is still empty, so folds never produce
+/// leading or repeated spaces.
+fn push_fold_space(value: &mut alloc::vec::Vec<u8>) {
+  while value.last().is_some_and(|&b| b == b' ' || b == b'\t') {
+    value.pop();
+  }
+  if !value.is_empty() {
+    value.push(b' ');
+  }
+}
+
 const fn is_token_char(b: u8) -> bool {
   matches!(b,
     b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.' |
@@ -16,6 +16,21 @@ impl HttpVersion {
     let remaining = input.get(8..).ok_or(ParseError::InvalidHttpVersion)?;
     Ok((version, remaining))
   }
+
+  /// Like `parse_http`, but accepts the `HTTP` name in any letter case
+  ///
+  /// # Errors
+  /// Returns an error if the input is not a valid HTTP version string.
+  pub fn parse_http_ignore_case(input: &[u8]) -> Result<(Self, &[u8]), ParseError> {
+    let mut token = [0u8; 8];
+    let prefix = input.get(..8).ok_or(ParseError::InvalidHttpVersion)?;
+    token.copy_from_slice(prefix);
+    token.make_ascii_uppercase();
+
+    let (version, _) = Self::parse_http(&token)?;
+    let remaining = input.get(8..).ok_or(ParseError::InvalidHttpVersion)?;
+    Ok((version, remaining))
+  }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -59,7 +74,23 @@ pub struct StatusLine<'a> {
 
 impl<'a> StatusLine<'a> {
   pub fn parse(input: &'a [u8]) -> Result<(Self, &'a [u8]), ParseError> {
-    let (version, rest1) = HttpVersion::parse_http(input)?;
+    Self::parse_with(input, false)
+  }
+
+  /// Parse a status line, optionally tolerating common non-compliant forms
+  ///
+  /// When `lenient` is true, the version name is matched case-insensitively
+  /// (`http/1.1`) and the space before an empty reason phrase may be omitted
+  /// (`HTTP/1.1 200\r\n`).
+  pub fn parse_with(
+    input: &'a [u8],
+    lenient: bool,
+  ) -> Result<(Self, &'a [u8]), ParseError> {
+    let (version, rest1) = if lenient {
+      HttpVersion::parse_http_ignore_case(input)?
+    } else {
+      HttpVersion::parse_http(input)?
+    };
 
     let first_char = rest1.first().copied();
     if rest1.is_empty() || first_char != Some(b' ') {
@@ -70,10 +101,14 @@ impl<'a> StatusLine<'a> {
     let (status, rest3) = StatusCode::parse(rest2)?;
 
     let second_space = rest3.first().copied();
-    if rest3.is_empty() || second_space != Some(b' ') {
-      return Err(ParseError::InvalidWhitespace);
-    }
-    let rest4 = rest3.get(1..).ok_or(ParseError::InvalidWhitespace)?;
+    let rest4 = if lenient && matches!(second_space, Some(b'\r' | b'\n')) {
+      rest3
+    } else {
+      if rest3.is_empty() || second_space != Some(b' ') {
+        return Err(ParseError::InvalidWhitespace);
+      }
+      rest3.get(1..).ok_or(ParseError::InvalidWhitespace)?
+    };
 
     let mut i = 0;
     while i < rest4.len() {
@@ -0,0 +1,155 @@
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// One link from a `Link` header (RFC 8288 Section 3)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkRef {
+  /// Target URI, as sent or resolved against the response URL
+  pub uri: String,
+  /// Link parameters with lowercase names and unquoted values;
+  /// a parameter without a value has an empty string
+  pub params: Vec<(String, String)>,
+}
+
+impl LinkRef {
+  /// First value of a parameter (case-insensitive name)
+  #[must_use]
+  pub fn param(
+    &self,
+    name: &str,
+  ) -> Option<&str> {
+    self
+      .params
+      .iter()
+      .find(|(n, _)| n.eq_ignore_ascii_case(name))
+      .map(|(_, v)| v.as_str())
+  }
+
+  /// The `rel` parameter
+  #[must_use]
+  pub fn rel(&self) -> Option<&str> {
+    self.param("rel")
+  }
+
+  /// Check whether `rel` lists the given relation type
+  ///
+  /// `rel` may hold several space-separated types; they compare case-insensitively.
+  #[must_use]
+  pub fn has_rel(
+    &self,
+    relation: &str,
+  ) -> bool {
+    self.rel().is_some_and(|rel| {
+      rel
+        .split_ascii_whitespace()
+        .any(|r| r.eq_ignore_ascii_case(relation))
+    })
+  }
+}
+
+/// Parse a `Link` field value into its links
+///
+/// Commas inside `<...>` and quoted strings do not separate links. Elements
+/// that do not start with `<` are skipped.
+#[must_use]
+pub fn parse_link_header(value: &str) -> Vec<LinkRef> {
+  let mut links = Vec::new();
+  let mut rest = value;
+  loop {
+    rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+    if rest.is_empty() {
+      break;
+    }
+    let Some(after_open) = rest.strip_prefix('<') else {
+      rest = skip_element(rest);
+      continue;
+    };
+    let Some((uri, after_uri)) = after_open.split_once('>') else {
+      break;
+    };
+    let (params, remaining) = parse_params(after_uri);
+    links.push(LinkRef {
+      uri: String::from(uri.trim()),
+      params,
+    });
+    rest = remaining;
+  }
+  links
+}
+
+/// Parse `;`-separated link-params up to the next top-level comma
+fn parse_params(input: &str) -> (Vec<(String, String)>, &str) {
+  let mut params = Vec::new();
+  let mut rest = input;
+  loop {
+    rest = rest.trim_start();
+    let Some(after_semicolon) = rest.strip_prefix(';') else {
+      break;
+    };
+    let name_end = after_semicolon
+      .find(['=', ';', ','])
+      .unwrap_or(after_semicolon.len());
+    let (raw_name, after_name) = after_semicolon.split_at(name_end);
+    let name = raw_name.trim().to_ascii_lowercase();
+
+    let Some(after_equals) = after_name.strip_prefix('=') else {
+      if !name.is_empty() {
+        params.push((name, String::new()));
+      }
+      rest = after_name;
+      continue;
+    };
+    let raw_value = after_equals.trim_start();
+    let (param_value, after_value) = if raw_value.starts_with('"') {
+      parse_quoted(raw_value)
+    } else {
+      let value_end = raw_value.find([';', ',']).unwrap_or(raw_value.len());
+      let (token, after_token) = raw_value.split_at(value_end);
+      (String::from(token.trim()), after_token)
+    };
+    if !name.is_empty() {
+      params.push((name, param_value));
+    }
+    rest = after_value;
+  }
+  (params, skip_element(rest))
+}
+
+/// Parse a quoted-string starting at `input`, returning it unescaped and the rest
+fn parse_quoted(input: &str) -> (String, &str) {
+  let mut unquoted = String::new();
+  let mut escaped = false;
+  for (index, c) in input.char_indices().skip(1) {
+    if escaped {
+      unquoted.push(c);
+      escaped = false;
+    } else if c == '\\' {
+      escaped = true;
+    } else if c == '"' {
+      return (unquoted, input.get(index + 1..).unwrap_or(""));
+    } else {
+      unquoted.push(c);
+    }
+  }
+  // Unterminated quote: take everything that is left
+  (unquoted, "")
+}
+
+/// Skip to just past the next comma outside quoted strings
+fn skip_element(input: &str) -> &str {
+  let mut in_quotes = false;
+  let mut escaped = false;
+  for (index, byte) in input.bytes().enumerate() {
+    if escaped {
+      escaped = false;
+    } else if in_quotes && byte == b'\\' {
+      escaped = true;
+    } else if byte == b'"' {
+      in_quotes = !in_quotes;
+    } else if byte == b',' && !in_quotes {
+      return input.get(index + 1..).unwrap_or("");
+    }
+  }
+  ""
+}
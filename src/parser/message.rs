@@ -2,34 +2,49 @@ extern crate alloc;
 use crate::body::Body;
 use crate::error::ParseError;
 use crate::headers::{HeaderName, Headers};
-use crate::parser::chunked::ChunkedDecoder;
+use crate::method::Method;
+use crate::parser::cache_control::CacheControl;
+use crate::parser::chunked::{ChunkedDecoder, DEFAULT_MAX_CHUNK_LINE_LENGTH};
+use crate::parser::content_coding::ContentDecoder;
+use crate::parser::event_stream::EventStream;
 use crate::parser::headers::HeaderField;
 use crate::parser::http::StatusLine;
+use crate::parser::link::{LinkRef, parse_link_header};
+use crate::parser::uri::Uri;
 use crate::parser::version::Version;
+use crate::util::IpAddr;
 use alloc::string::String;
 use alloc::vec::Vec;
 
-#[cfg(feature = "gzip-decompression")]
-use miniz_oxide::inflate::{decompress_to_vec, decompress_to_vec_zlib};
-
-#[cfg(feature = "zstd-decompression")]
-use ruzstd::decoding::StreamingDecoder;
-
+/// An HTTP response: status, header fields, decoded body and trailers
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Response {
+  /// Status code from the status line
   pub status_code: u16,
+  /// Reason phrase from the status line, possibly empty
   pub reason: String,
+  /// Header fields in the order received
   pub headers: Headers,
+  /// Message body with transfer and content codings removed
   pub body: Body,
   /// Trailer fields from chunked responses (RFC 9112 Section 7.1.2)
   /// Stored separately as they appear after the body in chunked encoding
   pub trailers: Vec<(String, String)>,
+  /// Address of the server the response was received from, when known
+  pub remote_addr: Option<IpAddr>,
+  /// URL the response was received from, after following redirects, when known
+  pub url: Option<String>,
+  /// The body was cut short by `call_prefix` and is only a prefix of what the server sent
+  pub truncated: bool,
 }
 
 impl Response {
   /// Parse HTTP/1.1 response with RFC 9112 robustness features.
   /// Per Section 2.2: clients MAY skip leading empty lines before status-line.
   /// Per Section 5.2: clients MUST handle obsolete line folding (obs-fold).
+  ///
+  /// # Errors
+  /// Returns a `ParseError` if `input` is not a complete, well-formed response.
   pub fn parse(input: &[u8]) -> Result<Self, ParseError> {
     // RFC 9112 Section 2.2: Skip leading CRLF (robustness)
     let mut data = input;
@@ -70,6 +85,7 @@ impl Response {
       Some(status_line.version),
       status_line.status.code(),
       None,
+      Some(DEFAULT_MAX_CHUNK_LINE_LENGTH),
     )?;
 
     let trailers = trailer_bytes
@@ -82,7 +98,7 @@ impl Response {
       })
       .collect();
 
-    let body = Self::decompress_body_if_needed(&Headers::from_vec(headers.clone()), body_bytes)?;
+    let body = Self::decompress_body_if_needed(&Headers::from_vec(headers.clone()), body_bytes, None)?;
 
     Ok(Self {
       status_code: status_line.status.code(),
@@ -90,61 +106,57 @@ impl Response {
       headers: Headers::from_vec(headers),
       body: Body::from_bytes(body),
       trailers,
+      remote_addr: None,
+      truncated: false,
+      url: None,
     })
   }
 
-  #[allow(clippy::unnecessary_wraps, unused_variables)]
+  /// Undo the content codings listed in `Content-Encoding`
+  ///
+  /// Runs the whole body through the `ContentDecoder` that streamed bodies use.
+  /// RFC 9110 Section 8.4: codings are listed in the order they were applied,
+  /// so they are decoded in reverse. Multiple `Content-Encoding` field lines are
+  /// combined in order. An unsupported coding is an error rather than silently
+  /// returning still-encoded bytes, except in builds without any decoder: those
+  /// advertise no coding in `Accept-Encoding`, so a coded body is returned as
+  /// received, with `Content-Encoding` telling the caller how to decode it.
+  ///
+  /// With `max_size` set, decoding stops with `ParseError::DecompressionTooLarge`
+  /// as soon as any coding produces more than `max_size` bytes.
   fn decompress_body_if_needed(
     headers: &Headers,
     body_bytes: Vec<u8>,
+    max_size: Option<usize>,
   ) -> Result<Vec<u8>, ParseError> {
-    if let Some(encoding) = headers.get("content-encoding") {
-      let encoding_lower = encoding.to_lowercase();
-
-      // Try gzip/deflate decompression
-      #[cfg(feature = "gzip-decompression")]
-      if encoding_lower.contains("gzip") {
-        // Gzip format: strip 10-byte header and 8-byte footer, decompress the middle
-        if body_bytes.len() < 18 {
-          return Err(ParseError::DecompressionFailed);
-        }
-        // Skip gzip header (10 bytes minimum) and footer (8 bytes)
-        // The actual compressed data is in between
-        let end_pos = body_bytes.len().saturating_sub(8);
-        let deflate_data = body_bytes
-          .get(10..end_pos)
-          .ok_or(ParseError::DecompressionFailed)?;
-        return decompress_to_vec(deflate_data).map_err(|_| ParseError::DecompressionFailed);
-      }
-
-      #[cfg(feature = "gzip-decompression")]
-      if encoding_lower.contains("deflate") {
-        return decompress_to_vec_zlib(&body_bytes).map_err(|_| ParseError::DecompressionFailed);
-      }
+    if body_bytes.is_empty() || cfg!(not(any(feature = "gzip-decompression", feature = "zstd-decompression"))) {
+      return Ok(body_bytes);
+    }
 
-      // Try zstd decompression
-      #[cfg(feature = "zstd-decompression")]
-      if encoding_lower.contains("zstd") {
-        use ruzstd::io_nostd::Read;
-        let mut decoder = StreamingDecoder::new(&body_bytes[..]).map_err(|_| ParseError::DecompressionFailed)?;
-        let mut decompressed = Vec::new();
-        decoder
-          .read_to_end(&mut decompressed)
-          .map_err(|_| ParseError::DecompressionFailed)?;
-        return Ok(decompressed);
-      }
+    let mut decoder = ContentDecoder::for_headers(headers, max_size)?;
+    if decoder.is_identity() {
+      return Ok(body_bytes);
     }
-    Ok(body_bytes)
+    let mut body = decoder.decode(&body_bytes)?;
+    body.extend(decoder.finish()?);
+    Ok(body)
   }
 
   #[cfg(test)]
-  pub fn parse_body(
+  pub(crate) fn parse_body(
     input: &[u8],
     headers: &[(Vec<u8>, Vec<u8>)],
     status_code: u16,
     method: Option<&str>,
   ) -> Result<Vec<u8>, ParseError> {
-    let (body, _trailers) = Self::parse_body_internal(input, headers, None, status_code, method)?;
+    let (body, _trailers) = Self::parse_body_internal(
+      input,
+      headers,
+      None,
+      status_code,
+      method,
+      Some(DEFAULT_MAX_CHUNK_LINE_LENGTH),
+    )?;
     Ok(body)
   }
 
@@ -154,6 +166,7 @@ impl Response {
     version: Option<Version>,
     status_code: u16,
     method: Option<&str>,
+    max_chunk_line_length: Option<usize>,
   ) -> Result<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>), ParseError> {
     // Check if Transfer-Encoding is present
     let has_transfer_encoding = headers
@@ -196,10 +209,12 @@ impl Response {
       .iter()
       .any(|(name, _)| name.eq_ignore_ascii_case(HeaderName::CONTENT_LENGTH.as_bytes()));
 
-    let content_length = headers
-      .iter()
-      .find(|(name, _)| name.eq_ignore_ascii_case(HeaderName::CONTENT_LENGTH.as_bytes()))
-      .and_then(|(_, value)| parse_content_length(value));
+    let content_length = content_length_of(
+      headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case(HeaderName::CONTENT_LENGTH.as_bytes()))
+        .map(|(_, value)| value.as_slice()),
+    )?;
 
     // RFC 9112 Section 6.3: If both Transfer-Encoding and Content-Length are present,
     // this is a potential request smuggling attack. Client MUST close connection
@@ -230,7 +245,7 @@ impl Response {
         }
 
         if is_chunked_final {
-          let mut decoder = ChunkedDecoder::new();
+          let mut decoder = ChunkedDecoder::new().with_max_line_length(max_chunk_line_length);
           let mut output = Vec::new();
           // RFC 9112 Section 8: Handle incomplete chunked message
           // If decoding fails, the message is incomplete
@@ -272,9 +287,18 @@ impl Response {
       return Ok((body_data.to_vec(), Vec::new()));
     }
 
-    Ok((Vec::new(), Vec::new()))
+    // A Content-Length that could not be used leaves the body empty
+    if has_content_length {
+      return Ok((Vec::new(), Vec::new()));
+    }
+
+    // RFC 9112 Section 6.3 rule 8: without framing, the body runs until the
+    // connection closes, which here is the end of the input
+    Ok((input.to_vec(), Vec::new()))
   }
 
+  /// First value of a header field (case-insensitive name)
+  #[must_use]
   pub fn get_header(
     &self,
     name: &str,
@@ -283,8 +307,42 @@ impl Response {
   }
 
   /// Parse response headers only (for two-phase reading)
-  /// Returns (`status_code`, reason, headers, `remaining_bytes_after_headers`)
-  pub fn parse_headers_only(input: &[u8]) -> Result<(u16, String, Headers, &[u8]), ParseError> {
+  /// Returns (`version`, `status_code`, reason, headers, `remaining_bytes_after_headers`)
+  ///
+  /// # Errors
+  /// Returns a `ParseError` if the status line or header section is malformed or incomplete.
+  pub fn parse_headers_only(input: &[u8]) -> Result<(Version, u16, String, Headers, &[u8]), ParseError> {
+    Self::parse_headers_only_with(input, false, true)
+  }
+
+  /// Status code from the status line at the start of `input`, if it parses
+  ///
+  /// Used for diagnostics when the rest of the header section is unusable.
+  pub(crate) fn parse_status_code_with(
+    input: &[u8],
+    lenient: bool,
+  ) -> Option<u16> {
+    let start = input
+      .iter()
+      .position(|byte| !matches!(byte, b'\r' | b'\n'))
+      .unwrap_or(input.len());
+    let data = input.get(start..)?;
+    StatusLine::parse_with(data, lenient)
+      .ok()
+      .map(|(status_line, _)| status_line.status.code())
+  }
+
+  /// Parse response headers only, with lenient status-line parsing when `lenient` is true
+  ///
+  /// Lenient parsing also consumes whitespace-preceded lines between the status
+  /// line and the first header field, which strict parsing rejects.
+  /// Folded header values are rejected with `ParseError::ObsoleteFoldInHeader`
+  /// unless `allow_obs_fold` is true.
+  pub(crate) fn parse_headers_only_with(
+    input: &[u8],
+    lenient: bool,
+    allow_obs_fold: bool,
+  ) -> Result<(Version, u16, String, Headers, &[u8]), ParseError> {
     // Skip leading CRLF (RFC 9112 Section 2.2 robustness)
     let mut data = input;
     loop {
@@ -306,10 +364,24 @@ impl Response {
       break;
     }
 
-    let (status_line, after_status) = StatusLine::parse(data)?;
+    let (status_line, after_status_line) = StatusLine::parse_with(data, lenient)?;
+    let after_status = if lenient {
+      skip_whitespace_lines(after_status_line)
+    } else {
+      after_status_line
+    };
 
     // RFC 9112 Section 5.2: Use obs-fold aware parsing for responses
-    let (headers_bytes, remaining) = HeaderField::parse(after_status)?;
+    let (headers_bytes, remaining) = HeaderField::parse_with(after_status, allow_obs_fold)?;
+
+    // RFC 9112 Section 6.3: disagreeing Content-Length fields make the framing
+    // ambiguous, so reject them before any body is read
+    content_length_of(
+      headers_bytes
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case(HeaderName::CONTENT_LENGTH.as_bytes()))
+        .map(|(_, value)| value.as_slice()),
+    )?;
 
     let mut headers = Vec::new();
     for (name_bytes, value_bytes) in &headers_bytes {
@@ -319,6 +391,7 @@ impl Response {
     }
 
     Ok((
+      status_line.version,
       status_line.status.code(),
       String::from_utf8_lossy(status_line.reason).into_owned(),
       Headers::from_vec(headers),
@@ -328,9 +401,15 @@ impl Response {
 
   /// Determine how many bytes to read for the response body
   /// Returns None for no body, Some(n) for Content-Length: n, or special handling for chunked
+  ///
+  /// Without Content-Length or Transfer-Encoding, an HTTP/1.0 response (or an
+  /// HTTP/1.1 response with `Connection: close`) is delimited by the server
+  /// closing the connection (RFC 9112 Section 6.3).
+  #[must_use]
   pub fn body_read_strategy(
     headers: &Headers,
     status_code: u16,
+    version: Version,
   ) -> BodyReadStrategy {
     // No body for certain status codes
     if (100..200).contains(&status_code) || status_code == 204 || status_code == 304 {
@@ -367,15 +446,30 @@ impl Response {
       return BodyReadStrategy::ContentLength(len);
     }
 
-    // No Content-Length or Transfer-Encoding: no body for responses
+    // RFC 9112 Section 6.3: No framing headers means the body runs until close.
+    // Only assume that when the connection is known not to persist.
+    let closes_connection = version == Version::HTTP_10
+      || headers
+        .get(HeaderName::CONNECTION)
+        .is_some_and(|value| value.eq_ignore_ascii_case("close"));
+    if closes_connection {
+      return BodyReadStrategy::UntilClose;
+    }
+
+    // No Content-Length or Transfer-Encoding on a persistent connection: no body
     BodyReadStrategy::NoBody
   }
 
   /// Parse body from remaining bytes after headers (for two-phase reading)
-  pub fn parse_body_from_bytes(
+  ///
+  /// When `decompress` is false the body is returned with its content codings intact.
+  /// `max_decompressed_size` bounds the decoded size (see `Config::max_decompressed_size`).
+  pub(crate) fn parse_body_from_bytes(
     body_bytes: &[u8],
     headers: &Headers,
     status_code: u16,
+    decompress: bool,
+    max_decompressed_size: Option<usize>,
   ) -> Result<Body, ParseError> {
     if (100..200).contains(&status_code) || status_code == 204 || status_code == 304 {
       return Ok(Body::from_bytes(Vec::new()));
@@ -386,33 +480,229 @@ impl Response {
       .map(|(k, v)| (k.as_bytes().to_vec(), v.as_bytes().to_vec()))
       .collect();
 
-    let (body_vec, _trailers) = Self::parse_body_internal(body_bytes, &headers_bytes, None, status_code, None)?;
+    let has_framing = headers.contains(HeaderName::CONTENT_LENGTH) || headers.contains(HeaderName::TRANSFER_ENCODING);
 
-    // Decompress if needed
-    let decompressed_body = Self::decompress_body_if_needed(headers, body_vec)?;
+    // Unframed bodies were already delimited by the reader (connection close).
+    // The reader also applied the configured chunk-line limit, so none is needed here
+    let body_vec = if has_framing {
+      Self::parse_body_internal(body_bytes, &headers_bytes, None, status_code, None, None)?.0
+    } else {
+      body_bytes.to_vec()
+    };
+
+    if !decompress {
+      return Ok(Body::from_bytes(body_vec));
+    }
+
+    let decompressed_body = Self::decompress_body_if_needed(headers, body_vec, max_decompressed_size)?;
     Ok(Body::from_bytes(decompressed_body))
   }
 
+  /// Header fields
   #[must_use]
   pub const fn headers(&self) -> &Headers {
     &self.headers
   }
 
+  /// Mutable access to the header fields
   #[must_use]
   pub const fn headers_mut(&mut self) -> &mut Headers {
     &mut self.headers
   }
 
+  /// Message body
   #[must_use]
   pub const fn body(&self) -> &Body {
     &self.body
   }
 
+  /// Mutable access to the message body
   #[must_use]
   pub const fn body_mut(&mut self) -> &mut Body {
     &mut self.body
   }
 
+  /// Body size advertised by the `Content-Length` header
+  ///
+  /// Works for responses without a body too: a HEAD response carries the size
+  /// the body would have had. Returns None when the header is missing or
+  /// invalid, or when `Transfer-Encoding` overrides it.
+  #[must_use]
+  pub fn content_length(&self) -> Option<usize> {
+    if self.headers.contains(HeaderName::TRANSFER_ENCODING) {
+      return None;
+    }
+    self
+      .headers
+      .get(HeaderName::CONTENT_LENGTH)
+      .and_then(|value| parse_content_length(value.as_bytes()))
+  }
+
+  /// Methods listed in the `Allow` header, e.g. of an OPTIONS or 405 response
+  ///
+  /// All `Allow` header lines are combined in order. Method names are
+  /// case-sensitive; tokens that are not a known `Method` are skipped.
+  #[must_use]
+  pub fn allowed_methods(&self) -> Vec<Method> {
+    self
+      .headers
+      .get_all(HeaderName::ALLOW)
+      .into_iter()
+      .flat_map(|value| value.split(','))
+      .filter_map(|token| token.trim().parse().ok())
+      .collect()
+  }
+
+  /// Parsed `Cache-Control` directives, or None if the response has none
+  #[must_use]
+  pub fn cache_control(&self) -> Option<CacheControl> {
+    CacheControl::from_headers(&self.headers)
+  }
+
+  /// Links from every `Link` header, in order
+  ///
+  /// Relative link targets are resolved against `url` when it is known;
+  /// targets that cannot be resolved are returned as sent.
+  #[must_use]
+  pub fn links(&self) -> Vec<LinkRef> {
+    let base = self.url.as_deref().and_then(|url| Uri::parse(url).ok());
+    let mut links: Vec<LinkRef> = self
+      .headers
+      .get_all(HeaderName::LINK)
+      .into_iter()
+      .flat_map(parse_link_header)
+      .collect();
+    if let Some(base_uri) = base {
+      for link in &mut links {
+        if let Ok(resolved) = base_uri.resolve_relative(&link.uri) {
+          link.uri = resolved;
+        }
+      }
+    }
+    links
+  }
+
+  /// First link whose `rel` includes `relation`, e.g. `"next"` for pagination
+  #[must_use]
+  pub fn link_rel(
+    &self,
+    relation: &str,
+  ) -> Option<LinkRef> {
+    self.links().into_iter().find(|link| link.has_rel(relation))
+  }
+
+  /// Absolute URL from the `Location` header, or None if the response has none
+  ///
+  /// A relative reference is resolved against `url`, the same way redirects
+  /// are followed, so the target is available even with `RedirectPolicy::NoFollow`.
+  ///
+  /// # Errors
+  /// The inner result is `ParseError::InvalidUri` if the value cannot be
+  /// resolved, including a relative reference when `url` is unknown.
+  #[must_use]
+  pub fn location(&self) -> Option<Result<String, ParseError>> {
+    let location = self.headers.get(HeaderName::LOCATION)?.trim();
+    Some(self.url.as_deref().map_or_else(
+      || Uri::parse(location).map(|_| String::from(location)),
+      |url| Uri::parse(url).and_then(|base| base.resolve_relative(location)),
+    ))
+  }
+
+  /// Events of a `text/event-stream` body
+  ///
+  /// The body has already been read in full; events are parsed from it in order.
+  /// To receive events as they arrive, send the request with `call_to_writer`
+  /// and an `EventSink` instead.
+  #[must_use]
+  pub fn event_stream(&self) -> EventStream {
+    EventStream::from_bytes(self.body.as_bytes())
+  }
+
+  /// Replace stored header fields with those of a 304 response
+  ///
+  /// RFC 9111 Section 4.3.4: each field in the 304 replaces every stored
+  /// field with that name; other stored fields are kept.
+  pub(crate) fn update_from_not_modified(
+    &mut self,
+    not_modified: &Self,
+  ) {
+    for (name, _) in &not_modified.headers {
+      self.headers.remove(name);
+    }
+    for (name, value) in &not_modified.headers {
+      self.headers.insert(name.as_str(), value.as_str());
+    }
+  }
+
+  /// Check whether `body` is only a prefix of the body the server sent
+  #[must_use]
+  pub const fn is_truncated(&self) -> bool {
+    self.truncated
+  }
+
+  /// IP address of the server that sent this response, if the socket adapter reports it
+  #[must_use]
+  pub const fn remote_addr(&self) -> Option<IpAddr> {
+    self.remote_addr
+  }
+
+  /// URL the response was received from, after following redirects, if known
+  #[must_use]
+  pub fn url(&self) -> Option<&str> {
+    self.url.as_deref()
+  }
+
+  /// Set the URL the response was received from, e.g. on one built with `from_parts`
+  #[must_use]
+  pub fn with_url(
+    mut self,
+    url: impl Into<String>,
+  ) -> Self {
+    self.url = Some(url.into());
+    self
+  }
+
+  /// Set the address of the server the response was received from
+  #[must_use]
+  pub const fn with_remote_addr(
+    mut self,
+    addr: IpAddr,
+  ) -> Self {
+    self.remote_addr = Some(addr);
+    self
+  }
+
+  /// Decompose the response into status code, reason, headers, body and trailers
+  #[must_use]
+  pub fn into_parts(self) -> (u16, String, Headers, Body, Vec<(String, String)>) {
+    (self.status_code, self.reason, self.headers, self.body, self.trailers)
+  }
+
+  /// Rebuild a response from the parts returned by `into_parts`
+  ///
+  /// The remote address and URL are not part of the tuple and are left unset;
+  /// restore them with `with_remote_addr` and `with_url`. The body is taken
+  /// to be complete.
+  #[must_use]
+  pub const fn from_parts(
+    status_code: u16,
+    reason: String,
+    headers: Headers,
+    body: Body,
+    trailers: Vec<(String, String)>,
+  ) -> Self {
+    Self {
+      status_code,
+      reason,
+      headers,
+      body,
+      trailers,
+      remote_addr: None,
+      truncated: false,
+      url: None,
+    }
+  }
+
   /// Check if the server sent Connection: close
   ///
   /// Per RFC 9112 Section 9.6: If server sends "close", client MUST:
@@ -440,6 +730,38 @@ pub enum BodyReadStrategy {
   UntilClose,
 }
 
+/// Skip whitespace-preceded lines between the start-line and the first header field
+///
+/// RFC 9112 Section 2.2: a recipient must either reject such lines or consume
+/// each of them, up to the first properly formed field line, without processing it.
+fn skip_whitespace_lines(input: &[u8]) -> &[u8] {
+  let mut rest = input;
+  while matches!(rest.first(), Some(b' ' | b'\t')) {
+    let line_end = rest
+      .iter()
+      .position(|&byte| byte == b'\n')
+      .map_or(rest.len(), |newline| newline + 1);
+    rest = rest.get(line_end..).unwrap_or_default();
+  }
+  rest
+}
+
+/// Content-Length shared by every `Content-Length` field line
+///
+/// RFC 9112 Section 6.3: separate field lines are only acceptable when they
+/// all carry the same value; otherwise `ParseError::MultipleContentLength`.
+fn content_length_of<'a>(values: impl Iterator<Item = &'a [u8]>) -> Result<Option<usize>, ParseError> {
+  let mut agreed = None;
+  for (index, value) in values.enumerate() {
+    let parsed = parse_content_length(value);
+    if index > 0 && parsed != agreed {
+      return Err(ParseError::MultipleContentLength);
+    }
+    agreed = parsed;
+  }
+  Ok(agreed)
+}
+
 fn parse_content_length(value: &[u8]) -> Option<usize> {
   let s = core::str::from_utf8(value).ok()?;
   let trimmed = s.trim();
@@ -467,15 +789,24 @@ fn parse_content_length(value: &[u8]) -> Option<usize> {
   trimmed.parse().ok()
 }
 
+/// Serializer for HTTP/1.1 request messages
+///
+/// `build` checks the message against the RFC 9112 client requirements
+/// (a single valid `Host`, no bare CR, obs-fold or other control characters,
+/// consistent `TE` and `Transfer-Encoding`) before producing the bytes to send.
 #[derive(Debug, Clone)]
 pub struct RequestBuilder {
   method: String,
   path: String,
   headers: Headers,
   body: Option<Body>,
+  auto_content_length: bool,
+  strict_ascii_values: bool,
 }
 
 impl RequestBuilder {
+  /// Start a request with the given method and request target
+  #[must_use]
   pub fn new(
     method: &str,
     path: &str,
@@ -485,9 +816,13 @@ impl RequestBuilder {
       path: String::from(path),
       headers: Headers::new(),
       body: None,
+      auto_content_length: true,
+      strict_ascii_values: false,
     }
   }
 
+  /// Append a header field
+  #[must_use]
   pub fn header(
     mut self,
     name: &str,
@@ -497,6 +832,14 @@ impl RequestBuilder {
     self
   }
 
+  /// Header fields added so far
+  #[must_use]
+  pub const fn headers_mut(&mut self) -> &mut Headers {
+    &mut self.headers
+  }
+
+  /// Set the message body
+  #[must_use]
   pub fn body(
     mut self,
     body: Vec<u8>,
@@ -505,7 +848,59 @@ impl RequestBuilder {
     self
   }
 
-  pub fn build(self) -> Result<Vec<u8>, ParseError> {
+  /// Do not add `Content-Length` for the body; the caller frames it
+  ///
+  /// The body must then be framed by a `Content-Length` or `Transfer-Encoding`
+  /// header added by the caller: an HTTP/1.1 request body cannot be delimited
+  /// by closing the connection (RFC 9112 Section 6.3).
+  #[must_use]
+  pub const fn without_content_length(mut self) -> Self {
+    self.auto_content_length = false;
+    self
+  }
+
+  /// Reject bytes above 0x7F (obs-text) in header values
+  ///
+  /// RFC 9110 Section 5.5 keeps obs-text only for compatibility, and servers
+  /// disagree on how to decode it, so strict senders may refuse it. It is sent
+  /// as given by default. Control characters are rejected either way.
+  #[must_use]
+  pub const fn strict_ascii_values(mut self) -> Self {
+    self.strict_ascii_values = true;
+    self
+  }
+
+  /// Serialize the request line, header section and body
+  ///
+  /// Header fields are written exactly in the order they were added,
+  /// duplicates included; only a missing `Content-Length` is appended last,
+  /// unless the body is framed with `Transfer-Encoding` or
+  /// `without_content_length` was called.
+  ///
+  /// # Errors
+  /// Returns a `ParseError` if the request violates an RFC 9112 requirement,
+  /// e.g. `MissingHostHeader` or `InvalidHeaderValue`.
+  pub fn build(mut self) -> Result<Vec<u8>, ParseError> {
+    let body = self.body.take();
+    let mut request = self.build_head(body.as_ref().map(Body::as_bytes))?;
+    if let Some(content) = body {
+      request.extend_from_slice(content.as_bytes());
+    }
+    Ok(request)
+  }
+
+  /// Serialize the request line and header section for `body`, which the
+  /// caller then sends straight from its own buffer
+  ///
+  /// Checks and frames the request as `build` would with `body` set; a body
+  /// given with `body` is ignored.
+  ///
+  /// # Errors
+  /// Returns a `ParseError` under the same conditions as `build`.
+  pub fn build_head(
+    self,
+    body: Option<&[u8]>,
+  ) -> Result<Vec<u8>, ParseError> {
     // RFC 9112 Section 3.2: Client MUST send Host in every HTTP/1.1 request
     if !self.headers.contains(HeaderName::HOST) {
       return Err(ParseError::MissingHostHeader);
@@ -536,6 +931,21 @@ impl RequestBuilder {
         return Err(ParseError::ObsoleteFoldInHeader);
       }
 
+      // RFC 9110 Section 5.5: CR and LF are not allowed in field values;
+      // sending them would let a value inject extra header lines
+      if value.contains(['\r', '\n']) {
+        return Err(ParseError::InvalidHeaderValue);
+      }
+
+      // RFC 9110 Section 5.5: field values are visible characters, spaces and
+      // tabs; other control characters such as NUL and DEL are not allowed
+      if value
+        .bytes()
+        .any(|b| (b.is_ascii_control() && b != b'\t') || (!b.is_ascii() && self.strict_ascii_values))
+      {
+        return Err(ParseError::InvalidHeaderValue);
+      }
+
       // RFC 9112 Section 7.4: Client MUST NOT send "chunked" in TE
       if name.eq_ignore_ascii_case(HeaderName::TE) && value.to_lowercase().contains("chunked") {
         return Err(ParseError::ChunkedInTeHeader);
@@ -570,6 +980,19 @@ impl RequestBuilder {
     if has_te && has_cl {
       return Err(ParseError::ConflictingFraming);
     }
+    if body.is_some() && !self.auto_content_length && !has_te && !has_cl {
+      return Err(ParseError::MissingBodyFraming);
+    }
+
+    // RFC 9112 Section 3.2.4: asterisk-form is only used for a server-wide OPTIONS
+    if self.path == "*" && self.method != "OPTIONS" {
+      return Err(ParseError::InvalidRequestTarget);
+    }
+
+    // RFC 9112 Section 3.2.3: CONNECT uses authority-form (host:port), never a path
+    if self.method == "CONNECT" && (self.path.starts_with('/') || !self.path.contains(':')) {
+      return Err(ParseError::InvalidRequestTarget);
+    }
 
     let mut request = Vec::new();
 
@@ -592,21 +1015,19 @@ impl RequestBuilder {
       request.extend_from_slice(b"\r\n");
     }
 
-    if let Some(body) = &self.body
-      && !self.headers.contains(HeaderName::CONTENT_LENGTH)
+    if let Some(content) = body
+      && self.auto_content_length
+      && !has_te
+      && !has_cl
     {
       use alloc::string::ToString;
       request.extend_from_slice(b"Content-Length: ");
-      request.extend_from_slice(body.len().to_string().as_bytes());
+      request.extend_from_slice(content.len().to_string().as_bytes());
       request.extend_from_slice(b"\r\n");
     }
 
     request.extend_from_slice(b"\r\n");
 
-    if let Some(body) = &self.body {
-      request.extend_from_slice(body.as_bytes());
-    }
-
     Ok(request)
   }
 
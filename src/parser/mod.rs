@@ -1,9 +1,12 @@
-mod chunked;
-#[cfg(feature = "cookie-jar")]
+pub mod cache_control;
+pub mod chunked;
+pub mod content_coding;
 pub mod cookie;
+pub mod event_stream;
 pub mod framing;
 mod headers;
 mod http;
+pub mod link;
 mod message;
 pub mod response_reader;
 pub mod status;
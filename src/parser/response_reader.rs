@@ -79,9 +79,9 @@ impl ResponseReader {
       return Err(ParseError::InvalidState);
     }
 
-    let (status_code, reason, headers, remaining) = Response::parse_headers_only(&self.buffer)?;
+    let (version, status_code, reason, headers, remaining) = Response::parse_headers_only(&self.buffer)?;
 
-    let strategy = Response::body_read_strategy(&headers, status_code);
+    let strategy = Response::body_read_strategy(&headers, status_code, version);
 
     // Replace buffer with only the body bytes (clear headers)
     self.buffer = remaining.to_vec();
@@ -138,7 +138,7 @@ impl ResponseReader {
       return Err(ParseError::InvalidState);
     }
 
-    Response::parse_body_from_bytes(&self.buffer, headers, status_code)
+    Response::parse_body_from_bytes(&self.buffer, headers, status_code, true, None)
   }
 }
 
@@ -115,6 +115,13 @@ impl StatusCode {
     )
   }
 
+  /// Statuses that may succeed when the same request is sent again later:
+  /// 408, 425, 429, 500, 502, 503 and 504
+  #[must_use]
+  pub const fn is_retryable(self) -> bool {
+    matches!(self.0, 408 | 425 | 429 | 500 | 502 | 503 | 504)
+  }
+
   #[must_use]
   pub const fn is_informational(self) -> bool {
     matches!(self.class(), StatusClass::Informational)
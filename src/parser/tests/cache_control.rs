@@ -0,0 +1,109 @@
+use crate::body::Body;
+use crate::headers::Headers;
+use crate::parser::Response;
+use crate::parser::cache_control::CacheControl;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[test]
+fn parses_flags_and_numeric_directives() {
+  let cc = CacheControl::parse("public, max-age=3600, s-maxage=60, must-revalidate, immutable");
+
+  assert!(cc.public);
+  assert!(cc.must_revalidate);
+  assert!(cc.immutable);
+  assert!(!cc.private);
+  assert!(!cc.no_store);
+  assert_eq!(cc.max_age, Some(3600));
+  assert_eq!(cc.s_maxage, Some(60));
+}
+
+#[test]
+fn directive_names_are_case_insensitive() {
+  let cc = CacheControl::parse("No-Store, NO-CACHE, Max-Age=5");
+
+  assert!(cc.no_store);
+  assert!(cc.no_cache);
+  assert_eq!(cc.max_age, Some(5));
+}
+
+#[test]
+fn quoted_arguments_are_accepted() {
+  let cc = CacheControl::parse("max-age=\"120\", private=\"set-cookie, x-token\", no-cache");
+
+  assert_eq!(cc.max_age, Some(120));
+  assert!(cc.private);
+  assert!(cc.no_cache);
+  assert!(cc.extensions.is_empty());
+}
+
+#[test]
+fn invalid_max_age_means_stale() {
+  assert_eq!(CacheControl::parse("max-age=abc").max_age, Some(0));
+  assert_eq!(CacheControl::parse("max-age").max_age, Some(0));
+  assert_eq!(CacheControl::parse("max-age=-1").max_age, Some(0));
+}
+
+#[test]
+fn first_occurrence_wins() {
+  let cc = CacheControl::parse("max-age=10, max-age=20");
+  assert_eq!(cc.max_age, Some(10));
+}
+
+#[test]
+fn huge_delta_seconds_are_capped() {
+  let cc = CacheControl::parse("max-age=99999999999999999999999");
+  assert_eq!(cc.max_age, Some(2_147_483_648));
+}
+
+#[test]
+fn request_directives() {
+  let cc = CacheControl::parse("max-stale, min-fresh=30, only-if-cached");
+  assert_eq!(cc.max_stale, Some(None));
+  assert_eq!(cc.min_fresh, Some(30));
+  assert!(cc.only_if_cached);
+
+  assert_eq!(CacheControl::parse("max-stale=15").max_stale, Some(Some(15)));
+}
+
+#[test]
+fn unknown_directives_are_kept_as_extensions() {
+  let cc = CacheControl::parse("community=\"UCI \\\"x\\\"\", Foo, , max-age=1");
+
+  assert_eq!(cc.max_age, Some(1));
+  assert_eq!(
+    cc.extensions,
+    Vec::from([
+      (String::from("community"), Some(String::from("UCI \"x\""))),
+      (String::from("foo"), None),
+    ])
+  );
+}
+
+#[test]
+fn empty_value_has_no_directives() {
+  assert_eq!(CacheControl::parse(""), CacheControl::default());
+}
+
+#[test]
+fn from_headers_combines_lines() {
+  let mut headers = Headers::new();
+  headers.insert("Cache-Control", "no-cache");
+  headers.insert("cache-control", "max-age=60, stale-while-revalidate=30");
+
+  let cc = CacheControl::from_headers(&headers).unwrap();
+  assert!(cc.no_cache);
+  assert_eq!(cc.max_age, Some(60));
+  assert_eq!(cc.stale_while_revalidate, Some(30));
+}
+
+#[test]
+fn response_cache_control_accessor() {
+  let mut headers = Headers::new();
+  headers.insert("Cache-Control", "no-store");
+  let response = Response::from_parts(200, String::from("OK"), headers, Body::empty(), Vec::new());
+  assert!(response.cache_control().unwrap().no_store);
+
+  let bare = Response::from_parts(200, String::from("OK"), Headers::new(), Body::empty(), Vec::new());
+  assert!(bare.cache_control().is_none());
+}
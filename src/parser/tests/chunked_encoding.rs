@@ -1,3 +1,4 @@
+use crate::error::ParseError;
 use crate::parser::*;
 extern crate alloc;
 use alloc::vec::Vec;
@@ -171,3 +172,133 @@ fn test_chunked_overflow_protection() {
   let result = Response::parse(input);
   assert!(result.is_err());
 }
+
+#[test]
+fn test_chunked_decode_available_byte_by_byte() {
+  let body = b"5;ext=1\r\nHello\r\n6\r\n World\r\n0\r\nX-Checksum: abc\r\n\r\n";
+  let mut decoder = crate::parser::chunked::ChunkedDecoder::new();
+  let mut output = Vec::new();
+  let mut pending = Vec::new();
+
+  for &byte in body {
+    pending.push(byte);
+    let consumed = decoder.decode_available(&pending, &mut output).unwrap();
+    pending.drain(..consumed);
+  }
+
+  assert!(decoder.is_complete());
+  assert!(pending.is_empty());
+  assert_eq!(output, b"Hello World");
+  assert_eq!(decoder.trailers().len(), 1);
+}
+
+#[test]
+fn test_chunked_decode_available_passes_partial_chunk_data() {
+  let mut decoder = crate::parser::chunked::ChunkedDecoder::new();
+  let mut output = Vec::new();
+
+  let first = decoder
+    .decode_available(b"A\r\n01234", &mut output)
+    .unwrap();
+  assert_eq!(first, 8);
+  assert_eq!(output, b"01234");
+  assert!(!decoder.is_complete());
+
+  let second = decoder
+    .decode_available(b"56789\r\n0\r\n\r\nextra", &mut output)
+    .unwrap();
+  assert_eq!(second, 12);
+  assert_eq!(output, b"0123456789");
+  assert!(decoder.is_complete());
+}
+
+#[test]
+fn test_chunked_decode_available_rejects_bad_size() {
+  let mut decoder = crate::parser::chunked::ChunkedDecoder::new();
+  let mut output = Vec::new();
+  assert!(decoder.decode_available(b"zz\r\n", &mut output).is_err());
+}
+
+#[test]
+fn test_oversized_chunk_extension_rejected() {
+  let mut input = Vec::from(&b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5;ext="[..]);
+  input.resize(input.len() + 1024 * 1024, b'a');
+  input.extend_from_slice(b"\r\nHello\r\n0\r\n\r\n");
+
+  assert_eq!(Response::parse(&input).unwrap_err(), ParseError::ChunkLineTooLong);
+}
+
+#[test]
+fn test_chunk_line_limit_applies_before_line_ends() {
+  let mut decoder = crate::parser::chunked::ChunkedDecoder::new().with_max_line_length(Some(8));
+  let mut output = Vec::new();
+
+  assert_eq!(decoder.decode_available(b"5;a=1234", &mut output), Ok(0));
+  assert_eq!(
+    decoder.decode_available(b"5;a=12345", &mut output),
+    Err(ParseError::ChunkLineTooLong)
+  );
+}
+
+#[test]
+fn test_chunk_line_limit_can_be_raised_or_lifted() {
+  let body = b"5;name=\"a long extension value\"\r\nHello\r\n0\r\n\r\n";
+  for limit in [Some(64), None] {
+    let mut decoder = crate::parser::chunked::ChunkedDecoder::new().with_max_line_length(limit);
+    let mut output = Vec::new();
+    decoder.decode_chunk(body, &mut output).unwrap();
+    assert_eq!(output, b"Hello");
+  }
+}
+
+#[test]
+fn test_chunked_total_size_limit_checks_declared_sizes() {
+  let mut decoder = crate::parser::chunked::ChunkedDecoder::new().with_max_body_size(Some(8));
+  let mut output = Vec::new();
+
+  assert_eq!(decoder.decode_available(b"5\r\nHello\r\n", &mut output), Ok(10));
+  // The second chunk would take the body to 13 bytes; its data never arrives
+  assert_eq!(
+    decoder.decode_available(b"8\r\n", &mut output),
+    Err(ParseError::BodyTooLarge)
+  );
+  assert_eq!(output, b"Hello");
+}
+
+#[test]
+fn test_chunked_chunk_count_limit() {
+  let body = b"1\r\na\r\n1\r\nb\r\n1\r\nc\r\n0\r\n\r\n";
+
+  let mut limited = crate::parser::chunked::ChunkedDecoder::new().with_max_chunk_count(Some(2));
+  assert_eq!(
+    limited.decode_chunk(body, &mut Vec::new()).unwrap_err(),
+    ParseError::TooManyChunks
+  );
+
+  let mut enough = crate::parser::chunked::ChunkedDecoder::new().with_max_chunk_count(Some(3));
+  let mut output = Vec::new();
+  enough.decode_chunk(body, &mut output).unwrap();
+  assert_eq!(output, b"abc");
+}
+
+fn decode(body: &[u8]) -> Result<Vec<u8>, ParseError> {
+  let mut decoder = crate::parser::chunked::ChunkedDecoder::new();
+  let mut output = Vec::new();
+  decoder.decode_chunk(body, &mut output)?;
+  Ok(output)
+}
+
+#[test]
+fn test_whitespace_around_chunk_size_rejected() {
+  assert_eq!(decode(b"5\r\nHello\r\n0\r\n\r\n").unwrap(), b"Hello");
+  for body in [
+    &b" 5 \r\nHello\r\n0\r\n\r\n"[..],
+    b" 5\r\nHello\r\n0\r\n\r\n",
+    b"5 \r\nHello\r\n0\r\n\r\n",
+    b"5\t\r\nHello\r\n0\r\n\r\n",
+    b"5 ;ext=1\r\nHello\r\n0\r\n\r\n",
+    b"5\r\nHello\r\n0 \r\n\r\n",
+  ] {
+    assert_eq!(decode(body), Err(ParseError::InvalidChunkSize), "{body:?}");
+  }
+}
@@ -0,0 +1,201 @@
+use crate::error::ParseError;
+use crate::headers::Headers;
+use crate::parser::content_coding::ContentDecoder;
+use alloc::vec::Vec;
+
+fn coded(encoding: &str) -> Headers {
+  let mut headers = Headers::new();
+  headers.insert("Content-Encoding", encoding);
+  headers
+}
+
+/// Feed `body` to `decoder` `piece` bytes at a time and finish it
+fn decode_in_pieces(
+  decoder: &mut ContentDecoder,
+  body: &[u8],
+  piece: usize,
+) -> Result<Vec<u8>, ParseError> {
+  let mut decoded = Vec::new();
+  for part in body.chunks(piece) {
+    decoded.extend(decoder.decode(part)?);
+  }
+  decoded.extend(decoder.finish()?);
+  Ok(decoded)
+}
+
+#[cfg(feature = "gzip-decompression")]
+fn gzip_wrap(data: &[u8]) -> Vec<u8> {
+  let mut out = alloc::vec![0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0xff];
+  out.extend_from_slice(&miniz_oxide::deflate::compress_to_vec(data, 6));
+  out.extend_from_slice(&crate::parser::content_coding::crc32(0, data).to_le_bytes());
+  out.extend_from_slice(&u32::try_from(data.len()).unwrap().to_le_bytes());
+  out
+}
+
+#[test]
+fn identity_passes_bytes_through() {
+  let mut decoder = ContentDecoder::for_headers(&coded("identity"), None).unwrap();
+  assert!(decoder.is_identity());
+  assert_eq!(decode_in_pieces(&mut decoder, b"plain", 2).unwrap(), b"plain");
+}
+
+#[test]
+fn unsupported_coding_is_rejected() {
+  assert!(matches!(
+    ContentDecoder::for_headers(&coded("br"), None),
+    Err(ParseError::UnsupportedContentEncoding)
+  ));
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn empty_body_needs_no_coded_data() {
+  let mut decoder = ContentDecoder::for_headers(&coded("gzip"), None).unwrap();
+  assert!(decoder.finish().unwrap().is_empty());
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn gzip_decodes_one_byte_at_a_time() {
+  let original = b"streamed through the decoder one byte at a time".repeat(20);
+  let mut decoder = ContentDecoder::for_headers(&coded("gzip"), None).unwrap();
+  assert_eq!(
+    decode_in_pieces(&mut decoder, &gzip_wrap(&original), 1).unwrap(),
+    original
+  );
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn layered_codings_decode_in_reverse() {
+  let original = b"layered content codings";
+  let encoded = gzip_wrap(&miniz_oxide::deflate::compress_to_vec_zlib(original, 6));
+  let mut decoder = ContentDecoder::for_headers(&coded("deflate, gzip"), None).unwrap();
+  assert_eq!(decode_in_pieces(&mut decoder, &encoded, 7).unwrap(), original);
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn truncated_gzip_fails_on_finish() {
+  let encoded = gzip_wrap(&b"cut short before the end".repeat(10));
+  let mut decoder = ContentDecoder::for_headers(&coded("gzip"), None).unwrap();
+  let cut = encoded.get(..encoded.len() - 20).unwrap();
+  assert!(matches!(
+    decode_in_pieces(&mut decoder, cut, 16),
+    Err(ParseError::DecompressionFailed)
+  ));
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn crc32_matches_check_value() {
+  assert_eq!(crate::parser::content_coding::crc32(0, b"123456789"), 0xcbf4_3926);
+  let first = crate::parser::content_coding::crc32(0, b"1234");
+  assert_eq!(crate::parser::content_coding::crc32(first, b"56789"), 0xcbf4_3926);
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn gzip_header_optional_fields_are_skipped() {
+  use crate::parser::content_coding::crc32;
+
+  let original = b"member with every optional header field";
+  // FTEXT | FHCRC | FEXTRA | FNAME | FCOMMENT
+  let mut encoded = alloc::vec![0x1f, 0x8b, 0x08, 0x1f, 0, 0, 0, 0, 0, 0xff];
+  encoded.extend_from_slice(&[3, 0, b'a', b'b', b'c']);
+  encoded.extend_from_slice(b"name.txt\0a comment\0");
+  let header_crc = crc32(0, &encoded).to_le_bytes();
+  encoded.extend_from_slice(&header_crc[..2]);
+  encoded.extend_from_slice(&gzip_wrap(original)[10..]);
+
+  for piece in [1, 3, encoded.len()] {
+    let mut decoder = ContentDecoder::for_headers(&coded("gzip"), None).unwrap();
+    assert_eq!(
+      decode_in_pieces(&mut decoder, &encoded, piece).unwrap(),
+      original,
+      "{piece}"
+    );
+  }
+
+  let mut corrupted = encoded.clone();
+  corrupted[16] ^= 1;
+  let mut decoder = ContentDecoder::for_headers(&coded("gzip"), None).unwrap();
+  assert!(matches!(
+    decode_in_pieces(&mut decoder, &corrupted, 4),
+    Err(ParseError::DecompressionFailed)
+  ));
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn gzip_members_decode_one_after_another() {
+  let mut encoded = gzip_wrap(b"first member, ");
+  encoded.extend(gzip_wrap(b"second member"));
+  for piece in [1, 9, encoded.len()] {
+    let mut decoder = ContentDecoder::for_headers(&coded("gzip"), None).unwrap();
+    assert_eq!(
+      decode_in_pieces(&mut decoder, &encoded, piece).unwrap(),
+      b"first member, second member",
+      "{piece}"
+    );
+  }
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn gzip_trailer_must_match_decoded_data() {
+  let encoded = gzip_wrap(b"checked against the trailer");
+  let trailer = encoded.len() - 8;
+  for byte in [trailer, trailer + 4] {
+    let mut corrupted = encoded.clone();
+    corrupted[byte] ^= 1;
+    let mut decoder = ContentDecoder::for_headers(&coded("gzip"), None).unwrap();
+    assert!(matches!(
+      decode_in_pieces(&mut decoder, &corrupted, 5),
+      Err(ParseError::DecompressionFailed)
+    ));
+  }
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn gzip_rejects_bad_magic_and_reserved_flags() {
+  let encoded = gzip_wrap(b"data");
+  for (byte, value) in [(0, 0x1e), (2, 0x07), (3, 0x20)] {
+    let mut corrupted = encoded.clone();
+    corrupted[byte] = value;
+    let mut decoder = ContentDecoder::for_headers(&coded("gzip"), None).unwrap();
+    assert!(matches!(
+      decoder.decode(&corrupted),
+      Err(ParseError::DecompressionFailed)
+    ));
+  }
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn gzip_bomb_stops_at_limit() {
+  let encoded = gzip_wrap(&alloc::vec![0u8; 1024 * 1024]);
+  let mut decoder = ContentDecoder::for_headers(&coded("gzip"), Some(64 * 1024)).unwrap();
+  assert!(matches!(
+    decode_in_pieces(&mut decoder, &encoded, 512),
+    Err(ParseError::DecompressionTooLarge)
+  ));
+}
+
+#[test]
+#[cfg(feature = "zstd-decompression")]
+fn zstd_decodes_in_pieces() {
+  use ruzstd::encoding::{CompressionLevel, compress_to_vec};
+
+  let original = b"zstd frames are decoded block by block".repeat(4000);
+  let encoded = compress_to_vec(&original[..], CompressionLevel::Fastest);
+  for piece in [5, 1000, encoded.len()] {
+    let mut decoder = ContentDecoder::for_headers(&coded("zstd"), None).unwrap();
+    assert_eq!(
+      decode_in_pieces(&mut decoder, &encoded, piece).unwrap(),
+      original,
+      "{piece}"
+    );
+  }
+}
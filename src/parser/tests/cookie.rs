@@ -1,4 +1,4 @@
-use crate::parser::cookie::{SetCookie, parse_cookie_date, serialize_cookie_header};
+use crate::parser::cookie::{SameSite, SetCookie, parse_cookie_date, serialize_cookie_header};
 use alloc::string::ToString;
 
 #[test]
@@ -449,3 +449,35 @@ fn parse_cookie_value_with_equals() {
   assert_eq!(cookie.name, "data");
   assert_eq!(cookie.value, "key=value");
 }
+
+#[test]
+fn parse_cookie_same_site_values() {
+  let strict = SetCookie::parse("id=1; SameSite=Strict").unwrap();
+  let lax = SetCookie::parse("id=1; samesite=lax").unwrap();
+  let none = SetCookie::parse("id=1; Secure; SameSite=NONE").unwrap();
+
+  assert_eq!(strict.same_site, Some(SameSite::Strict));
+  assert_eq!(lax.same_site, Some(SameSite::Lax));
+  assert_eq!(none.same_site, Some(SameSite::None));
+}
+
+#[test]
+fn parse_cookie_same_site_unknown_or_missing() {
+  assert_eq!(
+    SetCookie::parse("id=1; SameSite=Sometimes")
+      .unwrap()
+      .same_site,
+    None
+  );
+  assert_eq!(SetCookie::parse("id=1; SameSite").unwrap().same_site, None);
+  assert_eq!(SetCookie::parse("id=1").unwrap().same_site, None);
+}
+
+#[test]
+fn parse_cookie_partitioned() {
+  let cookie = SetCookie::parse("__Host-id=1; Secure; Path=/; SameSite=None; Partitioned").unwrap();
+  assert!(cookie.partitioned);
+  assert_eq!(cookie.same_site, Some(SameSite::None));
+
+  assert!(!SetCookie::parse("id=1; Secure").unwrap().partitioned);
+}
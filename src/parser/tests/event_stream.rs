@@ -0,0 +1,147 @@
+use crate::body::Body;
+use crate::headers::Headers;
+use crate::parser::Response;
+use crate::parser::event_stream::{EventSink, EventStream, ServerSentEvent};
+use crate::sink::ByteSink;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+fn event(
+  event: &str,
+  data: &str,
+  id: Option<&str>,
+) -> ServerSentEvent {
+  ServerSentEvent {
+    event: String::from(event),
+    data: String::from(data),
+    id: id.map(String::from),
+  }
+}
+
+#[test]
+fn multi_line_data_is_joined() {
+  let events: Vec<_> = EventStream::from_bytes(b"data: YHOO\ndata: +2\ndata: 10\n\n").collect();
+  assert_eq!(events, [event("message", "YHOO\n+2\n10", None)]);
+}
+
+#[test]
+fn comments_ids_and_event_types() {
+  let input =
+    b": test stream\n\ndata: first event\nid: 1\n\nevent: update\ndata:second event\nid\n\n data: ignored\n\n";
+  let events: Vec<_> = EventStream::from_bytes(input).collect();
+  assert_eq!(
+    events,
+    [
+      event("message", "first event", Some("1")),
+      event("update", "second event", None),
+    ]
+  );
+}
+
+#[test]
+fn empty_data_fields_and_incomplete_event() {
+  // Spec example: two events, the first with empty data; the trailing event is never dispatched
+  let events: Vec<_> = EventStream::from_bytes(b"data\n\ndata\ndata\n\ndata:").collect();
+  assert_eq!(events, [event("message", "", None), event("message", "\n", None)]);
+}
+
+#[test]
+fn only_one_leading_space_is_removed() {
+  let events: Vec<_> = EventStream::from_bytes(b"data:test\n\ndata:  test\n\n").collect();
+  assert_eq!(
+    events,
+    [
+      event("message", "test", None),
+      event("message", " test", None)
+    ]
+  );
+}
+
+#[test]
+fn event_without_data_is_not_dispatched() {
+  let mut stream = EventStream::from_bytes(b"event: ping\nid: 7\n\ndata: x\n\n");
+  assert_eq!(stream.next(), Some(event("message", "x", Some("7"))));
+  assert_eq!(stream.next(), None);
+}
+
+#[test]
+fn line_endings_and_chunk_boundaries() {
+  let input = b"\xEF\xBB\xBFdata: a\r\ndata: b\r\r\ndata: c\n\rid: 42\rdata: d\r\n\r\n";
+  let whole: Vec<_> = EventStream::from_bytes(input).collect();
+
+  let mut stream = EventStream::new();
+  for byte in input {
+    stream.feed(core::slice::from_ref(byte));
+  }
+  let split: Vec<_> = stream.collect();
+
+  assert_eq!(
+    whole,
+    [
+      event("message", "a\nb", None),
+      event("message", "c", None),
+      event("message", "d", Some("42")),
+    ]
+  );
+  assert_eq!(split, whole);
+}
+
+#[test]
+fn retry_and_last_event_id_for_reconnection() {
+  let mut stream = EventStream::from_bytes(b"retry: 3000\nretry: soon\nid: abc\ndata: x\n\nid: a\0b\n\n");
+  assert_eq!(stream.retry(), Some(3000));
+  assert_eq!(stream.last_event_id(), Some("abc"));
+  assert_eq!(stream.next(), Some(event("message", "x", Some("abc"))));
+
+  stream.feed(b"id:\n\n");
+  assert_eq!(stream.last_event_id(), None);
+}
+
+#[test]
+fn response_event_stream_reads_body() {
+  let mut headers = Headers::new();
+  headers.insert("Content-Type", "text/event-stream");
+  let response = Response::from_parts(
+    200,
+    String::from("OK"),
+    headers,
+    Body::from_bytes(b"event: tick\ndata: 1\n\n".to_vec()),
+    Vec::new(),
+  );
+  let events: Vec<_> = response.event_stream().collect();
+  assert_eq!(events, [event("tick", "1", None)]);
+}
+
+#[test]
+fn event_sink_delivers_each_event_once_complete() {
+  let mut seen = Vec::new();
+  let mut sink = EventSink::new(|event: ServerSentEvent| {
+    seen.push(event.data);
+    core::ops::ControlFlow::Continue(())
+  });
+
+  sink.write_all(b"data: one\n").unwrap();
+  sink.write_all(b"\ndata: tw").unwrap();
+  sink.write_all(b"o\n\nretry: 500\n").unwrap();
+  assert!(!sink.is_full());
+  assert_eq!(sink.stream().retry(), Some(500));
+  drop(sink);
+
+  assert_eq!(seen, ["one", "two"]);
+}
+
+#[test]
+fn event_sink_stops_when_callback_breaks() {
+  let mut count = 0;
+  let mut sink = EventSink::new(|_| {
+    count += 1;
+    core::ops::ControlFlow::Break(())
+  });
+
+  sink.write_all(b"data: a\n\ndata: b\n\n").unwrap();
+  assert!(sink.is_full());
+  sink.write_all(b"data: c\n\n").unwrap();
+  drop(sink);
+
+  assert_eq!(count, 1);
+}
@@ -1,4 +1,4 @@
-use crate::parser::framing::FramingDetector;
+use crate::parser::framing::{FramingDetector, HeaderScanner};
 use alloc::format;
 use alloc::vec::Vec;
 
@@ -192,3 +192,61 @@ fn test_rfc9112_robustness() {
   let (headers, _) = FramingDetector::split_headers(complex).unwrap();
   assert!(!headers.is_empty());
 }
+
+#[test]
+fn test_header_end_offset_marks_body_start() {
+  let with_body = b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nHello";
+  assert_eq!(FramingDetector::header_end_offset(with_body), Some(38));
+  assert_eq!(FramingDetector::header_end_offset(b"HTTP/1.1 200 OK\r\n"), None);
+  assert_eq!(FramingDetector::header_end_offset(b""), None);
+}
+
+#[test]
+fn test_header_end_offset_skips_leading_empty_lines() {
+  // RFC 9112 Section 2.2: empty lines before the status line are not the header end
+  assert_eq!(FramingDetector::header_end_offset(b"\r\n\r\n"), None);
+  assert!(!FramingDetector::has_complete_headers(b"\r\n\r\nHTTP/1.1 200 OK\r\n"));
+
+  let input = b"\r\n\nHTTP/1.1 200 OK\r\n\r\nbody";
+  assert_eq!(FramingDetector::header_end_offset(input), Some(22));
+}
+
+#[test]
+fn test_header_scanner_single_byte_feeds() {
+  let input = b"\r\nHTTP/1.1 200 OK\r\nX-A: \r\r\n\r\nbody";
+  let mut scanner = HeaderScanner::new();
+  let mut buffer = Vec::new();
+  let mut found = None;
+  for &byte in input {
+    buffer.push(byte);
+    found = scanner.scan(&buffer);
+    if found.is_some() {
+      break;
+    }
+  }
+
+  assert_eq!(found, FramingDetector::header_end_offset(input));
+  assert_eq!(found, Some(buffer.len()));
+  // Further calls keep reporting the same offset
+  buffer.extend_from_slice(b"more\r\n\r\n");
+  assert_eq!(scanner.scan(&buffer), found);
+}
+
+#[test]
+fn test_header_scanner_examines_each_byte_once() {
+  let mut input = b"HTTP/1.1 200 OK\r\n".to_vec();
+  for i in 0..500 {
+    input.extend_from_slice(format!("X-Custom-{i}: value{i}\r\n").as_bytes());
+  }
+  input.extend_from_slice(b"\r\n");
+
+  let mut scanner = HeaderScanner::new();
+  let mut found = None;
+  for end in 1..=input.len() {
+    found = scanner.scan(&input[..end]);
+  }
+
+  assert_eq!(found, Some(input.len()));
+  // Total work is linear: every byte was examined exactly once across all calls
+  assert_eq!(scanner.scanned(), input.len());
+}
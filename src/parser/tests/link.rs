@@ -0,0 +1,109 @@
+use crate::body::Body;
+use crate::headers::Headers;
+use crate::parser::Response;
+use crate::parser::link::parse_link_header;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+fn response_with_links(
+  links: &[&str],
+  url: Option<&str>,
+) -> Response {
+  let mut headers = Headers::new();
+  for link in links {
+    headers.insert("Link", *link);
+  }
+  let mut response = Response::from_parts(200, String::from("OK"), headers, Body::empty(), Vec::new());
+  response.url = url.map(String::from);
+  response
+}
+
+#[test]
+fn parses_github_style_pagination() {
+  let links = parse_link_header(
+    "<https://api.github.com/repos?page=2>; rel=\"next\", <https://api.github.com/repos?page=5>; rel=\"last\"",
+  );
+
+  assert_eq!(links.len(), 2);
+  assert_eq!(links[0].uri, "https://api.github.com/repos?page=2");
+  assert_eq!(links[0].rel(), Some("next"));
+  assert_eq!(links[1].uri, "https://api.github.com/repos?page=5");
+  assert_eq!(links[1].rel(), Some("last"));
+}
+
+#[test]
+fn commas_inside_uri_and_quotes_do_not_split() {
+  let links = parse_link_header("<https://x.test/a,b>; title=\"one, two\"; rel=next, </c>; rel=prev");
+
+  assert_eq!(links.len(), 2);
+  assert_eq!(links[0].uri, "https://x.test/a,b");
+  assert_eq!(links[0].param("title"), Some("one, two"));
+  assert_eq!(links[0].param("REL"), Some("next"));
+  assert_eq!(links[1].uri, "/c");
+}
+
+#[test]
+fn quoted_params_are_unescaped_and_names_lowercased() {
+  let links = parse_link_header("</doc>; Title=\"say \\\"hi\\\"\"; crossorigin; rel=\"preload  Next\"");
+
+  assert_eq!(links.len(), 1);
+  assert_eq!(
+    links[0].params,
+    Vec::from([
+      (String::from("title"), String::from("say \"hi\"")),
+      (String::from("crossorigin"), String::new()),
+      (String::from("rel"), String::from("preload  Next")),
+    ])
+  );
+  assert!(links[0].has_rel("next"));
+  assert!(links[0].has_rel("PRELOAD"));
+  assert!(!links[0].has_rel("prev"));
+}
+
+#[test]
+fn malformed_elements_are_skipped() {
+  let links = parse_link_header("garbage; rel=x, , <https://x.test/ok>; rel=next, <unterminated");
+
+  assert_eq!(links.len(), 1);
+  assert_eq!(links[0].uri, "https://x.test/ok");
+}
+
+#[test]
+fn empty_value_has_no_links() {
+  assert!(parse_link_header("").is_empty());
+  assert!(parse_link_header(" , ").is_empty());
+}
+
+#[test]
+fn response_links_combine_header_lines_and_resolve_relative_uris() {
+  let response = response_with_links(
+    &[
+      "</items?page=2>; rel=next",
+      "<https://cdn.test/x>; rel=\"preload\"",
+    ],
+    Some("https://api.test:8443/items?page=1"),
+  );
+
+  let links = response.links();
+  assert_eq!(links.len(), 2);
+  assert_eq!(links[0].uri, "https://api.test:8443/items?page=2");
+  assert_eq!(links[1].uri, "https://cdn.test/x");
+}
+
+#[test]
+fn response_links_without_url_keep_relative_uris() {
+  let response = response_with_links(&["</items?page=2>; rel=next"], None);
+  assert_eq!(response.links()[0].uri, "/items?page=2");
+}
+
+#[test]
+fn link_rel_finds_first_matching_relation() {
+  let response = response_with_links(
+    &["<https://x.test/1>; rel=\"prev\", <https://x.test/3>; rel=\"next last\""],
+    None,
+  );
+
+  assert_eq!(response.link_rel("next").unwrap().uri, "https://x.test/3");
+  assert_eq!(response.link_rel("last").unwrap().uri, "https://x.test/3");
+  assert!(response.link_rel("first").is_none());
+}
@@ -1,5 +1,7 @@
 use crate::parser::*;
 extern crate alloc;
+#[cfg(feature = "gzip-decompression")]
+use alloc::string::ToString;
 use alloc::vec::Vec;
 
 #[test]
@@ -87,7 +89,13 @@ fn test_body_no_content_length_no_transfer_encoding() {
   let result = Response::parse(input);
   assert!(result.is_ok());
   let response = result.unwrap();
-  assert!(response.body.is_empty());
+  assert_eq!(response.body.as_bytes(), b"Some body content");
+}
+
+#[test]
+fn test_body_http10_without_framing_runs_to_end_of_input() {
+  let response = Response::parse(b"HTTP/1.0 200 OK\r\n\r\nhello").unwrap();
+  assert_eq!(response.body.as_bytes(), b"hello");
 }
 
 #[test]
@@ -140,3 +148,144 @@ fn test_body_head_request_response() {
   let result = Response::parse(input);
   assert!(result.is_err());
 }
+
+#[cfg(feature = "gzip-decompression")]
+fn gzip_wrap(data: &[u8]) -> Vec<u8> {
+  let mut out = alloc::vec![0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0xff];
+  out.extend_from_slice(&miniz_oxide::deflate::compress_to_vec(data, 6));
+  out.extend_from_slice(&crate::parser::content_coding::crc32(0, data).to_le_bytes());
+  out.extend_from_slice(&u32::try_from(data.len()).unwrap().to_le_bytes());
+  out
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn test_body_layered_content_encoding_decoded_in_reverse() {
+  let original = b"layered content codings";
+  let deflated = miniz_oxide::deflate::compress_to_vec_zlib(original, 6);
+  let encoded = gzip_wrap(&deflated);
+
+  let mut headers = crate::headers::Headers::new();
+  headers.insert("Content-Encoding", "deflate, gzip");
+  headers.insert("Content-Length", encoded.len().to_string());
+  let body = Response::parse_body_from_bytes(&encoded, &headers, 200, true, None).unwrap();
+  assert_eq!(body.as_bytes(), original);
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn test_body_gzip_members_and_trailer_checked_when_buffered() {
+  let mut encoded = gzip_wrap(b"one ");
+  encoded.extend(gzip_wrap(b"two"));
+  let mut headers = crate::headers::Headers::new();
+  headers.insert("Content-Encoding", "gzip");
+  let body = Response::parse_body_from_bytes(&encoded, &headers, 200, true, None).unwrap();
+  assert_eq!(body.as_bytes(), b"one two");
+
+  let last = encoded.len() - 1;
+  encoded[last] ^= 1;
+  assert_eq!(
+    Response::parse_body_from_bytes(&encoded, &headers, 200, true, None),
+    Err(crate::error::ParseError::DecompressionFailed)
+  );
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn test_body_layered_content_encoding_across_header_lines() {
+  let original = b"split across fields";
+  let encoded = gzip_wrap(&miniz_oxide::deflate::compress_to_vec_zlib(original, 6));
+
+  let mut headers = crate::headers::Headers::new();
+  headers.insert("Content-Encoding", "deflate");
+  headers.insert("Content-Encoding", "GZIP");
+  headers.insert("Content-Length", encoded.len().to_string());
+  let body = Response::parse_body_from_bytes(&encoded, &headers, 200, true, None).unwrap();
+  assert_eq!(body.as_bytes(), original);
+}
+
+#[test]
+fn test_body_identity_content_encoding_passthrough() {
+  let input = b"HTTP/1.1 200 OK\r\nContent-Encoding: identity\r\nContent-Length: 5\r\n\r\nHello";
+  let response = Response::parse(input).unwrap();
+  assert_eq!(response.body.as_bytes(), b"Hello");
+}
+
+#[test]
+#[cfg(any(feature = "gzip-decompression", feature = "zstd-decompression"))]
+fn test_body_unknown_content_encoding_rejected() {
+  let input = b"HTTP/1.1 200 OK\r\nContent-Encoding: br\r\nContent-Length: 5\r\n\r\nHello";
+  let result = Response::parse(input);
+  assert!(matches!(
+    result,
+    Err(crate::error::ParseError::UnsupportedContentEncoding)
+  ));
+}
+
+#[test]
+#[cfg(not(any(feature = "gzip-decompression", feature = "zstd-decompression")))]
+fn test_body_content_encoding_kept_without_decoders() {
+  let input = b"HTTP/1.1 200 OK\r\nContent-Encoding: gzip\r\nContent-Length: 5\r\n\r\nHello";
+  let response = Response::parse(input).unwrap();
+  assert_eq!(response.body.as_bytes(), b"Hello");
+  assert_eq!(response.headers.get("content-encoding"), Some("gzip"));
+}
+
+#[test]
+fn test_body_unknown_content_encoding_kept_when_decompress_disabled() {
+  let mut headers = crate::headers::Headers::new();
+  headers.insert("Content-Encoding", "br, gzip");
+  headers.insert("Content-Length", "3");
+  let body = Response::parse_body_from_bytes(b"\x01\x02\x03", &headers, 200, false, None).unwrap();
+  assert_eq!(body.as_bytes(), b"\x01\x02\x03");
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn test_body_gzip_bomb_rejected_over_limit() {
+  let original = alloc::vec![0u8; 1024 * 1024];
+  let encoded = gzip_wrap(&original);
+  assert!(encoded.len() < 4096);
+
+  let mut headers = crate::headers::Headers::new();
+  headers.insert("Content-Encoding", "gzip");
+  headers.insert("Content-Length", encoded.len().to_string());
+
+  let result = Response::parse_body_from_bytes(&encoded, &headers, 200, true, Some(64 * 1024));
+  assert!(matches!(result, Err(crate::error::ParseError::DecompressionTooLarge)));
+
+  let body = Response::parse_body_from_bytes(&encoded, &headers, 200, true, Some(original.len())).unwrap();
+  assert_eq!(body.len(), original.len());
+}
+
+#[test]
+#[cfg(feature = "gzip-decompression")]
+fn test_body_deflate_bomb_rejected_over_limit() {
+  let encoded = miniz_oxide::deflate::compress_to_vec_zlib(&alloc::vec![b'a'; 256 * 1024], 6);
+
+  let mut headers = crate::headers::Headers::new();
+  headers.insert("Content-Encoding", "deflate");
+  headers.insert("Content-Length", encoded.len().to_string());
+
+  let result = Response::parse_body_from_bytes(&encoded, &headers, 200, true, Some(1024));
+  assert!(matches!(result, Err(crate::error::ParseError::DecompressionTooLarge)));
+}
+
+#[test]
+#[cfg(feature = "zstd-decompression")]
+fn test_body_zstd_bomb_rejected_over_limit() {
+  use ruzstd::encoding::{CompressionLevel, compress_to_vec};
+
+  let original = alloc::vec![0u8; 1024 * 1024];
+  let encoded = compress_to_vec(&original[..], CompressionLevel::Fastest);
+
+  let mut headers = crate::headers::Headers::new();
+  headers.insert("Content-Encoding", "zstd");
+  headers.insert("Content-Length", encoded.len().to_string());
+
+  let result = Response::parse_body_from_bytes(&encoded, &headers, 200, true, Some(64 * 1024));
+  assert!(matches!(result, Err(crate::error::ParseError::DecompressionTooLarge)));
+
+  let body = Response::parse_body_from_bytes(&encoded, &headers, 200, true, None).unwrap();
+  assert_eq!(body.len(), original.len());
+}
@@ -1,4 +1,11 @@
+use crate::body::Body;
+use crate::error::ParseError;
+use crate::headers::Headers;
+use crate::method::Method;
 use crate::parser::*;
+use crate::util::IpAddr;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 #[test]
 fn test_leading_crlf_before_status_line() {
@@ -118,3 +125,92 @@ fn test_incomplete_status_line() {
   let result = Response::parse(input);
   assert!(result.is_err());
 }
+
+#[test]
+fn test_response_into_parts_round_trip() {
+  let input =
+    b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\nTrailer: X-Sum\r\n\r\n5\r\nHello\r\n0\r\nX-Sum: 42\r\n\r\n";
+  let response = Response::parse(input).unwrap();
+  let original = response.clone();
+
+  let (status_code, reason, headers, body, trailers) = response.into_parts();
+  assert_eq!(status_code, 200);
+  assert_eq!(reason, "OK");
+  assert_eq!(body.as_bytes(), b"Hello");
+  assert_eq!(trailers.len(), 1);
+
+  let rebuilt = Response::from_parts(status_code, reason, headers, body, trailers);
+  assert_eq!(rebuilt, original);
+}
+
+#[test]
+fn test_from_parts_restores_url_and_remote_addr() {
+  let rebuilt = Response::from_parts(200, String::from("OK"), Headers::new(), Body::empty(), Vec::new())
+    .with_url("https://example.com/a")
+    .with_remote_addr(IpAddr::V4([192, 0, 2, 1]));
+
+  assert_eq!(rebuilt.url(), Some("https://example.com/a"));
+  assert_eq!(rebuilt.remote_addr(), Some(IpAddr::V4([192, 0, 2, 1])));
+  assert!(!rebuilt.is_truncated());
+}
+
+#[test]
+fn test_content_length_accessor() {
+  let sized = Response::parse(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nHello").unwrap();
+  assert_eq!(sized.content_length(), Some(5));
+
+  let chunked = Response::parse(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\n").unwrap();
+  assert_eq!(chunked.content_length(), None);
+
+  let mut headers = Headers::new();
+  headers.insert("Content-Length", "12, 13");
+  let conflicting = Response::from_parts(200, String::from("OK"), headers, Body::empty(), Vec::new());
+  assert_eq!(conflicting.content_length(), None);
+}
+
+#[test]
+fn test_allowed_methods() {
+  let response = Response::parse(b"HTTP/1.1 204 No Content\r\nAllow: GET, POST, OPTIONS\r\n\r\n").unwrap();
+  assert_eq!(response.allowed_methods(), [Method::Get, Method::Post, Method::Options]);
+
+  let mut headers = Headers::new();
+  headers.insert("Allow", "HEAD,, PROPFIND");
+  headers.insert("Allow", "delete , PUT");
+  let combined = Response::from_parts(
+    405,
+    String::from("Method Not Allowed"),
+    headers,
+    Body::empty(),
+    Vec::new(),
+  );
+  assert_eq!(combined.allowed_methods(), [Method::Head, Method::Put]);
+
+  let empty = Response::parse(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+  assert!(empty.allowed_methods().is_empty());
+}
+
+#[test]
+fn test_location_resolves_against_response_url() {
+  let mut relative = Response::parse(b"HTTP/1.1 302 Found\r\nLocation: /login?next=%2F\r\n\r\n").unwrap();
+  relative.url = Some(String::from("https://example.com:8443/account"));
+  assert_eq!(
+    relative.location().unwrap().unwrap(),
+    "https://example.com:8443/login?next=%2F"
+  );
+
+  let mut absolute =
+    Response::parse(b"HTTP/1.1 301 Moved Permanently\r\nLocation: http://other.test/new\r\n\r\n").unwrap();
+  assert_eq!(absolute.location().unwrap().unwrap(), "http://other.test/new");
+  absolute.url = Some(String::from("https://example.com/old"));
+  assert_eq!(absolute.location().unwrap().unwrap(), "http://other.test/new");
+}
+
+#[test]
+fn test_location_missing_or_unresolvable() {
+  let none = Response::parse(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+  assert!(none.location().is_none());
+
+  // A relative reference cannot be resolved without the response URL
+  let unknown_base = Response::parse(b"HTTP/1.1 303 See Other\r\nLocation: /done\r\n\r\n").unwrap();
+  assert!(matches!(unknown_base.location(), Some(Err(ParseError::InvalidUri))));
+}
@@ -6,11 +6,14 @@
 #![allow(clippy::shadow_reuse)]
 #![allow(clippy::shadow_same)]
 
+mod cache_control;
 mod chunked_encoding;
-#[cfg(feature = "cookie-jar")]
+mod content_coding;
 mod cookie;
+mod event_stream;
 mod framing;
 mod incomplete_messages;
+mod link;
 mod message_body;
 mod message_parsing;
 mod response_reading;
@@ -1,5 +1,6 @@
 /// Comprehensive test suite for RFC 9112 compliance validation
 /// Tests all edge cases and validation rules added to prevent future regressions
+use crate::error::ParseError;
 use crate::parser::RequestBuilder;
 extern crate alloc;
 
@@ -340,3 +341,146 @@ fn test_rfc9112_regression_transfer_encoding_other_values() {
   let result = builder.build();
   assert!(result.is_ok(), "Non-chunked TE should be accepted");
 }
+
+#[test]
+fn test_rfc9112_asterisk_form_only_for_options() {
+  // RFC 9112 Section 3.2.4: asterisk-form is only used with OPTIONS
+  let result = RequestBuilder::new("GET", "*")
+    .header("Host", "example.com")
+    .build();
+
+  assert_eq!(result.unwrap_err(), ParseError::InvalidRequestTarget);
+}
+
+#[test]
+fn test_rfc9112_connect_rejects_origin_form() {
+  // RFC 9112 Section 3.2.3: CONNECT target is authority-form, not a path
+  let result = RequestBuilder::new("CONNECT", "/tunnel")
+    .header("Host", "example.com:443")
+    .build();
+
+  assert_eq!(result.unwrap_err(), ParseError::InvalidRequestTarget);
+}
+
+// ============================================================================
+// RFC 9112 Section 6: Caller-controlled body framing
+// ============================================================================
+
+#[test]
+fn test_without_content_length_omits_automatic_header() {
+  let request = RequestBuilder::new("POST", "/upload")
+    .header("Host", "example.com")
+    .header("Transfer-Encoding", "chunked")
+    .body(b"5\r\nhello\r\n0\r\n\r\n".to_vec())
+    .without_content_length()
+    .build()
+    .unwrap();
+
+  let text = alloc::string::String::from_utf8(request).unwrap();
+  assert!(!text.to_ascii_lowercase().contains("content-length"));
+  assert!(text.ends_with("\r\n\r\n5\r\nhello\r\n0\r\n\r\n"));
+}
+
+#[test]
+fn test_transfer_encoding_body_gets_no_automatic_content_length() {
+  let request = RequestBuilder::new("POST", "/upload")
+    .header("Host", "example.com")
+    .header("Transfer-Encoding", "chunked")
+    .body(b"0\r\n\r\n".to_vec())
+    .build()
+    .unwrap();
+
+  assert!(
+    !alloc::string::String::from_utf8_lossy(&request)
+      .to_ascii_lowercase()
+      .contains("content-length")
+  );
+}
+
+#[test]
+fn test_without_content_length_requires_other_framing() {
+  let result = RequestBuilder::new("POST", "/upload")
+    .header("Host", "example.com")
+    .body(b"hello".to_vec())
+    .without_content_length()
+    .build();
+
+  assert_eq!(result.unwrap_err(), ParseError::MissingBodyFraming);
+}
+
+#[test]
+fn test_without_content_length_keeps_explicit_header() {
+  let request = RequestBuilder::new("POST", "/upload")
+    .header("Host", "example.com")
+    .header("Content-Length", "5")
+    .body(b"hello".to_vec())
+    .without_content_length()
+    .build()
+    .unwrap();
+
+  assert!(request.ends_with(b"Content-Length: 5\r\n\r\nhello"));
+}
+
+#[test]
+fn test_build_head_frames_body_without_including_it() {
+  let head = RequestBuilder::new("POST", "/upload")
+    .header("Host", "example.com")
+    .build_head(Some(b"hello"))
+    .unwrap();
+  assert!(head.ends_with(b"Content-Length: 5\r\n\r\n"));
+
+  let unframed = RequestBuilder::new("POST", "/upload")
+    .header("Host", "example.com")
+    .without_content_length()
+    .build_head(Some(b"hello"));
+  assert_eq!(unframed.unwrap_err(), ParseError::MissingBodyFraming);
+}
+
+// ============================================================================
+// RFC 9110 Section 5.5: Field Value Characters
+// ============================================================================
+
+#[test]
+fn test_header_value_control_characters_rejected() {
+  for value in ["a\0b", "a\x7fb", "a\x1bb", "\x01"] {
+    let result = RequestBuilder::new("GET", "/")
+      .header("Host", "example.com")
+      .header("X-Value", value)
+      .build();
+    assert_eq!(result.unwrap_err(), ParseError::InvalidHeaderValue, "{value:?}");
+  }
+}
+
+#[test]
+fn test_header_value_visible_characters_and_tab_accepted() {
+  let request = RequestBuilder::new("GET", "/")
+    .header("Host", "example.com")
+    .header("X-Value", "a b\t~!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}")
+    .build()
+    .unwrap();
+
+  assert!(request.ends_with(b"X-Value: a b\t~!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}\r\n\r\n"));
+}
+
+#[test]
+fn test_header_value_non_ascii_rejected_only_when_strict() {
+  let lenient = RequestBuilder::new("GET", "/")
+    .header("Host", "example.com")
+    .header("X-Name", "café")
+    .build()
+    .unwrap();
+  assert!(lenient.ends_with("X-Name: café\r\n\r\n".as_bytes()));
+
+  let strict = RequestBuilder::new("GET", "/")
+    .header("Host", "example.com")
+    .header("X-Name", "café")
+    .strict_ascii_values()
+    .build();
+  assert_eq!(strict.unwrap_err(), ParseError::InvalidHeaderValue);
+
+  let still_no_controls = RequestBuilder::new("GET", "/")
+    .header("Host", "example.com")
+    .header("X-Name", "caf\0é")
+    .build();
+  assert_eq!(still_no_controls.unwrap_err(), ParseError::InvalidHeaderValue);
+}
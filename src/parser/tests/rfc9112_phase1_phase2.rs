@@ -134,6 +134,23 @@ fn test_exactly_one_crlf_after_each_header() {
   );
 }
 
+#[test]
+fn test_headers_written_in_insertion_order_with_duplicates() {
+  // Field order is kept exactly, so identical builders produce identical bytes
+  let request = RequestBuilder::new("GET", "/")
+    .header("Host", "example.com")
+    .header("X-Trace", "2")
+    .header("Accept", "*/*")
+    .header("x-trace", "1")
+    .build()
+    .unwrap();
+
+  assert_eq!(
+    String::from_utf8_lossy(&request),
+    "GET / HTTP/1.1\r\nHost: example.com\r\nX-Trace: 2\r\nAccept: */*\r\nx-trace: 1\r\n\r\n"
+  );
+}
+
 // ============================================================================
 // Phase 2.1: Conflicting TE+CL Handling (RFC 9112 Section 6.3)
 // ============================================================================
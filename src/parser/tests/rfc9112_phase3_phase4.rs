@@ -1,3 +1,4 @@
+use crate::parser::version::Version;
 use crate::parser::*;
 extern crate alloc;
 use alloc::string::String;
@@ -45,7 +46,7 @@ fn test_response_without_connection_close() {
 fn test_body_read_strategy_no_body_for_1xx() {
   // 1xx responses should have no body
   let headers = crate::headers::Headers::new();
-  let strategy = Response::body_read_strategy(&headers, 100);
+  let strategy = Response::body_read_strategy(&headers, 100, Version::HTTP_11);
 
   assert_eq!(strategy, BodyReadStrategy::NoBody, "1xx responses should have no body");
 }
@@ -54,7 +55,7 @@ fn test_body_read_strategy_no_body_for_1xx() {
 fn test_body_read_strategy_no_body_for_204() {
   // 204 No Content should have no body
   let headers = crate::headers::Headers::new();
-  let strategy = Response::body_read_strategy(&headers, 204);
+  let strategy = Response::body_read_strategy(&headers, 204, Version::HTTP_11);
 
   assert_eq!(strategy, BodyReadStrategy::NoBody, "204 should have no body");
 }
@@ -63,7 +64,7 @@ fn test_body_read_strategy_no_body_for_204() {
 fn test_body_read_strategy_no_body_for_304() {
   // 304 Not Modified should have no body
   let headers = crate::headers::Headers::new();
-  let strategy = Response::body_read_strategy(&headers, 304);
+  let strategy = Response::body_read_strategy(&headers, 304, Version::HTTP_11);
 
   assert_eq!(strategy, BodyReadStrategy::NoBody, "304 should have no body");
 }
@@ -73,7 +74,7 @@ fn test_body_read_strategy_content_length() {
   // Response with Content-Length should use ContentLength strategy
   let mut headers = crate::headers::Headers::new();
   headers.insert("Content-Length", "100");
-  let strategy = Response::body_read_strategy(&headers, 200);
+  let strategy = Response::body_read_strategy(&headers, 200, Version::HTTP_11);
 
   assert_eq!(
     strategy,
@@ -87,7 +88,7 @@ fn test_body_read_strategy_chunked() {
   // Response with Transfer-Encoding: chunked should use Chunked strategy
   let mut headers = crate::headers::Headers::new();
   headers.insert("Transfer-Encoding", "chunked");
-  let strategy = Response::body_read_strategy(&headers, 200);
+  let strategy = Response::body_read_strategy(&headers, 200, Version::HTTP_11);
 
   assert_eq!(strategy, BodyReadStrategy::Chunked, "Should use Chunked strategy");
 }
@@ -97,7 +98,7 @@ fn test_body_read_strategy_until_close() {
   // Response with Transfer-Encoding but not chunked should read until close
   let mut headers = crate::headers::Headers::new();
   headers.insert("Transfer-Encoding", "gzip");
-  let strategy = Response::body_read_strategy(&headers, 200);
+  let strategy = Response::body_read_strategy(&headers, 200, Version::HTTP_11);
 
   assert_eq!(
     strategy,
@@ -268,7 +269,7 @@ fn test_body_read_strategy_precedence() {
   headers.insert("Transfer-Encoding", "chunked");
   headers.insert("Content-Length", "100");
 
-  let strategy = Response::body_read_strategy(&headers, 200);
+  let strategy = Response::body_read_strategy(&headers, 200, Version::HTTP_11);
 
   // Should use Chunked, not ContentLength (but this would be rejected by Phase 2.1)
   // In practice, this combination should trigger ConflictingFraming error
@@ -299,3 +300,38 @@ fn test_empty_path_with_connection_close() {
     "Should have Connection: close"
   );
 }
+
+#[test]
+fn test_body_read_strategy_http10_without_framing_reads_until_close() {
+  let mut headers = crate::headers::Headers::new();
+  headers.insert("Content-Type", "text/plain");
+  let strategy = Response::body_read_strategy(&headers, 200, Version::HTTP_10);
+
+  assert_eq!(strategy, BodyReadStrategy::UntilClose);
+}
+
+#[test]
+fn test_body_read_strategy_http11_connection_close_reads_until_close() {
+  let mut headers = crate::headers::Headers::new();
+  headers.insert("Connection", "close");
+  let strategy = Response::body_read_strategy(&headers, 200, Version::HTTP_11);
+
+  assert_eq!(strategy, BodyReadStrategy::UntilClose);
+}
+
+#[test]
+fn test_body_read_strategy_http11_keep_alive_without_framing_has_no_body() {
+  let headers = crate::headers::Headers::new();
+  let strategy = Response::body_read_strategy(&headers, 200, Version::HTTP_11);
+
+  assert_eq!(strategy, BodyReadStrategy::NoBody);
+}
+
+#[test]
+fn test_body_read_strategy_http10_content_length_still_wins() {
+  let mut headers = crate::headers::Headers::new();
+  headers.insert("Content-Length", "5");
+  let strategy = Response::body_read_strategy(&headers, 200, Version::HTTP_10);
+
+  assert_eq!(strategy, BodyReadStrategy::ContentLength(5));
+}
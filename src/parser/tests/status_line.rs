@@ -164,3 +164,40 @@ fn test_websocket_upgrade_101() {
   let (line, _) = result.unwrap();
   assert_eq!(line.status.code(), 101);
 }
+
+#[test]
+fn test_status_missing_reason_space_strict_rejected() {
+  let input = b"HTTP/1.1 200\r\n";
+  assert!(StatusLine::parse(input).is_err());
+}
+
+#[test]
+fn test_status_missing_reason_space_lenient_accepted() {
+  let input = b"HTTP/1.1 200\r\n";
+  let (line, rest) = StatusLine::parse_with(input, true).unwrap();
+  assert_eq!(line.status.code(), 200);
+  assert!(line.reason.is_empty());
+  assert!(rest.is_empty());
+}
+
+#[test]
+fn test_status_lowercase_version_strict_rejected() {
+  let input = b"http/1.1 200 OK\r\n";
+  assert!(StatusLine::parse(input).is_err());
+}
+
+#[test]
+fn test_status_lowercase_version_lenient_accepted() {
+  let input = b"http/1.1 200 OK\r\n";
+  let (line, _) = StatusLine::parse_with(input, true).unwrap();
+  assert_eq!(line.version, crate::parser::version::Version::HTTP_11);
+  assert_eq!(line.status.code(), 200);
+  assert_eq!(line.reason, b"OK");
+}
+
+#[test]
+fn test_status_lenient_still_rejects_garbage() {
+  assert!(StatusLine::parse_with(b"HTTX/1.1 200 OK\r\n", true).is_err());
+  assert!(StatusLine::parse_with(b"HTTP/1.1 20 OK\r\n", true).is_err());
+  assert!(StatusLine::parse_with(b"HTTP/1.1 200OK\r\n", true).is_err());
+}
@@ -1,5 +1,5 @@
 use crate::error::ParseError;
-use crate::parser::uri::{Host, Uri};
+use crate::parser::uri::{Host, Uri, UriBuilder, encode_unsafe_chars};
 use crate::util::IpAddr;
 
 #[test]
@@ -475,3 +475,137 @@ fn test_error_leftover_input() {
     Err(ParseError::InvalidUri)
   ));
 }
+
+#[test]
+fn test_builder_encodes_segments_and_query() {
+  let url = UriBuilder::new()
+    .scheme("HTTPS")
+    .host("Example.COM")
+    .port(8443)
+    .path_segment("files")
+    .path_segment("a/b c")
+    .query_param("q", "x&y=z")
+    .query_param("page", "2")
+    .build()
+    .unwrap();
+
+  assert_eq!(url, "https://example.com:8443/files/a%2Fb%20c?q=x%26y%3Dz&page=2");
+  let parsed = Uri::parse(&url).unwrap();
+  assert_eq!(parsed.path(), "/files/a%2Fb%20c");
+  assert_eq!(parsed.authority().unwrap().port(), Some(8443));
+}
+
+#[test]
+fn test_builder_defaults_and_ip_hosts() {
+  assert_eq!(
+    UriBuilder::new().host("example.com").build().unwrap(),
+    "http://example.com/"
+  );
+  assert_eq!(
+    UriBuilder::new().host("::1").port(80).build().unwrap(),
+    "http://[::1]:80/"
+  );
+  assert_eq!(UriBuilder::new().host("[::1]").build().unwrap(), "http://[::1]/");
+
+  let ipv4 = UriBuilder::new().host("127.0.0.1").build().unwrap();
+  assert_eq!(
+    Uri::parse(&ipv4).unwrap().authority().unwrap().host(),
+    &Host::IpAddr(IpAddr::V4([127, 0, 0, 1]))
+  );
+}
+
+#[test]
+fn test_builder_rejects_invalid_components() {
+  assert_eq!(UriBuilder::new().build(), Err(ParseError::InvalidUri));
+  assert_eq!(UriBuilder::new().host("").build(), Err(ParseError::InvalidUri));
+  assert_eq!(UriBuilder::new().host("bad host").build(), Err(ParseError::InvalidUri));
+  assert_eq!(UriBuilder::new().host("a..b").build(), Err(ParseError::InvalidUri));
+  assert_eq!(
+    UriBuilder::new().host("evil.com/x").build(),
+    Err(ParseError::InvalidUri)
+  );
+  assert_eq!(UriBuilder::new().host("::zz").build(), Err(ParseError::InvalidUri));
+  assert_eq!(
+    UriBuilder::new().scheme("1http").host("a.test").build(),
+    Err(ParseError::InvalidUri)
+  );
+  assert_eq!(
+    UriBuilder::new().host("a.test").port(0).build(),
+    Err(ParseError::InvalidUri)
+  );
+}
+
+#[test]
+fn test_resolve_relative_references() {
+  let base = Uri::parse("http://example.com/v1/items/list?page=1").unwrap();
+  let resolve = |location| base.resolve_relative(location).unwrap();
+
+  assert_eq!(resolve("/users"), "http://example.com/users");
+  assert_eq!(resolve("next"), "http://example.com/v1/items/next");
+  assert_eq!(resolve("../users/./1"), "http://example.com/v1/users/1");
+  assert_eq!(resolve(".."), "http://example.com/v1/");
+  assert_eq!(resolve("?page=2"), "http://example.com/v1/items/list?page=2");
+  assert_eq!(resolve("#top"), "http://example.com/v1/items/list?page=1#top");
+  assert_eq!(resolve("/a/b/../../../c"), "http://example.com/c");
+  assert_eq!(resolve("https://other.test/x"), "https://other.test/x");
+}
+
+#[test]
+fn test_resolve_relative_reference_kinds_in_rfc3986_order() {
+  let base = Uri::parse("https://example.com/a/b?q=1").unwrap();
+  let resolve = |location| base.resolve_relative(location).unwrap();
+
+  assert_eq!(resolve("HTTPS://h/"), "https://h/");
+  assert_eq!(resolve("Http://h:8080/x/../y"), "http://h:8080/y");
+  assert_eq!(resolve("//host/p"), "https://host/p");
+  assert_eq!(resolve("//host"), "https://host");
+  assert_eq!(resolve("//host/x/./../p?k=v#f"), "https://host/p?k=v#f");
+  assert_eq!(resolve("?q=2"), "https://example.com/a/b?q=2");
+  assert_eq!(resolve("#frag"), "https://example.com/a/b?q=1#frag");
+  assert_eq!(resolve("c:d"), "c:d");
+  assert_eq!(resolve("./c:d"), "https://example.com/a/c:d");
+}
+
+#[test]
+fn test_encode_unsafe_chars_in_path_and_query() {
+  let encoded = encode_unsafe_chars("http://example.com/a b/\"c\"?q=ü|x#frag ment");
+  assert_eq!(encoded, "http://example.com/a%20b/%22c%22?q=%C3%BC%7Cx#frag%20ment");
+  assert!(Uri::parse(&encoded).is_ok());
+}
+
+#[test]
+fn test_encode_unsafe_chars_encodes_lone_percent() {
+  assert_eq!(
+    encode_unsafe_chars("http://example.com/100%"),
+    "http://example.com/100%25"
+  );
+  assert_eq!(
+    encode_unsafe_chars("http://example.com/a%2?b=%zz"),
+    "http://example.com/a%252?b=%25zz"
+  );
+  assert_eq!(
+    encode_unsafe_chars("http://example.com/100%25"),
+    "http://example.com/100%25"
+  );
+}
+
+#[test]
+fn test_encode_unsafe_chars_keeps_valid_urls_and_escapes() {
+  let url = "https://user@example.com:8443/a%20b/c;p=1?x=1&y=%2F#top";
+  assert_eq!(encode_unsafe_chars(url), url);
+  assert_eq!(encode_unsafe_chars("http://example.com"), "http://example.com");
+}
+
+#[cfg(feature = "idna")]
+#[test]
+fn test_encode_idna_host_only_rewrites_unicode_hosts() {
+  use crate::parser::uri::encode_idna_host;
+
+  assert_eq!(
+    encode_idna_host("https://user@Bücher.de:8443/ü?q=ü"),
+    "https://user@xn--bcher-kva.de:8443/ü?q=ü"
+  );
+  assert_eq!(encode_idna_host("http://bücher.de"), "http://xn--bcher-kva.de");
+  assert_eq!(encode_idna_host("http://[::1]:80/ü"), "http://[::1]:80/ü");
+  assert_eq!(encode_idna_host("http://example.com/"), "http://example.com/");
+}
@@ -1,6 +1,11 @@
 use crate::error::ParseError;
-use crate::util::IpAddr;
+use crate::util::{IpAddr, percent_encode};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
 
+/// A URI reference parsed per RFC 3986, borrowing from the input string
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Uri<'a> {
   scheme: &'a str,
@@ -10,6 +15,7 @@ pub struct Uri<'a> {
   fragment: Option<&'a str>,
 }
 
+/// Authority component of a URI: optional userinfo, host and optional port
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Authority<'a> {
   userinfo: Option<&'a str>,
@@ -17,30 +23,44 @@ pub struct Authority<'a> {
   port: Option<u16>,
 }
 
+/// Host of a URI authority
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Host<'a> {
+  /// IPv4 address or bracketed IPv6 literal
   IpAddr(IpAddr),
+  /// Registered name, e.g. a DNS hostname
   RegName(&'a str),
 }
 
 impl<'a> Uri<'a> {
+  /// Parse a URI
+  ///
+  /// # Errors
+  /// Returns `ParseError::InvalidUri` if `input` is not a valid URI.
   pub fn parse(input: &'a str) -> Result<Self, ParseError> {
     Parser::new(input).parse_uri()
   }
 
+  /// Scheme, e.g. `http`
+  #[must_use]
   pub const fn scheme(&self) -> &'a str {
     self.scheme
   }
 
+  /// Authority component, if present
+  #[must_use]
   pub const fn authority(&self) -> Option<&Authority<'a>> {
     self.authority.as_ref()
   }
 
-  #[allow(dead_code)]
+  /// Path component, possibly empty
+  #[must_use]
   pub const fn path(&self) -> &'a str {
     self.path
   }
 
+  /// Path followed by `?` and the query, if there is one
+  #[must_use]
   pub fn path_and_query(&self) -> alloc::string::String {
     self.query.map_or_else(
       || alloc::string::String::from(self.path),
@@ -50,15 +70,55 @@ impl<'a> Uri<'a> {
 
   /// Resolves a relative URL against this URI as a base
   ///
+  /// Follows RFC 3986 Section 5.2.2: a reference with a scheme (matched
+  /// case-insensitively) is used as is, one starting with `//` keeps only the
+  /// base's scheme, and otherwise a path starting with `/` replaces the base
+  /// path while any other path replaces its last segment (`b/c` against
+  /// `/v1/a` gives `/v1/b/c`). `.` and `..` segments are removed.
+  ///
   /// # Errors
   /// Returns `ParseError::InvalidUri` if the location is not a valid relative or absolute URL
   pub fn resolve_relative(
     &self,
     location: &str,
   ) -> Result<alloc::string::String, ParseError> {
-    if location.starts_with("http://") || location.starts_with("https://") {
-      Ok(alloc::string::String::from(location))
-    } else if location.starts_with('/') {
+    if let Some(scheme) = reference_scheme(location) {
+      let rest = location.get(scheme.len()..).unwrap_or_default();
+      let hierarchy = rest.strip_prefix(':').unwrap_or(rest);
+      if hierarchy.starts_with("//") {
+        Ok(alloc::format!(
+          "{}:{}",
+          scheme.to_ascii_lowercase(),
+          normalize_network_path(hierarchy)
+        ))
+      } else {
+        Ok(alloc::format!("{}{rest}", scheme.to_ascii_lowercase()))
+      }
+    } else if location.starts_with("//") {
+      Ok(alloc::format!("{}:{}", self.scheme, normalize_network_path(location)))
+    } else {
+      let reference_end = location.find(['?', '#']).unwrap_or(location.len());
+      let (reference_path, suffix) = location
+        .split_at_checked(reference_end)
+        .unwrap_or((location, ""));
+      let target = if reference_path.is_empty() {
+        if suffix.starts_with('#') {
+          alloc::format!("{}{suffix}", self.path_and_query())
+        } else {
+          alloc::format!("{}{suffix}", self.path)
+        }
+      } else if reference_path.starts_with('/') {
+        alloc::format!("{}{suffix}", remove_dot_segments(reference_path))
+      } else {
+        let directory = self
+          .path
+          .rfind('/')
+          .map_or("/", |index| self.path.get(..=index).unwrap_or("/"));
+        alloc::format!(
+          "{}{suffix}",
+          remove_dot_segments(&alloc::format!("{directory}{reference_path}"))
+        )
+      };
       let authority = self.authority.as_ref().ok_or(ParseError::InvalidUri)?;
       let port = authority.port.unwrap_or_else(|| {
         if self.scheme == "https" {
@@ -75,33 +135,268 @@ impl<'a> Uri<'a> {
 
       if (self.scheme == "http" && port == 80) || (self.scheme == "https" && port == 443) {
         Ok(alloc::format!(
-          "{scheme}://{host}{location}",
+          "{scheme}://{host}{target}",
           scheme = self.scheme,
           host = host_str
         ))
       } else {
         Ok(alloc::format!(
-          "{scheme}://{host}:{port}{location}",
+          "{scheme}://{host}:{port}{target}",
           scheme = self.scheme,
           host = host_str
         ))
       }
-    } else {
-      Err(ParseError::InvalidUri)
     }
   }
 }
 
+/// Scheme of a URI reference, if it has one (RFC 3986 Section 3.1)
+fn reference_scheme(reference: &str) -> Option<&str> {
+  let (scheme, _) = reference.split_once(':')?;
+  let mut chars = scheme.chars();
+  let valid = chars
+    .next()
+    .is_some_and(|first| first.is_ascii_alphabetic())
+    && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+  valid.then_some(scheme)
+}
+
+/// A `//authority/path?query#fragment` reference with the dot segments of
+/// its path removed
+fn normalize_network_path(reference: &str) -> alloc::string::String {
+  let after_slashes = reference.get(2..).unwrap_or_default();
+  let authority_end = after_slashes
+    .find(['/', '?', '#'])
+    .unwrap_or(after_slashes.len());
+  let (authority, rest) = after_slashes
+    .split_at_checked(authority_end)
+    .unwrap_or((after_slashes, ""));
+  let path_end = rest.find(['?', '#']).unwrap_or(rest.len());
+  let (path, suffix) = rest.split_at_checked(path_end).unwrap_or((rest, ""));
+  if path.is_empty() {
+    alloc::format!("//{authority}{suffix}")
+  } else {
+    alloc::format!("//{authority}{}{suffix}", remove_dot_segments(path))
+  }
+}
+
+/// Remove `.` and `..` segments from an absolute path (RFC 3986 Section 5.2.4)
+fn remove_dot_segments(path: &str) -> alloc::string::String {
+  let mut segments: alloc::vec::Vec<&str> = alloc::vec::Vec::new();
+  let mut ends_in_directory = false;
+  for segment in path.split('/').skip(1) {
+    ends_in_directory = matches!(segment, "." | "..");
+    match segment {
+      "." => {},
+      ".." => {
+        segments.pop();
+      },
+      _ => segments.push(segment),
+    }
+  }
+  let mut resolved = alloc::string::String::new();
+  for segment in segments {
+    resolved.push('/');
+    resolved.push_str(segment);
+  }
+  if ends_in_directory || resolved.is_empty() {
+    resolved.push('/');
+  }
+  resolved
+}
+
 impl<'a> Authority<'a> {
+  /// Host
+  #[must_use]
   pub const fn host(&self) -> &Host<'a> {
     &self.host
   }
 
+  /// Explicit port, if the URI has one
+  #[must_use]
   pub const fn port(&self) -> Option<u16> {
     self.port
   }
 }
 
+/// Builder for assembling a URL from its components
+///
+/// Path segments and query parameters are percent-encoded, so callers pass
+/// them unencoded. The scheme defaults to `http` and a host is required.
+///
+/// ```
+/// use barehttp::wire::UriBuilder;
+///
+/// let url = UriBuilder::new()
+///   .scheme("https")
+///   .host("api.example.com")
+///   .path_segment("users")
+///   .path_segment("a b")
+///   .query_param("page", "2")
+///   .build()?;
+/// assert_eq!(url, "https://api.example.com/users/a%20b?page=2");
+/// # Ok::<(), barehttp::wire::ParseError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UriBuilder {
+  scheme: Option<String>,
+  host: Option<String>,
+  port: Option<u16>,
+  segments: Vec<String>,
+  query: Vec<(String, String)>,
+}
+
+impl UriBuilder {
+  /// Create a builder with no components set
+  #[must_use]
+  pub const fn new() -> Self {
+    Self {
+      scheme: None,
+      host: None,
+      port: None,
+      segments: Vec::new(),
+      query: Vec::new(),
+    }
+  }
+
+  /// Set the scheme, e.g. `https`
+  #[must_use]
+  pub fn scheme(
+    mut self,
+    scheme: &str,
+  ) -> Self {
+    self.scheme = Some(scheme.to_ascii_lowercase());
+    self
+  }
+
+  /// Set the host: a DNS name, an IPv4 address or an IPv6 address
+  ///
+  /// IPv6 addresses may be given with or without brackets.
+  #[must_use]
+  pub fn host(
+    mut self,
+    host: &str,
+  ) -> Self {
+    self.host = Some(String::from(host));
+    self
+  }
+
+  /// Set an explicit port
+  #[must_use]
+  pub const fn port(
+    mut self,
+    port: u16,
+  ) -> Self {
+    self.port = Some(port);
+    self
+  }
+
+  /// Append one path segment; a `/` inside it is encoded, not a separator
+  #[must_use]
+  pub fn path_segment(
+    mut self,
+    segment: &str,
+  ) -> Self {
+    self.segments.push(percent_encode(segment));
+    self
+  }
+
+  /// Append a query parameter
+  ///
+  /// Keys and values are percent-encoded with spaces written as `%20`.
+  #[must_use]
+  pub fn query_param(
+    mut self,
+    key: &str,
+    value: &str,
+  ) -> Self {
+    self
+      .query
+      .push((percent_encode(key), percent_encode(value)));
+    self
+  }
+
+  /// Assemble the URL
+  ///
+  /// # Errors
+  /// Returns `ParseError::InvalidUri` if there is no host, or if the scheme,
+  /// host or port (0) is invalid.
+  pub fn build(self) -> Result<String, ParseError> {
+    let scheme = self.scheme.as_deref().unwrap_or("http");
+    if !is_valid_scheme(scheme) {
+      return Err(ParseError::InvalidUri);
+    }
+    let host = format_host(self.host.as_deref().ok_or(ParseError::InvalidUri)?)?;
+    if self.port == Some(0) {
+      return Err(ParseError::InvalidUri);
+    }
+
+    let mut url = format!("{scheme}://{host}");
+    if let Some(port) = self.port {
+      let _ = write!(url, ":{port}");
+    }
+    if self.segments.is_empty() {
+      url.push('/');
+    }
+    for segment in &self.segments {
+      url.push('/');
+      url.push_str(segment);
+    }
+    for (index, (key, value)) in self.query.iter().enumerate() {
+      url.push(if index == 0 {
+        '?'
+      } else {
+        '&'
+      });
+      url.push_str(key);
+      url.push('=');
+      url.push_str(value);
+    }
+    Ok(url)
+  }
+}
+
+/// RFC 3986 Section 3.1: `ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )`
+fn is_valid_scheme(scheme: &str) -> bool {
+  let mut bytes = scheme.bytes();
+  bytes
+    .next()
+    .is_some_and(|first| first.is_ascii_alphabetic())
+    && bytes.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'))
+}
+
+/// Validate a host and put it in URL form, bracketing IPv6 addresses
+fn format_host(host: &str) -> Result<String, ParseError> {
+  let unbracketed = host
+    .strip_prefix('[')
+    .and_then(|rest| rest.strip_suffix(']'))
+    .unwrap_or(host);
+  if unbracketed.contains(':') {
+    if !unbracketed
+      .bytes()
+      .all(|b| b.is_ascii_hexdigit() || matches!(b, b':' | b'.'))
+    {
+      return Err(ParseError::InvalidUri);
+    }
+    parse_ipv6(unbracketed)?;
+    return Ok(format!("[{unbracketed}]"));
+  }
+  // DNS names and IPv4 addresses: non-empty labels of letters, digits, '-' and '_'
+  let valid = !unbracketed.is_empty()
+    && unbracketed.split('.').all(|label| {
+      !label.is_empty()
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label
+          .bytes()
+          .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_'))
+    });
+  if !valid {
+    return Err(ParseError::InvalidUri);
+  }
+  Ok(unbracketed.to_ascii_lowercase())
+}
+
 struct Parser<'a> {
   input: &'a str,
   pos: usize,
@@ -443,6 +738,72 @@ impl<'a> Parser<'a> {
   }
 }
 
+/// Percent-encode the bytes of a URL's path, query and fragment that may not appear in a URI
+///
+/// Spaces, non-ASCII text, characters such as `"`, `<` or `|` and a `%` not
+/// followed by two hex digits are encoded; delimiters and existing `%XX`
+/// escapes are kept, so encoding twice changes nothing. The scheme and authority are left as given.
+#[must_use]
+pub fn encode_unsafe_chars(url: &str) -> String {
+  let authority_start = url.find("://").map_or(0, |index| index + 3);
+  let rest_start = url
+    .get(authority_start..)
+    .and_then(|after_scheme| after_scheme.find(['/', '?', '#']))
+    .map_or(url.len(), |index| authority_start + index);
+  let (head, rest) = url.split_at_checked(rest_start).unwrap_or((url, ""));
+
+  let mut encoded = String::from(head);
+  let bytes = rest.as_bytes();
+  for (index, &byte) in bytes.iter().enumerate() {
+    let valid = byte != b'%'
+      || bytes
+        .get(index + 1..index + 3)
+        .is_some_and(|digits| digits.iter().all(u8::is_ascii_hexdigit));
+    if valid && (is_pchar(byte) || matches!(byte, b'/' | b'?' | b'#')) {
+      encoded.push(char::from(byte));
+    } else {
+      let _ = write!(encoded, "%{byte:02X}");
+    }
+  }
+  encoded
+}
+
+/// Rewrite a URL's non-ASCII host name in its ASCII (`xn--`) form
+///
+/// Hosts that are already ASCII, bracketed IP literals and hosts that cannot
+/// be encoded are left unchanged.
+#[cfg(feature = "idna")]
+#[must_use]
+pub fn encode_idna_host(url: &str) -> String {
+  let authority_start = url.find("://").map_or(0, |index| index + 3);
+  let after_scheme = url.get(authority_start..).unwrap_or_default();
+  let authority_end = after_scheme
+    .find(['/', '?', '#'])
+    .unwrap_or(after_scheme.len());
+  let authority = after_scheme.get(..authority_end).unwrap_or_default();
+  let host_start = authority.rfind('@').map_or(0, |index| index + 1);
+  let host_and_port = authority.get(host_start..).unwrap_or_default();
+  let host = host_and_port
+    .rfind(':')
+    .and_then(|index| host_and_port.get(..index))
+    .unwrap_or(host_and_port);
+  if host.is_ascii() {
+    return String::from(url);
+  }
+
+  let start = authority_start + host_start;
+  crate::util::punycode::domain_to_ascii(host).map_or_else(
+    || String::from(url),
+    |ascii| {
+      format!(
+        "{}{ascii}{}",
+        url.get(..start).unwrap_or_default(),
+        url.get(start + host.len()..).unwrap_or_default()
+      )
+    },
+  )
+}
+
 const fn is_alpha(ch: u8) -> bool {
   ch.is_ascii_alphabetic()
 }
@@ -7,6 +7,63 @@ use crate::method::Method;
 use crate::socket::BlockingSocket;
 use alloc::string::String;
 
+/// A request about to be written, passed to a `sign_with` closure
+///
+/// Every other header field, including `Host` and `Content-Length`, is
+/// already in place; fields added here are sent after them.
+pub struct SignableRequest<'r> {
+  method: Method,
+  target: &'r str,
+  headers: &'r mut Headers,
+  body: &'r [u8],
+}
+
+impl<'r> SignableRequest<'r> {
+  pub(crate) const fn new(
+    method: Method,
+    target: &'r str,
+    headers: &'r mut Headers,
+    body: &'r [u8],
+  ) -> Self {
+    Self {
+      method,
+      target,
+      headers,
+      body,
+    }
+  }
+
+  /// Request method
+  #[must_use]
+  pub const fn method(&self) -> Method {
+    self.method
+  }
+
+  /// Request target exactly as written on the request line, e.g. `/path?query`
+  #[must_use]
+  pub const fn target(&self) -> &str {
+    self.target
+  }
+
+  /// Header fields that will be sent
+  #[must_use]
+  pub const fn headers(&self) -> &Headers {
+    self.headers
+  }
+
+  /// Header fields that will be sent, for adding a signature
+  #[must_use]
+  pub const fn headers_mut(&mut self) -> &mut Headers {
+    self.headers
+  }
+
+  /// Body bytes that will be sent; empty without a body
+  #[must_use]
+  pub const fn body(&self) -> &[u8] {
+    self.body
+  }
+}
+
 /// A pure HTTP request data structure
 ///
 /// This struct holds request data without creating a client.
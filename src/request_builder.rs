@@ -1,16 +1,61 @@
-use crate::client::HttpClient;
-use crate::config::Config;
+use crate::body::Body;
+use crate::cancel::CancelToken;
+use crate::client::{HttpClient, TargetForm};
+use crate::clock::Clock;
+use crate::config::{Config, RedirectPolicy};
 use crate::dns::DnsResolver;
 use crate::error::Error;
 use crate::headers::{HeaderName, Headers};
 use crate::method::Method;
 use crate::parser::Response;
 use crate::parser::version::Version;
+use crate::request::SignableRequest;
+use crate::sink::ByteSink;
 use crate::socket::BlockingSocket;
+use crate::transport::{ProgressFn, TransferProgress};
 use crate::util::percent_encode;
+use alloc::boxed::Box;
 use alloc::string::String;
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::marker::PhantomData;
+use core::time::Duration;
+
+/// Run `request` and measure its duration with the monotonic reading of `clock`
+fn timed(
+  clock: Option<Arc<dyn Clock>>,
+  request: impl FnOnce() -> Result<Response, Error>,
+) -> Result<(Response, Duration), Error> {
+  let source = clock.ok_or(Error::ClockUnavailable)?;
+  let start = source.monotonic_millis();
+  let response = request()?;
+  let elapsed = source.monotonic_millis().saturating_sub(start);
+  Ok((response, Duration::from_millis(elapsed)))
+}
+
+/// Sink keeping the first `max_bytes` of a body for `call_prefix`
+struct PrefixSink {
+  bytes: Vec<u8>,
+  max_bytes: usize,
+  overflowed: bool,
+}
+
+impl ByteSink for PrefixSink {
+  fn write_all(
+    &mut self,
+    bytes: &[u8],
+  ) -> Result<(), Error> {
+    let room = self.max_bytes.saturating_sub(self.bytes.len());
+    let (kept, rest) = bytes.split_at_checked(room).unwrap_or((bytes, &[]));
+    self.bytes.extend_from_slice(kept);
+    self.overflowed |= !rest.is_empty();
+    Ok(())
+  }
+
+  fn is_full(&self) -> bool {
+    self.overflowed
+  }
+}
 
 /// Trait for types that can be converted into an HTTP body
 pub trait IntoBody {
@@ -64,9 +109,14 @@ pub struct ClientRequestBuilder<S, D, B = WithoutBody> {
   headers: Headers,
   query_params: Vec<(String, String)>,
   form_data: Vec<(String, String)>,
-  body: Option<Vec<u8>>,
+  body: Option<Body>,
   version: Version,
   request_config: Option<Config>,
+  cancel_token: Option<CancelToken>,
+  target_form: TargetForm,
+  upload_progress: Option<Box<ProgressFn>>,
+  download_progress: Option<Box<ProgressFn>>,
+  signer: Option<Box<dyn Fn(&mut SignableRequest<'_>)>>,
   _phantom: PhantomData<B>,
 }
 
@@ -76,6 +126,9 @@ where
   D: DnsResolver,
 {
   /// Add a header to the request
+  ///
+  /// Appends: setting the same name twice sends both fields. Use `set_header`
+  /// to replace an earlier value instead.
   #[must_use]
   pub fn header(
     mut self,
@@ -86,6 +139,33 @@ where
     self
   }
 
+  /// Set a header, replacing any earlier value with the same name (case-insensitive)
+  #[must_use]
+  pub fn set_header(
+    mut self,
+    name: impl Into<String>,
+    value: impl Into<String>,
+  ) -> Self {
+    self.headers.set(name, value);
+    self
+  }
+
+  /// Send `value` as the `Host` header instead of the URL's authority
+  ///
+  /// The connection still goes to the host in the URL, so this can reach a
+  /// virtual host by name through a fixed address. The override is kept on
+  /// redirects to the same host and port; a redirect elsewhere sends that
+  /// target's own host instead. A `Host` is always sent, since requests are written
+  /// as HTTP/1.1 (RFC 9112 Section 3.2); `HttpClient::send_raw` can send
+  /// a request without one.
+  #[must_use]
+  pub fn host_header(
+    self,
+    value: &str,
+  ) -> Self {
+    self.set_header(HeaderName::HOST, value)
+  }
+
   /// Add a URL-encoded query parameter
   #[must_use]
   pub fn query(
@@ -155,6 +235,69 @@ where
     self
   }
 
+  /// Add multiple form data fields from an iterator (application/x-www-form-urlencoded)
+  ///
+  /// Keys and values are percent-encoded with spaces written as `%20`, joined with `&`,
+  /// and sent with `Content-Type: application/x-www-form-urlencoded` unless a content type
+  /// was already set. An empty iterator still sends an empty form body (`Content-Length: 0`).
+  #[must_use]
+  pub fn form_pairs<I, K, V>(
+    mut self,
+    iter: I,
+  ) -> Self
+  where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<String>,
+  {
+    self
+      .form_data
+      .extend(iter.into_iter().map(|(k, v)| (k.into(), v.into())));
+    if self.body.is_none() {
+      self.body = Some(Body::empty());
+    }
+    self
+      .headers
+      .insert_if_absent(HeaderName::CONTENT_TYPE, "application/x-www-form-urlencoded");
+    self
+  }
+
+  /// Set the `If-None-Match` header for a conditional request
+  ///
+  /// Pass the `ETag` of a cached response; a 304 reply means the cached body is still valid.
+  #[must_use]
+  pub fn if_none_match(
+    self,
+    etag: impl Into<String>,
+  ) -> Self {
+    self.header(HeaderName::IF_NONE_MATCH, etag)
+  }
+
+  /// Set the `If-Modified-Since` header for a conditional request
+  ///
+  /// Takes an HTTP-date such as a cached `Last-Modified` value, or one built from a
+  /// Unix timestamp with `format_http_date`.
+  #[must_use]
+  pub fn if_modified_since(
+    self,
+    http_date: impl Into<String>,
+  ) -> Self {
+    self.header(HeaderName::IF_MODIFIED_SINCE, http_date)
+  }
+
+  /// Set the Accept-Language header, replacing any value set before
+  ///
+  /// Overrides `Config::accept_language` for this request. A value containing
+  /// CR or LF makes the request fail with `ParseError::InvalidHeaderValue`.
+  #[must_use]
+  pub fn accept_language(
+    mut self,
+    language: impl Into<String>,
+  ) -> Self {
+    self.headers.set(HeaderName::ACCEPT_LANGUAGE, language);
+    self
+  }
+
   /// Set the Content-Type header
   #[must_use]
   pub fn content_type(
@@ -190,16 +333,12 @@ where
     let value_str = value.into();
     let cookie_value = format!("{name_str}={value_str}");
 
-    // Check if Cookie header already exists
-    if let Some(existing) = self.headers.get(HeaderName::COOKIE) {
-      // Append to existing cookies with semicolon separator
-      let combined = format!("{existing}; {cookie_value}");
-      self.headers.remove(HeaderName::COOKIE);
-      self.headers.insert(HeaderName::COOKIE, combined);
-    } else {
-      // Create new Cookie header
-      self.headers.insert(HeaderName::COOKIE, cookie_value);
-    }
+    // Append to existing cookies with semicolon separator
+    let combined = match self.headers.get(HeaderName::COOKIE) {
+      Some(existing) => format!("{existing}; {cookie_value}"),
+      None => cookie_value,
+    };
+    self.headers.set(HeaderName::COOKIE, combined);
 
     self
   }
@@ -264,12 +403,203 @@ where
     self
   }
 
+  /// Override the overall timeout for this request only
+  #[must_use]
+  pub fn timeout(
+    mut self,
+    duration: Duration,
+  ) -> Self {
+    self.config_mut().timeout = Some(duration);
+    self
+  }
+
+  /// Override the connect timeout for this request only
+  #[must_use]
+  pub fn connect_timeout(
+    mut self,
+    duration: Duration,
+  ) -> Self {
+    self.config_mut().timeout_connect = Some(duration);
+    self
+  }
+
+  /// Override the maximum number of redirects for this request only
+  #[must_use]
+  pub fn max_redirects(
+    mut self,
+    max: u32,
+  ) -> Self {
+    self.config_mut().max_redirects = max;
+    self
+  }
+
+  /// Override the redirect policy for this request only
+  #[must_use]
+  pub fn redirect_policy(
+    mut self,
+    policy: RedirectPolicy,
+  ) -> Self {
+    self.config_mut().redirect_policy = policy;
+    self
+  }
+
+  /// Do not add `Content-Length` to this request's body
+  ///
+  /// Frame the body yourself with a `Content-Length` or `Transfer-Encoding`
+  /// header; without either the request fails to build. A request with
+  /// `Transfer-Encoding` never gets `Content-Length`, with or without this.
+  #[must_use]
+  pub fn without_content_length(mut self) -> Self {
+    self.config_mut().omit_content_length = true;
+    self
+  }
+
+  /// Attach a token that aborts this request with `Error::Cancelled` when cancelled
+  #[must_use]
+  pub fn cancel_token(
+    mut self,
+    token: CancelToken,
+  ) -> Self {
+    self.cancel_token = Some(token);
+    self
+  }
+
+  /// Call `callback` as response body bytes arrive
+  ///
+  /// It receives the number of body bytes read so far and the `Content-Length`,
+  /// if the response has one. Chunked bodies are counted as received, framing
+  /// included. Every response is reported, so the count starts over after a redirect.
+  #[must_use]
+  pub fn on_download_progress(
+    mut self,
+    callback: impl Fn(u64, Option<u64>) + 'static,
+  ) -> Self {
+    self.download_progress = Some(Box::new(callback));
+    self
+  }
+
+  /// Sign the request just before it is written
+  ///
+  /// `signer` runs after every other header field has been assembled and can
+  /// read the method, request target, headers and body to add fields such as
+  /// `Authorization` or `X-Amz-Date`. It runs again for each redirect hop.
+  #[must_use]
+  pub fn sign_with(
+    mut self,
+    signer: impl Fn(&mut SignableRequest<'_>) + 'static,
+  ) -> Self {
+    self.signer = Some(Box::new(signer));
+    self
+  }
+
+  /// Override the request-target form written on the request line
+  #[must_use]
+  pub(crate) const fn with_target_form(
+    mut self,
+    form: TargetForm,
+  ) -> Self {
+    self.target_form = form;
+    self
+  }
+
   /// Get the request-specific configuration if set
   #[must_use]
   pub const fn config_ref(&self) -> Option<&Config> {
     self.request_config.as_ref()
   }
 
+  /// Request-specific configuration, starting from a copy of the client's
+  ///
+  /// Overrides land on the copy, so the client's stored `Config` never changes.
+  fn config_mut(&mut self) -> &mut Config {
+    self
+      .request_config
+      .get_or_insert_with(|| self.client.config().clone())
+  }
+
+  /// Send the request and write the response body to `sink` as it is read
+  ///
+  /// The body is streamed straight from the connection, with chunked framing
+  /// and any `Content-Encoding` that is decoded removed, so it never has to
+  /// fit in memory. Responses served from the cache are written in one piece.
+  /// The returned response carries the status and headers with an empty body.
+  /// Error statuses and redirects are handled as in `call`; their bodies are
+  /// not written to `sink`.
+  /// `ByteSink::finish` is called once the final body has been written.
+  ///
+  /// # Errors
+  /// Returns an error if the request fails or `sink` rejects a write
+  pub fn call_to_writer<W: ByteSink>(
+    self,
+    sink: &mut W,
+  ) -> Result<Response, Error> {
+    let mut response = self.dispatch(Some(&mut *sink))?;
+    if !response.body.is_empty() {
+      sink.write_all(response.body.as_bytes())?;
+      response.body = Body::empty();
+    }
+    sink.finish()?;
+    Ok(response)
+  }
+
+  /// Send the request and read at most `max_bytes` of the response body
+  ///
+  /// Reading stops as soon as the body goes past `max_bytes`, whatever its
+  /// declared length, and the connection is then closed rather than reused.
+  /// `Response::is_truncated` tells whether the body was cut short. Unlike a
+  /// `Range` request this works even when the server ignores ranges. Bodies
+  /// that `call_to_writer` would write in one piece (cached, error statuses)
+  /// are read in full and then cut to `max_bytes`.
+  ///
+  /// # Errors
+  /// Returns an error if the request fails
+  pub fn call_prefix(
+    self,
+    max_bytes: usize,
+  ) -> Result<Response, Error> {
+    let mut prefix = PrefixSink {
+      bytes: Vec::new(),
+      max_bytes,
+      overflowed: false,
+    };
+    let mut response = self.dispatch(Some(&mut prefix))?;
+    prefix.write_all(response.body.as_bytes())?;
+    response.truncated = prefix.overflowed;
+    response.body = Body::from_bytes(prefix.bytes);
+    Ok(response)
+  }
+
+  /// Run the request through the client, streaming the body to `sink` if given
+  fn dispatch(
+    self,
+    sink: Option<&mut dyn ByteSink>,
+  ) -> Result<Response, Error> {
+    let url = self.build_url();
+
+    let body = if self.form_data.is_empty() {
+      self.body
+    } else {
+      Some(Body::from_bytes(self.build_form_body()))
+    };
+
+    let progress = TransferProgress {
+      upload: self.upload_progress.as_deref(),
+      download: self.download_progress.as_deref(),
+    };
+    self.client.request(
+      self.method,
+      &url,
+      &self.headers,
+      body,
+      self.target_form,
+      self.request_config.as_ref(),
+      self.cancel_token.as_ref(),
+      sink,
+      progress,
+      self.signer.as_deref(),
+    )
+  }
+
   fn build_url(&self) -> String {
     if self.query_params.is_empty() {
       return self.url.clone();
@@ -348,6 +678,11 @@ where
       body: None,
       version: Version::HTTP_11,
       request_config: None,
+      cancel_token: None,
+      target_form: TargetForm::Origin,
+      upload_progress: None,
+      download_progress: None,
+      signer: None,
       _phantom: PhantomData,
     }
   }
@@ -355,22 +690,70 @@ where
   /// # Errors
   /// Returns an error if the request fails
   pub fn call(self) -> Result<Response, Error> {
-    let url = self.build_url();
+    self.dispatch(None)
+  }
 
-    let body = if self.form_data.is_empty() {
-      self.body
-    } else {
-      Some(self.build_form_body())
-    };
+  /// Send the request and also return how long it took
+  ///
+  /// The duration is measured with the client's `Clock`, using its monotonic
+  /// reading so wall-clock adjustments do not skew it, and covers the whole
+  /// exchange, from connecting to reading the body, including redirects.
+  ///
+  /// # Errors
+  /// Returns `Error::ClockUnavailable` if the client has no clock, or an
+  /// error if the request fails
+  pub fn call_timed(self) -> Result<(Response, Duration), Error> {
+    timed(self.client.clock(), || self.call())
+  }
 
-    self
-      .client
-      .request(self.method, &url, &self.headers, body, self.request_config.as_ref())
+  /// Revalidate `cached`, a response received earlier for this URL
+  ///
+  /// Sends its `ETag` as `If-None-Match` and its `Last-Modified` as
+  /// `If-Modified-Since`, replacing any set before. On 304 Not Modified the
+  /// cached response is returned, status and body included, with its header
+  /// fields updated from the 304 (RFC 9111 Section 4.3.4); any other
+  /// response is returned as received.
+  ///
+  /// # Errors
+  /// Returns an error if the request fails
+  pub fn validate_with(
+    mut self,
+    cached: &Response,
+  ) -> Result<Response, Error> {
+    if let Some(etag) = cached.get_header(HeaderName::ETAG) {
+      self.headers.set(HeaderName::IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = cached.get_header(HeaderName::LAST_MODIFIED) {
+      self
+        .headers
+        .set(HeaderName::IF_MODIFIED_SINCE, last_modified);
+    }
+    let is_head = self.method == Method::Head;
+
+    let response = self.call()?;
+    if response.status_code != 304 {
+      return Ok(response);
+    }
+    let mut revalidated = cached.clone();
+    revalidated.update_from_not_modified(&response);
+    if is_head {
+      revalidated.body = Body::empty();
+    }
+    Ok(revalidated)
   }
 
-  /// Force this request to allow a body (e.g., for DELETE with body)
+  /// Attach `body` to a request whose method normally has none
+  ///
+  /// Deliberately bypasses the typestate restriction for APIs that expect a
+  /// body on GET or DELETE; `Content-Length` is set as for any other body.
+  /// RFC 9110 Sections 9.3.1 and 9.3.5: content in such requests has no
+  /// generally defined semantics, and some servers or intermediaries may
+  /// reject the request or drop the body.
   #[must_use]
-  pub fn force_send_body(self) -> ClientRequestBuilder<S, D, WithBody> {
+  pub fn force_send_body(
+    self,
+    body: Vec<u8>,
+  ) -> ClientRequestBuilder<S, D, WithBody> {
     ClientRequestBuilder {
       client: self.client,
       method: self.method,
@@ -378,9 +761,14 @@ where
       headers: self.headers,
       query_params: self.query_params,
       form_data: self.form_data,
-      body: self.body,
+      body: Some(Body::from_bytes(body)),
       version: self.version,
       request_config: self.request_config,
+      cancel_token: self.cancel_token,
+      target_form: self.target_form,
+      upload_progress: self.upload_progress,
+      download_progress: self.download_progress,
+      signer: self.signer,
       _phantom: PhantomData,
     }
   }
@@ -407,6 +795,11 @@ where
       body: None,
       version: Version::HTTP_11,
       request_config: None,
+      cancel_token: None,
+      target_form: TargetForm::Origin,
+      upload_progress: None,
+      download_progress: None,
+      signer: None,
       _phantom: PhantomData,
     }
   }
@@ -417,24 +810,41 @@ where
     mut self,
     data: Vec<u8>,
   ) -> Self {
-    self.body = Some(data);
+    self.body = Some(Body::from_bytes(data));
     self
   }
 
   /// # Errors
   /// Returns an error if the request fails
   pub fn call(self) -> Result<Response, Error> {
-    let url = self.build_url();
+    self.dispatch(None)
+  }
 
-    let body = if self.form_data.is_empty() {
-      self.body
-    } else {
-      Some(self.build_form_body())
-    };
+  /// Send the request and also return how long it took
+  ///
+  /// The duration is measured with the client's `Clock`, using its monotonic
+  /// reading so wall-clock adjustments do not skew it, and covers the whole
+  /// exchange, from connecting to reading the body, including redirects.
+  ///
+  /// # Errors
+  /// Returns `Error::ClockUnavailable` if the client has no clock, or an
+  /// error if the request fails
+  pub fn call_timed(self) -> Result<(Response, Duration), Error> {
+    timed(self.client.clock(), || self.call())
+  }
 
+  /// Call `callback` as the request body is written to the socket
+  ///
+  /// The body is written in pieces; after each one the callback receives the
+  /// bytes sent so far and the body length. A body that is sent again on a
+  /// redirect is reported again from zero.
+  #[must_use]
+  pub fn on_upload_progress(
+    mut self,
+    callback: impl Fn(u64, Option<u64>) + 'static,
+  ) -> Self {
+    self.upload_progress = Some(Box::new(callback));
     self
-      .client
-      .request(self.method, &url, &self.headers, body, self.request_config.as_ref())
   }
 
   /// # Errors
@@ -443,7 +853,7 @@ where
     mut self,
     content: impl Into<String>,
   ) -> Result<Response, Error> {
-    self.body = Some(content.into().into_bytes());
+    self.body = Some(Body::from_string(content.into()));
     self.call()
   }
 
@@ -453,7 +863,7 @@ where
     mut self,
     bytes: Vec<u8>,
   ) -> Result<Response, Error> {
-    self.body = Some(bytes);
+    self.body = Some(Body::from_bytes(bytes));
     self.call()
   }
 
@@ -463,10 +873,25 @@ where
     mut self,
     body: impl IntoBody,
   ) -> Result<Response, Error> {
-    self.body = Some(body.into_body());
+    self.body = Some(Body::from_bytes(body.into_body()));
     self.call()
   }
 
+  /// Send the request with `body` and also return how long it took
+  ///
+  /// See `call_timed` for what the duration covers.
+  ///
+  /// # Errors
+  /// Returns `Error::ClockUnavailable` if the client has no clock, or an
+  /// error if the request fails
+  pub fn send_timed(
+    mut self,
+    body: impl IntoBody,
+  ) -> Result<(Response, Duration), Error> {
+    self.body = Some(Body::from_bytes(body.into_body()));
+    self.call_timed()
+  }
+
   /// # Errors
   /// Returns an error if the request fails
   pub fn send_form<I, K, V>(
@@ -481,14 +906,32 @@ where
     let form_body = Self::build_form_url_encoded(iter);
     self
       .headers
-      .insert(HeaderName::CONTENT_TYPE, "application/x-www-form-urlencoded");
-    self.body = Some(form_body);
+      .set(HeaderName::CONTENT_TYPE, "application/x-www-form-urlencoded");
+    self.body = Some(Body::from_bytes(form_body));
+    self.call()
+  }
+
+  /// Send a static payload without copying it into an owned buffer first
+  ///
+  /// # Errors
+  /// Returns an error if the request fails
+  pub fn send_borrowed(
+    mut self,
+    bytes: &'static [u8],
+  ) -> Result<Response, Error> {
+    self.body = Some(Body::from_static(bytes));
     self.call()
   }
 
+  /// Send the request with an empty body
+  ///
+  /// `Content-Length: 0` is sent explicitly, since some servers refuse a POST
+  /// or PUT without it (RFC 9110 Section 8.6).
+  ///
   /// # Errors
   /// Returns an error if the request fails
-  pub fn send_empty(self) -> Result<Response, Error> {
+  pub fn send_empty(mut self) -> Result<Response, Error> {
+    self.body = Some(Body::empty());
     self.call()
   }
 }
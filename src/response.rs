@@ -1,4 +1,8 @@
+use crate::error::Error;
+use crate::headers::HeaderName;
 use crate::parser::Response as ParsedResponse;
+use crate::parser::cookie::SetCookie;
+use alloc::string::String;
 
 /// Extension trait for HTTP response convenience methods
 ///
@@ -8,19 +12,41 @@ pub trait ResponseExt {
   fn is_success(&self) -> bool;
   /// Check if the response has a 3xx status code
   fn is_redirect(&self) -> bool;
+  /// Check if the response is a 304 Not Modified reply to a conditional request
+  fn is_not_modified(&self) -> bool;
   /// Check if the response has a 4xx status code
   fn is_client_error(&self) -> bool;
   /// Check if the response has a 5xx status code
   fn is_server_error(&self) -> bool;
   /// Get the HTTP status code
   fn status(&self) -> u16;
-  /// Get all Set-Cookie header values from the response
-  fn cookies(&self) -> alloc::vec::Vec<&str>;
+  /// Parse the cookies set by the response's `Set-Cookie` headers
+  ///
+  /// Values that are not valid cookies are skipped. When several set the same
+  /// cookie (same name, domain and path), only the last one is kept, as a
+  /// cookie store would (RFC 6265 Section 5.3).
+  fn cookies(&self) -> alloc::vec::Vec<SetCookie>;
   /// Convert the response body to a UTF-8 string
   ///
   /// # Errors
   /// Returns an error if the response body contains invalid UTF-8.
   fn text(&self) -> Result<alloc::string::String, alloc::string::FromUtf8Error>;
+  /// Convert the response body to a UTF-8 string, never failing
+  ///
+  /// Invalid UTF-8 sequences are replaced with U+FFFD, as the parser does for
+  /// header values. Useful for logging bodies of unknown quality.
+  fn text_lossy(&self) -> alloc::string::String;
+  /// Decode the response body to text, detecting its character encoding
+  ///
+  /// A leading byte order mark picks UTF-8, UTF-16LE or UTF-16BE and is
+  /// stripped. Without one, the `charset` parameter of `Content-Type` is used,
+  /// falling back to UTF-8. Supported charsets are UTF-8, UTF-16 (big-endian
+  /// unless marked otherwise), UTF-16LE, UTF-16BE, ISO-8859-1 and US-ASCII.
+  ///
+  /// # Errors
+  /// Returns `Error::UnsupportedCharset` for any other charset, and
+  /// `Error::Utf8Error` if the body is not valid in its encoding.
+  fn text_smart(&self) -> Result<alloc::string::String, Error>;
   /// Get the response body as a byte slice
   fn bytes(&self) -> &[u8];
   /// Convert the response into its body bytes
@@ -32,8 +58,20 @@ impl ResponseExt for ParsedResponse {
     self.status_code
   }
 
-  fn cookies(&self) -> alloc::vec::Vec<&str> {
-    self.headers.get_all("Set-Cookie")
+  fn cookies(&self) -> alloc::vec::Vec<SetCookie> {
+    let mut cookies: alloc::vec::Vec<SetCookie> = alloc::vec::Vec::new();
+    for cookie in self
+      .headers
+      .get_all(HeaderName::SET_COOKIE)
+      .into_iter()
+      .filter_map(SetCookie::parse)
+    {
+      cookies.retain(|earlier| {
+        !(earlier.name == cookie.name && earlier.domain == cookie.domain && earlier.path == cookie.path)
+      });
+      cookies.push(cookie);
+    }
+    cookies
   }
 
   fn is_success(&self) -> bool {
@@ -44,6 +82,10 @@ impl ResponseExt for ParsedResponse {
     (300..400).contains(&self.status_code)
   }
 
+  fn is_not_modified(&self) -> bool {
+    self.status_code == 304
+  }
+
   fn is_client_error(&self) -> bool {
     (400..500).contains(&self.status_code)
   }
@@ -56,6 +98,29 @@ impl ResponseExt for ParsedResponse {
     self.body.to_string()
   }
 
+  fn text_lossy(&self) -> String {
+    String::from_utf8_lossy(self.body.as_bytes()).into_owned()
+  }
+
+  fn text_smart(&self) -> Result<String, Error> {
+    let body = self.body.as_bytes();
+    if let Some(rest) = body.strip_prefix(b"\xEF\xBB\xBF") {
+      return decode_text(rest, "utf-8");
+    }
+    if let Some(rest) = body.strip_prefix(b"\xFF\xFE") {
+      return decode_text(rest, "utf-16le");
+    }
+    if let Some(rest) = body.strip_prefix(b"\xFE\xFF") {
+      return decode_text(rest, "utf-16be");
+    }
+    let charset = self
+      .headers
+      .get(HeaderName::CONTENT_TYPE)
+      .and_then(charset_param)
+      .unwrap_or("utf-8");
+    decode_text(body, charset)
+  }
+
   fn bytes(&self) -> &[u8] {
     self.body.as_bytes()
   }
@@ -65,6 +130,46 @@ impl ResponseExt for ParsedResponse {
   }
 }
 
+/// Value of the `charset` parameter of a media type, without quotes
+fn charset_param(content_type: &str) -> Option<&str> {
+  content_type.split(';').skip(1).find_map(|param| {
+    let (name, value) = param.split_once('=')?;
+    name
+      .trim()
+      .eq_ignore_ascii_case("charset")
+      .then(|| value.trim().trim_matches('"'))
+  })
+}
+
+/// Decode `bytes` as text in the named character encoding
+fn decode_text(
+  bytes: &[u8],
+  charset: &str,
+) -> Result<String, Error> {
+  match charset.to_ascii_lowercase().as_str() {
+    "utf-8" | "utf8" => Ok(String::from_utf8(bytes.to_vec())?),
+    // RFC 2781 Section 4.3: unmarked UTF-16 is big-endian
+    "utf-16" | "utf-16be" => decode_utf16(bytes, u16::from_be_bytes),
+    "utf-16le" => decode_utf16(bytes, u16::from_le_bytes),
+    "iso-8859-1" | "latin1" | "us-ascii" | "ascii" => Ok(bytes.iter().copied().map(char::from).collect()),
+    _ => Err(Error::UnsupportedCharset),
+  }
+}
+
+fn decode_utf16(
+  bytes: &[u8],
+  to_unit: fn([u8; 2]) -> u16,
+) -> Result<String, Error> {
+  let pairs = bytes.chunks_exact(2);
+  if !pairs.remainder().is_empty() {
+    return Err(Error::Utf8Error);
+  }
+  let units = pairs.map(|pair| <[u8; 2]>::try_from(pair).map_or(0, to_unit));
+  char::decode_utf16(units)
+    .collect::<Result<String, _>>()
+    .map_err(|_| Error::Utf8Error)
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -83,6 +188,9 @@ mod tests {
       headers: Headers::new(),
       body: Body::from_bytes(body.to_vec()),
       trailers: alloc::vec::Vec::new(),
+      remote_addr: None,
+      truncated: false,
+      url: None,
     }
   }
 
@@ -117,6 +225,13 @@ mod tests {
     assert!(!make_response(400, b"").is_redirect());
   }
 
+  #[test]
+  fn is_not_modified_only_for_304() {
+    assert!(make_response(304, b"").is_not_modified());
+    assert!(!make_response(200, b"").is_not_modified());
+    assert!(!make_response(302, b"").is_not_modified());
+  }
+
   #[test]
   fn is_client_error_true_for_4xx() {
     assert!(make_response(400, b"").is_client_error());
@@ -164,12 +279,43 @@ mod tests {
       headers,
       body: Body::from_bytes(alloc::vec![]),
       trailers: alloc::vec::Vec::new(),
+      remote_addr: None,
+      truncated: false,
+      url: None,
     };
 
+    let pairs: alloc::vec::Vec<(String, String)> = response
+      .cookies()
+      .into_iter()
+      .map(|cookie| (cookie.name, cookie.value))
+      .collect();
+    assert_eq!(
+      pairs,
+      [
+        (String::from("session"), String::from("abc")),
+        (String::from("user"), String::from("john"))
+      ]
+    );
+  }
+
+  #[test]
+  fn cookies_keep_last_of_duplicate_names() {
+    let mut response = make_response(200, b"");
+    response.headers.insert("Set-Cookie", "id=first; Path=/");
+    response.headers.insert("Set-Cookie", "theme=dark");
+    response.headers.insert("Set-Cookie", "not a cookie");
+    response.headers.insert("Set-Cookie", "id=second; Path=/");
+    response
+      .headers
+      .insert("Set-Cookie", "id=scoped; Path=/admin");
+
     let cookies = response.cookies();
-    assert_eq!(cookies.len(), 2);
-    assert!(cookies.contains(&"session=abc"));
-    assert!(cookies.contains(&"user=john"));
+    let pairs: alloc::vec::Vec<(&str, &str)> = cookies
+      .iter()
+      .map(|cookie| (cookie.name.as_str(), cookie.value.as_str()))
+      .collect();
+
+    assert_eq!(pairs, [("theme", "dark"), ("id", "second"), ("id", "scoped")]);
   }
 
   #[test]
@@ -178,6 +324,65 @@ mod tests {
     assert_eq!(response.text().unwrap(), "Hello, World!");
   }
 
+  #[test]
+  fn text_lossy_replaces_invalid_utf8() {
+    let response = make_response(200, b"caf\xC3\xA9 \xFF\xC3 ok");
+    assert!(response.text().is_err());
+    assert_eq!(response.text_lossy(), "café \u{FFFD}\u{FFFD} ok");
+    assert_eq!(make_response(200, b"plain").text_lossy(), "plain");
+  }
+
+  fn response_with_content_type(
+    content_type: &str,
+    body: &[u8],
+  ) -> ParsedResponse {
+    let mut response = make_response(200, body);
+    response.headers.insert("Content-Type", content_type);
+    response
+  }
+
+  #[test]
+  fn text_smart_strips_utf8_bom() {
+    let response = make_response(200, b"\xEF\xBB\xBFcaf\xC3\xA9");
+    assert_eq!(response.text_smart().unwrap(), "café");
+  }
+
+  #[test]
+  fn text_smart_decodes_utf16le_bom_over_charset() {
+    let response = response_with_content_type("text/plain; charset=iso-8859-1", b"\xFF\xFEh\x00\xE9\x00=\xD8\x00\xDE");
+    assert_eq!(response.text_smart().unwrap(), "hé😀");
+  }
+
+  #[test]
+  fn text_smart_decodes_utf16be_bom() {
+    let response = make_response(200, b"\xFE\xFF\x00o\x00k");
+    assert_eq!(response.text_smart().unwrap(), "ok");
+  }
+
+  #[test]
+  fn text_smart_honors_content_type_charset() {
+    let latin1 = response_with_content_type("text/html; Charset=\"ISO-8859-1\"", b"caf\xE9");
+    assert_eq!(latin1.text_smart().unwrap(), "café");
+
+    let utf16 = response_with_content_type("text/plain;charset=utf-16le", b"o\x00k\x00");
+    assert_eq!(utf16.text_smart().unwrap(), "ok");
+
+    let plain = response_with_content_type("text/plain", b"caf\xC3\xA9");
+    assert_eq!(plain.text_smart().unwrap(), "café");
+  }
+
+  #[test]
+  fn text_smart_rejects_unsupported_or_invalid_text() {
+    let shift_jis = response_with_content_type("text/plain; charset=Shift_JIS", b"abc");
+    assert!(matches!(shift_jis.text_smart(), Err(Error::UnsupportedCharset)));
+
+    let odd_utf16 = make_response(200, b"\xFF\xFEa\x00b");
+    assert!(matches!(odd_utf16.text_smart(), Err(Error::Utf8Error)));
+
+    let bad_utf8 = make_response(200, b"\xC3\x28");
+    assert!(matches!(bad_utf8.text_smart(), Err(Error::Utf8Error)));
+  }
+
   #[test]
   fn bytes_returns_body_slice() {
     let response = make_response(200, b"test data");
@@ -0,0 +1,136 @@
+#[cfg(feature = "std")]
+extern crate std;
+
+use crate::error::Error;
+use alloc::vec::Vec;
+
+/// Destination for a response body written out while it is read
+///
+/// `call_to_writer` hands the body to the sink piece by piece, so a large
+/// download never has to fit in memory. With the `std` feature every
+/// `std::io::Write` type (files, `Vec<u8>`, `&mut` writers) is a sink.
+pub trait ByteSink {
+  /// Write all of `bytes`
+  ///
+  /// # Errors
+  /// An error aborts the request and is returned to the caller unchanged.
+  fn write_all(
+    &mut self,
+    bytes: &[u8],
+  ) -> Result<(), Error>;
+
+  /// Called once after the whole body has been written
+  ///
+  /// # Errors
+  /// An error is returned to the caller in place of the response.
+  fn finish(&mut self) -> Result<(), Error> {
+    Ok(())
+  }
+
+  /// Check whether the sink wants no more bytes
+  ///
+  /// Checked after each write; once true, the rest of the body is left
+  /// unread and the connection is closed instead of being reused.
+  fn is_full(&self) -> bool {
+    false
+  }
+}
+
+#[cfg(not(feature = "std"))]
+impl ByteSink for Vec<u8> {
+  fn write_all(
+    &mut self,
+    bytes: &[u8],
+  ) -> Result<(), Error> {
+    self.extend_from_slice(bytes);
+    Ok(())
+  }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> ByteSink for W {
+  fn write_all(
+    &mut self,
+    bytes: &[u8],
+  ) -> Result<(), Error> {
+    std::io::Write::write_all(self, bytes).map_err(|_| Error::SinkFailed)
+  }
+}
+
+/// Sink that shows the first bytes of a body to a callback before passing it on
+///
+/// Useful for content sniffing, such as checking a magic number before a
+/// download is written out. `inspect` is called once with the first `n` bytes,
+/// or the whole body if it is shorter; no byte reaches `inner` before that.
+/// Returning an error from `inspect` aborts the request. After the call, the
+/// body, starting with the inspected bytes, is written to `inner` unchanged.
+pub struct PeekSink<W, F> {
+  inner: W,
+  wanted: usize,
+  held: Vec<u8>,
+  inspect: Option<F>,
+}
+
+impl<W, F> PeekSink<W, F>
+where
+  W: ByteSink,
+  F: FnOnce(&[u8]) -> Result<(), Error>,
+{
+  /// Hold back the first `n` bytes written to `inner` until `inspect` has seen them
+  pub const fn new(
+    inner: W,
+    n: usize,
+    inspect: F,
+  ) -> Self {
+    Self {
+      inner,
+      wanted: n,
+      held: Vec::new(),
+      inspect: Some(inspect),
+    }
+  }
+
+  /// Get the sink the body is passed on to
+  pub fn into_inner(self) -> W {
+    self.inner
+  }
+
+  /// Run `inspect` on the held bytes, if it has not run yet, and pass them on
+  fn release(&mut self) -> Result<(), Error> {
+    if let Some(inspect) = self.inspect.take() {
+      inspect(self.held.get(..self.wanted).unwrap_or(&self.held))?;
+      let held = core::mem::take(&mut self.held);
+      self.inner.write_all(&held)?;
+    }
+    Ok(())
+  }
+}
+
+impl<W, F> ByteSink for PeekSink<W, F>
+where
+  W: ByteSink,
+  F: FnOnce(&[u8]) -> Result<(), Error>,
+{
+  fn write_all(
+    &mut self,
+    bytes: &[u8],
+  ) -> Result<(), Error> {
+    if self.inspect.is_none() {
+      return self.inner.write_all(bytes);
+    }
+    self.held.extend_from_slice(bytes);
+    if self.held.len() >= self.wanted {
+      self.release()?;
+    }
+    Ok(())
+  }
+
+  fn finish(&mut self) -> Result<(), Error> {
+    self.release()?;
+    self.inner.finish()
+  }
+
+  fn is_full(&self) -> bool {
+    self.inner.is_full()
+  }
+}
@@ -0,0 +1,8 @@
+use core::time::Duration;
+
+pub trait Sleeper {
+  fn sleep(
+    &self,
+    duration: Duration,
+  );
+}
@@ -0,0 +1,5 @@
+pub mod adapter;
+pub mod sleeper;
+
+pub use adapter::Sleeper;
+pub use sleeper::OsSleeper;
@@ -0,0 +1,55 @@
+use crate::sleep::adapter::Sleeper;
+use core::time::Duration;
+
+/// Operating system sleeper
+///
+/// Blocks the current thread using the platform's native sleep call
+/// (`nanosleep` on Unix, `Sleep` on Windows). On other targets it returns immediately.
+pub struct OsSleeper {
+  _marker: (),
+}
+
+impl OsSleeper {
+  /// Create a new OS sleeper
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { _marker: () }
+  }
+}
+
+impl Default for OsSleeper {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Sleeper for OsSleeper {
+  fn sleep(
+    &self,
+    duration: Duration,
+  ) {
+    #[cfg(windows)]
+    {
+      let millis = u32::try_from(duration.as_millis()).unwrap_or(u32::MAX);
+      unsafe {
+        windows_sys::Win32::System::Threading::Sleep(millis);
+      }
+    }
+    #[cfg(unix)]
+    {
+      #[allow(clippy::cast_lossless, clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+      let request = libc::timespec {
+        tv_sec: duration.as_secs() as libc::time_t,
+        tv_nsec: duration.subsec_nanos() as libc::c_long,
+      };
+      // Best effort: a signal may cut the sleep short, which is fine for backoff
+      unsafe {
+        libc::nanosleep(&raw const request, core::ptr::null_mut());
+      }
+    }
+    #[cfg(not(any(windows, unix)))]
+    {
+      let _ = duration;
+    }
+  }
+}
@@ -10,6 +10,31 @@ pub enum SocketAddr<'a> {
 
 pub trait BlockingSocket: Sized {
   fn new() -> Result<Self, SocketError>;
+  /// Bind the socket to a local address before connecting
+  ///
+  /// Adapters that cannot choose a local address keep the default no-op.
+  fn bind(
+    &mut self,
+    _addr: &SocketAddr<'_>,
+  ) -> Result<(), SocketError> {
+    Ok(())
+  }
+  /// Set the TLS server name for the next `connect`
+  ///
+  /// The client calls this before every connect: with the URL's hostname
+  /// for `https` URLs and with `None` for plain `http`. TLS adapters perform
+  /// the handshake in `connect` and use the name for SNI and certificate
+  /// verification. Plain adapters keep the default no-op.
+  fn set_tls_server_name(
+    &mut self,
+    _server_name: Option<&str>,
+  ) -> Result<(), SocketError> {
+    Ok(())
+  }
+  /// Connect to the given address
+  ///
+  /// After a failed connect the socket must accept another `bind`/`connect`
+  /// attempt, so callers can retry without creating a new socket.
   fn connect(
     &mut self,
     addr: &SocketAddr<'_>,
@@ -18,11 +43,43 @@ pub trait BlockingSocket: Sized {
     &mut self,
     buf: &mut [u8],
   ) -> Result<usize, SocketError>;
+  /// Read, failing with `TimedOut` unless data arrives within `deadline_ms`
+  ///
+  /// `deadline_ms` is the time left, in milliseconds, of the request's total
+  /// `timeout`; the client passes a shrinking value on every read. The
+  /// default sets the read timeout and reads. Adapters with native deadline
+  /// support can override it.
+  fn read_with_deadline(
+    &mut self,
+    buf: &mut [u8],
+    deadline_ms: u32,
+  ) -> Result<usize, SocketError> {
+    self.set_read_timeout(deadline_ms)?;
+    self.read(buf)
+  }
   fn write(
     &mut self,
     buf: &[u8],
   ) -> Result<usize, SocketError>;
   fn shutdown(&mut self) -> Result<(), SocketError>;
+  /// Address of the connected peer
+  ///
+  /// Adapters that cannot report it keep the default and return `None`.
+  fn peer_addr(&self) -> Option<SocketAddr<'static>> {
+    None
+  }
+  /// Apply socket options; flags that are not set are left at their current value
+  ///
+  /// Platform notes for the OS sockets:
+  /// - `TCP_NODELAY` maps to `TCP_NODELAY` everywhere.
+  /// - `KEEPALIVE` maps to `SO_KEEPALIVE`. The idle time is set through
+  ///   `TCP_KEEPIDLE` on Linux, `TCP_KEEPALIVE` on macOS and Windows,
+  ///   and is rounded down to whole seconds (minimum one second).
+  ///   Probe interval and count stay at the OS defaults.
+  /// - `CLOSE_ON_DROP` is ignored; OS sockets always close on drop.
+  ///
+  /// Adapters without socket options return `SocketError::Unsupported`, and
+  /// connections are then made without them.
   fn set_flags(
     &mut self,
     flags: SocketFlags,
@@ -41,6 +41,13 @@ impl BlockingSocket for OsBlockingSocket {
     Self::new()
   }
 
+  fn bind(
+    &mut self,
+    addr: &SocketAddr,
+  ) -> Result<(), SocketError> {
+    self.inner.bind(addr)
+  }
+
   fn connect(
     &mut self,
     addr: &SocketAddr,
@@ -66,6 +73,10 @@ impl BlockingSocket for OsBlockingSocket {
     self.inner.shutdown()
   }
 
+  fn peer_addr(&self) -> Option<SocketAddr<'static>> {
+    self.inner.peer_addr()
+  }
+
   fn set_flags(
     &mut self,
     flags: SocketFlags,
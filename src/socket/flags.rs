@@ -1,20 +1,30 @@
+use core::time::Duration;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SocketFlags {
   bits: u32,
+  keep_alive_idle: Option<Duration>,
 }
 
 impl SocketFlags {
-  pub const TCP_NODELAY: Self = Self { bits: 0b0001 };
-  pub const KEEPALIVE: Self = Self { bits: 0b0010 };
-  pub const REUSEADDR: Self = Self { bits: 0b0100 };
-  pub const CLOSE_ON_DROP: Self = Self { bits: 0b1000 };
+  pub const TCP_NODELAY: Self = Self::from_raw(0b0001);
+  pub const KEEPALIVE: Self = Self::from_raw(0b0010);
+  pub const REUSEADDR: Self = Self::from_raw(0b0100);
+  pub const CLOSE_ON_DROP: Self = Self::from_raw(0b1000);
+
+  const fn from_raw(bits: u32) -> Self {
+    Self {
+      bits,
+      keep_alive_idle: None,
+    }
+  }
 
   pub const fn empty() -> Self {
-    Self { bits: 0 }
+    Self::from_raw(0)
   }
 
   pub const fn all() -> Self {
-    Self { bits: 0b1111 }
+    Self::from_raw(0b1111)
   }
 
   pub const fn bits(self) -> u32 {
@@ -23,14 +33,14 @@ impl SocketFlags {
 
   pub const fn from_bits(bits: u32) -> Option<Self> {
     if bits & !0b1111 == 0 {
-      Some(Self { bits })
+      Some(Self::from_raw(bits))
     } else {
       None
     }
   }
 
   pub const fn from_bits_truncate(bits: u32) -> Self {
-    Self { bits: bits & 0b1111 }
+    Self::from_raw(bits & 0b1111)
   }
 
   pub const fn contains(
@@ -73,6 +83,42 @@ impl SocketFlags {
     }
   }
 
+  /// Whether Nagle's algorithm is disabled (`TCP_NODELAY`)
+  pub const fn no_delay(self) -> bool {
+    self.contains(Self::TCP_NODELAY)
+  }
+
+  #[must_use]
+  pub const fn with_no_delay(
+    mut self,
+    no_delay: bool,
+  ) -> Self {
+    self.set(Self::TCP_NODELAY, no_delay);
+    self
+  }
+
+  /// Idle time before the first keepalive probe, if `KEEPALIVE` is set
+  ///
+  /// `None` with `KEEPALIVE` set leaves the idle time at the OS default.
+  pub const fn keep_alive_idle(self) -> Option<Duration> {
+    if self.contains(Self::KEEPALIVE) {
+      self.keep_alive_idle
+    } else {
+      None
+    }
+  }
+
+  /// Enable `SO_KEEPALIVE` with the given idle time, or disable it with `None`
+  #[must_use]
+  pub const fn with_keep_alive(
+    mut self,
+    keep_alive: Option<Duration>,
+  ) -> Self {
+    self.set(Self::KEEPALIVE, keep_alive.is_some());
+    self.keep_alive_idle = keep_alive;
+    self
+  }
+
   pub const fn is_empty(self) -> bool {
     self.bits == 0
   }
@@ -87,6 +133,7 @@ impl SocketFlags {
   ) -> Self {
     Self {
       bits: self.bits | other.bits,
+      ..self
     }
   }
 
@@ -96,6 +143,7 @@ impl SocketFlags {
   ) -> Self {
     Self {
       bits: self.bits & other.bits,
+      ..self
     }
   }
 
@@ -105,6 +153,7 @@ impl SocketFlags {
   ) -> Self {
     Self {
       bits: self.bits & !other.bits,
+      ..self
     }
   }
 
@@ -114,6 +163,7 @@ impl SocketFlags {
   ) -> Self {
     Self {
       bits: self.bits ^ other.bits,
+      ..self
     }
   }
 }
@@ -0,0 +1,416 @@
+extern crate std;
+
+use crate::error::SocketError;
+use crate::socket::{BlockingSocket, SocketAddr, SocketFlags};
+use crate::util::IpAddr;
+use core::time::Duration;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, Ipv6Addr, Shutdown, TcpStream, ToSocketAddrs};
+
+/// A `std::io` stream that `IoSocket` can open and drive
+///
+/// Only `connect` is required; the other methods default to no-ops, for
+/// streams that have no such setting.
+pub trait IoStream: Read + Write + Sized {
+  /// Open a stream to `addr`
+  ///
+  /// `server_name` is the URL's hostname for `https` URLs and `None` for
+  /// plain `http`, so TLS streams know when to run a handshake.
+  ///
+  /// # Errors
+  /// Any I/O error fails the connection attempt.
+  fn connect(
+    addr: std::net::SocketAddr,
+    server_name: Option<&str>,
+  ) -> io::Result<Self>;
+
+  /// Set or clear the read timeout
+  ///
+  /// # Errors
+  /// Returns an error if the stream rejects the timeout.
+  fn set_read_timeout(
+    &self,
+    _timeout: Option<Duration>,
+  ) -> io::Result<()> {
+    Ok(())
+  }
+
+  /// Set or clear the write timeout
+  ///
+  /// # Errors
+  /// Returns an error if the stream rejects the timeout.
+  fn set_write_timeout(
+    &self,
+    _timeout: Option<Duration>,
+  ) -> io::Result<()> {
+    Ok(())
+  }
+
+  /// Enable or disable Nagle's algorithm
+  ///
+  /// # Errors
+  /// Returns an error if the option cannot be set.
+  fn set_nodelay(
+    &self,
+    _nodelay: bool,
+  ) -> io::Result<()> {
+    Ok(())
+  }
+
+  /// Close the stream in both directions
+  ///
+  /// # Errors
+  /// Returns an error if the stream cannot be shut down.
+  fn shutdown(&mut self) -> io::Result<()> {
+    self.flush()
+  }
+}
+
+impl IoStream for TcpStream {
+  fn connect(
+    addr: std::net::SocketAddr,
+    _server_name: Option<&str>,
+  ) -> io::Result<Self> {
+    Self::connect(addr)
+  }
+
+  fn set_read_timeout(
+    &self,
+    timeout: Option<Duration>,
+  ) -> io::Result<()> {
+    Self::set_read_timeout(self, timeout)
+  }
+
+  fn set_write_timeout(
+    &self,
+    timeout: Option<Duration>,
+  ) -> io::Result<()> {
+    Self::set_write_timeout(self, timeout)
+  }
+
+  fn set_nodelay(
+    &self,
+    nodelay: bool,
+  ) -> io::Result<()> {
+    Self::set_nodelay(self, nodelay)
+  }
+
+  fn shutdown(&mut self) -> io::Result<()> {
+    Self::shutdown(self, Shutdown::Both)
+  }
+}
+
+/// `BlockingSocket` over any `IoStream`
+///
+/// Lets a `std` stream, e.g. `TcpStream` or a TLS stream wrapping one, serve
+/// as the client's socket without writing a full adapter. The stream is
+/// opened in `connect`; timeouts set before that are applied once it exists.
+/// `bind` fails with `SocketError::Unsupported`, as `IoStream::connect` has
+/// no way to choose a local address, and socket flags other than
+/// `TCP_NODELAY` are ignored.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+/// ```no_run
+/// use barehttp::{HttpClient, IoSocket, OsDnsResolver};
+/// use std::net::TcpStream;
+///
+/// let client: HttpClient<IoSocket<TcpStream>, OsDnsResolver> = HttpClient::new_with_adapters(OsDnsResolver::new());
+/// let response = client.get("http://example.com").call()?;
+/// # Ok::<(), barehttp::Error>(())
+/// ```
+pub struct IoSocket<T> {
+  stream: Option<T>,
+  server_name: Option<std::string::String>,
+  read_timeout: Option<Duration>,
+  write_timeout: Option<Duration>,
+}
+
+impl<T> IoSocket<T> {
+  /// Wrap a stream that is already connected
+  ///
+  /// The stream must already lead to the server the client will ask for:
+  /// `connect` keeps it as it is and does not look at the address it is given.
+  #[must_use]
+  pub const fn from_stream(stream: T) -> Self {
+    Self {
+      stream: Some(stream),
+      server_name: None,
+      read_timeout: None,
+      write_timeout: None,
+    }
+  }
+
+  /// The underlying stream, if connected
+  #[must_use]
+  pub const fn get_ref(&self) -> Option<&T> {
+    self.stream.as_ref()
+  }
+
+  /// Take the underlying stream out of the socket
+  #[must_use]
+  pub fn into_inner(self) -> Option<T> {
+    self.stream
+  }
+}
+
+impl<T: IoStream> IoSocket<T> {
+  fn stream(&mut self) -> Result<&mut T, SocketError> {
+    self.stream.as_mut().ok_or(SocketError::NotConnected)
+  }
+}
+
+impl<T: IoStream> BlockingSocket for IoSocket<T> {
+  fn new() -> Result<Self, SocketError> {
+    Ok(Self {
+      stream: None,
+      server_name: None,
+      read_timeout: None,
+      write_timeout: None,
+    })
+  }
+
+  fn bind(
+    &mut self,
+    _addr: &SocketAddr<'_>,
+  ) -> Result<(), SocketError> {
+    Err(SocketError::Unsupported)
+  }
+
+  fn set_tls_server_name(
+    &mut self,
+    server_name: Option<&str>,
+  ) -> Result<(), SocketError> {
+    self.server_name = server_name.map(std::string::String::from);
+    Ok(())
+  }
+
+  fn connect(
+    &mut self,
+    addr: &SocketAddr<'_>,
+  ) -> Result<(), SocketError> {
+    // Already connected, e.g. built with `from_stream`: the stream is taken to
+    // lead to `addr` and kept as it is
+    if self.stream.is_some() {
+      return Ok(());
+    }
+    let target = to_std_addr(addr)?;
+    let stream = T::connect(target, self.server_name.as_deref()).map_err(|e| map_io_error(&e))?;
+    stream
+      .set_read_timeout(self.read_timeout)
+      .map_err(|e| map_io_error(&e))?;
+    stream
+      .set_write_timeout(self.write_timeout)
+      .map_err(|e| map_io_error(&e))?;
+    self.stream = Some(stream);
+    Ok(())
+  }
+
+  fn read(
+    &mut self,
+    buf: &mut [u8],
+  ) -> Result<usize, SocketError> {
+    self.stream()?.read(buf).map_err(|e| map_io_error(&e))
+  }
+
+  fn write(
+    &mut self,
+    buf: &[u8],
+  ) -> Result<usize, SocketError> {
+    let stream = self.stream()?;
+    let written = stream.write(buf).map_err(|e| map_io_error(&e))?;
+    // Buffered streams such as TLS sessions must not sit on request bytes
+    stream.flush().map_err(|e| map_io_error(&e))?;
+    Ok(written)
+  }
+
+  fn shutdown(&mut self) -> Result<(), SocketError> {
+    self.stream()?.shutdown().map_err(|e| map_io_error(&e))
+  }
+
+  fn set_flags(
+    &mut self,
+    flags: SocketFlags,
+  ) -> Result<(), SocketError> {
+    if flags.contains(SocketFlags::TCP_NODELAY) {
+      self
+        .stream()?
+        .set_nodelay(true)
+        .map_err(|e| map_io_error(&e))?;
+    }
+    Ok(())
+  }
+
+  fn set_read_timeout(
+    &mut self,
+    timeout_ms: u32,
+  ) -> Result<(), SocketError> {
+    self.read_timeout = timeout_from_millis(timeout_ms);
+    self.stream.as_ref().map_or(Ok(()), |stream| {
+      stream
+        .set_read_timeout(self.read_timeout)
+        .map_err(|e| map_io_error(&e))
+    })
+  }
+
+  fn set_write_timeout(
+    &mut self,
+    timeout_ms: u32,
+  ) -> Result<(), SocketError> {
+    self.write_timeout = timeout_from_millis(timeout_ms);
+    self.stream.as_ref().map_or(Ok(()), |stream| {
+      stream
+        .set_write_timeout(self.write_timeout)
+        .map_err(|e| map_io_error(&e))
+    })
+  }
+}
+
+/// A zero timeout means "no timeout", as for the OS sockets
+fn timeout_from_millis(timeout_ms: u32) -> Option<Duration> {
+  (timeout_ms > 0).then(|| Duration::from_millis(u64::from(timeout_ms)))
+}
+
+fn to_std_addr(addr: &SocketAddr<'_>) -> Result<std::net::SocketAddr, SocketError> {
+  match *addr {
+    SocketAddr::Ip {
+      addr: IpAddr::V4(octets),
+      port,
+    } => Ok(std::net::SocketAddr::from((Ipv4Addr::from(octets), port))),
+    SocketAddr::Ip {
+      addr: IpAddr::V6(segments),
+      port,
+    } => Ok(std::net::SocketAddr::from((Ipv6Addr::from(segments), port))),
+    SocketAddr::Hostname { host, port } => {
+      let host_str = core::str::from_utf8(host).map_err(|_| SocketError::InvalidAddress)?;
+      (host_str, port)
+        .to_socket_addrs()
+        .map_err(|e| map_io_error(&e))?
+        .next()
+        .ok_or(SocketError::InvalidAddress)
+    },
+  }
+}
+
+/// Map an `io::Error` to the closest `SocketError`
+fn map_io_error(error: &io::Error) -> SocketError {
+  match error.kind() {
+    io::ErrorKind::NotConnected => SocketError::NotConnected,
+    io::ErrorKind::ConnectionRefused => SocketError::ConnectionRefused,
+    io::ErrorKind::ConnectionReset | io::ErrorKind::ConnectionAborted => SocketError::ConnectionReset,
+    io::ErrorKind::BrokenPipe => SocketError::BrokenPipe,
+    io::ErrorKind::TimedOut => SocketError::TimedOut,
+    io::ErrorKind::WouldBlock => SocketError::WouldBlock,
+    io::ErrorKind::Interrupted => SocketError::Interrupted,
+    io::ErrorKind::InvalidInput | io::ErrorKind::AddrNotAvailable => SocketError::InvalidAddress,
+    io::ErrorKind::Unsupported => SocketError::Unsupported,
+    _ => error
+      .raw_os_error()
+      .map_or(SocketError::Unsupported, SocketError::OsError),
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+  use super::*;
+  use std::net::TcpListener;
+  use std::thread;
+
+  #[test]
+  fn tcp_stream_round_trip() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+      let mut request = [0u8; 4];
+      stream.read_exact(&mut request).unwrap();
+      stream.write_all(b"pong").unwrap();
+      request
+    });
+
+    let mut socket = <IoSocket<TcpStream> as BlockingSocket>::new().unwrap();
+    socket.set_read_timeout(5_000).unwrap();
+    socket
+      .connect(&SocketAddr::Ip {
+        addr: IpAddr::V4([127, 0, 0, 1]),
+        port,
+      })
+      .unwrap();
+    socket.set_flags(SocketFlags::TCP_NODELAY).unwrap();
+    assert_eq!(socket.write(b"ping").unwrap(), 4);
+
+    let mut reply = [0u8; 4];
+    let mut filled = 0;
+    while filled < reply.len() {
+      filled += socket.read(reply.get_mut(filled..).unwrap()).unwrap();
+    }
+    assert_eq!(&reply, b"pong");
+    assert_eq!(&server.join().unwrap(), b"ping");
+    assert_eq!(
+      socket.get_ref().unwrap().read_timeout().unwrap(),
+      Some(Duration::from_secs(5))
+    );
+    socket.shutdown().unwrap();
+  }
+
+  #[test]
+  fn connect_keeps_an_open_stream() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let mut socket = <IoSocket<TcpStream> as BlockingSocket>::new().unwrap();
+    let target = SocketAddr::Ip {
+      addr: IpAddr::V4([127, 0, 0, 1]),
+      port,
+    };
+    socket.connect(&target).unwrap();
+    let local = socket.get_ref().unwrap().local_addr().unwrap();
+    drop(listener);
+
+    socket.connect(&target).unwrap();
+
+    assert_eq!(socket.get_ref().unwrap().local_addr().unwrap(), local);
+  }
+
+  #[test]
+  fn bind_is_unsupported() {
+    let mut socket = <IoSocket<TcpStream> as BlockingSocket>::new().unwrap();
+    let local = SocketAddr::Ip {
+      addr: IpAddr::V4([127, 0, 0, 1]),
+      port: 0,
+    };
+    assert_eq!(socket.bind(&local), Err(SocketError::Unsupported));
+  }
+
+  #[test]
+  fn unconnected_socket_reports_not_connected() {
+    let mut socket = <IoSocket<TcpStream> as BlockingSocket>::new().unwrap();
+    assert_eq!(socket.read(&mut [0u8; 1]), Err(SocketError::NotConnected));
+    assert_eq!(socket.write(b"x"), Err(SocketError::NotConnected));
+  }
+
+  #[test]
+  fn io_errors_map_to_socket_errors() {
+    let table = [
+      (io::ErrorKind::ConnectionRefused, SocketError::ConnectionRefused),
+      (io::ErrorKind::ConnectionReset, SocketError::ConnectionReset),
+      (io::ErrorKind::ConnectionAborted, SocketError::ConnectionReset),
+      (io::ErrorKind::BrokenPipe, SocketError::BrokenPipe),
+      (io::ErrorKind::NotConnected, SocketError::NotConnected),
+      (io::ErrorKind::TimedOut, SocketError::TimedOut),
+      (io::ErrorKind::WouldBlock, SocketError::WouldBlock),
+      (io::ErrorKind::Interrupted, SocketError::Interrupted),
+      (io::ErrorKind::InvalidInput, SocketError::InvalidAddress),
+      (io::ErrorKind::AddrNotAvailable, SocketError::InvalidAddress),
+      (io::ErrorKind::Unsupported, SocketError::Unsupported),
+    ];
+    for (kind, expected) in table {
+      assert_eq!(map_io_error(&io::Error::from(kind)), expected, "{kind:?}");
+    }
+    assert_eq!(
+      map_io_error(&io::Error::from_raw_os_error(1234)),
+      SocketError::OsError(1234)
+    );
+  }
+}
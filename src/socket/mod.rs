@@ -1,7 +1,11 @@
 pub mod adapter;
 pub mod blocking;
 pub mod flags;
+#[cfg(feature = "std")]
+pub mod io;
 mod os;
+#[cfg(feature = "tls")]
+pub mod tls;
 
 pub use adapter::BlockingSocket;
 pub use adapter::SocketAddr;
@@ -5,6 +5,8 @@ use libc::{c_int, c_void, sockaddr, sockaddr_in, socklen_t, timeval};
 const fn map_errno(err: c_int) -> SocketError {
   match err {
     libc::ECONNREFUSED => SocketError::ConnectionRefused,
+    libc::ECONNRESET | libc::ECONNABORTED => SocketError::ConnectionReset,
+    libc::EPIPE => SocketError::BrokenPipe,
     libc::ETIMEDOUT => SocketError::TimedOut,
     libc::EWOULDBLOCK => SocketError::WouldBlock,
     libc::EINTR => SocketError::Interrupted,
@@ -85,6 +87,46 @@ impl OsSocket {
     Ok(())
   }
 
+  pub fn bind(
+    &mut self,
+    addr: &SocketAddr,
+  ) -> Result<(), SocketError> {
+    let (ipv4, port) = match addr {
+      SocketAddr::Ip {
+        addr: crate::util::IpAddr::V4(ipv4),
+        port,
+      } => (*ipv4, *port),
+      SocketAddr::Ip {
+        addr: crate::util::IpAddr::V6(_),
+        ..
+      } => return Err(SocketError::Unsupported),
+      SocketAddr::Hostname { .. } => return Err(SocketError::InvalidAddress),
+    };
+
+    unsafe {
+      let mut sockaddr: sockaddr_in = core::mem::zeroed();
+      #[allow(clippy::cast_possible_truncation)]
+      {
+        sockaddr.sin_family = libc::AF_INET as u16;
+      }
+      sockaddr.sin_port = port.to_be();
+      sockaddr.sin_addr.s_addr = u32::from_ne_bytes(ipv4);
+
+      #[allow(clippy::cast_possible_truncation)]
+      let result = libc::bind(
+        self.fd,
+        &raw const sockaddr as *const sockaddr,
+        core::mem::size_of::<sockaddr_in>() as socklen_t,
+      );
+
+      if result < 0 {
+        return Err(get_last_error());
+      }
+    }
+
+    Ok(())
+  }
+
   fn connect_ipv4(
     &mut self,
     addr: [u8; 4],
@@ -107,7 +149,14 @@ impl OsSocket {
       );
 
       if result < 0 {
-        return Err(get_last_error());
+        let err = get_last_error();
+        // A failed connect leaves the socket in an unspecified state; start over with a fresh one.
+        // The connect error is what the caller sees either way: if no fresh socket can be made,
+        // the old one is kept and a retry fails on it
+        if let Ok(fresh) = Self::new() {
+          *self = fresh;
+        }
+        return Err(err);
       }
     }
 
@@ -182,6 +231,28 @@ impl OsSocket {
     Ok(())
   }
 
+  pub fn peer_addr(&self) -> Option<SocketAddr<'static>> {
+    if !self.connected {
+      return None;
+    }
+
+    unsafe {
+      let mut sockaddr: sockaddr_in = core::mem::zeroed();
+      #[allow(clippy::cast_possible_truncation)]
+      let mut len = core::mem::size_of::<sockaddr_in>() as socklen_t;
+      let result = libc::getpeername(self.fd, &raw mut sockaddr as *mut sockaddr, &raw mut len);
+
+      if result < 0 || c_int::from(sockaddr.sin_family) != libc::AF_INET {
+        return None;
+      }
+
+      Some(SocketAddr::Ip {
+        addr: crate::util::IpAddr::V4(sockaddr.sin_addr.s_addr.to_ne_bytes()),
+        port: u16::from_be(sockaddr.sin_port),
+      })
+    }
+  }
+
   pub fn set_flags(
     &mut self,
     flags: SocketFlags,
@@ -217,6 +288,21 @@ impl OsSocket {
         }
       }
 
+      if let Some(idle) = flags.keep_alive_idle() {
+        let val = c_int::try_from(idle.as_secs()).unwrap_or(c_int::MAX).max(1);
+        #[allow(clippy::cast_possible_truncation)]
+        let result = libc::setsockopt(
+          self.fd,
+          libc::IPPROTO_TCP,
+          libc::TCP_KEEPIDLE,
+          &raw const val as *const c_void,
+          core::mem::size_of::<c_int>() as socklen_t,
+        );
+        if result < 0 {
+          return Err(get_last_error());
+        }
+      }
+
       if flags.contains(SocketFlags::REUSEADDR) {
         let val: c_int = 1;
         #[allow(clippy::cast_possible_truncation)]
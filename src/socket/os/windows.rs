@@ -5,8 +5,8 @@ use core::sync::atomic::{AtomicBool, Ordering};
 use windows_sys::Win32::Foundation::TRUE;
 use windows_sys::Win32::Networking::WinSock::{
   AF_INET, INVALID_SOCKET, IPPROTO_TCP, SD_BOTH, SO_KEEPALIVE, SO_RCVTIMEO, SO_REUSEADDR, SO_SNDTIMEO, SOCK_STREAM,
-  SOCKADDR_IN, SOCKET, SOCKET_ERROR, SOL_SOCKET, TCP_NODELAY, WSADATA, WSAGetLastError, WSAStartup, closesocket,
-  connect, recv, send, setsockopt, shutdown, socket,
+  SOCKADDR_IN, SOCKET, SOCKET_ERROR, SOL_SOCKET, TCP_KEEPALIVE, TCP_NODELAY, WSADATA, WSAGetLastError, WSAStartup,
+  bind, closesocket, connect, getpeername, recv, send, setsockopt, shutdown, socket,
 };
 use windows_sys::core::BOOL;
 
@@ -32,6 +32,8 @@ fn ensure_wsa_initialized() -> Result<(), SocketError> {
 const fn map_wsa_error(code: i32) -> SocketError {
   match code {
     10061 => SocketError::ConnectionRefused,
+    10053 | 10054 => SocketError::ConnectionReset,
+    10058 => SocketError::BrokenPipe,
     10060 => SocketError::TimedOut,
     10035 => SocketError::WouldBlock,
     10004 => SocketError::Interrupted,
@@ -116,6 +118,43 @@ impl OsSocket {
     Ok(())
   }
 
+  pub fn bind(
+    &mut self,
+    addr: &SocketAddr,
+  ) -> Result<(), SocketError> {
+    let (ipv4, port) = match addr {
+      SocketAddr::Ip {
+        addr: crate::util::IpAddr::V4(ipv4),
+        port,
+      } => (*ipv4, *port),
+      SocketAddr::Ip {
+        addr: crate::util::IpAddr::V6(_),
+        ..
+      } => return Err(SocketError::Unsupported),
+      SocketAddr::Hostname { .. } => return Err(SocketError::InvalidAddress),
+    };
+
+    unsafe {
+      let mut sockaddr: SOCKADDR_IN = core::mem::zeroed();
+      sockaddr.sin_family = AF_INET;
+      sockaddr.sin_port = port.to_be();
+      sockaddr.sin_addr.S_un.S_addr = u32::from_ne_bytes(ipv4);
+
+      #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+      let result = bind(
+        self.socket,
+        &raw const sockaddr as *const _,
+        core::mem::size_of::<SOCKADDR_IN>() as i32,
+      );
+
+      if result == SOCKET_ERROR {
+        return Err(get_last_wsa_error());
+      }
+    }
+
+    Ok(())
+  }
+
   fn connect_ipv4(
     &mut self,
     addr: [u8; 4],
@@ -137,7 +176,14 @@ impl OsSocket {
       );
 
       if result == SOCKET_ERROR {
-        return Err(get_last_wsa_error());
+        let err = get_last_wsa_error();
+        // A failed connect leaves the socket in an unspecified state; start over with a fresh one.
+        // The connect error is what the caller sees either way: if no fresh socket can be made,
+        // the old one is kept and a retry fails on it
+        if let Ok(fresh) = Self::new() {
+          *self = fresh;
+        }
+        return Err(err);
       }
     }
 
@@ -221,6 +267,28 @@ impl OsSocket {
     Ok(())
   }
 
+  pub fn peer_addr(&self) -> Option<SocketAddr<'static>> {
+    if !self.connected {
+      return None;
+    }
+
+    unsafe {
+      let mut sockaddr: SOCKADDR_IN = core::mem::zeroed();
+      #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+      let mut len = core::mem::size_of::<SOCKADDR_IN>() as i32;
+      let result = getpeername(self.socket, &raw mut sockaddr as *mut _, &raw mut len);
+
+      if result == SOCKET_ERROR || sockaddr.sin_family != AF_INET {
+        return None;
+      }
+
+      Some(SocketAddr::Ip {
+        addr: crate::util::IpAddr::V4(sockaddr.sin_addr.S_un.S_addr.to_ne_bytes()),
+        port: u16::from_be(sockaddr.sin_port),
+      })
+    }
+  }
+
   pub fn set_flags(
     &mut self,
     flags: SocketFlags,
@@ -256,6 +324,21 @@ impl OsSocket {
         }
       }
 
+      if let Some(idle) = flags.keep_alive_idle() {
+        let val = u32::try_from(idle.as_secs()).unwrap_or(u32::MAX).max(1);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let result = setsockopt(
+          self.socket,
+          IPPROTO_TCP,
+          TCP_KEEPALIVE,
+          &raw const val as *const _,
+          core::mem::size_of::<u32>() as i32,
+        );
+        if result == SOCKET_ERROR {
+          return Err(get_last_wsa_error());
+        }
+      }
+
       if flags.contains(SocketFlags::REUSEADDR) {
         let val: BOOL = TRUE;
         #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
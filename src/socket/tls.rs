@@ -0,0 +1,439 @@
+extern crate std;
+
+use crate::error::SocketError;
+use crate::socket::blocking::OsBlockingSocket;
+use crate::socket::{BlockingSocket, SocketAddr, SocketFlags};
+use alloc::string::String;
+use alloc::sync::Arc;
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, RootCertStore};
+use std::io::{self, Read, Write};
+use std::sync::OnceLock;
+
+/// TLS socket built on rustls and the OS socket
+///
+/// `https` URLs get a TLS session: the handshake runs in `connect`, with the
+/// URL's hostname used for SNI and certificate verification against the
+/// Mozilla root store (`webpki-roots`). Plain `http` URLs use the TCP
+/// connection directly, so one client can serve both schemes.
+///
+/// Requires the `tls` feature, which pulls in `std`.
+///
+/// # Examples
+/// ```no_run
+/// use barehttp::{HttpClient, OsDnsResolver, RustlsSocket};
+///
+/// let client: HttpClient<RustlsSocket, OsDnsResolver> = HttpClient::new_with_adapters(OsDnsResolver::new());
+/// let response = client.get("https://example.com").call()?;
+/// # Ok::<(), barehttp::Error>(())
+/// ```
+pub struct RustlsSocket {
+  tcp: OsBlockingSocket,
+  config: Arc<ClientConfig>,
+  server_name: Option<ServerName<'static>>,
+  session: Option<ClientConnection>,
+}
+
+impl RustlsSocket {
+  /// Create a socket that verifies servers with a custom rustls configuration
+  ///
+  /// # Errors
+  /// Returns an error if the underlying OS socket cannot be created.
+  pub fn with_config(config: Arc<ClientConfig>) -> Result<Self, SocketError> {
+    Ok(Self {
+      tcp: OsBlockingSocket::new()?,
+      config,
+      server_name: None,
+      session: None,
+    })
+  }
+
+  /// Shared default configuration: ring crypto and the `webpki-roots` trust anchors
+  fn default_config() -> Result<Arc<ClientConfig>, SocketError> {
+    static CONFIG: OnceLock<Option<Arc<ClientConfig>>> = OnceLock::new();
+    CONFIG
+      .get_or_init(|| {
+        let roots = RootCertStore {
+          roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+          .with_safe_default_protocol_versions()
+          .ok()
+          .map(|builder| Arc::new(builder.with_root_certificates(roots).with_no_client_auth()))
+      })
+      .clone()
+      .ok_or(SocketError::TlsFailed)
+  }
+
+  /// Run the handshake for a freshly connected TCP stream
+  fn handshake(
+    &mut self,
+    server_name: ServerName<'static>,
+  ) -> Result<(), SocketError> {
+    let mut session =
+      ClientConnection::new(Arc::clone(&self.config), server_name).map_err(|_| SocketError::TlsFailed)?;
+    let mut io = TcpIo::new(&mut self.tcp);
+    while session.is_handshaking() {
+      match session.complete_io(&mut io) {
+        Ok((0, 0)) => return Err(SocketError::NotConnected),
+        Ok(_) => {},
+        Err(_) => return Err(io.take_error()),
+      }
+    }
+    self.session = Some(session);
+    Ok(())
+  }
+}
+
+impl BlockingSocket for RustlsSocket {
+  fn new() -> Result<Self, SocketError> {
+    Self::with_config(Self::default_config()?)
+  }
+
+  fn bind(
+    &mut self,
+    addr: &SocketAddr<'_>,
+  ) -> Result<(), SocketError> {
+    self.tcp.bind(addr)
+  }
+
+  fn set_tls_server_name(
+    &mut self,
+    server_name: Option<&str>,
+  ) -> Result<(), SocketError> {
+    self.server_name = server_name
+      .map(|name| ServerName::try_from(String::from(name)).map_err(|_| SocketError::InvalidAddress))
+      .transpose()?;
+    Ok(())
+  }
+
+  fn connect(
+    &mut self,
+    addr: &SocketAddr<'_>,
+  ) -> Result<(), SocketError> {
+    self.session = None;
+    self.tcp.connect(addr)?;
+    self
+      .server_name
+      .clone()
+      .map_or(Ok(()), |server_name| self.handshake(server_name))
+  }
+
+  fn read(
+    &mut self,
+    buf: &mut [u8],
+  ) -> Result<usize, SocketError> {
+    let Some(session) = self.session.as_mut() else {
+      return self.tcp.read(buf);
+    };
+    loop {
+      match session.reader().read(buf) {
+        Ok(n) => return Ok(n),
+        Err(e) if e.kind() == io::ErrorKind::WouldBlock => {},
+        // The TCP stream ended without close_notify, so the data may have been
+        // cut short by an attacker; reading it as a clean end would hide that
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Err(SocketError::ConnectionReset),
+        Err(_) => return Err(SocketError::TlsFailed),
+      }
+      let mut io = TcpIo::new(&mut self.tcp);
+      if session.read_tls(&mut io).is_err() {
+        return Err(io.take_error());
+      }
+      session
+        .process_new_packets()
+        .map_err(|_| SocketError::TlsFailed)?;
+    }
+  }
+
+  fn write(
+    &mut self,
+    buf: &[u8],
+  ) -> Result<usize, SocketError> {
+    let Some(session) = self.session.as_mut() else {
+      return self.tcp.write(buf);
+    };
+    let written = session
+      .writer()
+      .write(buf)
+      .map_err(|_| SocketError::TlsFailed)?;
+    let mut io = TcpIo::new(&mut self.tcp);
+    while session.wants_write() {
+      if session.write_tls(&mut io).is_err() {
+        return Err(io.take_error());
+      }
+    }
+    Ok(written)
+  }
+
+  fn shutdown(&mut self) -> Result<(), SocketError> {
+    if let Some(mut session) = self.session.take() {
+      session.send_close_notify();
+      let mut io = TcpIo::new(&mut self.tcp);
+      // Best effort: the peer may already be gone
+      while session.wants_write() && session.write_tls(&mut io).is_ok() {}
+    }
+    self.tcp.shutdown()
+  }
+
+  fn peer_addr(&self) -> Option<SocketAddr<'static>> {
+    self.tcp.peer_addr()
+  }
+
+  fn set_flags(
+    &mut self,
+    flags: SocketFlags,
+  ) -> Result<(), SocketError> {
+    self.tcp.set_flags(flags)
+  }
+
+  fn set_read_timeout(
+    &mut self,
+    timeout_ms: u32,
+  ) -> Result<(), SocketError> {
+    BlockingSocket::set_read_timeout(&mut self.tcp, timeout_ms)
+  }
+
+  fn set_write_timeout(
+    &mut self,
+    timeout_ms: u32,
+  ) -> Result<(), SocketError> {
+    BlockingSocket::set_write_timeout(&mut self.tcp, timeout_ms)
+  }
+}
+
+/// `std::io` view of the TCP socket for rustls, keeping the original socket error
+struct TcpIo<'a> {
+  socket: &'a mut OsBlockingSocket,
+  error: Option<SocketError>,
+}
+
+impl<'a> TcpIo<'a> {
+  const fn new(socket: &'a mut OsBlockingSocket) -> Self {
+    Self { socket, error: None }
+  }
+
+  /// Socket error behind the last failed I/O call, or a TLS error if there was none
+  const fn take_error(&mut self) -> SocketError {
+    match self.error.take() {
+      Some(error) => error,
+      None => SocketError::TlsFailed,
+    }
+  }
+}
+
+impl Read for TcpIo<'_> {
+  fn read(
+    &mut self,
+    buf: &mut [u8],
+  ) -> io::Result<usize> {
+    self.socket.read(buf).map_err(|error| {
+      self.error = Some(error);
+      io::Error::other("socket read failed")
+    })
+  }
+}
+
+impl Write for TcpIo<'_> {
+  fn write(
+    &mut self,
+    buf: &[u8],
+  ) -> io::Result<usize> {
+    self.socket.write(buf).map_err(|error| {
+      self.error = Some(error);
+      io::Error::other("socket write failed")
+    })
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+mod tests {
+  use super::*;
+  use crate::util::IpAddr;
+  use alloc::vec::Vec;
+  use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+  use rustls::{ServerConfig, ServerConnection, StreamOwned};
+  use std::net::TcpListener;
+  use std::thread;
+
+  const CA: &[u8] = include_bytes!("../../tests/fixtures/tls_ca.der");
+  const LEAF: &[u8] = include_bytes!("../../tests/fixtures/tls_localhost.der");
+  const LEAF_KEY: &[u8] = include_bytes!("../../tests/fixtures/tls_localhost.key.der");
+
+  fn provider() -> Arc<rustls::crypto::CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+  }
+
+  /// Serve one connection, answering the request with `reply`; returns the request bytes
+  fn serve(
+    tls: bool,
+    reply: &'static [u8],
+  ) -> (u16, thread::JoinHandle<Vec<u8>>) {
+    serve_closing(tls, true, reply)
+  }
+
+  /// Like `serve`, with TLS sending `close_notify` after `reply` only if `close_notify` is set
+  fn serve_closing(
+    tls: bool,
+    close_notify: bool,
+    reply: &'static [u8],
+  ) -> (u16, thread::JoinHandle<Vec<u8>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = thread::spawn(move || {
+      let (mut tcp, _) = listener.accept().unwrap();
+      if tls {
+        let config = ServerConfig::builder_with_provider(provider())
+          .with_safe_default_protocol_versions()
+          .unwrap()
+          .with_no_client_auth()
+          .with_single_cert(
+            alloc::vec![CertificateDer::from(LEAF)],
+            PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(LEAF_KEY)),
+          )
+          .unwrap();
+        let session = ServerConnection::new(Arc::new(config)).unwrap();
+        let mut stream = StreamOwned::new(session, tcp);
+        let request = exchange(&mut stream, reply);
+        if close_notify {
+          stream.conn.send_close_notify();
+          let _ = stream.flush();
+        }
+        request
+      } else {
+        exchange(&mut tcp, reply)
+      }
+    });
+    (port, handle)
+  }
+
+  /// Read a request from `stream` and write `reply`; returns the request bytes
+  fn exchange(
+    stream: &mut dyn ReadWrite,
+    reply: &[u8],
+  ) -> Vec<u8> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.ends_with(b"\r\n\r\n") {
+      let Ok(n @ 1..) = stream.read(&mut buf) else {
+        return request;
+      };
+      request.extend_from_slice(&buf[..n]);
+    }
+    stream.write_all(reply).unwrap();
+    stream.flush().unwrap();
+    request
+  }
+
+  trait ReadWrite: Read + Write {}
+  impl<T: Read + Write> ReadWrite for T {}
+
+  fn trusting_test_ca() -> Arc<ClientConfig> {
+    let mut roots = RootCertStore::empty();
+    roots.add(CertificateDer::from(CA)).unwrap();
+    Arc::new(
+      ClientConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots)
+        .with_no_client_auth(),
+    )
+  }
+
+  fn local(port: u16) -> SocketAddr<'static> {
+    SocketAddr::Ip {
+      addr: IpAddr::V4([127, 0, 0, 1]),
+      port,
+    }
+  }
+
+  fn read_to_end(socket: &mut RustlsSocket) -> Vec<u8> {
+    let mut response = Vec::new();
+    let mut buf = [0u8; 256];
+    loop {
+      match socket.read(&mut buf).unwrap() {
+        0 => return response,
+        n => response.extend_from_slice(&buf[..n]),
+      }
+    }
+  }
+
+  #[test]
+  fn handshake_uses_server_name_and_carries_http() {
+    let (port, server) = serve(
+      true,
+      b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+    );
+    let mut socket = RustlsSocket::with_config(trusting_test_ca()).unwrap();
+    socket.set_tls_server_name(Some("localhost")).unwrap();
+    socket.connect(&local(port)).unwrap();
+
+    let request = b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n";
+    assert_eq!(socket.write(request).unwrap(), request.len());
+    let response = read_to_end(&mut socket);
+    socket.shutdown().unwrap();
+
+    assert!(response.ends_with(b"\r\n\r\nok"));
+    assert_eq!(server.join().unwrap(), request);
+  }
+
+  #[test]
+  fn stream_closed_without_close_notify_is_an_error() {
+    let (port, server) = serve_closing(true, false, b"HTTP/1.1 200 OK\r\n\r\ncut");
+    let mut socket = RustlsSocket::with_config(trusting_test_ca()).unwrap();
+    socket.set_tls_server_name(Some("localhost")).unwrap();
+    socket.connect(&local(port)).unwrap();
+    socket
+      .write(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+      .unwrap();
+    server.join().unwrap();
+
+    let mut buf = [0u8; 256];
+    let end = loop {
+      match socket.read(&mut buf) {
+        Ok(1..) => {},
+        end => break end,
+      }
+    };
+    assert_eq!(end, Err(SocketError::ConnectionReset));
+  }
+
+  #[test]
+  fn wrong_server_name_fails_verification() {
+    let (port, server) = serve(true, b"");
+    let mut socket = RustlsSocket::with_config(trusting_test_ca()).unwrap();
+    socket.set_tls_server_name(Some("example.com")).unwrap();
+
+    assert_eq!(socket.connect(&local(port)), Err(SocketError::TlsFailed));
+    drop(socket);
+    let _ = server.join();
+  }
+
+  #[test]
+  fn default_roots_reject_untrusted_certificate() {
+    let (port, server) = serve(true, b"");
+    let mut socket = <RustlsSocket as BlockingSocket>::new().unwrap();
+    socket.set_tls_server_name(Some("localhost")).unwrap();
+
+    assert_eq!(socket.connect(&local(port)), Err(SocketError::TlsFailed));
+    drop(socket);
+    let _ = server.join();
+  }
+
+  #[test]
+  fn without_server_name_the_connection_stays_plain() {
+    let (port, server) = serve(false, b"HTTP/1.1 204 No Content\r\n\r\n");
+    let mut socket = RustlsSocket::with_config(trusting_test_ca()).unwrap();
+    socket.set_tls_server_name(None).unwrap();
+    socket.connect(&local(port)).unwrap();
+
+    let request = b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n";
+    socket.write(request).unwrap();
+    assert_eq!(read_to_end(&mut socket), b"HTTP/1.1 204 No Content\r\n\r\n");
+    assert_eq!(server.join().unwrap(), request);
+  }
+}
@@ -0,0 +1,256 @@
+extern crate std;
+
+use crate::client::HttpClient;
+use crate::config::Config;
+use crate::dns::DnsResolver;
+use crate::dns::resolver::OsDnsResolver;
+use crate::error::SocketError;
+use crate::socket::blocking::OsBlockingSocket;
+use crate::socket::{BlockingSocket, SocketAddr, SocketFlags};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use spin::Mutex;
+
+/// Bytes sent and received for one request on a connection
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Exchange {
+  /// Request bytes exactly as written to the socket
+  pub request: Vec<u8>,
+  /// Response bytes exactly as read from the socket, framing included
+  pub response: Vec<u8>,
+}
+
+type Log = Arc<Mutex<Vec<Exchange>>>;
+
+std::thread_local! {
+  /// Log that sockets created by `RecordingClient::record` on this thread write to
+  static ACTIVE_LOG: RefCell<Option<Log>> = const { RefCell::new(None) };
+}
+
+/// Socket that forwards to `S` and records every byte written and read
+///
+/// Created by the client of a `RecordingClient`; a socket created outside
+/// `RecordingClient::record` records nothing.
+pub struct RecordingSocket<S> {
+  inner: S,
+  log: Option<Log>,
+  exchange: Option<usize>,
+  responded: bool,
+}
+
+impl<S> RecordingSocket<S> {
+  /// Add written bytes, starting a new exchange after a response was read
+  fn record_request(
+    &mut self,
+    bytes: &[u8],
+  ) {
+    let Some(log) = &self.log else {
+      return;
+    };
+    let mut exchanges = log.lock();
+    match self.exchange.and_then(|index| exchanges.get_mut(index)) {
+      Some(exchange) if !self.responded => exchange.request.extend_from_slice(bytes),
+      _ => {
+        self.exchange = Some(exchanges.len());
+        self.responded = false;
+        exchanges.push(Exchange {
+          request: Vec::from(bytes),
+          response: Vec::new(),
+        });
+      },
+    }
+  }
+
+  /// Add read bytes to the current exchange
+  fn record_response(
+    &mut self,
+    bytes: &[u8],
+  ) {
+    let Some(log) = &self.log else {
+      return;
+    };
+    let mut exchanges = log.lock();
+    if let Some(exchange) = self.exchange.and_then(|index| exchanges.get_mut(index)) {
+      exchange.response.extend_from_slice(bytes);
+    } else {
+      self.exchange = Some(exchanges.len());
+      exchanges.push(Exchange {
+        request: Vec::new(),
+        response: Vec::from(bytes),
+      });
+    }
+    self.responded = true;
+  }
+}
+
+impl<S: BlockingSocket> BlockingSocket for RecordingSocket<S> {
+  fn new() -> Result<Self, SocketError> {
+    Ok(Self {
+      inner: S::new()?,
+      log: ACTIVE_LOG.with(|active| active.borrow().clone()),
+      exchange: None,
+      responded: false,
+    })
+  }
+
+  fn bind(
+    &mut self,
+    addr: &SocketAddr<'_>,
+  ) -> Result<(), SocketError> {
+    self.inner.bind(addr)
+  }
+
+  fn set_tls_server_name(
+    &mut self,
+    server_name: Option<&str>,
+  ) -> Result<(), SocketError> {
+    self.inner.set_tls_server_name(server_name)
+  }
+
+  fn connect(
+    &mut self,
+    addr: &SocketAddr<'_>,
+  ) -> Result<(), SocketError> {
+    self.inner.connect(addr)
+  }
+
+  fn read(
+    &mut self,
+    buf: &mut [u8],
+  ) -> Result<usize, SocketError> {
+    let n = self.inner.read(buf)?;
+    self.record_response(buf.get(..n).unwrap_or_default());
+    Ok(n)
+  }
+
+  fn read_with_deadline(
+    &mut self,
+    buf: &mut [u8],
+    deadline_ms: u32,
+  ) -> Result<usize, SocketError> {
+    let n = self.inner.read_with_deadline(buf, deadline_ms)?;
+    self.record_response(buf.get(..n).unwrap_or_default());
+    Ok(n)
+  }
+
+  fn write(
+    &mut self,
+    buf: &[u8],
+  ) -> Result<usize, SocketError> {
+    let n = self.inner.write(buf)?;
+    self.record_request(buf.get(..n).unwrap_or_default());
+    Ok(n)
+  }
+
+  fn shutdown(&mut self) -> Result<(), SocketError> {
+    self.inner.shutdown()
+  }
+
+  fn peer_addr(&self) -> Option<SocketAddr<'static>> {
+    self.inner.peer_addr()
+  }
+
+  fn set_flags(
+    &mut self,
+    flags: SocketFlags,
+  ) -> Result<(), SocketError> {
+    self.inner.set_flags(flags)
+  }
+
+  fn set_read_timeout(
+    &mut self,
+    timeout_ms: u32,
+  ) -> Result<(), SocketError> {
+    self.inner.set_read_timeout(timeout_ms)
+  }
+
+  fn set_write_timeout(
+    &mut self,
+    timeout_ms: u32,
+  ) -> Result<(), SocketError> {
+    self.inner.set_write_timeout(timeout_ms)
+  }
+}
+
+/// Client wrapper that records the exact bytes of every exchange
+///
+/// Make requests inside `record`; afterwards `last_request_bytes` and
+/// `last_response_bytes` hold what went over the wire for the last one,
+/// the final hop when redirects were followed.
+///
+/// ```no_run
+/// use barehttp::config::Config;
+/// use barehttp::test_util::RecordingClient;
+/// use barehttp::OsDnsResolver;
+///
+/// let recorder: RecordingClient = RecordingClient::with_adapters_and_config(OsDnsResolver::new(), Config::default());
+/// recorder.record(|client| client.get("http://example.com/").call())?;
+/// let request = recorder.last_request_bytes().unwrap_or_default();
+/// assert!(request.starts_with(b"GET / HTTP/1.1\r\n"));
+/// # Ok::<(), barehttp::Error>(())
+/// ```
+pub struct RecordingClient<S = OsBlockingSocket, D = OsDnsResolver> {
+  client: HttpClient<RecordingSocket<S>, D>,
+  log: Log,
+}
+
+impl<S, D> RecordingClient<S, D>
+where
+  S: BlockingSocket,
+  D: DnsResolver,
+{
+  /// Create a recording client with the given adapters and configuration
+  #[must_use]
+  pub fn with_adapters_and_config(
+    dns: D,
+    config: Config,
+  ) -> Self {
+    Self {
+      client: HttpClient::with_adapters_and_config(dns, config),
+      log: Arc::new(Mutex::new(Vec::new())),
+    }
+  }
+
+  /// Run `requests` with the wrapped client, recording their traffic
+  ///
+  /// Sockets opened while `requests` runs record into this client's log,
+  /// including when they are reused from the pool later. The log is tied to
+  /// them through a thread-local, so `requests` should not hand the client
+  /// to other threads.
+  pub fn record<T>(
+    &self,
+    requests: impl FnOnce(&HttpClient<RecordingSocket<S>, D>) -> T,
+  ) -> T {
+    let previous = ACTIVE_LOG.with(|active| active.replace(Some(Arc::clone(&self.log))));
+    let result = requests(&self.client);
+    ACTIVE_LOG.with(|active| active.replace(previous));
+    result
+  }
+
+  /// Every exchange recorded so far, oldest first
+  #[must_use]
+  pub fn exchanges(&self) -> Vec<Exchange> {
+    self.log.lock().clone()
+  }
+
+  /// Request bytes of the most recent exchange
+  #[must_use]
+  pub fn last_request_bytes(&self) -> Option<Vec<u8>> {
+    self
+      .log
+      .lock()
+      .last()
+      .map(|exchange| exchange.request.clone())
+  }
+
+  /// Raw response bytes of the most recent exchange
+  #[must_use]
+  pub fn last_response_bytes(&self) -> Option<Vec<u8>> {
+    self
+      .log
+      .lock()
+      .last()
+      .map(|exchange| exchange.response.clone())
+  }
+}
@@ -1,9 +1,15 @@
-use crate::error::Error;
+use crate::cancel::CancelToken;
+use crate::clock::Clock;
+use crate::error::{Error, ParseError, SocketError};
 use crate::headers::{HeaderName, Headers};
-use crate::parser::framing::FramingDetector;
+use crate::parser::chunked::{ChunkedDecoder, DEFAULT_MAX_CHUNK_LINE_LENGTH};
+use crate::parser::content_coding::{ContentDecoder, DecodingSink};
+use crate::parser::framing::HeaderScanner;
 use crate::parser::{BodyReadStrategy, Response};
-use crate::socket::BlockingSocket;
+use crate::sink::ByteSink;
+use crate::socket::{BlockingSocket, SocketAddr};
 use crate::transport::connection_state::ConnectionState;
+use crate::util::IpAddr;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -19,17 +25,69 @@ pub enum ResponseBodyExpectation {
 /// Raw HTTP response without policy interpretation
 #[derive(Debug, Clone)]
 pub struct RawResponse {
+  /// Status code from the status line
   pub status_code: u16,
+  /// Reason phrase from the status line, possibly empty
   pub reason: String,
+  /// Header fields in the order received
   pub headers: Headers,
+  /// Body as received: chunked framing and content codings are left in place
   pub body_bytes: Vec<u8>,
+  /// Address of the peer the response was read from, if the socket reports it
+  pub remote_addr: Option<IpAddr>,
+  /// The body went to a `BodySink` and `body_bytes` is empty
+  pub body_streamed: bool,
+  /// Trailer fields that ended a chunked body, whether buffered or streamed
+  pub trailers: Vec<(String, String)>,
 }
 
+/// Where to write a response body instead of buffering it
+pub struct BodySink<'s> {
+  /// Receives the body with its transfer coding and content codings removed
+  pub sink: &'s mut dyn ByteSink,
+  /// Decides from the status code and headers whether a response's body goes
+  /// to `sink`, returning the decoder for the content codings to remove
+  pub accept: &'s dyn Fn(u16, &Headers) -> Option<ContentDecoder>,
+}
+
+/// Callback told how many body bytes have been transferred so far, and the total if known
+pub type ProgressFn = dyn Fn(u64, Option<u64>);
+
+/// Optional progress callbacks for one request/response exchange
+#[derive(Clone, Copy, Default)]
+pub struct TransferProgress<'p> {
+  /// Called after each write of the request body
+  pub upload: Option<&'p (dyn Fn(u64, Option<u64>) + 'p)>,
+  /// Called as response body bytes arrive, with the `Content-Length` as the total
+  pub download: Option<&'p (dyn Fn(u64, Option<u64>) + 'p)>,
+}
+
+/// Request bodies are written in pieces of this size so upload progress can be reported
+const UPLOAD_CHUNK_SIZE: usize = 16 * 1024;
+
 /// A single live HTTP connection (policy-free I/O operations)
+#[allow(clippy::struct_excessive_bools)]
 pub struct Connection<'a, S> {
   socket: &'a mut S,
   max_header_size: usize,
+  max_header_count: Option<usize>,
+  max_chunk_line_length: Option<usize>,
+  max_body_size: Option<usize>,
+  max_chunk_count: Option<usize>,
   state: ConnectionState,
+  /// Either side sent "Connection: close"
+  closing: bool,
+  cancel: Option<CancelToken>,
+  lenient_parsing: bool,
+  discard_surplus: bool,
+  progress: TransferProgress<'a>,
+  body_received: u64,
+  body_total: Option<u64>,
+  body_complete: bool,
+  /// Bytes read after an interim response, the start of the next one
+  carried: Vec<u8>,
+  deadline: Option<(&'a dyn Clock, u64)>,
+  read_timeout_ms: Option<u32>,
 }
 
 impl<'a, S: BlockingSocket> Connection<'a, S> {
@@ -40,17 +98,159 @@ impl<'a, S: BlockingSocket> Connection<'a, S> {
     Self {
       socket,
       max_header_size,
+      max_header_count: None,
+      max_chunk_line_length: Some(DEFAULT_MAX_CHUNK_LINE_LENGTH),
+      max_body_size: None,
+      max_chunk_count: None,
       state: ConnectionState::new(),
+      closing: false,
+      cancel: None,
+      lenient_parsing: false,
+      discard_surplus: false,
+      progress: TransferProgress {
+        upload: None,
+        download: None,
+      },
+      body_received: 0,
+      body_total: None,
+      body_complete: true,
+      carried: Vec::new(),
+      deadline: None,
+      read_timeout_ms: None,
     }
   }
 
+  /// Fail reads with `TimedOut` once `clock` reaches `deadline_millis`
+  ///
+  /// Each read is given only the time left until the deadline, so a slow
+  /// server cannot stretch the request past it one read at a time.
+  #[must_use]
+  pub const fn with_deadline(
+    mut self,
+    clock: &'a dyn Clock,
+    deadline_millis: u64,
+  ) -> Self {
+    self.deadline = Some((clock, deadline_millis));
+    self
+  }
+
+  /// Per-read timeout already set on the socket, which deadline reads never exceed
+  #[must_use]
+  pub const fn with_read_timeout(
+    mut self,
+    timeout_ms: Option<u32>,
+  ) -> Self {
+    self.read_timeout_ms = timeout_ms;
+    self
+  }
+
+  /// Reject responses with more header fields than `max`
+  #[must_use]
+  pub const fn with_max_header_count(
+    mut self,
+    max: Option<usize>,
+  ) -> Self {
+    self.max_header_count = max;
+    self
+  }
+
+  /// Reject chunked bodies with a chunk-size line longer than `max`
+  #[must_use]
+  pub const fn with_max_chunk_line_length(
+    mut self,
+    max: Option<usize>,
+  ) -> Self {
+    self.max_chunk_line_length = max;
+    self
+  }
+
+  /// Reject response bodies read into memory that are larger than `max` bytes
+  ///
+  /// Bodies streamed to a `BodySink` are not limited.
+  #[must_use]
+  pub const fn with_max_body_size(
+    mut self,
+    max: Option<usize>,
+  ) -> Self {
+    self.max_body_size = max;
+    self
+  }
+
+  /// Reject chunked bodies with more than `max` chunks
+  #[must_use]
+  pub const fn with_max_chunk_count(
+    mut self,
+    max: Option<usize>,
+  ) -> Self {
+    self.max_chunk_count = max;
+    self
+  }
+
+  /// Accept slightly malformed status lines from non-compliant servers
+  #[must_use]
+  pub const fn with_lenient_parsing(
+    mut self,
+    lenient: bool,
+  ) -> Self {
+    self.lenient_parsing = lenient;
+    self
+  }
+
+  /// Drop bytes sent after a response's framing ends instead of failing;
+  /// the connection is then closed
+  #[must_use]
+  pub const fn with_discard_surplus(
+    mut self,
+    discard: bool,
+  ) -> Self {
+    self.discard_surplus = discard;
+    self
+  }
+
+  /// Abort reads with `Error::Cancelled` once the token is cancelled
+  #[must_use]
+  pub fn with_cancel_token(
+    mut self,
+    token: Option<CancelToken>,
+  ) -> Self {
+    self.cancel = token;
+    self
+  }
+
+  /// Report body transfer progress to the given callbacks
+  #[must_use]
+  pub const fn with_progress(
+    mut self,
+    progress: TransferProgress<'a>,
+  ) -> Self {
+    self.progress = progress;
+    self
+  }
+
+  /// Fail if the attached cancel token has been triggered
+  fn check_cancelled(&self) -> Result<(), Error> {
+    if self.cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+      return Err(Error::Cancelled);
+    }
+    Ok(())
+  }
+
+  /// Where the connection is in its request/response cycle
+  #[cfg(test)]
+  pub(crate) const fn state(&self) -> ConnectionState {
+    self.state
+  }
+
   /// Send HTTP request bytes to the socket
   ///
+  /// Fails with `Error::ConnectionUnusable` once the connection is closed or broken.
   pub fn send_request(
     &mut self,
     request_bytes: &[u8],
   ) -> Result<(), Error> {
-    self.socket.write(request_bytes).map_err(Error::Socket)?;
+    self.state.begin_send()?;
+    let result = self.write_all(request_bytes);
+    self.track(result)?;
 
     // RFC 9112 Section 9.6: If the client sends "Connection: close", it MUST NOT
     // send further requests on that connection.
@@ -61,12 +261,60 @@ impl<'a, S: BlockingSocket> Connection<'a, S> {
       .windows("connection: close".len())
       .any(|w| w.eq_ignore_ascii_case(b"connection: close"))
     {
-      self.state.mark_sent_close();
+      self.closing = true;
     }
 
     Ok(())
   }
 
+  /// Send a request body, reporting upload progress after each piece
+  ///
+  /// Call after `send_request` has written the request head.
+  pub fn send_body(
+    &mut self,
+    body: &[u8],
+  ) -> Result<(), Error> {
+    self.state.begin_send()?;
+    let total = body.len() as u64;
+    let mut sent = 0u64;
+    for piece in body.chunks(UPLOAD_CHUNK_SIZE) {
+      let result = self.check_cancelled().and_then(|()| self.write_all(piece));
+      self.track(result)?;
+      sent += piece.len() as u64;
+      if let Some(report) = self.progress.upload {
+        report(sent, Some(total));
+      }
+    }
+    Ok(())
+  }
+
+  /// Mark the connection broken if `result` is an error
+  const fn track<T>(
+    &mut self,
+    result: Result<T, Error>,
+  ) -> Result<T, Error> {
+    if result.is_err() {
+      self.state.mark_broken();
+    }
+    result
+  }
+
+  /// Write all of `bytes`, retrying after short writes
+  fn write_all(
+    &mut self,
+    bytes: &[u8],
+  ) -> Result<(), Error> {
+    let mut rest = bytes;
+    while !rest.is_empty() {
+      let written = self.socket.write(rest).map_err(Error::Socket)?;
+      if written == 0 {
+        return Err(Error::Socket(SocketError::NotConnected));
+      }
+      rest = rest.get(written..).unwrap_or_default();
+    }
+    Ok(())
+  }
+
   /// Read complete HTTP response (headers + body) with HTTP protocol awareness
   ///
   /// The `expectation` parameter handles protocol-level body semantics:
@@ -77,23 +325,47 @@ impl<'a, S: BlockingSocket> Connection<'a, S> {
   pub fn read_raw_response(
     &mut self,
     expectation: ResponseBodyExpectation,
+  ) -> Result<RawResponse, Error> {
+    self.read_raw_response_into(expectation, None)
+  }
+
+  /// Read a response like `read_raw_response`, writing the body to `body_sink`
+  /// when its `accept` check passes
+  ///
+  /// A streamed body is never held in memory as a whole; the returned
+  /// response has `body_streamed` set and an empty `body_bytes`.
+  ///
+  /// Any error leaves the connection `Broken`. Otherwise it becomes `Idle` when
+  /// it can carry another request, `Closed` when it cannot, or keeps waiting for
+  /// the final response after an interim one.
+  pub fn read_raw_response_into(
+    &mut self,
+    expectation: ResponseBodyExpectation,
+    body_sink: Option<BodySink<'_>>,
+  ) -> Result<RawResponse, Error> {
+    self.state.begin_read()?;
+    let result = self.read_response(expectation, body_sink);
+    let raw = self.track(result)?;
+    self
+      .state
+      .finish_response(raw.status_code, !self.closing && self.body_complete);
+    Ok(raw)
+  }
+
+  fn read_response(
+    &mut self,
+    expectation: ResponseBodyExpectation,
+    body_sink: Option<BodySink<'_>>,
   ) -> Result<RawResponse, Error> {
     let max_header_size = self.max_header_size;
     let mut buffer = alloc::vec![0u8; max_header_size.min(8192)];
-    let mut total_read = 0usize;
-    let mut header_buffer = Vec::new();
-
-    loop {
-      let n = match self.socket.read(&mut buffer) {
-        Ok(n) => n,
-        Err(e) => {
-          // RFC 9112 Section 9.5: If timing out, implementation SHOULD issue a graceful close
-          if e == crate::error::SocketError::TimedOut {
-            let _ = self.socket.shutdown();
-          }
-          return Err(Error::Socket(e));
-        },
-      };
+    let mut header_buffer = core::mem::take(&mut self.carried);
+    let mut total_read = header_buffer.len();
+    let mut scanner = HeaderScanner::new();
+    let mut header_end = scanner.scan(&header_buffer);
+
+    while header_end.is_none() {
+      let n = self.read_socket(&mut buffer)?;
       if n == 0 {
         break;
       }
@@ -104,49 +376,98 @@ impl<'a, S: BlockingSocket> Connection<'a, S> {
       total_read += n;
 
       if total_read > max_header_size {
-        return Err(Error::ResponseHeaderTooLarge);
+        return Err(Error::ResponseHeaderTooLarge {
+          bytes_read: total_read,
+          status_code: Response::parse_status_code_with(&header_buffer, self.lenient_parsing),
+        });
       }
 
-      if FramingDetector::has_complete_headers(&header_buffer) {
-        break;
-      }
+      header_end = scanner.scan(&header_buffer);
     }
 
-    let (status_code, reason, headers, remaining_after_headers) =
-      Response::parse_headers_only(&header_buffer).map_err(Error::Parse)?;
+    // Parse only the header section; whatever follows it is the start of the body
+    let split = header_end.unwrap_or(header_buffer.len());
+    let (header_bytes, remaining_after_headers) = header_buffer
+      .split_at_checked(split)
+      .unwrap_or((&header_buffer, &[]));
+    let (version, status_code, reason, headers, _) =
+      Response::parse_headers_only_with(header_bytes, self.lenient_parsing, self.lenient_parsing)
+        .map_err(Error::Parse)?;
+    if self.max_header_count.is_some_and(|max| headers.len() > max) {
+      return Err(Error::Parse(ParseError::TooManyHeaders));
+    }
 
-    let body_bytes = match expectation {
-      ResponseBodyExpectation::NoBody => Vec::new(),
-      ResponseBodyExpectation::Normal => {
-        let body_strategy = Response::body_read_strategy(&headers, status_code);
-        self.read_body(body_strategy, remaining_after_headers)?
-      },
+    // Cleared until the body has been read up to exactly where its framing ends
+    self.body_complete = false;
+    let streamed_to = body_sink
+      .and_then(|target| (target.accept)(status_code, &headers).map(|decoder| DecodingSink::new(target.sink, decoder)));
+    let body_streamed = streamed_to.is_some() && expectation == ResponseBodyExpectation::Normal;
+    // Bytes after an interim response are kept for reading the final one; after
+    // 101 they belong to the new protocol. Servers sending a body in answer to
+    // HEAD are common enough that those bytes are dropped rather than rejected
+    let (body_bytes, trailers) = if matches!(status_code, 100 | 102..=199) {
+      self.carried = remaining_after_headers.to_vec();
+      (Vec::new(), Vec::new())
+    } else if expectation == ResponseBodyExpectation::NoBody || status_code == 101 {
+      self.body_complete = remaining_after_headers.is_empty();
+      (Vec::new(), Vec::new())
+    } else {
+      let body_strategy = Response::body_read_strategy(&headers, status_code, version);
+      self.body_received = 0;
+      self.body_total = match body_strategy {
+        BodyReadStrategy::ContentLength(len) => Some(len as u64),
+        BodyReadStrategy::NoBody => Some(0),
+        BodyReadStrategy::Chunked | BodyReadStrategy::UntilClose => None,
+      };
+      match streamed_to {
+        Some(mut sink) => {
+          let trailers = self.stream_body(body_strategy, remaining_after_headers, &mut sink)?;
+          sink.end()?;
+          (Vec::new(), trailers)
+        },
+        None => self.read_body(body_strategy, remaining_after_headers)?,
+      }
     };
 
     // RFC 9112 Section 9.6: Check if server sent Connection: close
     if let Some(conn_value) = headers.get(HeaderName::CONNECTION)
       && conn_value.eq_ignore_ascii_case("close")
     {
-      self.state.mark_received_close();
+      self.closing = true;
     }
 
+    let remote_addr = self.socket.peer_addr().and_then(|peer| match peer {
+      SocketAddr::Ip { addr, .. } => Some(addr),
+      SocketAddr::Hostname { .. } => None,
+    });
+
     Ok(RawResponse {
       status_code,
       reason,
       headers,
       body_bytes,
+      remote_addr,
+      body_streamed,
+      trailers,
     })
   }
 
+  /// Read a body into memory, returning it with any chunked trailer fields
   fn read_body(
     &mut self,
     strategy: BodyReadStrategy,
     initial_bytes: &[u8],
-  ) -> Result<Vec<u8>, Error> {
+  ) -> Result<(Vec<u8>, Vec<(String, String)>), Error> {
     match strategy {
-      BodyReadStrategy::NoBody => Ok(Vec::new()),
+      BodyReadStrategy::NoBody => {
+        self.body_complete = self.reject_surplus(initial_bytes.len())?;
+        Ok((Vec::new(), Vec::new()))
+      },
       BodyReadStrategy::ContentLength(len) => {
-        let mut body_bytes = Vec::from(initial_bytes);
+        self.check_body_size(len)?;
+        self.body_complete = self.reject_surplus(initial_bytes.len().saturating_sub(len))?;
+        let mut body_bytes = Vec::from(initial_bytes.get(..len).unwrap_or(initial_bytes));
+        self.report_download(initial_bytes.len().min(len));
         let bytes_needed = len.saturating_sub(body_bytes.len());
 
         if bytes_needed > 0 {
@@ -156,15 +477,7 @@ impl<'a, S: BlockingSocket> Connection<'a, S> {
           while bytes_read < bytes_needed {
             let to_read = (bytes_needed - bytes_read).min(read_buffer.len());
             if let Some(buf_slice) = read_buffer.get_mut(..to_read) {
-              let n = match self.socket.read(buf_slice) {
-                Ok(n) => n,
-                Err(e) => {
-                  if e == crate::error::SocketError::TimedOut {
-                    let _ = self.socket.shutdown();
-                  }
-                  return Err(Error::Socket(e));
-                },
-              };
+              let n = self.read_socket(buf_slice)?;
 
               if n == 0 {
                 return Err(Error::Socket(crate::error::SocketError::NotConnected));
@@ -174,71 +487,263 @@ impl<'a, S: BlockingSocket> Connection<'a, S> {
                 body_bytes.extend_from_slice(slice);
               }
               bytes_read += n;
+              self.report_download(n);
             }
           }
         }
 
-        Ok(body_bytes)
+        Ok((body_bytes, Vec::new()))
       },
       BodyReadStrategy::Chunked => {
+        // The body is kept with its framing; decoding here only finds where it ends
         let mut raw_bytes = Vec::from(initial_bytes);
         let mut chunk_buffer = alloc::vec![0u8; 8192];
+        let mut decoder = ChunkedDecoder::new()
+          .with_max_line_length(self.max_chunk_line_length)
+          .with_max_body_size(self.max_body_size)
+          .with_max_chunk_count(self.max_chunk_count);
+        let mut decoded_to = 0usize;
+        let mut scratch = Vec::new();
+        self.report_download(initial_bytes.len());
 
         loop {
-          if FramingDetector::has_chunked_terminator(&raw_bytes) {
+          decoded_to += decoder
+            .decode_available(raw_bytes.get(decoded_to..).unwrap_or_default(), &mut scratch)
+            .map_err(Error::Parse)?;
+          scratch.clear();
+          if decoder.is_complete() {
+            self.body_complete = self.reject_surplus(raw_bytes.len().saturating_sub(decoded_to))?;
+            raw_bytes.truncate(decoded_to);
             break;
           }
 
-          let n = match self.socket.read(&mut chunk_buffer) {
-            Ok(n) => n,
-            Err(e) => {
-              if e == crate::error::SocketError::TimedOut {
-                let _ = self.socket.shutdown();
-              }
-              return Err(Error::Socket(e));
-            },
-          };
+          let n = self.read_socket(&mut chunk_buffer)?;
           if n == 0 {
             return Err(Error::Socket(crate::error::SocketError::NotConnected));
           }
           if let Some(slice) = chunk_buffer.get(..n) {
             raw_bytes.extend_from_slice(slice);
           }
+          self.report_download(n);
         }
 
-        Ok(raw_bytes)
+        Ok((raw_bytes, trailer_fields(&decoder)))
       },
+      // The server closes the connection to end the body, so it is never reused
       BodyReadStrategy::UntilClose => {
         let mut body_bytes = Vec::from(initial_bytes);
         let mut read_buffer = alloc::vec![0u8; 8192];
+        self.report_download(initial_bytes.len());
+        self.check_body_size(body_bytes.len())?;
 
         loop {
-          let n = match self.socket.read(&mut read_buffer) {
-            Ok(n) => n,
-            Err(e) => {
-              if e == crate::error::SocketError::TimedOut {
-                let _ = self.socket.shutdown();
-              }
-              return Err(Error::Socket(e));
-            },
-          };
+          let n = self.read_socket(&mut read_buffer)?;
           if n == 0 {
             break;
           }
           if let Some(slice) = read_buffer.get(..n) {
             body_bytes.extend_from_slice(slice);
           }
+          self.report_download(n);
+          self.check_body_size(body_bytes.len())?;
         }
 
-        Ok(body_bytes)
+        Ok((body_bytes, Vec::new()))
+      },
+    }
+  }
+
+  /// Check the `surplus` bytes read past where a response's framing ends
+  ///
+  /// Requests are never pipelined, so such bytes can only come from a faulty
+  /// server, for example one sending a second response unasked. RFC 9112
+  /// Section 6.3 forbids treating them as a response, so they fail the read
+  /// with `ParseError::ExtraDataAfterResponse`. With `discard_surplus` they are
+  /// dropped instead and `false` is returned so the connection is not reused.
+  /// Surplus that arrives only after the response was read is not seen here;
+  /// it makes the next response on a pooled connection fail to parse.
+  const fn reject_surplus(
+    &self,
+    surplus: usize,
+  ) -> Result<bool, Error> {
+    if surplus == 0 {
+      Ok(true)
+    } else if self.discard_surplus {
+      Ok(false)
+    } else {
+      Err(Error::Parse(ParseError::ExtraDataAfterResponse))
+    }
+  }
+
+  /// Fail once a body read into memory grows past the size limit
+  fn check_body_size(
+    &self,
+    len: usize,
+  ) -> Result<(), Error> {
+    if self.max_body_size.is_some_and(|max| len > max) {
+      return Err(Error::Parse(ParseError::BodyTooLarge));
+    }
+    Ok(())
+  }
+
+  /// Count `count` more body bytes read from the socket and report the running total
+  ///
+  /// Chunked bodies are counted as received, framing included.
+  fn report_download(
+    &mut self,
+    count: usize,
+  ) {
+    if count == 0 {
+      return;
+    }
+    self.body_received += count as u64;
+    if let Some(report) = self.progress.download {
+      report(self.body_received, self.body_total);
+    }
+  }
+
+  /// Read from the socket within the deadline, closing it gracefully on a timeout
+  ///
+  /// RFC 9112 Section 9.5: If timing out, implementation SHOULD issue a graceful close
+  fn read_socket(
+    &mut self,
+    buf: &mut [u8],
+  ) -> Result<usize, Error> {
+    self.check_cancelled()?;
+    let result = match self.deadline {
+      Some((clock, deadline_millis)) => {
+        let remaining = deadline_millis.saturating_sub(clock.now_millis());
+        let limit = u32::try_from(remaining).unwrap_or(u32::MAX);
+        let deadline_ms = self
+          .read_timeout_ms
+          .map_or(limit, |timeout_ms| limit.min(timeout_ms));
+        if deadline_ms == 0 {
+          Err(SocketError::TimedOut)
+        } else {
+          self.socket.read_with_deadline(buf, deadline_ms)
+        }
+      },
+      None => self.socket.read(buf),
+    };
+    result.map_err(|e| {
+      if e == SocketError::TimedOut {
+        let _ = self.socket.shutdown();
+      }
+      Error::Socket(e)
+    })
+  }
+
+  /// Read a body and pass it to `sink` as it arrives, removing chunked framing
+  ///
+  /// Returns the trailer fields of a chunked body once its last chunk has been
+  /// read. Stops early, leaving the body incomplete, once `sink` is full.
+  fn stream_body(
+    &mut self,
+    strategy: BodyReadStrategy,
+    initial_bytes: &[u8],
+    sink: &mut dyn ByteSink,
+  ) -> Result<Vec<(String, String)>, Error> {
+    let mut read_buffer = alloc::vec![0u8; 8192];
+    match strategy {
+      BodyReadStrategy::NoBody => {
+        self.body_complete = self.reject_surplus(initial_bytes.len())?;
+        Ok(Vec::new())
+      },
+      BodyReadStrategy::ContentLength(len) => {
+        self.body_complete = self.reject_surplus(initial_bytes.len().saturating_sub(len))?;
+        let initial = initial_bytes.get(..len).unwrap_or(initial_bytes);
+        sink.write_all(initial)?;
+        self.report_download(initial.len());
+        let mut bytes_left = len - initial.len();
+        while bytes_left > 0 {
+          if sink.is_full() {
+            self.body_complete = false;
+            return Ok(Vec::new());
+          }
+          let read_slice = read_buffer
+            .get_mut(..bytes_left.min(8192))
+            .unwrap_or_default();
+          let n = self.read_socket(read_slice)?;
+          if n == 0 {
+            return Err(Error::Socket(SocketError::NotConnected));
+          }
+          sink.write_all(read_slice.get(..n).unwrap_or_default())?;
+          bytes_left = bytes_left.saturating_sub(n);
+          self.report_download(n);
+        }
+        Ok(Vec::new())
+      },
+      BodyReadStrategy::Chunked => {
+        let mut decoder = ChunkedDecoder::new()
+          .with_max_line_length(self.max_chunk_line_length)
+          .with_max_chunk_count(self.max_chunk_count);
+        let mut pending = Vec::from(initial_bytes);
+        let mut decoded = Vec::new();
+        self.report_download(initial_bytes.len());
+        loop {
+          let consumed = decoder
+            .decode_available(&pending, &mut decoded)
+            .map_err(Error::Parse)?;
+          pending.drain(..consumed);
+          sink.write_all(&decoded)?;
+          decoded.clear();
+          if decoder.is_complete() {
+            self.body_complete = self.reject_surplus(pending.len())?;
+            return Ok(trailer_fields(&decoder));
+          }
+          if sink.is_full() {
+            return Ok(Vec::new());
+          }
+
+          let n = self.read_socket(&mut read_buffer)?;
+          if n == 0 {
+            return Err(Error::Socket(SocketError::NotConnected));
+          }
+          pending.extend_from_slice(read_buffer.get(..n).unwrap_or_default());
+          self.report_download(n);
+        }
+      },
+      BodyReadStrategy::UntilClose => {
+        sink.write_all(initial_bytes)?;
+        self.report_download(initial_bytes.len());
+        loop {
+          if sink.is_full() {
+            return Ok(Vec::new());
+          }
+          let n = self.read_socket(&mut read_buffer)?;
+          if n == 0 {
+            return Ok(Vec::new());
+          }
+          sink.write_all(read_buffer.get(..n).unwrap_or_default())?;
+          self.report_download(n);
+        }
       },
     }
   }
 
   /// Check if the connection can be reused for another request
   ///
-  /// RFC 9112 Section 9.6: Connection cannot be reused if either side sent Connection: close
+  /// RFC 9112 Section 9.6: Connection cannot be reused if either side sent Connection: close.
+  /// It also cannot be reused unless the last response body was read to exactly
+  /// where its framing ends: unread bytes, or surplus ones dropped under lenient
+  /// parsing, would be taken as the start of the next response. Bodies delimited
+  /// by closing the connection never qualify.
+  /// Together these leave only an `Idle` connection reusable.
   pub const fn is_reusable(&self) -> bool {
     self.state.can_be_reused()
   }
 }
+
+/// Trailer fields a finished chunked decoder collected, as text
+fn trailer_fields(decoder: &ChunkedDecoder) -> Vec<(String, String)> {
+  decoder
+    .trailers()
+    .iter()
+    .map(|(name, value)| {
+      (
+        String::from_utf8_lossy(name).into_owned(),
+        String::from_utf8_lossy(value).into_owned(),
+      )
+    })
+    .collect()
+}
@@ -1,45 +1,82 @@
-/// Connection state tracking for RFC 9112 Section 9.6 compliance
-/// Tracks whether "Connection: close" has been sent or received
+use crate::error::Error;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct ConnectionState {
-  pub(crate) sent_close: bool,
-  pub(crate) received_close: bool,
-  pub(crate) can_reuse: bool,
+/// Where a connection is in its request/response cycle
+///
+/// RFC 9112 Section 9.6: a connection may carry another request only once the
+/// previous response has been read to exactly where its framing ends and
+/// neither side sent "Connection: close". Only `Idle` connections meet that.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnectionState {
+  /// Ready for a request; any previous response was read completely
+  #[default]
+  Idle,
+  /// The request head or body is being written
+  SendingRequest,
+  /// The request was sent and the final response has not been read yet
+  ReadingResponse,
+  /// Finished but not reusable: "Connection: close" was sent or received,
+  /// or the response body did not end exactly where its framing said
+  Closed,
+  /// An I/O or protocol error left the connection at an unknown position
+  Broken,
 }
 
 impl ConnectionState {
   pub const fn new() -> Self {
-    Self {
-      sent_close: false,
-      received_close: false,
-      can_reuse: true,
+    Self::Idle
+  }
+
+  /// Move to `SendingRequest` before writing request bytes
+  ///
+  /// Allowed while idle or still sending; a closed or broken connection fails
+  /// with `Error::ConnectionUnusable` so nothing is written after a response
+  /// that ended the connection.
+  pub const fn begin_send(&mut self) -> Result<(), Error> {
+    match self {
+      Self::Idle | Self::SendingRequest => {
+        *self = Self::SendingRequest;
+        Ok(())
+      },
+      Self::ReadingResponse | Self::Closed | Self::Broken => Err(Error::ConnectionUnusable),
     }
   }
 
-  /// Mark that we sent "Connection: close" header
-  /// RFC 9112 Section 9.6: Client MUST NOT send further requests on this connection
-  pub const fn mark_sent_close(&mut self) {
-    self.sent_close = true;
-    self.can_reuse = false;
+  /// Move to `ReadingResponse` before reading a response
+  pub const fn begin_read(&mut self) -> Result<(), Error> {
+    match self {
+      Self::Idle | Self::SendingRequest | Self::ReadingResponse => {
+        *self = Self::ReadingResponse;
+        Ok(())
+      },
+      Self::Closed | Self::Broken => Err(Error::ConnectionUnusable),
+    }
   }
 
-  /// Mark that we received "Connection: close" header
-  /// RFC 9112 Section 9.6: Client MUST stop sending and close after reading response
-  pub const fn mark_received_close(&mut self) {
-    self.received_close = true;
-    self.can_reuse = false;
+  /// Record that a response with `status_code` has been read
+  ///
+  /// Interim (1xx) responses keep the connection waiting for the final one,
+  /// except 101, after which the connection no longer speaks HTTP/1.1.
+  pub const fn finish_response(
+    &mut self,
+    status_code: u16,
+    reusable: bool,
+  ) {
+    *self = if matches!(status_code, 100 | 102..=199) {
+      Self::ReadingResponse
+    } else if reusable {
+      Self::Idle
+    } else {
+      Self::Closed
+    };
   }
 
-  /// Check if connection can be reused for another request
-  /// RFC 9112 Section 9.6: Only reusable if neither side sent "Connection: close"
-  pub const fn can_be_reused(self) -> bool {
-    self.can_reuse
+  /// Record an I/O or protocol error
+  pub const fn mark_broken(&mut self) {
+    *self = Self::Broken;
   }
-}
 
-impl Default for ConnectionState {
-  fn default() -> Self {
-    Self::new()
+  /// Check if connection can be reused for another request
+  pub const fn can_be_reused(self) -> bool {
+    matches!(self, Self::Idle)
   }
 }
@@ -1,14 +1,19 @@
 use crate::config::Config;
 use crate::dns::DnsResolver;
-use crate::error::Error;
+use crate::error::{Error, SocketError};
 use crate::parser::uri::{Host, Uri};
-use crate::socket::{BlockingSocket, SocketAddr};
+use crate::sleep::{OsSleeper, Sleeper};
+use crate::socket::{BlockingSocket, SocketAddr, SocketFlags};
 use crate::transport::connection::Connection;
+use crate::util::IpAddr;
+
+static OS_SLEEPER: OsSleeper = OsSleeper::new();
 
 /// Handles DNS resolution and socket connection setup
 pub struct Connector<'a, S, D> {
   socket: &'a mut S,
   dns: &'a D,
+  sleeper: &'a dyn Sleeper,
 }
 
 impl<'a, S, D> Connector<'a, S, D>
@@ -20,14 +25,31 @@ where
     socket: &'a mut S,
     dns: &'a D,
   ) -> Self {
-    Self { socket, dns }
+    Self {
+      socket,
+      dns,
+      sleeper: &OS_SLEEPER,
+    }
+  }
+
+  /// Use a custom sleeper for the backoff between connect retries
+  #[cfg(test)]
+  #[must_use]
+  pub fn with_sleeper(
+    mut self,
+    sleeper: &'a dyn Sleeper,
+  ) -> Self {
+    self.sleeper = sleeper;
+    self
   }
 
   /// Establish a connection to the given URI
   ///
-  /// Performs DNS resolution, socket connection, and timeout configuration
+  /// Performs DNS resolution, socket connection, and timeout and socket option configuration.
+  /// The socket must be freshly created: sockets can be bound and connected only once,
+  /// so a pooled one goes through `resume` instead.
   pub fn connect(
-    self,
+    mut self,
     uri: &Uri,
     config: &Config,
   ) -> Result<Connection<'a, S>, Error> {
@@ -36,33 +58,49 @@ where
       Host::RegName(name) => name,
       Host::IpAddr(_) => return Err(Error::IpAddressNotSupported),
     };
-    let port = authority.port().unwrap_or_else(|| {
-      if uri.scheme() == "https" {
-        443
-      } else {
-        80
-      }
-    });
+    let port = authority
+      .port()
+      .unwrap_or_else(|| config.default_port(uri.scheme()));
 
     let addresses = self.dns.resolve(host_str).map_err(Error::Dns)?;
     let addr = addresses.first().ok_or(Error::NoAddresses)?;
+    if config.block_private_addresses && is_blocked(*addr) {
+      return Err(Error::BlockedAddress);
+    }
 
     let socket_addr = SocketAddr::Ip { addr: *addr, port };
 
-    if let Some(timeout_connect) = config.timeout_connect {
-      let timeout_ms = timeout_connect.as_millis();
-      if timeout_ms <= u128::from(u32::MAX) {
-        #[allow(clippy::cast_possible_truncation)]
-        let timeout_u32 = timeout_ms as u32;
-        self
-          .socket
-          .set_write_timeout(timeout_u32)
-          .map_err(Error::Socket)?;
+    let server_name = (uri.scheme() == "https").then_some(*host_str);
+    self
+      .socket
+      .set_tls_server_name(server_name)
+      .map_err(Error::Socket)?;
+    self.connect_with_retries(&socket_addr, config)?;
+
+    let flags = SocketFlags::empty()
+      .with_no_delay(config.tcp_nodelay)
+      .with_keep_alive(config.tcp_keepalive);
+    // The flags only tune the connection, so an adapter without them still works
+    if !flags.is_empty() {
+      match self.socket.set_flags(flags) {
+        Ok(()) | Err(SocketError::Unsupported) => {},
+        Err(error) => return Err(Error::Socket(error)),
       }
     }
 
-    self.socket.connect(&socket_addr).map_err(Error::Socket)?;
+    self.resume(config)
+  }
 
+  /// Set up a connection on a socket that is already connected, e.g. one
+  /// taken from the pool
+  ///
+  /// Nothing is resolved, bound or connected; only the timeouts are applied
+  /// again, since they may differ between requests.
+  pub fn resume(
+    self,
+    config: &Config,
+  ) -> Result<Connection<'a, S>, Error> {
+    let mut read_timeout_ms = None;
     if let Some(timeout_read) = config.timeout_read {
       let timeout_ms = timeout_read.as_millis();
       if timeout_ms <= u128::from(u32::MAX) {
@@ -72,6 +110,7 @@ where
           .socket
           .set_read_timeout(timeout_u32)
           .map_err(Error::Socket)?;
+        read_timeout_ms = Some(timeout_u32);
       }
     } else if let Some(timeout) = config.timeout {
       let timeout_ms = timeout.as_millis();
@@ -86,9 +125,106 @@ where
           .socket
           .set_write_timeout(timeout_u32)
           .map_err(Error::Socket)?;
+        read_timeout_ms = Some(timeout_u32);
+      }
+    }
+
+    Ok(
+      Connection::new(self.socket, config.max_response_header_size)
+        .with_read_timeout(read_timeout_ms)
+        .with_max_header_count(config.max_header_count)
+        .with_max_chunk_line_length(config.max_chunk_line_length)
+        .with_max_body_size(config.max_response_body_size)
+        .with_max_chunk_count(config.max_chunk_count)
+        .with_lenient_parsing(config.lenient_parsing)
+        .with_discard_surplus(config.discard_surplus_bytes),
+    )
+  }
+
+  /// Check the host a request sends through a proxy against `block_private_addresses`
+  ///
+  /// The connection itself goes to the proxy, so only the proxy's address is
+  /// checked when connecting. The target host is resolved here as well, and
+  /// fails with `Error::BlockedAddress` if any of its addresses is refused.
+  pub fn check_proxied_target(
+    &self,
+    uri: &Uri,
+    config: &Config,
+  ) -> Result<(), Error> {
+    if !config.block_private_addresses {
+      return Ok(());
+    }
+    let authority = uri.authority().ok_or(Error::InvalidUrl)?;
+    let addresses = match authority.host() {
+      Host::RegName(name) => self.dns.resolve(name).map_err(Error::Dns)?,
+      Host::IpAddr(addr) => alloc::vec![*addr],
+    };
+    if addresses.iter().any(|addr| is_blocked(*addr)) {
+      return Err(Error::BlockedAddress);
+    }
+    Ok(())
+  }
+
+  /// Connect, retrying refused or timed-out attempts up to `config.connect_retries` times
+  ///
+  /// The wait between attempts starts at `config.connect_backoff` and doubles each retry.
+  fn connect_with_retries(
+    &mut self,
+    socket_addr: &SocketAddr<'_>,
+    config: &Config,
+  ) -> Result<(), Error> {
+    let mut backoff = config.connect_backoff;
+    let mut attempt = 0u32;
+
+    loop {
+      match self.connect_once(socket_addr, config) {
+        Ok(()) => return Ok(()),
+        Err(e) if attempt < config.connect_retries && Self::is_retryable(e) => {
+          self.sleeper.sleep(backoff);
+          backoff = backoff.saturating_mul(2);
+          attempt += 1;
+        },
+        Err(e) => return Err(Error::Socket(e)),
+      }
+    }
+  }
+
+  /// Only failures to establish the connection are worth retrying
+  const fn is_retryable(error: SocketError) -> bool {
+    matches!(error, SocketError::ConnectionRefused | SocketError::TimedOut)
+  }
+
+  /// Apply pre-connect options and make a single connect attempt
+  fn connect_once(
+    &mut self,
+    socket_addr: &SocketAddr<'_>,
+    config: &Config,
+  ) -> Result<(), SocketError> {
+    if let Some(timeout_connect) = config.timeout_connect {
+      let timeout_ms = timeout_connect.as_millis();
+      if timeout_ms <= u128::from(u32::MAX) {
+        #[allow(clippy::cast_possible_truncation)]
+        let timeout_u32 = timeout_ms as u32;
+        self.socket.set_write_timeout(timeout_u32)?;
       }
     }
 
-    Ok(Connection::new(self.socket, config.max_response_header_size))
+    if let Some(local) = config.local_bind {
+      self.socket.bind(&SocketAddr::Ip { addr: local, port: 0 })?;
+    }
+
+    self.socket.connect(socket_addr)
   }
 }
+
+/// Check whether `block_private_addresses` refuses `addr`
+///
+/// IPv4-mapped IPv6 addresses are checked as the IPv4 address they carry.
+fn is_blocked(addr: IpAddr) -> bool {
+  let unmapped = addr.to_ipv4_mapped().map_or(addr, IpAddr::V4);
+  unmapped.is_loopback()
+    || unmapped.is_private()
+    || unmapped.is_link_local()
+    || unmapped.is_unique_local()
+    || unmapped.is_unspecified()
+}
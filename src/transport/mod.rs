@@ -3,9 +3,9 @@ pub mod connection_state;
 pub mod connector;
 pub mod pool;
 
-pub use connection::{RawResponse, ResponseBodyExpectation};
+pub use connection::{BodySink, ProgressFn, RawResponse, ResponseBodyExpectation, TransferProgress};
 pub use connector::Connector;
-pub use pool::{ConnectionPool, PoolKey};
+pub use pool::{ConnectionPool, PoolKey, PoolStats};
 
 #[cfg(test)]
 mod tests;
@@ -1,3 +1,7 @@
+use crate::clock::Clock;
+use crate::config::Config;
+use crate::error::Error;
+use crate::parser::uri::{Host, Uri};
 use crate::socket::BlockingSocket;
 use alloc::collections::BTreeMap;
 use alloc::string::String;
@@ -5,28 +9,69 @@ use alloc::vec::Vec;
 use core::time::Duration;
 use spin::Mutex;
 
+/// Origin a pooled connection belongs to
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PoolKey {
+  scheme: String,
   host: String,
   port: u16,
 }
 
 impl PoolKey {
-  pub const fn new(
-    host: String,
-    port: u16,
-  ) -> Self {
-    Self { host, port }
+  /// Key for the origin of `uri`
+  ///
+  /// Scheme and host are compared case-insensitively, and a missing port is
+  /// the scheme's default from `config`, so `http://host` and `http://host:80`
+  /// share connections while `http://host:8080` and `https://host` do not.
+  ///
+  /// # Errors
+  /// Returns `Error::IpAddressNotSupported` for an IP literal host, which the
+  /// client cannot connect to either.
+  pub fn from_uri(
+    uri: &Uri<'_>,
+    config: &Config,
+  ) -> Result<Self, Error> {
+    let (host, explicit_port) = match uri.authority() {
+      Some(authority) => {
+        let host = match authority.host() {
+          Host::RegName(name) => name.to_ascii_lowercase(),
+          Host::IpAddr(_) => return Err(Error::IpAddressNotSupported),
+        };
+        (host, authority.port())
+      },
+      None => (String::new(), None),
+    };
+    let scheme = uri.scheme().to_ascii_lowercase();
+    let port = explicit_port.unwrap_or_else(|| config.default_port(&scheme));
+    Ok(Self { scheme, host, port })
   }
 }
 
+/// Counters of pool lookups since the pool was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStats {
+  /// Requests that reused an idle connection
+  pub hits: u64,
+  /// Requests that found no usable idle connection and opened a new one
+  pub misses: u64,
+}
+
 pub struct PooledSocket<S> {
   socket: S,
-  last_used: core::time::Duration,
+  /// Monotonic clock reading when the socket went idle, if a clock was available
+  last_used: Option<u64>,
 }
 
+/// Idle connections kept for reuse, keyed by host and port
+///
+/// Entries idle for longer than `idle_timeout` are dropped instead of being
+/// handed out, since the server has likely closed them. Idle time is measured
+/// with the `Clock` passed in, using its monotonic reading so setting the
+/// wall clock does not expire or revive entries; without a clock entries
+/// never expire.
 pub struct ConnectionPool<S> {
   connections: Mutex<BTreeMap<PoolKey, Vec<PooledSocket<S>>>>,
+  stats: Mutex<PoolStats>,
   max_idle_per_host: usize,
   idle_timeout: Option<Duration>,
 }
@@ -38,75 +83,93 @@ impl<S: BlockingSocket> ConnectionPool<S> {
   ) -> Self {
     Self {
       connections: Mutex::new(BTreeMap::new()),
+      stats: Mutex::new(PoolStats { hits: 0, misses: 0 }),
       max_idle_per_host,
       idle_timeout,
     }
   }
 
+  /// Take the most recently used idle socket for `key`, evicting expired ones first
   pub fn get(
     &self,
     key: &PoolKey,
+    clock: Option<&dyn Clock>,
+  ) -> Option<S> {
+    let socket = self.take(key, clock);
+    let mut stats = self.stats.lock();
+    if socket.is_some() {
+      stats.hits = stats.hits.saturating_add(1);
+    } else {
+      stats.misses = stats.misses.saturating_add(1);
+    }
+    socket
+  }
+
+  fn take(
+    &self,
+    key: &PoolKey,
+    clock: Option<&dyn Clock>,
   ) -> Option<S> {
     let mut connections = self.connections.lock();
     let sockets = connections.get_mut(key)?;
-
-    while let Some(pooled) = sockets.pop() {
-      if let Some(timeout) = self.idle_timeout {
-        let now = Self::current_time();
-        let elapsed = now.saturating_sub(pooled.last_used);
-        if elapsed > timeout {
-          continue;
-        }
-      }
-      return Some(pooled.socket);
+    if let Some(now) = clock.map(Clock::monotonic_millis) {
+      sockets.retain(|pooled| !self.is_expired(pooled, now));
     }
-
-    None
+    let socket = sockets.pop().map(|pooled| pooled.socket);
+    if sockets.is_empty() {
+      connections.remove(key);
+    }
+    socket
   }
 
+  /// Keep `socket` for reuse, dropping the oldest idle socket if `key` is at capacity
   pub fn return_connection(
     &self,
     key: PoolKey,
     socket: S,
+    clock: Option<&dyn Clock>,
   ) {
+    if self.max_idle_per_host == 0 {
+      return;
+    }
     let mut connections = self.connections.lock();
     let sockets = connections.entry(key).or_default();
-
-    if sockets.len() >= self.max_idle_per_host {
-      return;
+    while sockets.len() >= self.max_idle_per_host {
+      sockets.remove(0);
     }
-
     sockets.push(PooledSocket {
       socket,
-      last_used: Self::current_time(),
+      last_used: clock.map(Clock::monotonic_millis),
     });
   }
 
-  fn current_time() -> Duration {
-    #[cfg(windows)]
-    {
-      use core::mem::MaybeUninit;
-      unsafe {
-        let mut filetime = MaybeUninit::<windows_sys::Win32::Foundation::FILETIME>::uninit();
-        windows_sys::Win32::System::SystemInformation::GetSystemTimeAsFileTime(filetime.as_mut_ptr());
-        let ft = filetime.assume_init();
-        let ticks = (u64::from(ft.dwHighDateTime) << 32) | u64::from(ft.dwLowDateTime);
-        let nanos = ticks.saturating_mul(100);
-        Duration::from_nanos(nanos)
-      }
-    }
-    #[cfg(unix)]
-    {
-      unsafe {
-        let mut ts_uninit = core::mem::MaybeUninit::<libc::timespec>::uninit();
-        libc::clock_gettime(libc::CLOCK_MONOTONIC, ts_uninit.as_mut_ptr());
-        let ts = ts_uninit.assume_init();
-        Duration::from_secs(ts.tv_sec.cast_unsigned()).saturating_add(Duration::from_nanos(ts.tv_nsec.cast_unsigned()))
-      }
-    }
-    #[cfg(not(any(windows, unix)))]
-    {
-      Duration::from_secs(0)
+  /// Number of idle sockets held for all hosts
+  pub fn len(&self) -> usize {
+    self.connections.lock().values().map(Vec::len).sum()
+  }
+
+  /// Shut down and drop every idle socket
+  pub fn clear(&self) {
+    let connections = core::mem::take(&mut *self.connections.lock());
+    for mut pooled in connections.into_values().flatten() {
+      let _ = pooled.socket.shutdown();
     }
   }
+
+  /// Hit and miss counters of `get`
+  pub fn stats(&self) -> PoolStats {
+    *self.stats.lock()
+  }
+
+  fn is_expired(
+    &self,
+    pooled: &PooledSocket<S>,
+    now_millis: u64,
+  ) -> bool {
+    let (Some(timeout), Some(last_used)) = (self.idle_timeout, pooled.last_used) else {
+      return false;
+    };
+    let idle = Duration::from_millis(now_millis.saturating_sub(last_used));
+    idle > timeout
+  }
 }
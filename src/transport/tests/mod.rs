@@ -5,6 +5,8 @@
 #![allow(clippy::indexing_slicing)]
 #![allow(clippy::shadow_reuse)]
 #![allow(clippy::shadow_same)]
+#![allow(clippy::duration_suboptimal_units)]
 mod test_connection;
 mod test_connection_state;
 mod test_connector;
+mod test_pool;
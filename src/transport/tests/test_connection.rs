@@ -1,16 +1,24 @@
-use crate::error::{Error, SocketError};
+use crate::clock::ManualClock;
+use crate::error::{Error, ParseError, SocketError};
 use crate::headers::Headers;
+use crate::parser::content_coding::ContentDecoder;
 use crate::socket::{BlockingSocket, SocketAddr, SocketFlags};
-use crate::transport::connection::{Connection, RawResponse, ResponseBodyExpectation};
+use crate::transport::connection::{BodySink, Connection, RawResponse, ResponseBodyExpectation, TransferProgress};
+use crate::transport::connection_state::ConnectionState;
+use crate::util::IpAddr;
 use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
+use core::cell::RefCell;
 
 struct MockSocket {
   read_data: Vec<u8>,
   read_pos: usize,
   written: Vec<u8>,
+  peer: Option<IpAddr>,
+  max_read: usize,
+  max_write: usize,
 }
 
 impl MockSocket {
@@ -19,6 +27,19 @@ impl MockSocket {
       read_data: response.as_bytes().to_vec(),
       read_pos: 0,
       written: Vec::new(),
+      peer: None,
+      max_read: usize::MAX,
+      max_write: usize::MAX,
+    }
+  }
+
+  fn with_max_read(
+    response: &str,
+    max_read: usize,
+  ) -> Self {
+    Self {
+      max_read,
+      ..Self::new(response)
     }
   }
 
@@ -33,6 +54,9 @@ impl BlockingSocket for MockSocket {
       read_data: Vec::new(),
       read_pos: 0,
       written: Vec::new(),
+      peer: None,
+      max_read: usize::MAX,
+      max_write: usize::MAX,
     })
   }
 
@@ -51,7 +75,7 @@ impl BlockingSocket for MockSocket {
       return Ok(0);
     }
     let remaining = &self.read_data[self.read_pos..];
-    let to_read = remaining.len().min(buf.len());
+    let to_read = remaining.len().min(buf.len()).min(self.max_read);
     buf[..to_read].copy_from_slice(&remaining[..to_read]);
     self.read_pos += to_read;
     Ok(to_read)
@@ -61,14 +85,19 @@ impl BlockingSocket for MockSocket {
     &mut self,
     buf: &[u8],
   ) -> Result<usize, SocketError> {
-    self.written.extend_from_slice(buf);
-    Ok(buf.len())
+    let count = buf.len().min(self.max_write);
+    self.written.extend_from_slice(&buf[..count]);
+    Ok(count)
   }
 
   fn shutdown(&mut self) -> Result<(), SocketError> {
     Ok(())
   }
 
+  fn peer_addr(&self) -> Option<SocketAddr<'static>> {
+    self.peer.map(|addr| SocketAddr::Ip { addr, port: 80 })
+  }
+
   fn set_flags(
     &mut self,
     _flags: SocketFlags,
@@ -103,6 +132,78 @@ fn send_request_writes_to_socket() {
   assert_eq!(socket.get_written(), "GET / HTTP/1.1\r\nHost: example.com\r\n\r\n");
 }
 
+#[test]
+fn send_request_retries_short_writes() {
+  let mut socket = MockSocket::new("");
+  socket.max_write = 3;
+  let mut conn = Connection::new(&mut socket, 8192);
+
+  conn
+    .send_request(b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n")
+    .unwrap();
+
+  assert_eq!(socket.get_written(), "GET / HTTP/1.1\r\nHost: example.com\r\n\r\n");
+}
+
+#[test]
+fn send_body_reports_upload_progress_per_piece() {
+  let body = vec![b'x'; 40_000];
+  let reports = RefCell::new(Vec::new());
+  let record = |sent: u64, total: Option<u64>| reports.borrow_mut().push((sent, total));
+  let mut socket = MockSocket::new("");
+  let mut conn = Connection::new(&mut socket, 8192).with_progress(TransferProgress {
+    upload: Some(&record),
+    download: None,
+  });
+
+  conn.send_body(&body).unwrap();
+
+  assert_eq!(
+    reports.into_inner(),
+    [
+      (16_384, Some(40_000)),
+      (32_768, Some(40_000)),
+      (40_000, Some(40_000))
+    ]
+  );
+  assert_eq!(socket.written, body);
+}
+
+#[test]
+fn read_response_reports_download_progress() {
+  let response = "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nHello";
+  let reports = RefCell::new(Vec::new());
+  let record = |received: u64, total: Option<u64>| reports.borrow_mut().push((received, total));
+  let mut socket = MockSocket::with_max_read(response, 2);
+  let mut conn = Connection::new(&mut socket, 8192).with_progress(TransferProgress {
+    upload: None,
+    download: Some(&record),
+  });
+
+  let raw = conn
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+
+  assert_eq!(raw.body_bytes, b"Hello");
+  let reports = reports.into_inner();
+  assert_eq!(reports.last(), Some(&(5, Some(5))));
+  assert!(reports.windows(2).all(|pair| pair[0].0 < pair[1].0));
+}
+
+#[test]
+fn read_response_with_single_byte_reads() {
+  let response = "\r\nHTTP/1.1 200 OK\r\nContent-Length: 5\r\nX-Test: a\r\n\r\nHello";
+  let mut socket = MockSocket::with_max_read(response, 1);
+  let mut conn = Connection::new(&mut socket, 8192);
+
+  let raw = conn
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+  assert_eq!(raw.status_code, 200);
+  assert_eq!(raw.headers.get("x-test"), Some("a"));
+  assert_eq!(raw.body_bytes, b"Hello");
+}
+
 #[test]
 fn read_response_with_content_length() {
   let response = "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nHello";
@@ -146,6 +247,292 @@ fn read_response_chunked_encoding() {
   assert_eq!(raw.body_bytes, b"5\r\nHello\r\n0\r\n\r\n");
 }
 
+#[test]
+fn body_sink_receives_content_length_body() {
+  let response = "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nHello";
+  let mut socket = MockSocket::with_max_read(response, 1);
+  let mut conn = Connection::new(&mut socket, 8192);
+  let mut streamed = Vec::new();
+  let accept = |_: u16, _: &Headers| Some(ContentDecoder::default());
+
+  let raw = conn
+    .read_raw_response_into(
+      ResponseBodyExpectation::Normal,
+      Some(BodySink {
+        sink: &mut streamed,
+        accept: &accept,
+      }),
+    )
+    .unwrap();
+
+  assert!(raw.body_streamed);
+  assert!(raw.body_bytes.is_empty());
+  assert_eq!(streamed, b"Hello");
+}
+
+#[test]
+fn body_sink_receives_decoded_chunked_body() {
+  let response = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHello\r\n6\r\n world\r\n0\r\n\r\n";
+  let mut socket = MockSocket::with_max_read(response, 3);
+  let mut conn = Connection::new(&mut socket, 8192);
+  let mut streamed = Vec::new();
+  let accept = |_: u16, _: &Headers| Some(ContentDecoder::default());
+
+  let raw = conn
+    .read_raw_response_into(
+      ResponseBodyExpectation::Normal,
+      Some(BodySink {
+        sink: &mut streamed,
+        accept: &accept,
+      }),
+    )
+    .unwrap();
+
+  assert!(raw.body_streamed);
+  assert_eq!(streamed, b"Hello world");
+}
+
+#[test]
+fn body_sink_chunked_body_exposes_trailers_after_last_chunk() {
+  let response = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\nTrailer: Checksum\r\n\r\n5\r\nHello\r\n0\r\nChecksum: abc123\r\nServer-Timing: db;dur=5\r\n\r\n";
+  let mut socket = MockSocket::with_max_read(response, 4);
+  let mut conn = Connection::new(&mut socket, 8192);
+  let mut streamed = Vec::new();
+  let accept = |_: u16, _: &Headers| Some(ContentDecoder::default());
+
+  let raw = conn
+    .read_raw_response_into(
+      ResponseBodyExpectation::Normal,
+      Some(BodySink {
+        sink: &mut streamed,
+        accept: &accept,
+      }),
+    )
+    .unwrap();
+
+  assert_eq!(streamed, b"Hello");
+  assert_eq!(
+    raw.trailers,
+    vec![
+      (String::from("Checksum"), String::from("abc123")),
+      (String::from("Server-Timing"), String::from("db;dur=5")),
+    ]
+  );
+  assert!(conn.is_reusable());
+}
+
+#[test]
+fn buffered_chunked_body_keeps_trailers() {
+  let response = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n2\r\nok\r\n0\r\nChecksum: abc123\r\n\r\n";
+  let mut socket = MockSocket::with_max_read(response, 5);
+  let mut conn = Connection::new(&mut socket, 8192);
+
+  let raw = conn
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+
+  assert_eq!(raw.trailers, vec![(String::from("Checksum"), String::from("abc123"))]);
+}
+
+#[test]
+fn body_sink_declined_keeps_body_in_memory() {
+  let response = "HTTP/1.1 404 Not Found\r\nContent-Length: 4\r\n\r\nnope";
+  let mut socket = MockSocket::new(response);
+  let mut conn = Connection::new(&mut socket, 8192);
+  let mut streamed = Vec::new();
+  let accept = |status: u16, _: &Headers| (status < 400).then(ContentDecoder::default);
+
+  let raw = conn
+    .read_raw_response_into(
+      ResponseBodyExpectation::Normal,
+      Some(BodySink {
+        sink: &mut streamed,
+        accept: &accept,
+      }),
+    )
+    .unwrap();
+
+  assert!(!raw.body_streamed);
+  assert_eq!(raw.body_bytes, b"nope");
+  assert!(streamed.is_empty());
+}
+
+#[test]
+fn connection_reusable_only_when_body_ends_at_framing_boundary() {
+  let cases: [(&str, bool); 4] = [
+    ("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nHello", true),
+    (
+      "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHello\r\n0\r\nX-Sum: 1\r\n\r\n",
+      true,
+    ),
+    ("HTTP/1.1 200 OK\r\nConnection: keep-alive\r\n\r\n", true),
+    ("HTTP/1.0 200 OK\r\n\r\nuntil close", false),
+  ];
+  for (response, reusable) in cases {
+    let mut socket = MockSocket::new(response);
+    let mut conn = Connection::new(&mut socket, 8192);
+    conn
+      .read_raw_response(ResponseBodyExpectation::Normal)
+      .unwrap();
+    assert_eq!(conn.is_reusable(), reusable, "{response:?}");
+  }
+}
+
+// Responses followed by surplus bytes, with the body kept when surplus is discarded
+const SURPLUS_CASES: [(&str, ResponseBodyExpectation, &[u8]); 3] = [
+  (
+    "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nHelloHTTP/1.1 200 OK\r\n\r\n",
+    ResponseBodyExpectation::Normal,
+    b"Hello",
+  ),
+  (
+    "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\nextra",
+    ResponseBodyExpectation::Normal,
+    b"0\r\n\r\n",
+  ),
+  (
+    "HTTP/1.1 204 No Content\r\n\r\nHTTP/1.1 200 OK\r\n\r\n",
+    ResponseBodyExpectation::Normal,
+    b"",
+  ),
+];
+
+#[test]
+fn bytes_after_response_framing_are_rejected() {
+  for (response, expectation, _) in SURPLUS_CASES {
+    let mut socket = MockSocket::new(response);
+    let mut conn = Connection::new(&mut socket, 8192);
+
+    let result = conn.read_raw_response(expectation);
+
+    assert!(
+      matches!(result, Err(Error::Parse(ParseError::ExtraDataAfterResponse))),
+      "{response:?}"
+    );
+    assert!(!conn.is_reusable());
+  }
+}
+
+#[test]
+fn discard_surplus_drops_bytes_after_response_framing() {
+  for (response, expectation, body) in SURPLUS_CASES {
+    let mut socket = MockSocket::new(response);
+    let mut conn = Connection::new(&mut socket, 8192).with_discard_surplus(true);
+
+    let raw = conn.read_raw_response(expectation).unwrap();
+
+    assert_eq!(raw.body_bytes, body, "{response:?}");
+    assert!(!conn.is_reusable(), "{response:?}");
+  }
+}
+
+#[test]
+fn connection_not_reusable_after_head_response_with_body_bytes() {
+  let mut socket = MockSocket::new("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nHello");
+  let mut conn = Connection::new(&mut socket, 8192);
+
+  conn
+    .read_raw_response(ResponseBodyExpectation::NoBody)
+    .unwrap();
+
+  assert!(!conn.is_reusable());
+}
+
+#[test]
+fn bytes_after_interim_response_start_the_final_response() {
+  let mut socket = MockSocket::new("HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok");
+  let mut conn = Connection::new(&mut socket, 8192);
+
+  let interim = conn
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+  let last = conn
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+
+  assert_eq!(interim.status_code, 100);
+  assert_eq!(last.status_code, 200);
+  assert_eq!(last.body_bytes, b"ok");
+  assert!(conn.is_reusable());
+}
+
+#[test]
+fn streamed_chunked_body_leaves_connection_reusable() {
+  let response = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHello\r\n0\r\n\r\n";
+  let mut socket = MockSocket::with_max_read(response, 4);
+  let mut conn = Connection::new(&mut socket, 8192);
+  let mut streamed = Vec::new();
+  let accept = |_: u16, _: &Headers| Some(ContentDecoder::default());
+
+  conn
+    .read_raw_response_into(
+      ResponseBodyExpectation::Normal,
+      Some(BodySink {
+        sink: &mut streamed,
+        accept: &accept,
+      }),
+    )
+    .unwrap();
+
+  assert!(conn.is_reusable());
+}
+
+#[test]
+fn connection_state_follows_request_cycle() {
+  let mut socket = MockSocket::new("HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok");
+  let mut conn = Connection::new(&mut socket, 8192);
+  assert_eq!(conn.state(), ConnectionState::Idle);
+
+  conn
+    .send_request(b"GET / HTTP/1.1\r\nHost: a\r\n\r\n")
+    .unwrap();
+  assert_eq!(conn.state(), ConnectionState::SendingRequest);
+
+  conn
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+  assert_eq!(conn.state(), ConnectionState::Idle);
+}
+
+#[test]
+fn connection_close_response_leaves_connection_closed() {
+  let mut socket = MockSocket::new("HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: 2\r\n\r\nok");
+  let mut conn = Connection::new(&mut socket, 8192);
+
+  conn
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+
+  assert_eq!(conn.state(), ConnectionState::Closed);
+  assert!(matches!(
+    conn.send_request(b"GET / HTTP/1.1\r\n\r\n"),
+    Err(Error::ConnectionUnusable)
+  ));
+}
+
+#[test]
+fn read_error_marks_connection_broken() {
+  let mut socket = MockSocket::new("HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nshort");
+  let mut conn = Connection::new(&mut socket, 8192);
+
+  assert!(
+    conn
+      .read_raw_response(ResponseBodyExpectation::Normal)
+      .is_err()
+  );
+
+  assert_eq!(conn.state(), ConnectionState::Broken);
+  assert!(!conn.is_reusable());
+  assert!(matches!(
+    conn.send_request(b"GET / HTTP/1.1\r\n\r\n"),
+    Err(Error::ConnectionUnusable)
+  ));
+  assert!(matches!(
+    conn.read_raw_response(ResponseBodyExpectation::Normal),
+    Err(Error::ConnectionUnusable)
+  ));
+}
+
 #[test]
 fn read_response_204_no_content() {
   let response = "HTTP/1.1 204 No Content\r\n\r\n";
@@ -183,7 +570,120 @@ fn header_size_limit_enforced() {
   let result = conn.read_raw_response(ResponseBodyExpectation::Normal);
 
   assert!(result.is_err());
-  assert!(matches!(result.unwrap_err(), Error::ResponseHeaderTooLarge));
+  match result.unwrap_err() {
+    Error::ResponseHeaderTooLarge { bytes_read, status_code } => {
+      assert!(bytes_read > 1024);
+      assert_eq!(status_code, Some(200));
+    },
+    other => panic!("expected ResponseHeaderTooLarge, got {other:?}"),
+  }
+}
+
+#[test]
+fn header_size_limit_reports_unparseable_status_line() {
+  let garbage = "NOT-HTTP ".to_string() + &"A".repeat(4000);
+  let mut socket = MockSocket::new(&garbage);
+  let mut conn = Connection::new(&mut socket, 1024);
+
+  let result = conn.read_raw_response(ResponseBodyExpectation::Normal);
+
+  assert!(matches!(
+    result,
+    Err(Error::ResponseHeaderTooLarge { status_code: None, .. })
+  ));
+}
+
+#[test]
+fn differing_content_length_fields_rejected_before_body() {
+  let response = "HTTP/1.1 200 OK\r\nContent-Length: 5\r\nContent-Length: 10\r\n\r\nHelloWorld";
+  let mut socket = MockSocket::new(response);
+  let mut conn = Connection::new(&mut socket, 8192);
+
+  let result = conn.read_raw_response(ResponseBodyExpectation::Normal);
+
+  assert!(matches!(result, Err(Error::Parse(ParseError::MultipleContentLength))));
+}
+
+#[test]
+fn chunk_line_limit_stops_reading_long_extensions() {
+  let response = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5;ext=".to_string() + &"a".repeat(64 * 1024);
+  let mut socket = MockSocket::with_max_read(&response, 512);
+  let mut conn = Connection::new(&mut socket, 8192).with_max_chunk_line_length(Some(1024));
+
+  let result = conn.read_raw_response(ResponseBodyExpectation::Normal);
+
+  assert!(matches!(result, Err(Error::Parse(ParseError::ChunkLineTooLong))));
+  assert!(socket.read_pos < 4096);
+}
+
+#[test]
+fn body_size_limit_applies_to_every_framing() {
+  let responses = [
+    "HTTP/1.1 200 OK\r\nContent-Length: 11\r\n\r\nHello World",
+    "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHello\r\n6\r\n World\r\n0\r\n\r\n",
+    "HTTP/1.0 200 OK\r\n\r\nHello World",
+  ];
+  for response in responses {
+    let mut socket = MockSocket::with_max_read(response, 16);
+    let result = Connection::new(&mut socket, 8192)
+      .with_max_body_size(Some(10))
+      .read_raw_response(ResponseBodyExpectation::Normal);
+    assert!(
+      matches!(result, Err(Error::Parse(ParseError::BodyTooLarge))),
+      "{response:?}"
+    );
+
+    let mut allowing = MockSocket::with_max_read(response, 16);
+    let allowed = Connection::new(&mut allowing, 8192)
+      .with_max_body_size(Some(11))
+      .read_raw_response(ResponseBodyExpectation::Normal);
+    assert!(allowed.is_ok(), "{response:?}");
+  }
+}
+
+#[test]
+fn chunk_count_limit_stops_endless_tiny_chunks() {
+  let response = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n".to_string() + &"1\r\nx\r\n".repeat(10_000);
+  let mut socket = MockSocket::with_max_read(&response, 512);
+  let mut conn = Connection::new(&mut socket, 8192).with_max_chunk_count(Some(100));
+
+  let result = conn.read_raw_response(ResponseBodyExpectation::Normal);
+
+  assert!(matches!(result, Err(Error::Parse(ParseError::TooManyChunks))));
+  assert!(socket.read_pos < 2048);
+}
+
+#[test]
+fn whitespace_after_chunk_size_rejected() {
+  let response = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5 \r\nHello\r\n0\r\n\r\n";
+  let mut socket = MockSocket::new(response);
+
+  let result = Connection::new(&mut socket, 8192).read_raw_response(ResponseBodyExpectation::Normal);
+
+  assert!(matches!(result, Err(Error::Parse(ParseError::InvalidChunkSize))));
+}
+
+#[test]
+fn header_count_limit_enforced() {
+  let response = "HTTP/1.1 200 OK\r\n".to_string() + &"a: b\r\n".repeat(10_000) + "Content-Length: 0\r\n\r\n";
+  let mut socket = MockSocket::new(&response);
+  let mut conn = Connection::new(&mut socket, 64 * 1024).with_max_header_count(Some(100));
+
+  let result = conn.read_raw_response(ResponseBodyExpectation::Normal);
+
+  assert!(matches!(result, Err(Error::Parse(ParseError::TooManyHeaders))));
+}
+
+#[test]
+fn header_count_limit_allows_fields_up_to_the_limit() {
+  let response = "HTTP/1.1 200 OK\r\n".to_string() + &"a: b\r\n".repeat(99) + "Content-Length: 0\r\n\r\n";
+  let mut socket = MockSocket::new(&response);
+  let mut conn = Connection::new(&mut socket, 64 * 1024).with_max_header_count(Some(100));
+
+  let raw = conn
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+  assert_eq!(raw.headers.len(), 100);
 }
 
 #[test]
@@ -246,6 +746,9 @@ fn raw_response_can_be_cloned() {
     reason: String::from("OK"),
     headers,
     body_bytes: vec![1, 2, 3],
+    remote_addr: None,
+    body_streamed: false,
+    trailers: Vec::new(),
   };
 
   let cloned = response.clone();
@@ -309,3 +812,257 @@ fn read_response_chunked_multiple_chunks() {
   let raw = result.unwrap();
   assert!(!raw.body_bytes.is_empty());
 }
+
+#[test]
+fn read_response_records_peer_address() {
+  let mut socket = MockSocket::new("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+  socket.peer = Some(IpAddr::V4([127, 0, 0, 1]));
+  let mut conn = Connection::new(&mut socket, 8192);
+
+  let raw = conn
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+
+  assert_eq!(raw.remote_addr, Some(IpAddr::V4([127, 0, 0, 1])));
+}
+
+#[test]
+fn read_response_without_peer_address() {
+  let mut socket = MockSocket::new("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+  let mut conn = Connection::new(&mut socket, 8192);
+
+  let raw = conn
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+
+  assert!(raw.remote_addr.is_none());
+}
+
+#[test]
+fn read_response_aborts_when_cancelled() {
+  let mut socket = MockSocket::new("HTTP/1.0 200 OK\r\n\r\nstreaming body");
+  let token = crate::cancel::CancelToken::new();
+  token.cancel();
+  let mut conn = Connection::new(&mut socket, 8192).with_cancel_token(Some(token));
+
+  let result = conn.read_raw_response(ResponseBodyExpectation::Normal);
+
+  assert!(matches!(result, Err(Error::Cancelled)));
+}
+
+#[test]
+fn read_response_unaffected_by_uncancelled_token() {
+  let mut socket = MockSocket::new("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nHello");
+  let token = crate::cancel::CancelToken::new();
+  let mut conn = Connection::new(&mut socket, 8192).with_cancel_token(Some(token));
+
+  let raw = conn
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+
+  assert_eq!(raw.body_bytes, b"Hello");
+}
+
+#[test]
+fn read_response_lenient_status_line() {
+  let response = "http/1.1 200\r\nContent-Length: 2\r\n\r\nOK";
+
+  let mut strict_socket = MockSocket::new(response);
+  let mut strict = Connection::new(&mut strict_socket, 8192);
+  assert!(matches!(
+    strict.read_raw_response(ResponseBodyExpectation::Normal),
+    Err(Error::Parse(_))
+  ));
+
+  let mut lenient_socket = MockSocket::new(response);
+  let mut lenient = Connection::new(&mut lenient_socket, 8192).with_lenient_parsing(true);
+  let raw = lenient
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+  assert_eq!(raw.status_code, 200);
+  assert_eq!(raw.body_bytes, b"OK");
+}
+
+#[test]
+fn read_response_whitespace_line_before_headers_needs_lenient_parsing() {
+  let response = "HTTP/1.1 200 OK\r\n \r\nContent-Length: 2\r\n\r\nOK";
+
+  let mut strict_socket = MockSocket::new(response);
+  let mut strict = Connection::new(&mut strict_socket, 8192);
+  assert!(matches!(
+    strict.read_raw_response(ResponseBodyExpectation::Normal),
+    Err(Error::Parse(_))
+  ));
+
+  let mut lenient_socket = MockSocket::new(response);
+  let mut lenient = Connection::new(&mut lenient_socket, 8192).with_lenient_parsing(true);
+  let raw = lenient
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+  assert_eq!(raw.headers.get("Content-Length"), Some("2"));
+  assert_eq!(raw.body_bytes, b"OK");
+}
+
+#[test]
+fn read_response_obs_fold_needs_lenient_parsing() {
+  let response = "HTTP/1.1 200 OK\r\nX-Folded: first\r\n second\r\nContent-Length: 2\r\n\r\nOK";
+
+  let mut strict_socket = MockSocket::new(response);
+  let mut strict = Connection::new(&mut strict_socket, 8192);
+  assert!(matches!(
+    strict.read_raw_response(ResponseBodyExpectation::Normal),
+    Err(Error::Parse(ParseError::ObsoleteFoldInHeader))
+  ));
+
+  let mut lenient_socket = MockSocket::new(response);
+  let mut lenient = Connection::new(&mut lenient_socket, 8192).with_lenient_parsing(true);
+  let raw = lenient
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+  assert_eq!(raw.headers.get("X-Folded"), Some("first second"));
+  assert_eq!(raw.body_bytes, b"OK");
+}
+
+#[test]
+fn read_response_http10_body_until_close() {
+  let response = "HTTP/1.0 200 OK\r\nContent-Type: text/plain\r\n\r\nbody delimited by close";
+  let mut socket = MockSocket::new(response);
+  let mut conn = Connection::new(&mut socket, 8192);
+
+  let raw = conn
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+
+  assert_eq!(raw.status_code, 200);
+  assert_eq!(raw.body_bytes, b"body delimited by close");
+
+  let body =
+    crate::parser::Response::parse_body_from_bytes(&raw.body_bytes, &raw.headers, raw.status_code, true, None).unwrap();
+  assert_eq!(body.as_bytes(), b"body delimited by close");
+}
+
+/// Socket that takes `step` milliseconds of `clock` time per read and records each deadline
+struct SlowSocket<'c> {
+  inner: MockSocket,
+  clock: &'c ManualClock,
+  step: u64,
+  deadlines: Vec<u32>,
+}
+
+impl<'c> SlowSocket<'c> {
+  fn new(
+    response: &str,
+    clock: &'c ManualClock,
+    step: u64,
+  ) -> Self {
+    Self {
+      inner: MockSocket::with_max_read(response, 10),
+      clock,
+      step,
+      deadlines: Vec::new(),
+    }
+  }
+}
+
+impl BlockingSocket for SlowSocket<'_> {
+  fn new() -> Result<Self, SocketError> {
+    Err(SocketError::Unsupported)
+  }
+
+  fn connect(
+    &mut self,
+    _addr: &SocketAddr<'_>,
+  ) -> Result<(), SocketError> {
+    Ok(())
+  }
+
+  fn read(
+    &mut self,
+    buf: &mut [u8],
+  ) -> Result<usize, SocketError> {
+    self.clock.advance(self.step);
+    self.inner.read(buf)
+  }
+
+  fn read_with_deadline(
+    &mut self,
+    buf: &mut [u8],
+    deadline_ms: u32,
+  ) -> Result<usize, SocketError> {
+    self.deadlines.push(deadline_ms);
+    self.read(buf)
+  }
+
+  fn write(
+    &mut self,
+    buf: &[u8],
+  ) -> Result<usize, SocketError> {
+    self.inner.write(buf)
+  }
+
+  fn shutdown(&mut self) -> Result<(), SocketError> {
+    Ok(())
+  }
+
+  fn set_flags(
+    &mut self,
+    _flags: SocketFlags,
+  ) -> Result<(), SocketError> {
+    Ok(())
+  }
+
+  fn set_read_timeout(
+    &mut self,
+    _timeout_ms: u32,
+  ) -> Result<(), SocketError> {
+    Ok(())
+  }
+
+  fn set_write_timeout(
+    &mut self,
+    _timeout_ms: u32,
+  ) -> Result<(), SocketError> {
+    Ok(())
+  }
+}
+
+#[test]
+fn deadline_shrinks_with_each_read() {
+  let clock = ManualClock::new(0);
+  let mut socket = SlowSocket::new("HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\nbody", &clock, 100);
+
+  let raw = Connection::new(&mut socket, 8192)
+    .with_deadline(&clock, 1_000)
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+
+  assert_eq!(raw.body_bytes, b"body");
+  assert_eq!(socket.deadlines, [1_000, 900, 800, 700, 600]);
+}
+
+#[test]
+fn deadline_never_exceeds_read_timeout() {
+  let clock = ManualClock::new(0);
+  let mut socket = SlowSocket::new("HTTP/1.1 204 No Content\r\n\r\n", &clock, 100);
+
+  Connection::new(&mut socket, 8192)
+    .with_deadline(&clock, 1_000)
+    .with_read_timeout(Some(250))
+    .read_raw_response(ResponseBodyExpectation::Normal)
+    .unwrap();
+
+  assert_eq!(socket.deadlines, [250, 250, 250]);
+}
+
+#[test]
+fn expired_deadline_times_out_without_reading() {
+  let clock = ManualClock::new(0);
+  let mut socket = SlowSocket::new("HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\nbody", &clock, 400);
+
+  let result = Connection::new(&mut socket, 8192)
+    .with_deadline(&clock, 1_000)
+    .read_raw_response(ResponseBodyExpectation::Normal);
+
+  assert!(matches!(result, Err(Error::Socket(SocketError::TimedOut))));
+  assert_eq!(socket.deadlines, [1_000, 600, 200]);
+}
@@ -1,25 +1,63 @@
+use crate::error::Error;
 use crate::transport::connection_state::ConnectionState;
 
 #[test]
 fn test_new_connection() {
   let state = ConnectionState::new();
-  assert!(state.can_reuse);
-  assert!(!state.sent_close);
-  assert!(!state.received_close);
+  assert_eq!(state, ConnectionState::Idle);
+  assert!(state.can_be_reused());
 }
 
 #[test]
-fn test_sent_close_prevents_reuse() {
+fn test_request_cycle_returns_to_idle() {
   let mut state = ConnectionState::new();
-  state.mark_sent_close();
-  assert!(!state.can_reuse);
-  assert!(state.sent_close);
+  state.begin_send().unwrap();
+  assert_eq!(state, ConnectionState::SendingRequest);
+  state.begin_send().unwrap();
+  state.begin_read().unwrap();
+  assert_eq!(state, ConnectionState::ReadingResponse);
+  state.finish_response(200, true);
+  assert_eq!(state, ConnectionState::Idle);
+  assert!(state.can_be_reused());
 }
 
 #[test]
-fn test_received_close_prevents_reuse() {
+fn test_interim_response_keeps_reading() {
   let mut state = ConnectionState::new();
-  state.mark_received_close();
-  assert!(!state.can_reuse);
-  assert!(state.received_close);
+  state.begin_read().unwrap();
+  state.finish_response(100, true);
+  assert_eq!(state, ConnectionState::ReadingResponse);
+  assert!(!state.can_be_reused());
+
+  state.finish_response(101, true);
+  assert_eq!(state, ConnectionState::Idle);
+}
+
+#[test]
+fn test_unreusable_response_closes() {
+  let mut state = ConnectionState::new();
+  state.begin_read().unwrap();
+  state.finish_response(200, false);
+  assert_eq!(state, ConnectionState::Closed);
+  assert!(!state.can_be_reused());
+  assert!(matches!(state.begin_send(), Err(Error::ConnectionUnusable)));
+  assert!(matches!(state.begin_read(), Err(Error::ConnectionUnusable)));
+}
+
+#[test]
+fn test_broken_connection_rejects_everything() {
+  let mut state = ConnectionState::new();
+  state.begin_send().unwrap();
+  state.mark_broken();
+  assert!(!state.can_be_reused());
+  assert!(matches!(state.begin_send(), Err(Error::ConnectionUnusable)));
+  assert!(matches!(state.begin_read(), Err(Error::ConnectionUnusable)));
+}
+
+#[test]
+fn test_cannot_send_while_reading() {
+  let mut state = ConnectionState::new();
+  state.begin_read().unwrap();
+  assert!(matches!(state.begin_send(), Err(Error::ConnectionUnusable)));
+  assert_eq!(state, ConnectionState::ReadingResponse);
 }
@@ -2,6 +2,7 @@ use crate::config::Config;
 use crate::dns::DnsResolver;
 use crate::error::{DnsError, Error, SocketError};
 use crate::parser::uri::Uri;
+use crate::sleep::Sleeper;
 use crate::socket::{BlockingSocket, SocketAddr, SocketFlags};
 use crate::transport::connector::Connector;
 use crate::util::IpAddr;
@@ -9,31 +10,64 @@ use alloc::format;
 use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
+use core::cell::RefCell;
 use core::time::Duration;
 
 struct MockSocket {
   connected_addr: Option<String>,
+  bound_addr: Option<String>,
   read_timeout: Option<u32>,
   write_timeout: Option<u32>,
+  flags: Option<SocketFlags>,
+  flags_unsupported: bool,
   should_fail_connect: bool,
+  refused_connects: u32,
+  connect_attempts: u32,
+  tls_server_names: Vec<Option<String>>,
 }
 
 impl MockSocket {
   fn new() -> Self {
     Self {
       connected_addr: None,
+      bound_addr: None,
       read_timeout: None,
       write_timeout: None,
+      flags: None,
+      flags_unsupported: false,
       should_fail_connect: false,
+      refused_connects: 0,
+      connect_attempts: 0,
+      tls_server_names: Vec::new(),
+    }
+  }
+
+  fn with_refused_connects(count: u32) -> Self {
+    Self {
+      refused_connects: count,
+      ..Self::new()
+    }
+  }
+
+  fn with_unsupported_flags() -> Self {
+    Self {
+      flags_unsupported: true,
+      ..Self::new()
     }
   }
 
   fn with_connect_failure() -> Self {
     Self {
       connected_addr: None,
+      bound_addr: None,
       read_timeout: None,
       write_timeout: None,
+      flags: None,
+      flags_unsupported: false,
       should_fail_connect: true,
+      refused_connects: 0,
+      connect_attempts: 0,
+      tls_server_names: Vec::new(),
     }
   }
 }
@@ -42,19 +76,48 @@ impl BlockingSocket for MockSocket {
   fn new() -> Result<Self, SocketError> {
     Ok(Self {
       connected_addr: None,
+      bound_addr: None,
       read_timeout: None,
       write_timeout: None,
+      flags: None,
+      flags_unsupported: false,
       should_fail_connect: false,
+      refused_connects: 0,
+      connect_attempts: 0,
+      tls_server_names: Vec::new(),
     })
   }
 
+  fn bind(
+    &mut self,
+    addr: &SocketAddr<'_>,
+  ) -> Result<(), SocketError> {
+    if let SocketAddr::Ip { addr: ip_addr, port } = addr {
+      self.bound_addr = Some(format!("{ip_addr:?}:{port}"));
+    }
+    Ok(())
+  }
+
+  fn set_tls_server_name(
+    &mut self,
+    server_name: Option<&str>,
+  ) -> Result<(), SocketError> {
+    self.tls_server_names.push(server_name.map(String::from));
+    Ok(())
+  }
+
   fn connect(
     &mut self,
     addr: &SocketAddr<'_>,
   ) -> Result<(), SocketError> {
+    self.connect_attempts += 1;
     if self.should_fail_connect {
       return Err(SocketError::NotConnected);
     }
+    if self.refused_connects > 0 {
+      self.refused_connects -= 1;
+      return Err(SocketError::ConnectionRefused);
+    }
     match addr {
       SocketAddr::Ip { addr: ip_addr, port } => {
         self.connected_addr = Some(format!("{ip_addr:?}:{port}"));
@@ -87,8 +150,12 @@ impl BlockingSocket for MockSocket {
 
   fn set_flags(
     &mut self,
-    _flags: SocketFlags,
+    flags: SocketFlags,
   ) -> Result<(), SocketError> {
+    if self.flags_unsupported {
+      return Err(SocketError::Unsupported);
+    }
+    self.flags = Some(flags);
     Ok(())
   }
 
@@ -109,6 +176,27 @@ impl BlockingSocket for MockSocket {
   }
 }
 
+struct MockSleeper {
+  sleeps: RefCell<Vec<Duration>>,
+}
+
+impl MockSleeper {
+  fn new() -> Self {
+    Self {
+      sleeps: RefCell::new(Vec::new()),
+    }
+  }
+}
+
+impl Sleeper for MockSleeper {
+  fn sleep(
+    &self,
+    duration: Duration,
+  ) {
+    self.sleeps.borrow_mut().push(duration);
+  }
+}
+
 struct MockDns {
   addresses: Vec<IpAddr>,
 }
@@ -174,6 +262,44 @@ fn connector_uses_default_https_port_443() {
   assert!(addr.contains(":443"), "Should use port 443 for HTTPS");
 }
 
+#[test]
+fn connector_passes_tls_server_name_only_for_https() {
+  let dns = MockDns::new(vec![IpAddr::V4([127, 0, 0, 1])]);
+
+  let mut https_socket = MockSocket::new();
+  let https_uri = Uri::parse("https://secure.example.com").unwrap();
+  Connector::new(&mut https_socket, &dns)
+    .connect(&https_uri, &Config::default())
+    .unwrap();
+  assert_eq!(
+    https_socket.tls_server_names,
+    [Some(String::from("secure.example.com"))]
+  );
+
+  let mut http_socket = MockSocket::new();
+  let http_uri = Uri::parse("http://example.com").unwrap();
+  Connector::new(&mut http_socket, &dns)
+    .connect(&http_uri, &Config::default())
+    .unwrap();
+  assert_eq!(http_socket.tls_server_names, [None]);
+}
+
+#[test]
+fn connector_uses_registered_default_port_for_scheme() {
+  let mut socket = MockSocket::new();
+  let dns = MockDns::new(vec![IpAddr::V4([127, 0, 0, 1])]);
+  let connector = Connector::new(&mut socket, &dns);
+  let config = crate::config::ConfigBuilder::new()
+    .default_port("wss", 9443)
+    .build();
+
+  let uri = Uri::parse("wss://example.com/socket").unwrap();
+  connector.connect(&uri, &config).unwrap();
+
+  let addr = socket.connected_addr.unwrap();
+  assert!(addr.contains(":9443"), "Should use the port registered for wss");
+}
+
 #[test]
 fn connector_uses_explicit_port() {
   let mut socket = MockSocket::new();
@@ -404,3 +530,264 @@ fn connector_borrows_socket_and_dns() {
 
   assert!(socket.connected_addr.is_some());
 }
+
+#[test]
+fn connector_applies_nodelay_by_default() {
+  let mut socket = MockSocket::new();
+  let dns = MockDns::new(vec![IpAddr::V4([127, 0, 0, 1])]);
+  let connector = Connector::new(&mut socket, &dns);
+
+  let uri = Uri::parse("http://example.com").unwrap();
+  let _result = connector.connect(&uri, &Config::default());
+
+  let flags = socket.flags.unwrap();
+  assert!(flags.no_delay());
+  assert!(!flags.contains(SocketFlags::KEEPALIVE));
+}
+
+#[test]
+fn connector_applies_keepalive_from_config() {
+  let mut socket = MockSocket::new();
+  let dns = MockDns::new(vec![IpAddr::V4([127, 0, 0, 1])]);
+  let connector = Connector::new(&mut socket, &dns);
+
+  let config = Config {
+    tcp_nodelay: false,
+    tcp_keepalive: Some(Duration::from_secs(30)),
+    ..Default::default()
+  };
+
+  let uri = Uri::parse("http://example.com").unwrap();
+  let _result = connector.connect(&uri, &config);
+
+  let flags = socket.flags.unwrap();
+  assert!(!flags.no_delay());
+  assert!(flags.contains(SocketFlags::KEEPALIVE));
+  assert_eq!(flags.keep_alive_idle(), Some(Duration::from_secs(30)));
+}
+
+#[test]
+fn connector_skips_set_flags_when_no_options() {
+  let mut socket = MockSocket::new();
+  let dns = MockDns::new(vec![IpAddr::V4([127, 0, 0, 1])]);
+  let connector = Connector::new(&mut socket, &dns);
+
+  let config = Config {
+    tcp_nodelay: false,
+    ..Default::default()
+  };
+
+  let uri = Uri::parse("http://example.com").unwrap();
+  let _result = connector.connect(&uri, &config);
+
+  assert!(socket.flags.is_none());
+}
+
+#[test]
+fn connector_connects_when_adapter_does_not_support_flags() {
+  let mut socket = MockSocket::with_unsupported_flags();
+  let dns = MockDns::new(vec![IpAddr::V4([127, 0, 0, 1])]);
+  let connector = Connector::new(&mut socket, &dns);
+
+  let uri = Uri::parse("http://example.com").unwrap();
+  let result = connector.connect(&uri, &Config::default());
+
+  assert!(result.is_ok());
+  assert!(socket.flags.is_none());
+  assert!(socket.connected_addr.is_some());
+}
+
+#[test]
+fn connector_binds_local_address_when_configured() {
+  let mut socket = MockSocket::new();
+  let dns = MockDns::new(vec![IpAddr::V4([127, 0, 0, 1])]);
+  let connector = Connector::new(&mut socket, &dns);
+
+  let config = Config {
+    local_bind: Some(IpAddr::V4([10, 0, 0, 5])),
+    ..Default::default()
+  };
+
+  let uri = Uri::parse("http://example.com").unwrap();
+  let result = connector.connect(&uri, &config);
+
+  assert!(result.is_ok());
+  assert_eq!(socket.bound_addr.as_deref(), Some("V4([10, 0, 0, 5]):0"));
+  assert!(socket.connected_addr.is_some());
+}
+
+#[test]
+fn connector_does_not_bind_by_default() {
+  let mut socket = MockSocket::new();
+  let dns = MockDns::new(vec![IpAddr::V4([127, 0, 0, 1])]);
+  let connector = Connector::new(&mut socket, &dns);
+
+  let uri = Uri::parse("http://example.com").unwrap();
+  let _result = connector.connect(&uri, &Config::default());
+
+  assert!(socket.bound_addr.is_none());
+}
+
+#[test]
+fn connector_retries_refused_connect_with_backoff() {
+  let mut socket = MockSocket::with_refused_connects(2);
+  let dns = MockDns::new(vec![IpAddr::V4([127, 0, 0, 1])]);
+  let sleeper = MockSleeper::new();
+  let connector = Connector::new(&mut socket, &dns).with_sleeper(&sleeper);
+
+  let config = Config {
+    connect_retries: 3,
+    connect_backoff: Duration::from_millis(50),
+    ..Default::default()
+  };
+
+  let uri = Uri::parse("http://example.com").unwrap();
+  let result = connector.connect(&uri, &config);
+
+  assert!(result.is_ok());
+  assert_eq!(socket.connect_attempts, 3);
+  assert_eq!(
+    *sleeper.sleeps.borrow(),
+    vec![Duration::from_millis(50), Duration::from_millis(100)]
+  );
+}
+
+#[test]
+fn connector_gives_up_after_connect_retries() {
+  let mut socket = MockSocket::with_refused_connects(2);
+  let dns = MockDns::new(vec![IpAddr::V4([127, 0, 0, 1])]);
+  let sleeper = MockSleeper::new();
+  let connector = Connector::new(&mut socket, &dns).with_sleeper(&sleeper);
+
+  let config = Config {
+    connect_retries: 1,
+    ..Default::default()
+  };
+
+  let uri = Uri::parse("http://example.com").unwrap();
+  let result = connector.connect(&uri, &config);
+
+  assert!(matches!(result, Err(Error::Socket(SocketError::ConnectionRefused))));
+  assert_eq!(socket.connect_attempts, 2);
+}
+
+#[test]
+fn connector_does_not_retry_non_transient_errors() {
+  let mut socket = MockSocket::with_connect_failure();
+  let dns = MockDns::new(vec![IpAddr::V4([127, 0, 0, 1])]);
+  let sleeper = MockSleeper::new();
+  let connector = Connector::new(&mut socket, &dns).with_sleeper(&sleeper);
+
+  let config = Config {
+    connect_retries: 3,
+    ..Default::default()
+  };
+
+  let uri = Uri::parse("http://example.com").unwrap();
+  let result = connector.connect(&uri, &config);
+
+  assert!(result.is_err());
+  assert_eq!(socket.connect_attempts, 1);
+  assert!(sleeper.sleeps.borrow().is_empty());
+}
+
+#[test]
+fn connector_does_not_retry_by_default() {
+  let mut socket = MockSocket::with_refused_connects(1);
+  let dns = MockDns::new(vec![IpAddr::V4([127, 0, 0, 1])]);
+  let connector = Connector::new(&mut socket, &dns);
+
+  let uri = Uri::parse("http://example.com").unwrap();
+  let result = connector.connect(&uri, &Config::default());
+
+  assert!(result.is_err());
+  assert_eq!(socket.connect_attempts, 1);
+}
+
+#[test]
+fn connector_blocks_private_addresses_when_configured() {
+  let blocked = [
+    IpAddr::V4([127, 0, 0, 1]),
+    IpAddr::V4([127, 255, 255, 254]),
+    IpAddr::V4([10, 0, 0, 1]),
+    IpAddr::V4([172, 16, 0, 1]),
+    IpAddr::V4([172, 31, 255, 255]),
+    IpAddr::V4([192, 168, 1, 1]),
+    IpAddr::V4([169, 254, 169, 254]),
+    IpAddr::V4([0, 0, 0, 0]),
+    IpAddr::V6([0, 0, 0, 0, 0, 0, 0, 0]),
+    IpAddr::V6([0, 0, 0, 0, 0, 0, 0, 1]),
+    IpAddr::V6([0xfe80, 0, 0, 0, 0, 0, 0, 1]),
+    IpAddr::V6([0xfc00, 0, 0, 0, 0, 0, 0, 1]),
+    IpAddr::V6([0xfd12, 0x3456, 0, 0, 0, 0, 0, 1]),
+    IpAddr::V6([0, 0, 0, 0, 0, 0xffff, 0x7f00, 0x0001]),
+    IpAddr::V6([0, 0, 0, 0, 0, 0xffff, 0xa9fe, 0xa9fe]),
+  ];
+  let config = Config {
+    block_private_addresses: true,
+    ..Default::default()
+  };
+  let uri = Uri::parse("http://example.com").unwrap();
+
+  for addr in blocked {
+    let mut socket = MockSocket::new();
+    let dns = MockDns::new(vec![addr]);
+    let result = Connector::new(&mut socket, &dns).connect(&uri, &config);
+
+    assert!(matches!(result, Err(Error::BlockedAddress)), "{addr:?}");
+    assert_eq!(socket.connect_attempts, 0, "{addr:?}");
+  }
+}
+
+#[test]
+fn connector_allows_public_addresses_when_blocking_private_ones() {
+  let public = [
+    IpAddr::V4([93, 184, 216, 34]),
+    IpAddr::V4([172, 32, 0, 1]),
+    IpAddr::V4([192, 169, 0, 1]),
+    IpAddr::V6([0x2606, 0x4700, 0, 0, 0, 0, 0, 0x1111]),
+  ];
+  let config = Config {
+    block_private_addresses: true,
+    ..Default::default()
+  };
+  let uri = Uri::parse("http://example.com").unwrap();
+
+  for addr in public {
+    let mut socket = MockSocket::new();
+    let dns = MockDns::new(vec![addr]);
+    let result = Connector::new(&mut socket, &dns).connect(&uri, &config);
+
+    assert!(result.is_ok(), "{addr:?}");
+  }
+}
+
+#[test]
+fn connector_allows_private_addresses_by_default() {
+  let mut socket = MockSocket::new();
+  let dns = MockDns::new(vec![IpAddr::V4([10, 0, 0, 1])]);
+
+  let uri = Uri::parse("http://example.com").unwrap();
+  let result = Connector::new(&mut socket, &dns).connect(&uri, &Config::default());
+
+  assert!(result.is_ok());
+}
+
+#[test]
+fn connector_checks_proxied_target_addresses() {
+  let config = Config {
+    block_private_addresses: true,
+    ..Default::default()
+  };
+  let mut socket = MockSocket::new();
+  let dns = MockDns::new(vec![IpAddr::V4([93, 184, 216, 34]), IpAddr::V4([10, 0, 0, 1])]);
+  let connector = Connector::new(&mut socket, &dns);
+
+  let named = connector.check_proxied_target(&Uri::parse("http://internal.example").unwrap(), &config);
+  let literal = connector.check_proxied_target(&Uri::parse("http://169.254.169.254/").unwrap(), &config);
+  let unchecked = connector.check_proxied_target(&Uri::parse("http://internal.example").unwrap(), &Config::default());
+
+  assert!(matches!(named, Err(Error::BlockedAddress)));
+  assert!(matches!(literal, Err(Error::BlockedAddress)));
+  assert!(unchecked.is_ok());
+}
@@ -0,0 +1,181 @@
+use crate::clock::ManualClock;
+use crate::config::{Config, ConfigBuilder};
+use crate::error::SocketError;
+use crate::parser::uri::Uri;
+use crate::socket::{BlockingSocket, SocketAddr, SocketFlags};
+use crate::transport::{ConnectionPool, PoolKey, PoolStats};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::time::Duration;
+
+/// Sockets shut down by `ConnectionPool::clear`; only `clear_shuts_down_idle_sockets` clears the pool
+static SHUT_DOWN: AtomicUsize = AtomicUsize::new(0);
+
+/// Socket that only carries an id, to tell pooled sockets apart
+struct IdSocket(u32);
+
+impl BlockingSocket for IdSocket {
+  fn new() -> Result<Self, SocketError> {
+    Ok(Self(0))
+  }
+
+  fn connect(
+    &mut self,
+    _addr: &SocketAddr<'_>,
+  ) -> Result<(), SocketError> {
+    Ok(())
+  }
+
+  fn read(
+    &mut self,
+    _buf: &mut [u8],
+  ) -> Result<usize, SocketError> {
+    Ok(0)
+  }
+
+  fn write(
+    &mut self,
+    buf: &[u8],
+  ) -> Result<usize, SocketError> {
+    Ok(buf.len())
+  }
+
+  fn shutdown(&mut self) -> Result<(), SocketError> {
+    SHUT_DOWN.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+  }
+
+  fn set_flags(
+    &mut self,
+    _flags: SocketFlags,
+  ) -> Result<(), SocketError> {
+    Ok(())
+  }
+
+  fn set_read_timeout(
+    &mut self,
+    _timeout_ms: u32,
+  ) -> Result<(), SocketError> {
+    Ok(())
+  }
+
+  fn set_write_timeout(
+    &mut self,
+    _timeout_ms: u32,
+  ) -> Result<(), SocketError> {
+    Ok(())
+  }
+}
+
+fn key() -> PoolKey {
+  key_for("http://example.com")
+}
+
+#[test]
+fn idle_connection_is_reused_before_timeout() {
+  let clock = ManualClock::new(1_000);
+  let pool = ConnectionPool::new(5, Some(Duration::from_secs(90)));
+
+  pool.return_connection(key(), IdSocket(1), Some(&clock));
+  clock.advance(90_000);
+
+  let socket = pool.get(&key(), Some(&clock)).unwrap();
+  assert_eq!(socket.0, 1);
+}
+
+#[test]
+fn idle_connection_is_evicted_after_timeout() {
+  let clock = ManualClock::new(1_000);
+  let pool = ConnectionPool::new(5, Some(Duration::from_secs(90)));
+
+  pool.return_connection(key(), IdSocket(1), Some(&clock));
+  clock.advance(60_000);
+  pool.return_connection(key(), IdSocket(2), Some(&clock));
+  clock.advance(30_001);
+
+  // The older socket expired and is gone; the newer one is still usable
+  assert_eq!(pool.get(&key(), Some(&clock)).unwrap().0, 2);
+  assert_eq!(pool.len(), 0);
+
+  pool.return_connection(key(), IdSocket(3), Some(&clock));
+  clock.advance(90_001);
+  assert!(pool.get(&key(), Some(&clock)).is_none());
+}
+
+#[test]
+fn connections_never_expire_without_a_clock() {
+  let pool = ConnectionPool::new(5, Some(Duration::from_secs(1)));
+
+  pool.return_connection(key(), IdSocket(1), None);
+
+  assert_eq!(pool.get(&key(), None).unwrap().0, 1);
+}
+
+#[test]
+fn full_pool_drops_oldest_idle_connection() {
+  let pool = ConnectionPool::new(2, None);
+
+  pool.return_connection(key(), IdSocket(1), None);
+  pool.return_connection(key(), IdSocket(2), None);
+  pool.return_connection(key(), IdSocket(3), None);
+
+  assert_eq!(pool.len(), 2);
+  assert_eq!(pool.get(&key(), None).unwrap().0, 3);
+  assert_eq!(pool.get(&key(), None).unwrap().0, 2);
+  assert!(pool.get(&key(), None).is_none());
+}
+
+#[test]
+fn zero_capacity_pool_keeps_nothing() {
+  let pool = ConnectionPool::new(0, None);
+
+  pool.return_connection(key(), IdSocket(1), None);
+
+  assert_eq!(pool.len(), 0);
+}
+
+#[test]
+fn stats_count_hits_and_misses() {
+  let pool = ConnectionPool::new(5, None);
+
+  assert!(pool.get(&key(), None).is_none());
+  pool.return_connection(key(), IdSocket(1), None);
+  assert!(pool.get(&key(), None).is_some());
+
+  assert_eq!(pool.stats(), PoolStats { hits: 1, misses: 1 });
+}
+
+#[test]
+fn clear_shuts_down_idle_sockets() {
+  let pool = ConnectionPool::new(5, None);
+  pool.return_connection(key(), IdSocket(1), None);
+  pool.return_connection(key(), IdSocket(2), None);
+  pool.return_connection(key_for("http://other.example"), IdSocket(3), None);
+  assert_eq!(pool.len(), 3);
+
+  pool.clear();
+
+  assert_eq!(pool.len(), 0);
+  assert_eq!(SHUT_DOWN.load(Ordering::SeqCst), 3);
+  assert!(pool.get(&key(), None).is_none());
+}
+
+fn key_for(url: &str) -> PoolKey {
+  PoolKey::from_uri(&Uri::parse(url).unwrap(), &Config::default()).unwrap()
+}
+
+#[test]
+fn pool_key_normalizes_default_port_and_case() {
+  assert_eq!(key_for("http://h"), key_for("http://h:80"));
+  assert_eq!(key_for("HTTP://Example.COM/a"), key_for("http://example.com:80/b"));
+  assert_eq!(key_for("https://h"), key_for("https://h:443"));
+  assert_ne!(key_for("http://h"), key_for("http://h:8080"));
+  assert_ne!(key_for("http://h:443"), key_for("https://h"));
+}
+
+#[test]
+fn pool_key_uses_configured_default_ports() {
+  let config = ConfigBuilder::new().default_port("ws", 8080).build();
+  let explicit = PoolKey::from_uri(&Uri::parse("ws://h:8080").unwrap(), &config).unwrap();
+  let implicit = PoolKey::from_uri(&Uri::parse("ws://h").unwrap(), &config).unwrap();
+  assert_eq!(explicit, implicit);
+}
@@ -0,0 +1,424 @@
+use crate::clock::{Clock, OsClock};
+use alloc::string::String;
+use core::fmt;
+
+const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+const WEEKDAY_NAMES: [&str; 7] = [
+  "Thursday",
+  "Friday",
+  "Saturday",
+  "Sunday",
+  "Monday",
+  "Tuesday",
+  "Wednesday",
+];
+const MONTHS: [&str; 12] = [
+  "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Calendar date and time in UTC, as carried by HTTP-date header fields
+///
+/// Used for `Date`, `Last-Modified`, `Expires`, `Retry-After` and cookie expiry.
+/// Formats as an IMF-fixdate (RFC 9110 Section 5.6.7) through `Display`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HttpDate {
+  /// Full year, e.g. 1994
+  pub year: u16,
+  /// Month of the year, 1-12
+  pub month: u8,
+  /// Day of the month, 1-31
+  pub day: u8,
+  /// Hour, 0-23
+  pub hour: u8,
+  /// Minute, 0-59
+  pub minute: u8,
+  /// Second, 0-59
+  pub second: u8,
+}
+
+impl HttpDate {
+  /// Build a date from seconds since the Unix epoch
+  ///
+  /// Years past 65535 saturate.
+  #[must_use]
+  pub fn from_unix(unix_secs: u64) -> Self {
+    let days = unix_secs.div_euclid(86_400);
+    let secs_of_day = unix_secs.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+
+    Self {
+      year: u16::try_from(year).unwrap_or(u16::MAX),
+      month,
+      day,
+      hour: u8::try_from(secs_of_day.div_euclid(3600)).unwrap_or(0),
+      minute: u8::try_from(secs_of_day.rem_euclid(3600).div_euclid(60)).unwrap_or(0),
+      second: u8::try_from(secs_of_day.rem_euclid(60)).unwrap_or(0),
+    }
+  }
+
+  /// Seconds since the Unix epoch, or None for dates before 1970
+  #[must_use]
+  pub const fn to_unix(self) -> Option<u64> {
+    if self.year < 1970 {
+      return None;
+    }
+    let days = days_from_civil(self.year as u64, self.month as u64, self.day as u64);
+    Some(days * 86_400 + self.hour as u64 * 3600 + self.minute as u64 * 60 + self.second as u64)
+  }
+
+  /// Parse an HTTP-date in any of the formats RFC 9110 Section 5.6.7 accepts
+  ///
+  /// These are the IMF-fixdate `Sun, 06 Nov 1994 08:49:37 GMT` and the
+  /// obsolete RFC 850 `Sunday, 06-Nov-94 08:49:37 GMT` and asctime
+  /// `Sun Nov  6 08:49:37 1994` formats. Day names are checked for shape only;
+  /// the date itself is validated field by field. The century of a two-digit
+  /// RFC 850 year is chosen as `parse_at` does, with the time from `OsClock`.
+  #[must_use]
+  pub fn parse(input: &str) -> Option<Self> {
+    Self::parse_at(input, OsClock::new().now_millis().div_euclid(1000))
+  }
+
+  /// Parse an HTTP-date as `parse` does, taking `now_unix` as the current time
+  ///
+  /// A two-digit RFC 850 year that would be more than 50 years after
+  /// `now_unix` is taken to be in the previous century, e.g. `94` is 1994
+  /// rather than 2094 in 2026.
+  #[must_use]
+  pub fn parse_at(
+    input: &str,
+    now_unix: u64,
+  ) -> Option<Self> {
+    let trimmed = input.trim();
+    Self::parse_imf_fixdate(trimmed)
+      .or_else(|| Self::parse_rfc850(trimmed, Self::from_unix(now_unix).year))
+      .or_else(|| Self::parse_asctime(trimmed))
+      .filter(|date| date.is_valid())
+  }
+
+  /// `Sun, 06 Nov 1994 08:49:37 GMT`
+  fn parse_imf_fixdate(input: &str) -> Option<Self> {
+    let mut parts = input.split(' ');
+    let day_name = parts.next()?;
+    let day = parts.next()?;
+    let month_name = parts.next()?;
+    let year = parts.next()?;
+    let time = parts.next()?;
+    if parts.next()? != "GMT" || parts.next().is_some() {
+      return None;
+    }
+    if !WEEKDAYS.contains(&day_name.strip_suffix(',')?) {
+      return None;
+    }
+    Self::from_fields(parse_digits(year, 4)?, month_name, parse_digits(day, 2)?, time)
+  }
+
+  /// `Sunday, 06-Nov-94 08:49:37 GMT`, with the century taken from `current_year`
+  fn parse_rfc850(
+    input: &str,
+    current_year: u16,
+  ) -> Option<Self> {
+    let mut parts = input.split(' ');
+    let day_name = parts.next()?;
+    let date = parts.next()?;
+    let time = parts.next()?;
+    if parts.next()? != "GMT" || parts.next().is_some() {
+      return None;
+    }
+    if !WEEKDAY_NAMES.contains(&day_name.strip_suffix(',')?) {
+      return None;
+    }
+    let mut fields = date.split('-');
+    let day = parse_digits(fields.next()?, 2)?;
+    let month_name = fields.next()?;
+    let two_digit_year: u16 = parse_digits(fields.next()?, 2)?;
+    if fields.next().is_some() {
+      return None;
+    }
+    // RFC 9110 Section 5.6.7: a year more than 50 years ahead is in the past
+    let mut year = current_year - current_year.rem_euclid(100) + two_digit_year;
+    if year > current_year.saturating_add(50) {
+      year = year.checked_sub(100)?;
+    } else if year.saturating_add(50) <= current_year {
+      year = year.checked_add(100)?;
+    }
+    Self::from_fields(year, month_name, day, time)
+  }
+
+  /// `Sun Nov  6 08:49:37 1994`, with a space-padded day
+  fn parse_asctime(input: &str) -> Option<Self> {
+    let mut parts = input.split(' ');
+    let day_name = parts.next()?;
+    let month_name = parts.next()?;
+    let mut day_field = parts.next()?;
+    let day = if day_field.is_empty() {
+      day_field = parts.next()?;
+      parse_digits(day_field, 1)?
+    } else {
+      parse_digits(day_field, 2)?
+    };
+    let time = parts.next()?;
+    let year = parts.next()?;
+    if parts.next().is_some() || !WEEKDAYS.contains(&day_name) {
+      return None;
+    }
+    Self::from_fields(parse_digits(year, 4)?, month_name, day, time)
+  }
+
+  /// Date from a year, a month name, a day and an `HH:MM:SS` time
+  fn from_fields(
+    year: u16,
+    month_name: &str,
+    day: u8,
+    time: &str,
+  ) -> Option<Self> {
+    let month = MONTHS.iter().position(|m| *m == month_name)?;
+    let mut clock = time.split(':');
+    let date = Self {
+      year,
+      month: u8::try_from(month).ok()?.checked_add(1)?,
+      day,
+      hour: parse_digits(clock.next()?, 2)?,
+      minute: parse_digits(clock.next()?, 2)?,
+      second: parse_digits(clock.next()?, 2)?,
+    };
+    if clock.next().is_some() {
+      return None;
+    }
+    Some(date)
+  }
+
+  const fn is_valid(self) -> bool {
+    self.month >= 1
+      && self.month <= 12
+      && self.day >= 1
+      && self.day <= days_in_month(self.year, self.month)
+      && self.hour <= 23
+      && self.minute <= 59
+      && self.second <= 60
+  }
+
+  fn weekday(self) -> &'static str {
+    let days = days_from_civil(u64::from(self.year), u64::from(self.month), u64::from(self.day));
+    usize::try_from(days.rem_euclid(7))
+      .ok()
+      .and_then(|index| WEEKDAYS.get(index))
+      .copied()
+      .unwrap_or("Thu")
+  }
+}
+
+impl fmt::Display for HttpDate {
+  fn fmt(
+    &self,
+    f: &mut fmt::Formatter<'_>,
+  ) -> fmt::Result {
+    let month = MONTHS
+      .get(usize::from(self.month.saturating_sub(1)))
+      .copied()
+      .unwrap_or("Jan");
+    write!(
+      f,
+      "{}, {:02} {month} {:04} {:02}:{:02}:{:02} GMT",
+      self.weekday(),
+      self.day,
+      self.year,
+      self.hour,
+      self.minute,
+      self.second
+    )
+  }
+}
+
+/// Format Unix seconds as an IMF-fixdate (RFC 9110 Section 5.6.7)
+///
+/// e.g. `Sun, 06 Nov 1994 08:49:37 GMT`, as used by `If-Modified-Since`.
+#[must_use]
+pub fn format_http_date(unix_secs: u64) -> String {
+  use alloc::string::ToString;
+  HttpDate::from_unix(unix_secs).to_string()
+}
+
+/// Parse an HTTP-date in any format `HttpDate::parse` accepts into Unix seconds
+#[must_use]
+pub fn parse_http_date(input: &str) -> Option<u64> {
+  HttpDate::parse(input)?.to_unix()
+}
+
+fn parse_digits<T: TryFrom<u32>>(
+  input: &str,
+  len: usize,
+) -> Option<T> {
+  if input.len() != len || !input.bytes().all(|b| b.is_ascii_digit()) {
+    return None;
+  }
+  T::try_from(input.parse::<u32>().ok()?).ok()
+}
+
+const fn is_leap_year(year: u16) -> bool {
+  year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+const fn days_in_month(
+  year: u16,
+  month: u8,
+) -> u8 {
+  match month {
+    2 if is_leap_year(year) => 29,
+    2 => 28,
+    4 | 6 | 9 | 11 => 30,
+    _ => 31,
+  }
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) civil date
+///
+/// Howard Hinnant's `civil_from_days`, restricted to dates on or after the epoch.
+#[allow(clippy::cast_possible_truncation)]
+const fn civil_from_days(days: u64) -> (u64, u8, u8) {
+  let z = days + 719_468;
+  let era = z.div_euclid(146_097);
+  let doe = z - era * 146_097;
+  let yoe = (doe - doe.div_euclid(1460) + doe.div_euclid(36_524) - doe.div_euclid(146_096)).div_euclid(365);
+  let doy = doe - (365 * yoe + yoe.div_euclid(4) - yoe.div_euclid(100));
+  let mp = (5 * doy + 2).div_euclid(153);
+  let day = doy - (153 * mp + 2).div_euclid(5) + 1;
+  let month = if mp < 10 {
+    mp + 3
+  } else {
+    mp - 9
+  };
+  let year = yoe
+    + era * 400
+    + if month <= 2 {
+      1
+    } else {
+      0
+    };
+  (year, month as u8, day as u8)
+}
+
+/// Convert a civil date on or after 1970-01-01 to days since the epoch
+///
+/// Inverse of `civil_from_days`.
+const fn days_from_civil(
+  year: u64,
+  month: u64,
+  day: u64,
+) -> u64 {
+  let shifted_year = if month <= 2 {
+    year - 1
+  } else {
+    year
+  };
+  let era = shifted_year.div_euclid(400);
+  let yoe = shifted_year - era * 400;
+  let mp = if month > 2 {
+    month - 3
+  } else {
+    month + 9
+  };
+  let doy = (153 * mp + 2).div_euclid(5) + day - 1;
+  let doe = yoe * 365 + yoe.div_euclid(4) - yoe.div_euclid(100) + doy;
+  era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn formats_epoch() {
+    assert_eq!(format_http_date(0), "Thu, 01 Jan 1970 00:00:00 GMT");
+  }
+
+  #[test]
+  fn formats_rfc_example() {
+    assert_eq!(format_http_date(784_111_777), "Sun, 06 Nov 1994 08:49:37 GMT");
+  }
+
+  #[test]
+  fn formats_leap_day() {
+    assert_eq!(format_http_date(951_782_400), "Tue, 29 Feb 2000 00:00:00 GMT");
+  }
+
+  #[test]
+  fn parses_imf_fixdate() {
+    let date = HttpDate::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+    assert_eq!(
+      date,
+      HttpDate {
+        year: 1994,
+        month: 11,
+        day: 6,
+        hour: 8,
+        minute: 49,
+        second: 37,
+      }
+    );
+    assert_eq!(date.to_unix(), Some(784_111_777));
+  }
+
+  #[test]
+  fn round_trips_through_unix_seconds() {
+    for secs in [0, 951_782_400, 1_700_000_000, 4_102_444_800] {
+      assert_eq!(parse_http_date(&format_http_date(secs)), Some(secs));
+    }
+  }
+
+  #[test]
+  fn rejects_malformed_dates() {
+    assert!(HttpDate::parse("Sun, 06-Nov-94 08:49:37 GMT").is_none());
+    assert!(HttpDate::parse("Sunday, 06 Nov 1994 08:49:37 GMT").is_none());
+    assert!(HttpDate::parse("Sun Nov 6 08:49:37 1994").is_none());
+    assert!(HttpDate::parse("Sun Nov  6 08:49:37 1994 GMT").is_none());
+    assert!(HttpDate::parse("Sun, 06 Nov 1994 08:49:37 UTC").is_none());
+    assert!(HttpDate::parse("Sun, 31 Feb 1994 08:49:37 GMT").is_none());
+    assert!(HttpDate::parse("Sun, 06 Nov 1994 24:00:00 GMT").is_none());
+    assert!(HttpDate::parse("Sun, 6 Nov 1994 08:49:37 GMT").is_none());
+  }
+
+  #[test]
+  fn parses_obsolete_formats() {
+    // 2026-10-16
+    let now = 1_792_108_800;
+    for input in [
+      "Sun, 06 Nov 1994 08:49:37 GMT",
+      "Sunday, 06-Nov-94 08:49:37 GMT",
+      "Sun Nov  6 08:49:37 1994",
+      "Sun Nov 06 08:49:37 1994",
+    ] {
+      assert_eq!(
+        HttpDate::parse_at(input, now).and_then(HttpDate::to_unix),
+        Some(784_111_777),
+        "{input}"
+      );
+    }
+    assert_eq!(parse_http_date("Sun Nov  6 08:49:37 1994"), Some(784_111_777));
+    assert!(HttpDate::parse_at("Tuesday, 31-Feb-25 00:00:00 GMT", now).is_none());
+    assert!(HttpDate::parse_at("Tue Feb 31 00:00:00 2025", now).is_none());
+  }
+
+  #[test]
+  fn rfc850_year_more_than_50_years_ahead_is_in_the_past() {
+    // 2026-10-16
+    let now = 1_792_108_800;
+    let year = |input| HttpDate::parse_at(input, now).unwrap().year;
+    assert_eq!(year("Friday, 01-Jan-76 00:00:00 GMT"), 2076);
+    assert_eq!(year("Saturday, 01-Jan-77 00:00:00 GMT"), 1977);
+    assert_eq!(year("Saturday, 01-Jan-00 00:00:00 GMT"), 2000);
+    assert_eq!(year("Thursday, 01-Jan-26 00:00:00 GMT"), 2026);
+
+    // 2090-01-01: 41 is 49 years back and 39 is 49 years ahead
+    let later = 3_786_912_000;
+    let later_year = |input| HttpDate::parse_at(input, later).unwrap().year;
+    assert_eq!(later_year("Tuesday, 01-Jan-41 00:00:00 GMT"), 2041);
+    assert_eq!(later_year("Saturday, 01-Jan-39 00:00:00 GMT"), 2139);
+  }
+
+  #[test]
+  fn pre_epoch_dates_have_no_unix_time() {
+    let date = HttpDate::parse("Thu, 01 Jan 1950 00:00:00 GMT").unwrap();
+    assert!(date.to_unix().is_none());
+  }
+}
@@ -1,6 +1,12 @@
+/// HTTP-date parsing and formatting
+pub mod httpdate;
 /// Network utilities
 pub mod network;
+/// Punycode encoding of internationalized host names
+#[cfg(feature = "idna")]
+pub mod punycode;
 
+pub use httpdate::{HttpDate, format_http_date, parse_http_date};
 pub use network::IpAddr;
 
 /// Percent-encode a string for use in URLs
@@ -1,3 +1,6 @@
+use alloc::string::String;
+use core::fmt::Write;
+
 /// IP address (IPv4 or IPv6)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum IpAddr {
@@ -25,4 +28,306 @@ impl IpAddr {
       Self::V6(addr) => Some(addr),
     }
   }
+
+  #[must_use]
+  /// Returns the IPv4 address inside an IPv4-mapped IPv6 address (`::ffff:a.b.c.d`)
+  pub const fn to_ipv4_mapped(&self) -> Option<[u8; 4]> {
+    match *self {
+      Self::V6([0, 0, 0, 0, 0, 0xffff, high, low]) => {
+        let [a, b] = high.to_be_bytes();
+        let [c, d] = low.to_be_bytes();
+        Some([a, b, c, d])
+      },
+      Self::V4(_) | Self::V6(_) => None,
+    }
+  }
+
+  #[must_use]
+  /// Checks for a loopback address: `127.0.0.0/8` or `::1`
+  pub const fn is_loopback(&self) -> bool {
+    match *self {
+      Self::V4([a, ..]) => a == 127,
+      Self::V6(segments) => matches!(segments, [0, 0, 0, 0, 0, 0, 0, 1]),
+    }
+  }
+
+  #[must_use]
+  /// Checks for a private IPv4 address (RFC 1918): `10.0.0.0/8`, `172.16.0.0/12` or `192.168.0.0/16`
+  ///
+  /// Always false for IPv6, whose private range is checked by `is_unique_local`.
+  pub const fn is_private(&self) -> bool {
+    match *self {
+      Self::V4([a, b, ..]) => a == 10 || (a == 172 && b >= 16 && b <= 31) || (a == 192 && b == 168),
+      Self::V6(_) => false,
+    }
+  }
+
+  #[must_use]
+  /// Checks for a link-local address: `169.254.0.0/16` or `fe80::/10`
+  pub const fn is_link_local(&self) -> bool {
+    match *self {
+      Self::V4([a, b, ..]) => a == 169 && b == 254,
+      Self::V6([first, ..]) => first & 0xffc0 == 0xfe80,
+    }
+  }
+
+  #[must_use]
+  /// Checks for an IPv6 unique local address, `fc00::/7` (RFC 4193)
+  ///
+  /// Always false for IPv4.
+  pub const fn is_unique_local(&self) -> bool {
+    match *self {
+      Self::V4(_) => false,
+      Self::V6([first, ..]) => first & 0xfe00 == 0xfc00,
+    }
+  }
+
+  #[must_use]
+  /// Checks for a multicast address: `224.0.0.0/4` or `ff00::/8`
+  pub const fn is_multicast(&self) -> bool {
+    match *self {
+      Self::V4([a, ..]) => a & 0xf0 == 224,
+      Self::V6([first, ..]) => first & 0xff00 == 0xff00,
+    }
+  }
+
+  #[must_use]
+  /// Checks for the unspecified address: `0.0.0.0` or `::`
+  pub const fn is_unspecified(&self) -> bool {
+    match *self {
+      Self::V4(octets) => matches!(octets, [0, 0, 0, 0]),
+      Self::V6(segments) => matches!(segments, [0, 0, 0, 0, 0, 0, 0, 0]),
+    }
+  }
+
+  /// Format the address as the host part of a URI authority or `Host` header
+  ///
+  /// IPv6 addresses are written in the RFC 5952 canonical form and enclosed in
+  /// brackets (RFC 3986 Section 3.2.2); `port`, if given, is appended after a colon.
+  #[must_use]
+  pub fn to_authority_string(
+    &self,
+    port: Option<u16>,
+  ) -> String {
+    let mut authority = String::new();
+    match *self {
+      Self::V4([a, b, c, d]) => {
+        let _ = write!(authority, "{a}.{b}.{c}.{d}");
+      },
+      Self::V6(segments) => {
+        authority.push('[');
+        write_ipv6(&mut authority, segments);
+        authority.push(']');
+      },
+    }
+    if let Some(number) = port {
+      let _ = write!(authority, ":{number}");
+    }
+    authority
+  }
+}
+
+/// Write IPv6 segments in the RFC 5952 canonical form
+///
+/// Hex digits are lowercase without leading zeros, and the longest run of two or
+/// more zero segments (the first one on a tie) is shortened to `::`.
+fn write_ipv6(
+  out: &mut String,
+  segments: [u16; 8],
+) {
+  let mut longest = (0, 0);
+  let mut run_start = 0;
+  for (index, &segment) in segments.iter().enumerate() {
+    if segment != 0 {
+      run_start = index + 1;
+    } else if index + 1 - run_start > longest.1 {
+      longest = (run_start, index + 1 - run_start);
+    }
+  }
+  let (zeros_start, zeros_len) = if longest.1 >= 2 {
+    longest
+  } else {
+    (8, 0)
+  };
+
+  for (index, &segment) in segments.iter().enumerate() {
+    if index == zeros_start {
+      out.push_str("::");
+    } else if index < zeros_start || index >= zeros_start + zeros_len {
+      if index > 0 && index != zeros_start + zeros_len {
+        out.push(':');
+      }
+      let _ = write!(out, "{segment:x}");
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use alloc::vec::Vec;
+
+  const fn v4(
+    a: u8,
+    b: u8,
+    c: u8,
+    d: u8,
+  ) -> IpAddr {
+    IpAddr::V4([a, b, c, d])
+  }
+
+  const fn v6(first: u16) -> IpAddr {
+    IpAddr::V6([first, 0, 0, 0, 0, 0, 0, 1])
+  }
+
+  #[test]
+  fn classifies_boundary_addresses() {
+    // (address, loopback, private, link-local, unique local, multicast, unspecified)
+    let table = [
+      (v4(0, 0, 0, 0), false, false, false, false, false, true),
+      (v4(0, 0, 0, 1), false, false, false, false, false, false),
+      (v4(9, 255, 255, 255), false, false, false, false, false, false),
+      (v4(10, 0, 0, 0), false, true, false, false, false, false),
+      (v4(10, 255, 255, 255), false, true, false, false, false, false),
+      (v4(11, 0, 0, 0), false, false, false, false, false, false),
+      (v4(126, 255, 255, 255), false, false, false, false, false, false),
+      (v4(127, 0, 0, 0), true, false, false, false, false, false),
+      (v4(127, 255, 255, 255), true, false, false, false, false, false),
+      (v4(128, 0, 0, 0), false, false, false, false, false, false),
+      (v4(169, 253, 255, 255), false, false, false, false, false, false),
+      (v4(169, 254, 0, 0), false, false, true, false, false, false),
+      (v4(169, 254, 255, 255), false, false, true, false, false, false),
+      (v4(169, 255, 0, 0), false, false, false, false, false, false),
+      (v4(172, 15, 255, 255), false, false, false, false, false, false),
+      (v4(172, 16, 0, 0), false, true, false, false, false, false),
+      (v4(172, 31, 255, 255), false, true, false, false, false, false),
+      (v4(172, 32, 0, 0), false, false, false, false, false, false),
+      (v4(192, 167, 255, 255), false, false, false, false, false, false),
+      (v4(192, 168, 0, 0), false, true, false, false, false, false),
+      (v4(192, 168, 255, 255), false, true, false, false, false, false),
+      (v4(192, 169, 0, 0), false, false, false, false, false, false),
+      (v4(223, 255, 255, 255), false, false, false, false, false, false),
+      (v4(224, 0, 0, 0), false, false, false, false, true, false),
+      (v4(239, 255, 255, 255), false, false, false, false, true, false),
+      (v4(240, 0, 0, 0), false, false, false, false, false, false),
+      (v4(255, 255, 255, 255), false, false, false, false, false, false),
+      (IpAddr::V6([0; 8]), false, false, false, false, false, true),
+      (
+        IpAddr::V6([0, 0, 0, 0, 0, 0, 0, 1]),
+        true,
+        false,
+        false,
+        false,
+        false,
+        false,
+      ),
+      (
+        IpAddr::V6([0, 0, 0, 0, 0, 0, 0, 2]),
+        false,
+        false,
+        false,
+        false,
+        false,
+        false,
+      ),
+      (
+        IpAddr::V6([0, 0, 0, 0, 0, 0, 1, 1]),
+        false,
+        false,
+        false,
+        false,
+        false,
+        false,
+      ),
+      (v6(0xfbff), false, false, false, false, false, false),
+      (v6(0xfc00), false, false, false, true, false, false),
+      (v6(0xfdff), false, false, false, true, false, false),
+      (v6(0xfe00), false, false, false, false, false, false),
+      (v6(0xfe7f), false, false, false, false, false, false),
+      (v6(0xfe80), false, false, true, false, false, false),
+      (v6(0xfebf), false, false, true, false, false, false),
+      (v6(0xfec0), false, false, false, false, false, false),
+      (v6(0xfeff), false, false, false, false, false, false),
+      (v6(0xff00), false, false, false, false, true, false),
+      (v6(0xff02), false, false, false, false, true, false),
+      (v6(0xffff), false, false, false, false, true, false),
+      (v6(0x2001), false, false, false, false, false, false),
+      // IPv4-mapped addresses are classified as IPv6, not by the IPv4 they carry
+      (
+        IpAddr::V6([0, 0, 0, 0, 0, 0xffff, 0x7f00, 1]),
+        false,
+        false,
+        false,
+        false,
+        false,
+        false,
+      ),
+      (
+        IpAddr::V6([0, 0, 0, 0, 0, 0xffff, 0x0a00, 1]),
+        false,
+        false,
+        false,
+        false,
+        false,
+        false,
+      ),
+    ];
+
+    for (addr, loopback, private, link_local, unique_local, multicast, unspecified) in table {
+      assert_eq!(addr.is_loopback(), loopback, "is_loopback {addr:?}");
+      assert_eq!(addr.is_private(), private, "is_private {addr:?}");
+      assert_eq!(addr.is_link_local(), link_local, "is_link_local {addr:?}");
+      assert_eq!(addr.is_unique_local(), unique_local, "is_unique_local {addr:?}");
+      assert_eq!(addr.is_multicast(), multicast, "is_multicast {addr:?}");
+      assert_eq!(addr.is_unspecified(), unspecified, "is_unspecified {addr:?}");
+    }
+  }
+
+  #[test]
+  fn unwraps_ipv4_mapped_addresses() {
+    assert_eq!(
+      IpAddr::V6([0, 0, 0, 0, 0, 0xffff, 0xc0a8, 0x0102]).to_ipv4_mapped(),
+      Some([192, 168, 1, 2])
+    );
+    assert_eq!(IpAddr::V6([0, 0, 0, 0, 0, 0, 0xc0a8, 0x0102]).to_ipv4_mapped(), None);
+    assert_eq!(
+      IpAddr::V6([0, 0, 0, 0, 0, 0xfffe, 0xc0a8, 0x0102]).to_ipv4_mapped(),
+      None
+    );
+    assert_eq!(v4(192, 168, 1, 2).to_ipv4_mapped(), None);
+  }
+
+  #[test]
+  fn formats_authority_with_and_without_port() {
+    assert_eq!(v4(192, 168, 0, 1).to_authority_string(None), "192.168.0.1");
+    assert_eq!(v4(192, 168, 0, 1).to_authority_string(Some(8080)), "192.168.0.1:8080");
+    assert_eq!(IpAddr::V6([0, 0, 0, 0, 0, 0, 0, 1]).to_authority_string(None), "[::1]");
+    assert_eq!(
+      IpAddr::V6([0, 0, 0, 0, 0, 0, 0, 1]).to_authority_string(Some(443)),
+      "[::1]:443"
+    );
+  }
+
+  #[test]
+  fn formats_ipv6_in_canonical_form() {
+    let cases = [
+      ([0; 8], "[::]"),
+      ([0x2001, 0xdb8, 0, 0, 0, 0, 0, 1], "[2001:db8::1]"),
+      ([0xfe80, 0, 0, 0, 0, 0, 0, 0], "[fe80::]"),
+      ([0, 0, 0, 0, 0, 0xffff, 0xc000, 0x0280], "[::ffff:c000:280]"),
+      // A single zero segment is not shortened
+      ([0x2001, 0xdb8, 0, 1, 1, 1, 1, 1], "[2001:db8:0:1:1:1:1:1]"),
+      // The longest run wins, and the first one on a tie
+      ([0x2001, 0, 0, 1, 0, 0, 0, 1], "[2001:0:0:1::1]"),
+      ([0x2001, 0xdb8, 0, 0, 1, 0, 0, 1], "[2001:db8::1:0:0:1]"),
+      ([0xABCD, 0x00EF, 1, 2, 3, 4, 5, 6], "[abcd:ef:1:2:3:4:5:6]"),
+    ];
+    let formatted: Vec<String> = cases
+      .iter()
+      .map(|(segments, _)| IpAddr::V6(*segments).to_authority_string(None))
+      .collect();
+    let expected: Vec<&str> = cases.iter().map(|(_, text)| *text).collect();
+
+    assert_eq!(formatted, expected);
+  }
 }
@@ -0,0 +1,142 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// Bootstring parameters for Punycode (RFC 3492 Section 5)
+const BASE: u32 = 36;
+const T_MIN: u32 = 1;
+const T_MAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 128;
+const DIGITS: &[u8; 36] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Encode one label with Punycode (RFC 3492), without the `xn--` prefix
+///
+/// Returns `None` if the label is too long for the encoder's counters.
+#[must_use]
+pub fn encode(label: &str) -> Option<String> {
+  let code_points: Vec<u32> = label.chars().map(u32::from).collect();
+  let mut output: String = label.chars().filter(char::is_ascii).collect();
+  let basic_count = u32::try_from(output.len()).ok()?;
+  if basic_count > 0 {
+    output.push('-');
+  }
+
+  let mut n = INITIAL_N;
+  let mut delta = 0u32;
+  let mut bias = INITIAL_BIAS;
+  let mut handled = basic_count;
+  let total = u32::try_from(code_points.len()).ok()?;
+
+  while handled < total {
+    let next = code_points
+      .iter()
+      .copied()
+      .filter(|&code_point| code_point >= n)
+      .min()?;
+    delta = delta.checked_add((next - n).checked_mul(handled + 1)?)?;
+    n = next;
+
+    for &code_point in &code_points {
+      if code_point < n {
+        delta = delta.checked_add(1)?;
+      }
+      if code_point == n {
+        let mut q = delta;
+        let mut k = BASE;
+        loop {
+          let threshold = if k <= bias {
+            T_MIN
+          } else if k >= bias + T_MAX {
+            T_MAX
+          } else {
+            k - bias
+          };
+          if q < threshold {
+            break;
+          }
+          output.push(digit(threshold + (q - threshold).rem_euclid(BASE - threshold))?);
+          q = (q - threshold).div_euclid(BASE - threshold);
+          k += BASE;
+        }
+        output.push(digit(q)?);
+        bias = adapt(delta, handled + 1, handled == basic_count);
+        delta = 0;
+        handled += 1;
+      }
+    }
+    delta = delta.checked_add(1)?;
+    n += 1;
+  }
+  Some(output)
+}
+
+/// Convert a host name to its ASCII form for DNS and the `Host` header
+///
+/// Labels with non-ASCII characters are lowercased and written as `xn--`
+/// followed by their Punycode encoding; ASCII labels are kept as given.
+/// Returns `None` if a label cannot be encoded.
+#[must_use]
+pub fn domain_to_ascii(host: &str) -> Option<String> {
+  let mut ascii = String::with_capacity(host.len());
+  for (index, label) in host.split('.').enumerate() {
+    if index > 0 {
+      ascii.push('.');
+    }
+    if label.is_ascii() {
+      ascii.push_str(label);
+    } else {
+      let lowercase: String = label.chars().flat_map(char::to_lowercase).collect();
+      ascii.push_str("xn--");
+      ascii.push_str(&encode(&lowercase)?);
+    }
+  }
+  Some(ascii)
+}
+
+/// Bias adaptation after each encoded code point (RFC 3492 Section 6.1)
+const fn adapt(
+  delta: u32,
+  points: u32,
+  first_time: bool,
+) -> u32 {
+  let mut scaled = if first_time {
+    delta.div_euclid(DAMP)
+  } else {
+    delta.div_euclid(2)
+  };
+  scaled += scaled.div_euclid(points);
+  let mut k = 0;
+  while scaled > ((BASE - T_MIN) * T_MAX).div_euclid(2) {
+    scaled = scaled.div_euclid(BASE - T_MIN);
+    k += BASE;
+  }
+  k + ((BASE - T_MIN + 1) * scaled).div_euclid(scaled + SKEW)
+}
+
+fn digit(value: u32) -> Option<char> {
+  let index = usize::try_from(value).ok()?;
+  DIGITS.get(index).copied().map(char::from)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn encodes_rfc_3492_samples() {
+    assert_eq!(encode("bücher").unwrap(), "bcher-kva");
+    assert_eq!(encode("münchen").unwrap(), "mnchen-3ya");
+    assert_eq!(encode("他们为什么不说中文").unwrap(), "ihqwcrb4cv8a8dqg056pqjye");
+    assert_eq!(encode("안녕하세요").unwrap(), "o70b841ad5c9qb763b");
+  }
+
+  #[test]
+  fn converts_only_non_ascii_labels() {
+    assert_eq!(domain_to_ascii("bücher.de").unwrap(), "xn--bcher-kva.de");
+    assert_eq!(domain_to_ascii("www.MÜNCHEN.de").unwrap(), "www.xn--mnchen-3ya.de");
+    assert_eq!(domain_to_ascii("example.com").unwrap(), "example.com");
+  }
+}
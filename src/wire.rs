@@ -0,0 +1,17 @@
+//! HTTP/1.1 wire format: the parser and serializer the client is built on
+//!
+//! These types let other transports reuse barehttp's RFC 9112 handling:
+//! parse responses read from a file or a custom framing layer with
+//! `Response::parse`, or serialize requests with `RequestBuilder`. `UriBuilder`
+//! assembles URLs to pass to the client.
+//!
+//! # Stability
+//!
+//! The items re-exported here follow the crate's semver like the rest of the
+//! public API. Everything else in the parser stays private and may change in
+//! any release; the client-facing API (`HttpClient`, the convenience
+//! functions) does not depend on this module.
+
+pub use crate::error::ParseError;
+pub use crate::parser::uri::{Authority, Host, Uri, UriBuilder};
+pub use crate::parser::{BodyReadStrategy, RequestBuilder, Response};
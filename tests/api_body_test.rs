@@ -146,3 +146,36 @@ fn test_body_default() {
   assert!(body.is_empty());
   assert_eq!(body.len(), 0);
 }
+
+#[test]
+fn test_body_from_static_is_borrowed() {
+  let body = Body::from_static(b"fixed payload");
+  assert!(body.is_borrowed());
+  assert_eq!(body.as_bytes(), b"fixed payload");
+  assert_eq!(body.len(), 13);
+}
+
+#[test]
+fn test_body_from_bytes_is_owned() {
+  let body = Body::from_bytes(b"owned".to_vec());
+  assert!(!body.is_borrowed());
+}
+
+#[test]
+fn test_body_borrowed_equals_owned() {
+  assert_eq!(Body::from_static(b"same"), Body::from_bytes(b"same".to_vec()));
+}
+
+#[test]
+fn test_body_borrowed_as_bytes_mut_copies() {
+  let mut body = Body::from_static(b"abc");
+  body.as_bytes_mut().push(b'd');
+  assert!(!body.is_borrowed());
+  assert_eq!(body.as_bytes(), b"abcd");
+}
+
+#[test]
+fn test_body_borrowed_into_bytes() {
+  let body = Body::from_static(b"data");
+  assert_eq!(body.into_bytes(), b"data".to_vec());
+}
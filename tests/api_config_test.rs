@@ -1,7 +1,8 @@
 //! Integration tests for Config and ConfigBuilder
 
 use barehttp::config::{
-  Config, ConfigBuilder, HttpStatusHandling, ProtocolRestriction, RedirectAuthHeaders, RedirectPolicy,
+  Config, ConfigBuilder, DEFAULT_USER_AGENT, HttpStatusHandling, ProtocolRestriction, RedirectAuthHeaders,
+  RedirectPolicy,
 };
 use core::time::Duration;
 
@@ -10,7 +11,7 @@ fn test_config_default() {
   let config = Config::default();
 
   assert!(config.timeout.is_none());
-  assert_eq!(config.user_agent, Some(String::from("barehttp/1.0")));
+  assert_eq!(config.user_agent, Some(String::from(DEFAULT_USER_AGENT)));
   assert_eq!(config.redirect_policy, RedirectPolicy::Follow);
   assert_eq!(config.max_redirects, 10);
   assert_eq!(config.http_status_handling, HttpStatusHandling::AsError);
@@ -1,6 +1,11 @@
 //! Integration tests for Error enum
 
 use barehttp::Error;
+use barehttp::wire::Response;
+
+fn not_found() -> Response {
+  barehttp::parse_response(b"HTTP/1.1 404 Not Found\r\nContent-Length: 7\r\n\r\nmissing").unwrap()
+}
 
 #[test]
 fn test_error_debug() {
@@ -18,17 +23,30 @@ fn test_error_variants_exist() {
   let _error5 = Error::MissingRedirectLocation;
   let _error6 = Error::InvalidRedirectLocation;
   let _error7 = Error::RedirectLoop;
-  let _error8 = Error::HttpStatus(404);
+  let _error8 = Error::HttpStatus {
+    code: 404,
+    response: Box::new(not_found()),
+  };
   let _error9 = Error::HttpsRequired;
-  let _error10 = Error::ResponseHeaderTooLarge;
+  let _error10 = Error::ResponseHeaderTooLarge {
+    bytes_read: 0,
+    status_code: None,
+  };
   let _error11 = Error::Utf8Error;
 }
 
 #[test]
 fn test_error_http_status() {
-  let error = Error::HttpStatus(404);
+  let error = Error::HttpStatus {
+    code: 404,
+    response: Box::new(not_found()),
+  };
   match error {
-    Error::HttpStatus(code) => assert_eq!(code, 404),
+    Error::HttpStatus { code, response } => {
+      assert_eq!(code, 404);
+      assert_eq!(response.reason, "Not Found");
+      assert_eq!(response.body.as_bytes(), b"missing");
+    },
     _ => panic!("Expected HttpStatus variant"),
   }
 }
@@ -0,0 +1,40 @@
+//! Integration tests for the standalone response parser
+
+use barehttp::response::ResponseExt;
+use barehttp::{Error, parse_response};
+
+#[test]
+fn test_parse_response_content_length() -> Result<(), Error> {
+  let response = parse_response(b"HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 5\r\n\r\nHello")?;
+  assert_eq!(response.status(), 200);
+  assert_eq!(response.get_header("content-type"), Some("text/plain"));
+  assert_eq!(response.bytes(), b"Hello");
+  Ok(())
+}
+
+#[test]
+fn test_parse_response_chunked_with_trailers() -> Result<(), Error> {
+  let response = parse_response(
+    b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHello\r\n6\r\n World\r\n0\r\nX-Checksum: abc\r\n\r\n",
+  )?;
+  assert_eq!(response.text().ok().as_deref(), Some("Hello World"));
+  assert_eq!(response.trailers, [("X-Checksum".to_string(), "abc".to_string())]);
+  Ok(())
+}
+
+#[test]
+fn test_parse_response_skips_leading_empty_lines() -> Result<(), Error> {
+  let response = parse_response(b"\r\n\r\nHTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")?;
+  assert_eq!(response.status(), 404);
+  assert!(response.bytes().is_empty());
+  Ok(())
+}
+
+#[test]
+fn test_parse_response_rejects_invalid_input() {
+  assert!(matches!(parse_response(b"not http"), Err(Error::Parse(_))));
+  assert!(matches!(
+    parse_response(b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nshort"),
+    Err(Error::Parse(_))
+  ));
+}
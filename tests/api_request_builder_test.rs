@@ -108,6 +108,60 @@ fn test_request_builder_form() -> Result<(), Error> {
   Ok(())
 }
 
+#[test]
+fn test_request_builder_form_pairs() -> Result<(), Error> {
+  let client = HttpClient::new()?;
+  let response = client
+    .post(format!("{}/post", httpbin_url()))
+    .form_pairs([("name", "John Doe"), ("city", "a&b")])
+    .call()?;
+
+  let body = response.text()?;
+  assert!(body.contains("application/x-www-form-urlencoded"));
+  assert!(body.contains("John Doe"));
+  assert!(body.contains("a&b"));
+  Ok(())
+}
+
+#[test]
+fn test_request_builder_form_pairs_empty() -> Result<(), Error> {
+  let client = HttpClient::new()?;
+  let response = client
+    .post(format!("{}/post", httpbin_url()))
+    .form_pairs(Vec::<(String, String)>::new())
+    .call()?;
+
+  let body = response.text()?;
+  assert!(body.contains("application/x-www-form-urlencoded"));
+  Ok(())
+}
+
+#[test]
+fn test_request_builder_if_none_match_not_modified() -> Result<(), Error> {
+  let client = HttpClient::new()?;
+  let response = client
+    .get(format!("{}/etag/abc", httpbin_url()))
+    .if_none_match("\"abc\"")
+    .call()?;
+
+  assert!(response.is_not_modified());
+  assert!(response.bytes().is_empty());
+  Ok(())
+}
+
+#[test]
+fn test_request_builder_if_modified_since() -> Result<(), Error> {
+  let client = HttpClient::new()?;
+  let response = client
+    .get(format!("{}/headers", httpbin_url()))
+    .if_modified_since(barehttp::format_http_date(784_111_777))
+    .call()?;
+
+  let body = response.text()?;
+  assert!(body.contains("Sun, 06 Nov 1994 08:49:37 GMT"));
+  Ok(())
+}
+
 #[test]
 fn test_request_builder_content_type() -> Result<(), Error> {
   let client = HttpClient::new()?;
@@ -210,6 +264,18 @@ fn test_request_builder_send_string() -> Result<(), Error> {
   Ok(())
 }
 
+#[test]
+fn test_request_builder_send_borrowed() -> Result<(), Error> {
+  let client = HttpClient::new()?;
+  let response = client
+    .post(format!("{}/post", httpbin_url()))
+    .send_borrowed(b"fixed payload")?;
+
+  assert!(response.is_success());
+  assert!(response.text()?.contains("fixed payload"));
+  Ok(())
+}
+
 #[test]
 fn test_request_builder_send_empty() -> Result<(), Error> {
   let client = HttpClient::new()?;
@@ -294,6 +360,34 @@ fn test_request_builder_config_ref() -> Result<(), Error> {
   Ok(())
 }
 
+#[test]
+fn test_request_builder_overrides_do_not_touch_client_config() -> Result<(), Error> {
+  use barehttp::config::{ConfigBuilder, RedirectPolicy};
+
+  let client = HttpClient::with_config(
+    ConfigBuilder::new()
+      .max_redirects(5)
+      .user_agent("ua/1")
+      .build(),
+  )?;
+  let builder = client
+    .get(format!("{}/get", httpbin_url()))
+    .max_redirects(1)
+    .redirect_policy(RedirectPolicy::NoFollow);
+
+  let overlay = builder
+    .config_ref()
+    .expect("override creates a request config");
+  assert_eq!(overlay.max_redirects, 1);
+  assert_eq!(overlay.redirect_policy, RedirectPolicy::NoFollow);
+  assert_eq!(overlay.user_agent.as_deref(), Some("ua/1"));
+
+  assert_eq!(client.config().max_redirects, 5);
+  assert_eq!(client.config().redirect_policy, RedirectPolicy::Follow);
+  assert!(client.get("http://example.com").config_ref().is_none());
+  Ok(())
+}
+
 #[test]
 fn test_request_builder_query_raw() -> Result<(), Error> {
   let client = HttpClient::new()?;
@@ -340,3 +434,21 @@ fn test_request_builder_query_pairs_raw() -> Result<(), Error> {
   assert!(body.contains("val1"));
   Ok(())
 }
+
+#[test]
+fn test_request_builder_force_send_body_on_get() -> Result<(), Error> {
+  let client = HttpClient::new()?;
+  let response = client
+    .get(format!("{}/anything", httpbin_url()))
+    .header("Content-Type", "application/json")
+    .force_send_body(br#"{"query":"match_all"}"#.to_vec())
+    .call()?;
+
+  let body = response.text()?;
+
+  // httpbin echoes the method and the raw request body
+  assert!(body.contains("\"method\": \"GET\""));
+  assert!(body.contains(r#""data": "{\"query\":\"match_all\"}""#));
+  assert!(body.contains("\"Content-Length\": \"21\""));
+  Ok(())
+}
@@ -255,3 +255,32 @@ fn test_status_class_copy() {
   let class2 = class1;
   assert_eq!(class1, class2);
 }
+
+#[test]
+fn test_status_code_is_retryable() {
+  let cases = [
+    (408, true),
+    (425, true),
+    (429, true),
+    (500, true),
+    (502, true),
+    (503, true),
+    (504, true),
+    (200, false),
+    (304, false),
+    (400, false),
+    (401, false),
+    (404, false),
+    (409, false),
+    (501, false),
+    (505, false),
+  ];
+
+  for (code, retryable) in cases {
+    assert_eq!(
+      StatusCode::new(code).unwrap().is_retryable(),
+      retryable,
+      "status {code}"
+    );
+  }
+}
@@ -0,0 +1,56 @@
+//! Integration tests for the public wire-format types
+
+use barehttp::wire::{BodyReadStrategy, Host, ParseError, RequestBuilder, Response, Uri};
+use barehttp::{Headers, Version};
+
+#[test]
+fn test_request_builder_serializes_request() -> Result<(), ParseError> {
+  let bytes = RequestBuilder::new("POST", "/submit")
+    .header("Host", "example.com")
+    .header("Content-Type", "text/plain")
+    .body(b"hi".to_vec())
+    .build()?;
+  let text = String::from_utf8_lossy(&bytes);
+  assert!(text.starts_with("POST /submit HTTP/1.1\r\n"));
+  assert!(text.contains("Host: example.com\r\n"));
+  assert!(text.ends_with("\r\n\r\nhi"));
+  Ok(())
+}
+
+#[test]
+fn test_request_builder_enforces_rfc_requirements() {
+  assert_eq!(
+    RequestBuilder::new("GET", "/").build(),
+    Err(ParseError::MissingHostHeader)
+  );
+}
+
+#[test]
+fn test_response_parse_and_framing() -> Result<(), ParseError> {
+  let response = Response::parse(b"HTTP/1.1 201 Created\r\nContent-Length: 2\r\n\r\nok")?;
+  assert_eq!(response.status_code, 201);
+  assert_eq!(response.reason, "Created");
+  assert_eq!(response.body.as_bytes(), b"ok");
+
+  let mut headers = Headers::new();
+  headers.insert("Transfer-Encoding", "chunked");
+  assert_eq!(
+    Response::body_read_strategy(&headers, 200, Version::HTTP_11),
+    BodyReadStrategy::Chunked
+  );
+  Ok(())
+}
+
+#[test]
+fn test_uri_parts() -> Result<(), ParseError> {
+  let uri = Uri::parse("https://example.com:8443/a/b?q=1")?;
+  assert_eq!(uri.scheme(), "https");
+  assert_eq!(uri.path(), "/a/b");
+  assert_eq!(uri.path_and_query(), "/a/b?q=1");
+
+  let authority = uri.authority().ok_or(ParseError::InvalidUri)?;
+  assert_eq!(authority.host(), &Host::RegName("example.com"));
+  assert_eq!(authority.port(), Some(8443));
+  assert_eq!(uri.resolve_relative("/next")?, "https://example.com:8443/next");
+  Ok(())
+}